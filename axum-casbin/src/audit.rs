@@ -0,0 +1,166 @@
+//! 授权决策审计
+//!
+//! 中间件通过 `enforce_ex` 得到每次决策命中的策略，交给可插拔的 [`DecisionSink`] 记录。
+
+use casbin::{CachedApi, CachedEnforcer, CoreApi, Result as CasbinResult};
+
+use crate::extractor::{EnforceRequest, SubjectMatch};
+
+/// 一次授权决策
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Decision {
+    /// 参与判定的全部主体（通常为用户的角色）
+    pub subjects: Vec<String>,
+    /// 放行请求的主体，拒绝时为 `None`
+    pub subject: Option<String>,
+    pub domain: Option<String>,
+    pub object: String,
+    pub action: String,
+    pub allowed: bool,
+    /// 命中的策略；结果来自 `CachedEnforcer` 缓存时为 `None`
+    pub matched_policy: Option<Vec<String>>,
+}
+
+/// 授权决策的记录方式
+pub trait DecisionSink: Send + Sync {
+    fn record(&self, decision: &Decision);
+}
+
+/// 默认的记录方式，通过 tracing 输出决策，拒绝以 warn 级别记录
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingSink;
+
+impl DecisionSink for TracingSink {
+    fn record(&self, decision: &Decision) {
+        let matched_policy = decision
+            .matched_policy
+            .as_ref()
+            .map(|policy| policy.join(", "))
+            .unwrap_or_default();
+        if decision.allowed {
+            tracing::info!(
+                target: "casbin::decision",
+                subjects = ?decision.subjects,
+                subject = decision.subject.as_deref().unwrap_or_default(),
+                domain = decision.domain.as_deref().unwrap_or_default(),
+                object = %decision.object,
+                action = %decision.action,
+                matched_policy = %matched_policy,
+                "casbin allowed"
+            );
+        } else {
+            tracing::warn!(
+                target: "casbin::decision",
+                subjects = ?decision.subjects,
+                domain = decision.domain.as_deref().unwrap_or_default(),
+                object = %decision.object,
+                action = %decision.action,
+                "casbin denied"
+            );
+        }
+    }
+}
+
+/// 依次以每个主体判定，任意主体放行即放行
+pub fn decide(
+    enforcer: &CachedEnforcer,
+    subjects: &[String],
+    domain: Option<&str>,
+    object: &str,
+    action: &str,
+) -> CasbinResult<Decision> {
+    decide_with(
+        enforcer,
+        subjects,
+        domain,
+        object,
+        action,
+        SubjectMatch::Any,
+    )
+}
+
+/// 按请求指定的主体匹配方式判定
+pub fn decide_request(
+    enforcer: &CachedEnforcer,
+    request: &EnforceRequest,
+) -> CasbinResult<Decision> {
+    decide_with(
+        enforcer,
+        &request.subjects,
+        request.domain.as_deref(),
+        &request.object,
+        &request.action,
+        request.subject_match,
+    )
+}
+
+/// `SubjectMatch::All` 放行时，`subject` 与 `matched_policy` 取最后一个主体的判定结果
+fn decide_with(
+    enforcer: &CachedEnforcer,
+    subjects: &[String],
+    domain: Option<&str>,
+    object: &str,
+    action: &str,
+    subject_match: SubjectMatch,
+) -> CasbinResult<Decision> {
+    let mut decision = Decision {
+        subjects: subjects.to_vec(),
+        subject: None,
+        domain: domain.map(str::to_string),
+        object: object.to_string(),
+        action: action.to_string(),
+        allowed: false,
+        matched_policy: None,
+    };
+
+    for subject in subjects {
+        let rvals = match domain {
+            Some(domain) => vec![
+                subject.clone(),
+                domain.to_string(),
+                object.to_string(),
+                action.to_string(),
+            ],
+            None => vec![subject.clone(), object.to_string(), action.to_string()],
+        };
+        let (allowed, mut matched) = enforcer.enforce_ex(rvals)?;
+        match (subject_match, allowed) {
+            (SubjectMatch::Any, false) => continue,
+            (SubjectMatch::All, false) => {
+                decision.allowed = false;
+                decision.subject = None;
+                decision.matched_policy = None;
+                return Ok(decision);
+            },
+            (_, true) => {
+                decision.allowed = true;
+                decision.subject = Some(subject.clone());
+                decision.matched_policy = (!matched.is_empty()).then(|| matched.swap_remove(0));
+                if subject_match == SubjectMatch::Any {
+                    break;
+                }
+            },
+        }
+    }
+
+    Ok(decision)
+}
+
+/// 解释一次授权决策，不发起实际请求
+///
+/// 缓存命中时 `enforce_ex` 无法给出命中的策略，此时清空缓存后重新判定。
+pub fn explain(
+    enforcer: &mut CachedEnforcer,
+    subjects: &[String],
+    domain: Option<&str>,
+    object: &str,
+    action: &str,
+) -> CasbinResult<Decision> {
+    let decision = decide(enforcer, subjects, domain, object, action)?;
+    if decision.allowed && decision.matched_policy.is_none() {
+        enforcer.get_mut_cache().clear();
+        return decide(enforcer, subjects, domain, object, action);
+    }
+    Ok(decision)
+}
@@ -0,0 +1,132 @@
+//! 从请求中构造 Casbin 判定参数
+//!
+//! [`CasbinAxumLayer`](crate::CasbinAxumLayer) 默认使用 [`DefaultExtractor`]，
+//! 以 [`CasbinVals`] 中的主体和域、请求路径和方法判定。需要从路径参数取资源 ID
+//! 或从请求头取域时，实现 [`RequestExtractor`] 或直接传入闭包。
+
+use http::{header::HeaderName, request::Parts, StatusCode};
+
+use crate::CasbinVals;
+
+/// 多个主体的判定方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubjectMatch {
+    /// 任意主体放行即放行
+    #[default]
+    Any,
+    /// 所有主体都放行才放行
+    All,
+}
+
+/// 一次 Casbin 判定的参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnforceRequest {
+    pub subjects: Vec<String>,
+    pub domain: Option<String>,
+    pub object: String,
+    pub action: String,
+    pub subject_match: SubjectMatch,
+}
+
+/// 无法构造判定参数时返回给客户端的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractError {
+    /// 缺少认证信息，返回 401
+    Unauthorized(String),
+    /// 请求缺少判定所需的参数，返回 400
+    BadRequest(String),
+}
+
+impl ExtractError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ExtractError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ExtractError::BadRequest(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            ExtractError::Unauthorized(message) | ExtractError::BadRequest(message) => message,
+        }
+    }
+}
+
+/// 从请求构造判定参数
+pub trait RequestExtractor: Send + Sync {
+    fn extract(&self, parts: &Parts) -> Result<EnforceRequest, ExtractError>;
+}
+
+impl<F> RequestExtractor for F
+where
+    F: Fn(&Parts) -> Result<EnforceRequest, ExtractError> + Send + Sync,
+{
+    fn extract(&self, parts: &Parts) -> Result<EnforceRequest, ExtractError> {
+        self(parts)
+    }
+}
+
+/// 默认的判定参数：`CasbinVals` 中的主体和域、请求路径、请求方法
+#[derive(Debug, Clone, Default)]
+pub struct DefaultExtractor {
+    domain_header: Option<HeaderName>,
+    subject_match: SubjectMatch,
+}
+
+impl DefaultExtractor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 优先从请求头读取域，请求头不存在时使用 `CasbinVals` 中的域
+    pub fn with_domain_header(mut self, header: HeaderName) -> Self {
+        self.domain_header = Some(header);
+        self
+    }
+
+    pub fn with_subject_match(mut self, subject_match: SubjectMatch) -> Self {
+        self.subject_match = subject_match;
+        self
+    }
+
+    /// 读取认证中间件写入的 `CasbinVals`
+    pub fn casbin_vals(parts: &Parts) -> Result<&CasbinVals, ExtractError> {
+        let vals = parts.extensions.get::<CasbinVals>().ok_or_else(|| {
+            ExtractError::Unauthorized("No authentication token was provided. Please ensure your request includes a valid token.".to_string())
+        })?;
+        if vals.subject.is_empty() {
+            return Err(ExtractError::Unauthorized(
+                "No token provided or invalid token type".to_string(),
+            ));
+        }
+        Ok(vals)
+    }
+}
+
+impl RequestExtractor for DefaultExtractor {
+    fn extract(&self, parts: &Parts) -> Result<EnforceRequest, ExtractError> {
+        let vals = Self::casbin_vals(parts)?;
+
+        let domain = match self
+            .domain_header
+            .as_ref()
+            .and_then(|header| parts.headers.get(header))
+        {
+            Some(value) => Some(
+                value
+                    .to_str()
+                    .map_err(|_| ExtractError::BadRequest("Invalid domain header".to_string()))?
+                    .to_string(),
+            ),
+            None => vals.domain.clone(),
+        };
+
+        Ok(EnforceRequest {
+            subjects: vals.subject.clone(),
+            domain,
+            object: parts.uri.path().to_string(),
+            action: parts.method.as_str().to_string(),
+            subject_match: self.subject_match,
+        })
+    }
+}
@@ -1,4 +1,14 @@
+pub use audit::{Decision, DecisionSink, TracingSink};
 pub use casbin;
+pub use extractor::{
+    DefaultExtractor, EnforceRequest, ExtractError, RequestExtractor, SubjectMatch,
+};
 pub use middleware::{CasbinAxumLayer, CasbinAxumMiddleware, CasbinVals};
+#[cfg(any(feature = "watcher-redis", feature = "watcher-postgres"))]
+pub use watcher::{apply_policy_change, PolicyChange, PolicyWatcher, WatcherBackend};
 
+pub mod audit;
+pub mod extractor;
 pub mod middleware;
+#[cfg(any(feature = "watcher-redis", feature = "watcher-postgres"))]
+pub mod watcher;
@@ -13,6 +13,11 @@ use casbin::{
     prelude::{TryIntoAdapter, TryIntoModel},
     CachedEnforcer, CoreApi, Result as CasbinResult,
 };
+
+use crate::{
+    audit::{decide_request, DecisionSink, TracingSink},
+    extractor::{DefaultExtractor, RequestExtractor},
+};
 use futures::future::BoxFuture;
 use http::{Request, StatusCode};
 use http_body::Body as HttpBody;
@@ -30,6 +35,8 @@ pub struct CasbinVals {
 #[derive(Clone)]
 pub struct CasbinAxumLayer {
     enforcer: Arc<RwLock<CachedEnforcer>>,
+    extractor: Arc<dyn RequestExtractor>,
+    sink: Option<Arc<dyn DecisionSink>>,
 }
 
 impl CasbinAxumLayer {
@@ -37,6 +44,8 @@ impl CasbinAxumLayer {
         let enforcer: CachedEnforcer = CachedEnforcer::new(m, a).await?;
         Ok(CasbinAxumLayer {
             enforcer: Arc::new(RwLock::new(enforcer)),
+            extractor: Arc::new(DefaultExtractor::new()),
+            sink: None,
         })
     }
 
@@ -45,7 +54,34 @@ impl CasbinAxumLayer {
     }
 
     pub fn set_enforcer(e: Arc<RwLock<CachedEnforcer>>) -> CasbinAxumLayer {
-        CasbinAxumLayer { enforcer: e }
+        CasbinAxumLayer {
+            enforcer: e,
+            extractor: Arc::new(DefaultExtractor::new()),
+            sink: None,
+        }
+    }
+
+    /// 自定义从请求构造判定参数的方式，默认为 [`DefaultExtractor`]
+    pub fn with_extractor(mut self, extractor: impl RequestExtractor + 'static) -> Self {
+        self.extractor = Arc::new(extractor);
+        self
+    }
+
+    /// 记录每次授权决策，默认通过 tracing 输出
+    pub fn with_audit(self) -> Self {
+        self.with_decision_sink(Arc::new(TracingSink))
+    }
+
+    /// 使用自定义的方式记录每次授权决策
+    pub fn with_decision_sink(mut self, sink: Arc<dyn DecisionSink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// 注册策略同步 watcher，使多个实例之间的策略变更保持一致
+    #[cfg(any(feature = "watcher-redis", feature = "watcher-postgres"))]
+    pub async fn set_watcher(&self, watcher: crate::watcher::PolicyWatcher) {
+        watcher.watch(&self.enforcer).await;
     }
 }
 
@@ -55,6 +91,8 @@ impl<S> Layer<S> for CasbinAxumLayer {
     fn layer(&self, inner: S) -> Self::Service {
         CasbinAxumMiddleware {
             enforcer: self.enforcer.clone(),
+            extractor: self.extractor.clone(),
+            sink: self.sink.clone(),
             inner,
         }
     }
@@ -78,6 +116,8 @@ impl DerefMut for CasbinAxumLayer {
 pub struct CasbinAxumMiddleware<S> {
     inner: S,
     enforcer: Arc<RwLock<CachedEnforcer>>,
+    extractor: Arc<dyn RequestExtractor>,
+    sink: Option<Arc<dyn DecisionSink>>,
 }
 
 impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for CasbinAxumMiddleware<S>
@@ -103,107 +143,46 @@ where
 
     fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
         let cloned_enforcer = self.enforcer.clone();
+        let extractor = self.extractor.clone();
+        let sink = self.sink.clone();
         let not_ready_inner = self.inner.clone();
         let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
 
         Box::pin(async move {
-            let path = req.uri().path().to_string();
-            let action = req.method().as_str().to_string();
-            let option_vals = req.extensions().get::<CasbinVals>().map(|x| x.to_owned());
-            let vals = match option_vals {
-                Some(value) => value,
-                None => {
+            let (parts, body) = req.into_parts();
+            let request = match extractor.extract(&parts) {
+                Ok(request) => request,
+                Err(e) => {
                     return Ok(Response::builder()
-                        .status(StatusCode::UNAUTHORIZED)
-                        .body(body::Body::new(Full::from("No authentication token was provided. Please ensure your request includes a valid token.")))
+                        .status(e.status())
+                        .body(body::Body::new(Full::from(e.message().to_string())))
                         .unwrap());
                 },
             };
+            let req = Request::from_parts(parts, body);
 
-            let subject = vals.subject.clone();
-
-            if !vals.subject.is_empty() {
-                if let Some(domain) = vals.domain {
-                    let mut lock = cloned_enforcer.write().await;
-                    let mut authorized = false;
-                    let mut enforcement_error = false;
-
-                    for sub in subject.iter() {
-                        match lock.enforce_mut(vec![
-                            sub.clone(),
-                            domain.clone(),
-                            path.clone(),
-                            action.clone(),
-                        ]) {
-                            Ok(true) => {
-                                authorized = true;
-                                break;
-                            },
-                            Ok(false) => continue,
-                            Err(_) => {
-                                enforcement_error = true;
-                                break;
-                            },
-                        }
-                    }
-
-                    drop(lock);
+            let lock = cloned_enforcer.read().await;
+            let decision = decide_request(&lock, &request);
+            drop(lock);
 
-                    if enforcement_error {
-                        Ok(Response::builder()
-                                .status(StatusCode::BAD_GATEWAY)
-                                .body(body::Body::new(Full::from("We encountered an unexpected error while processing your request. Our team has been notified, and we are investigating the issue.")))
-                                .unwrap())
-                    } else if authorized {
-                        Ok(inner.call(req).await?.map(body::Body::new))
-                    } else {
-                        Ok(Response::builder()
-                        .status(StatusCode::FORBIDDEN)
-                        .body(body::Body::new(Full::from("You do not have the necessary permissions to access this resource. Please contact support if you believe this is an error.")))
-                        .unwrap())
+            match decision {
+                Ok(decision) => {
+                    if let Some(sink) = sink {
+                        sink.record(&decision);
                     }
-                } else {
-                    let mut lock = cloned_enforcer.write().await;
-                    let mut authorized = false;
-                    let mut enforcement_error = false;
-
-                    for sub in subject.iter() {
-                        match lock.enforce_mut(vec![sub.clone(), path.clone(), action.clone()]) {
-                            Ok(true) => {
-                                authorized = true;
-                                break;
-                            },
-                            Ok(false) => continue,
-                            Err(_) => {
-                                enforcement_error = true;
-                                break;
-                            },
-                        }
-                    }
-
-                    drop(lock);
-
-                    if enforcement_error {
-                        Ok(Response::builder()
-                        .status(StatusCode::BAD_GATEWAY)
-                        .body(body::Body::new(Full::from("We encountered an unexpected error while processing your request. Our team has been notified, and we are investigating the issue.")))
-                        .unwrap())
-                    } else if authorized {
+                    if decision.allowed {
                         Ok(inner.call(req).await?.map(body::Body::new))
                     } else {
                         Ok(Response::builder()
-                                .status(StatusCode::FORBIDDEN)
-                                .body(body::Body::new(Full::from("You do not have the necessary permissions to access this resource. Please contact support if you believe this is an error.")))
-                                .unwrap())
+                            .status(StatusCode::FORBIDDEN)
+                            .body(body::Body::new(Full::from("You do not have the necessary permissions to access this resource. Please contact support if you believe this is an error.")))
+                            .unwrap())
                     }
-                }
-            } else {
-                Ok(Response::builder()
-                    .status(StatusCode::UNAUTHORIZED)
-                    .body(body::Body::new(Full::from(
-                        "No token provided or invalid token type",
-                    )))
-                    .unwrap())
+                },
+                Err(_) => Ok(Response::builder()
+                    .status(StatusCode::BAD_GATEWAY)
+                    .body(body::Body::new(Full::from("We encountered an unexpected error while processing your request. Our team has been notified, and we are investigating the issue.")))
+                    .unwrap()),
             }
         })
     }
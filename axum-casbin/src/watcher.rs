@@ -0,0 +1,423 @@
+//! 多实例之间的策略同步
+//!
+//! `PolicyWatcher` 实现了 casbin 的 [`Watcher`]，本实例通过 `MgmtApi` 修改策略后，
+//! casbin 会回调 [`Watcher::update`]，变更经 Redis pub/sub 或 Postgres LISTEN/NOTIFY
+//! 广播给其他实例。其他实例收到后增量更新内存中的策略并清空 `CachedEnforcer` 的缓存，
+//! 增量更新失败或可能丢失消息（如断线重连）时退化为完整的 `load_policy`。
+
+use std::{
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+
+use casbin::{
+    CachedApi, CachedEnforcer, CoreApi, EventData, InternalApi, Result as CasbinResult, Watcher,
+};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+        RwLock,
+    },
+    task::JoinHandle,
+};
+
+/// 默认的通知频道
+pub const DEFAULT_POLICY_CHANNEL: &str = "casbin:policy";
+
+/// 订阅断开后重连的间隔
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Postgres NOTIFY 的负载上限为 8000 字节，超出时改为通知对端全量重载
+#[cfg(feature = "watcher-postgres")]
+const MAX_NOTIFY_PAYLOAD: usize = 7999;
+
+type UpdateCallback = Box<dyn FnMut() + Send + Sync>;
+
+/// 策略变更
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PolicyChange {
+    AddPolicies {
+        sec: String,
+        ptype: String,
+        rules: Vec<Vec<String>>,
+    },
+    RemovePolicies {
+        sec: String,
+        ptype: String,
+        rules: Vec<Vec<String>>,
+    },
+    /// 无法增量表达的变更（保存、清空策略），对端从存储中重新加载
+    ReloadPolicy,
+    ClearCache,
+}
+
+impl From<EventData> for PolicyChange {
+    fn from(data: EventData) -> Self {
+        match data {
+            EventData::AddPolicy(sec, ptype, rule) => Self::AddPolicies {
+                sec,
+                ptype,
+                rules: vec![rule],
+            },
+            EventData::AddPolicies(sec, ptype, rules) => Self::AddPolicies { sec, ptype, rules },
+            EventData::RemovePolicy(sec, ptype, rule) => Self::RemovePolicies {
+                sec,
+                ptype,
+                rules: vec![rule],
+            },
+            EventData::RemovePolicies(sec, ptype, rules)
+            | EventData::RemoveFilteredPolicy(sec, ptype, rules) => {
+                Self::RemovePolicies { sec, ptype, rules }
+            },
+            EventData::SavePolicy(_) | EventData::ClearPolicy => Self::ReloadPolicy,
+            EventData::ClearCache => Self::ClearCache,
+        }
+    }
+}
+
+/// 在频道中传输的消息，`instance_id` 用于忽略本实例发出的通知
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PolicyMessage {
+    instance_id: String,
+    #[serde(flatten)]
+    change: PolicyChange,
+}
+
+/// 通知的传输方式
+#[derive(Clone)]
+pub enum WatcherBackend {
+    #[cfg(feature = "watcher-redis")]
+    Redis(redis::Client),
+    #[cfg(feature = "watcher-postgres")]
+    Postgres(sqlx::PgPool),
+}
+
+/// 基于 Redis pub/sub 或 Postgres LISTEN/NOTIFY 的 casbin [`Watcher`]
+pub struct PolicyWatcher {
+    backend: WatcherBackend,
+    channel: String,
+    instance_id: String,
+    sender: UnboundedSender<PolicyMessage>,
+    receiver: Option<UnboundedReceiver<PolicyMessage>>,
+    callback: Arc<Mutex<Option<UpdateCallback>>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl PolicyWatcher {
+    pub fn new(backend: WatcherBackend, channel: impl Into<String>) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            backend,
+            channel: channel.into(),
+            instance_id: uuid::Uuid::new_v4().to_string(),
+            sender,
+            receiver: Some(receiver),
+            callback: Arc::new(Mutex::new(None)),
+            tasks: Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "watcher-redis")]
+    pub fn redis(client: redis::Client) -> Self {
+        Self::new(WatcherBackend::Redis(client), DEFAULT_POLICY_CHANNEL)
+    }
+
+    #[cfg(feature = "watcher-postgres")]
+    pub fn postgres(pool: sqlx::PgPool) -> Self {
+        Self::new(WatcherBackend::Postgres(pool), DEFAULT_POLICY_CHANNEL)
+    }
+
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// 启动发布与订阅任务，并把自身注册为 `enforcer` 的 watcher
+    ///
+    /// 订阅任务只持有 enforcer 的弱引用，enforcer 释放后任务随 watcher 一起结束。
+    pub async fn watch(mut self, enforcer: &Arc<RwLock<CachedEnforcer>>) {
+        if let Some(receiver) = self.receiver.take() {
+            self.tasks.push(tokio::spawn(publish_loop(
+                self.backend.clone(),
+                self.channel.clone(),
+                receiver,
+            )));
+        }
+        self.tasks.push(tokio::spawn(subscribe_loop(
+            self.backend.clone(),
+            self.channel.clone(),
+            self.instance_id.clone(),
+            Arc::downgrade(enforcer),
+            self.callback.clone(),
+        )));
+
+        enforcer.write().await.set_watcher(Box::new(self));
+    }
+}
+
+impl Watcher for PolicyWatcher {
+    fn set_update_callback(&mut self, cb: UpdateCallback) {
+        *self.callback.lock().unwrap() = Some(cb);
+    }
+
+    fn update(&mut self, d: EventData) {
+        let message = PolicyMessage {
+            instance_id: self.instance_id.clone(),
+            change: d.into(),
+        };
+        // 缓存清理只影响本实例，无需广播
+        if message.change != PolicyChange::ClearCache {
+            let _ = self.sender.send(message);
+        }
+    }
+}
+
+impl Drop for PolicyWatcher {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// 将其他实例的策略变更应用到本地 enforcer
+///
+/// 增量更新时关闭自动保存和自动通知，避免重复写库以及把变更再次广播出去。
+/// 本地策略与对端不一致导致增量更新失败时，从存储中完整重新加载。
+pub async fn apply_policy_change(
+    enforcer: &mut CachedEnforcer,
+    change: PolicyChange,
+) -> CasbinResult<()> {
+    let auto_save = enforcer.has_auto_save_enabled();
+    let auto_notify = enforcer.has_auto_notify_watcher_enabled();
+    enforcer.enable_auto_save(false);
+    enforcer.enable_auto_notify_watcher(false);
+
+    let applied = match change {
+        PolicyChange::AddPolicies { sec, ptype, rules } => {
+            enforcer.add_policies_internal(&sec, &ptype, rules).await
+        },
+        PolicyChange::RemovePolicies { sec, ptype, rules } => {
+            enforcer.remove_policies_internal(&sec, &ptype, rules).await
+        },
+        PolicyChange::ReloadPolicy => Ok(false),
+        PolicyChange::ClearCache => Ok(true),
+    };
+
+    enforcer.enable_auto_save(auto_save);
+    enforcer.enable_auto_notify_watcher(auto_notify);
+
+    if !applied.unwrap_or(false) {
+        enforcer.load_policy().await?;
+    }
+    enforcer.get_mut_cache().clear();
+    Ok(())
+}
+
+async fn publish_loop(
+    backend: WatcherBackend,
+    channel: String,
+    mut receiver: UnboundedReceiver<PolicyMessage>,
+) {
+    let mut publisher = Publisher::new(backend);
+    while let Some(message) = receiver.recv().await {
+        let payload = match serde_json::to_string(&message) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("Failed to serialize casbin policy change: {}", e);
+                continue;
+            },
+        };
+        if let Err(e) = publisher.publish(&channel, &message, payload).await {
+            tracing::error!("Failed to publish casbin policy change: {}", e);
+        }
+    }
+}
+
+async fn subscribe_loop(
+    backend: WatcherBackend,
+    channel: String,
+    instance_id: String,
+    enforcer: Weak<RwLock<CachedEnforcer>>,
+    callback: Arc<Mutex<Option<UpdateCallback>>>,
+) {
+    let handler = MessageHandler {
+        instance_id,
+        enforcer,
+        callback,
+    };
+    match backend {
+        #[cfg(feature = "watcher-redis")]
+        WatcherBackend::Redis(client) => redis_subscribe_loop(client, channel, handler).await,
+        #[cfg(feature = "watcher-postgres")]
+        WatcherBackend::Postgres(pool) => postgres_subscribe_loop(pool, channel, handler).await,
+    }
+}
+
+struct MessageHandler {
+    instance_id: String,
+    enforcer: Weak<RwLock<CachedEnforcer>>,
+    callback: Arc<Mutex<Option<UpdateCallback>>>,
+}
+
+impl MessageHandler {
+    fn is_alive(&self) -> bool {
+        self.enforcer.strong_count() > 0
+    }
+
+    async fn handle(&self, payload: &str) {
+        match serde_json::from_str::<PolicyMessage>(payload) {
+            Ok(message) if message.instance_id == self.instance_id => {},
+            Ok(message) => self.apply(message.change).await,
+            Err(e) => tracing::error!("Ignoring malformed casbin policy change: {}", e),
+        }
+    }
+
+    async fn apply(&self, change: PolicyChange) {
+        let Some(enforcer) = self.enforcer.upgrade() else {
+            return;
+        };
+        let result = apply_policy_change(&mut *enforcer.write().await, change).await;
+        match result {
+            Ok(()) => {
+                if let Some(callback) = self.callback.lock().unwrap().as_mut() {
+                    callback();
+                }
+            },
+            Err(e) => tracing::error!("Failed to apply casbin policy change: {}", e),
+        }
+    }
+}
+
+enum Publisher {
+    #[cfg(feature = "watcher-redis")]
+    Redis {
+        client: redis::Client,
+        connection: Option<redis::aio::MultiplexedConnection>,
+    },
+    #[cfg(feature = "watcher-postgres")]
+    Postgres(sqlx::PgPool),
+}
+
+impl Publisher {
+    fn new(backend: WatcherBackend) -> Self {
+        match backend {
+            #[cfg(feature = "watcher-redis")]
+            WatcherBackend::Redis(client) => Self::Redis {
+                client,
+                connection: None,
+            },
+            #[cfg(feature = "watcher-postgres")]
+            WatcherBackend::Postgres(pool) => Self::Postgres(pool),
+        }
+    }
+
+    #[allow(unused_variables)]
+    async fn publish(
+        &mut self,
+        channel: &str,
+        message: &PolicyMessage,
+        payload: String,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match self {
+            #[cfg(feature = "watcher-redis")]
+            Self::Redis { client, connection } => {
+                use redis::AsyncCommands;
+
+                let conn = match connection {
+                    Some(conn) => conn,
+                    None => connection.insert(client.get_multiplexed_async_connection().await?),
+                };
+                let result: redis::RedisResult<i64> = conn.publish(channel, payload).await;
+                if result.is_err() {
+                    // 连接可能已失效，下次发布时重新建立
+                    *connection = None;
+                }
+                result?;
+            },
+            #[cfg(feature = "watcher-postgres")]
+            Self::Postgres(pool) => {
+                let payload = if payload.len() > MAX_NOTIFY_PAYLOAD {
+                    serde_json::to_string(&PolicyMessage {
+                        instance_id: message.instance_id.clone(),
+                        change: PolicyChange::ReloadPolicy,
+                    })?
+                } else {
+                    payload
+                };
+                sqlx::query("SELECT pg_notify($1, $2)")
+                    .bind(channel)
+                    .bind(payload)
+                    .execute(&*pool)
+                    .await?;
+            },
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "watcher-redis")]
+async fn redis_subscribe_loop(client: redis::Client, channel: String, handler: MessageHandler) {
+    use futures::StreamExt;
+
+    let mut reconnected = false;
+    while handler.is_alive() {
+        match client.get_async_pubsub().await {
+            Ok(mut pubsub) => match pubsub.subscribe(&channel).await {
+                Ok(()) => {
+                    // 断线期间的通知已丢失，重连后全量同步一次
+                    if reconnected {
+                        handler.apply(PolicyChange::ReloadPolicy).await;
+                    }
+                    let mut messages = pubsub.into_on_message();
+                    while let Some(message) = messages.next().await {
+                        match message.get_payload::<String>() {
+                            Ok(payload) => handler.handle(&payload).await,
+                            Err(e) => tracing::error!("Invalid casbin policy payload: {}", e),
+                        }
+                    }
+                    tracing::error!("Casbin policy subscription closed, reconnecting");
+                },
+                Err(e) => tracing::error!("Failed to subscribe to casbin policy changes: {}", e),
+            },
+            Err(e) => tracing::error!("Failed to subscribe to casbin policy changes: {}", e),
+        }
+        reconnected = true;
+        tokio::time::sleep(RECONNECT_INTERVAL).await;
+    }
+}
+
+#[cfg(feature = "watcher-postgres")]
+async fn postgres_subscribe_loop(pool: sqlx::PgPool, channel: String, handler: MessageHandler) {
+    use sqlx::postgres::PgListener;
+
+    let mut reconnected = false;
+    while handler.is_alive() {
+        let listener = match PgListener::connect_with(&pool).await {
+            Ok(mut listener) => listener.listen(&channel).await.map(|()| listener),
+            Err(e) => Err(e),
+        };
+        match listener {
+            Ok(mut listener) => {
+                if reconnected {
+                    handler.apply(PolicyChange::ReloadPolicy).await;
+                }
+                loop {
+                    match listener.try_recv().await {
+                        Ok(Some(notification)) => handler.handle(notification.payload()).await,
+                        // 连接断开，下一次 try_recv 会自动重连，期间的通知已丢失
+                        Ok(None) => handler.apply(PolicyChange::ReloadPolicy).await,
+                        Err(e) => {
+                            tracing::error!("Casbin policy listener failed, reconnecting: {}", e);
+                            break;
+                        },
+                    }
+                }
+            },
+            Err(e) => tracing::error!("Failed to listen for casbin policy changes: {}", e),
+        }
+        reconnected = true;
+        tokio::time::sleep(RECONNECT_INTERVAL).await;
+    }
+}
@@ -0,0 +1,60 @@
+use axum_casbin::audit::{decide, explain};
+use casbin::{CachedEnforcer, CoreApi, DefaultModel, FileAdapter};
+
+async fn new_enforcer() -> CachedEnforcer {
+    let m = DefaultModel::from_file("examples/rbac_with_domains_model.conf")
+        .await
+        .unwrap();
+    let a = FileAdapter::new("examples/rbac_with_domains_policy.csv");
+    CachedEnforcer::new(m, a).await.unwrap()
+}
+
+fn subjects(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+async fn test_decide() {
+    let e = new_enforcer().await;
+
+    let decision = decide(
+        &e,
+        &subjects(&["bob", "alice"]),
+        Some("domain1"),
+        "/pen/1",
+        "GET",
+    )
+    .unwrap();
+    assert!(decision.allowed);
+    assert_eq!(decision.subject.as_deref(), Some("alice"));
+    assert_eq!(
+        decision.matched_policy,
+        Some(subjects(&["admin", "domain1", "/pen/1", "GET"]))
+    );
+
+    let decision = decide(&e, &subjects(&["alice"]), Some("domain2"), "/book/1", "GET").unwrap();
+    assert!(!decision.allowed);
+    assert_eq!(decision.subject, None);
+    assert_eq!(decision.matched_policy, None);
+}
+
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+async fn test_explain_cached_decision() {
+    let mut e = new_enforcer().await;
+    let alice = subjects(&["alice"]);
+
+    decide(&e, &alice, Some("domain1"), "/pen/1", "GET").unwrap();
+    // 缓存命中时无法得到命中的策略
+    let cached = decide(&e, &alice, Some("domain1"), "/pen/1", "GET").unwrap();
+    assert!(cached.allowed);
+    assert_eq!(cached.matched_policy, None);
+
+    let explained = explain(&mut e, &alice, Some("domain1"), "/pen/1", "GET").unwrap();
+    assert!(explained.allowed);
+    assert_eq!(
+        explained.matched_policy,
+        Some(subjects(&["admin", "domain1", "/pen/1", "GET"]))
+    );
+}
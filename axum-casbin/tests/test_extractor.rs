@@ -0,0 +1,135 @@
+use axum::{body::Body, routing::get, Router};
+use axum_casbin::{
+    CasbinAxumLayer, CasbinVals, DefaultExtractor, EnforceRequest, ExtractError, SubjectMatch,
+};
+use casbin::{DefaultModel, FileAdapter};
+use http::{header::HeaderName, request::Parts, Request, StatusCode};
+use tower::ServiceExt;
+
+async fn handler() {}
+
+async fn new_layer() -> CasbinAxumLayer {
+    let m = DefaultModel::from_file("examples/rbac_with_domains_model.conf")
+        .await
+        .unwrap();
+    let a = FileAdapter::new("examples/rbac_with_domains_policy.csv");
+    CasbinAxumLayer::new(m, a).await.unwrap()
+}
+
+fn app(layer: CasbinAxumLayer) -> Router {
+    Router::new()
+        .route("/pen/1", get(handler))
+        .route("/book/1", get(handler))
+        .route("/pens/{id}", get(handler))
+        .layer(layer)
+}
+
+async fn send(app: &Router, request: Request<Body>) -> StatusCode {
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+fn request(uri: &str, subjects: &[&str], domain: &str) -> http::request::Builder {
+    Request::builder().uri(uri).extension(CasbinVals {
+        subject: subjects.iter().map(|s| s.to_string()).collect(),
+        domain: Some(domain.to_string()),
+    })
+}
+
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+async fn test_default_extractor() {
+    let app = app(new_layer().await);
+
+    let req = request("/pen/1", &["alice"], "domain1")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&app, req).await, StatusCode::OK);
+
+    let req = Request::builder()
+        .uri("/pen/1")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&app, req).await, StatusCode::UNAUTHORIZED);
+
+    let req = request("/pen/1", &[], "domain1")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&app, req).await, StatusCode::UNAUTHORIZED);
+}
+
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+async fn test_domain_header() {
+    let layer = new_layer().await.with_extractor(
+        DefaultExtractor::new().with_domain_header(HeaderName::from_static("x-tenant")),
+    );
+    let app = app(layer);
+
+    let req = request("/book/1", &["bob"], "domain1")
+        .header("X-Tenant", "domain2")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&app, req).await, StatusCode::OK);
+
+    // 请求头不存在时使用 CasbinVals 中的域
+    let req = request("/book/1", &["bob"], "domain1")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&app, req).await, StatusCode::FORBIDDEN);
+}
+
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+async fn test_closure_extractor() {
+    // 以路径中的资源 ID 作为判定对象
+    let layer = new_layer().await.with_extractor(|parts: &Parts| {
+        let vals = DefaultExtractor::casbin_vals(parts)?;
+        let id = parts
+            .uri
+            .path()
+            .rsplit('/')
+            .next()
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| ExtractError::BadRequest("Missing resource id".to_string()))?;
+        Ok(EnforceRequest {
+            subjects: vals.subject.clone(),
+            domain: vals.domain.clone(),
+            object: format!("/pen/{}", id),
+            action: parts.method.as_str().to_string(),
+            subject_match: SubjectMatch::Any,
+        })
+    });
+    let app = app(layer);
+
+    let req = request("/pens/1", &["alice"], "domain1")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&app, req).await, StatusCode::OK);
+
+    let req = request("/pens/3", &["alice"], "domain1")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&app, req).await, StatusCode::FORBIDDEN);
+}
+
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+async fn test_subject_match() {
+    let any = app(new_layer().await);
+    let all = app(new_layer()
+        .await
+        .with_extractor(DefaultExtractor::new().with_subject_match(SubjectMatch::All)));
+
+    let req = || {
+        request("/pen/1", &["alice", "bob"], "domain1")
+            .body(Body::empty())
+            .unwrap()
+    };
+    assert_eq!(send(&any, req()).await, StatusCode::OK);
+    assert_eq!(send(&all, req()).await, StatusCode::FORBIDDEN);
+
+    let req = request("/pen/1", &["alice", "admin"], "domain1")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&all, req).await, StatusCode::OK);
+}
@@ -0,0 +1,93 @@
+#![cfg(any(feature = "watcher-redis", feature = "watcher-postgres"))]
+
+use axum_casbin::{apply_policy_change, PolicyChange};
+use casbin::{CachedEnforcer, CoreApi, DefaultModel, EventData, FileAdapter};
+
+async fn new_enforcer() -> CachedEnforcer {
+    let m = DefaultModel::from_file("examples/rbac_with_domains_model.conf")
+        .await
+        .unwrap();
+    let a = FileAdapter::new("examples/rbac_with_domains_policy.csv");
+    CachedEnforcer::new(m, a).await.unwrap()
+}
+
+fn rule(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn test_policy_change_from_event_data() {
+    let change: PolicyChange =
+        EventData::AddPolicy("p".into(), "p".into(), rule(&["admin", "domain1", "/pen/3", "GET"]))
+            .into();
+    assert_eq!(
+        change,
+        PolicyChange::AddPolicies {
+            sec: "p".into(),
+            ptype: "p".into(),
+            rules: vec![rule(&["admin", "domain1", "/pen/3", "GET"])],
+        }
+    );
+
+    let change: PolicyChange = EventData::ClearPolicy.into();
+    assert_eq!(change, PolicyChange::ReloadPolicy);
+}
+
+#[tokio::test]
+async fn test_apply_policy_change() {
+    let mut e = new_enforcer().await;
+    assert!(!e.enforce_mut(("alice", "domain1", "/pen/3", "GET")).unwrap());
+
+    // 增量添加后缓存被清空，新策略立即生效，且不会写回存储
+    apply_policy_change(
+        &mut e,
+        PolicyChange::AddPolicies {
+            sec: "p".into(),
+            ptype: "p".into(),
+            rules: vec![rule(&["admin", "domain1", "/pen/3", "GET"])],
+        },
+    )
+    .await
+    .unwrap();
+    assert!(e.enforce_mut(("alice", "domain1", "/pen/3", "GET")).unwrap());
+
+    // 角色继承关系同样增量更新
+    apply_policy_change(
+        &mut e,
+        PolicyChange::AddPolicies {
+            sec: "g".into(),
+            ptype: "g".into(),
+            rules: vec![rule(&["carol", "admin", "domain2"])],
+        },
+    )
+    .await
+    .unwrap();
+    assert!(e.enforce_mut(("carol", "domain2", "/book/1", "GET")).unwrap());
+
+    apply_policy_change(
+        &mut e,
+        PolicyChange::RemovePolicies {
+            sec: "p".into(),
+            ptype: "p".into(),
+            rules: vec![rule(&["admin", "domain1", "/pen/1", "GET"])],
+        },
+    )
+    .await
+    .unwrap();
+    assert!(!e.enforce_mut(("alice", "domain1", "/pen/1", "GET")).unwrap());
+
+    // 本地缺少要删除的策略时退化为全量重载，恢复到存储中的状态
+    apply_policy_change(
+        &mut e,
+        PolicyChange::RemovePolicies {
+            sec: "p".into(),
+            ptype: "p".into(),
+            rules: vec![rule(&["admin", "domain1", "/missing", "GET"])],
+        },
+    )
+    .await
+    .unwrap();
+    assert!(e.enforce_mut(("alice", "domain1", "/pen/1", "GET")).unwrap());
+    assert!(!e.enforce_mut(("alice", "domain1", "/pen/3", "GET")).unwrap());
+    assert!(!e.enforce_mut(("carol", "domain2", "/book/1", "GET")).unwrap());
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::{prelude::*, sea_orm::Statement};
+
+/// 子角色会继承上级角色的权限，内置的管理员和用户角色不能挂在超级管理员之下
+///
+/// 角色继承通过 casbin 的 `g` 规则生效，这里按现有的角色树为每个域补齐链接，
+/// 之后由角色的增删改维护。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let detach_roles_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"
+            UPDATE sys_role SET pid = '0'
+            WHERE code IN ('ROLE_ADMIN', 'ROLE_USER') AND pid = '1'
+        "#
+            .to_string(),
+        );
+
+        db.execute(detach_roles_stmt).await?;
+
+        let insert_role_links_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"
+            INSERT INTO casbin_rule (ptype, v0, v1, v2, v3, v4, v5)
+            SELECT 'g', child.code, parent.code, child.domain, '', '', ''
+            FROM sys_role child
+            JOIN sys_role parent ON parent.id = child.pid AND parent.domain = child.domain
+            WHERE NOT EXISTS (
+                SELECT 1 FROM casbin_rule c
+                WHERE c.ptype = 'g' AND c.v0 = child.code AND c.v1 = parent.code
+                  AND c.v2 = child.domain
+            )
+        "#
+            .to_string(),
+        );
+
+        db.execute(insert_role_links_stmt).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let delete_role_links_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            "DELETE FROM casbin_rule WHERE ptype = 'g'".to_string(),
+        );
+
+        db.execute(delete_role_links_stmt).await?;
+
+        let attach_roles_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"
+            UPDATE sys_role SET pid = '1'
+            WHERE code IN ('ROLE_ADMIN', 'ROLE_USER') AND pid = '0'
+        "#
+            .to_string(),
+        );
+
+        db.execute(attach_roles_stmt).await?;
+
+        Ok(())
+    }
+}
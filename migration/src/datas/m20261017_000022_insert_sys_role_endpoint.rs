@@ -0,0 +1,44 @@
+use sea_orm_migration::{prelude::*, sea_orm::Statement};
+
+/// 已有的角色接口策略都按直接分配记录，移除权限码时不会回收升级前授予的策略
+///
+/// 接口表在服务启动时才同步，全新安装时这里没有可记录的数据。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let insert_role_endpoints_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"
+            INSERT INTO sys_role_endpoint (role_id, endpoint_id, domain)
+            SELECT DISTINCT r.id, e.id, c.v1
+            FROM casbin_rule c
+            JOIN sys_role r ON r.code = c.v0 AND r.domain = c.v1
+            JOIN sys_endpoint e ON e.path = c.v2 AND e.method = c.v3
+            WHERE c.ptype = 'p'
+        "#
+            .to_string(),
+        );
+
+        db.execute(insert_role_endpoints_stmt).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        let delete_role_endpoints_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            "DELETE FROM sys_role_endpoint".to_string(),
+        );
+
+        db.execute(delete_role_endpoints_stmt).await?;
+
+        Ok(())
+    }
+}
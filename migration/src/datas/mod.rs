@@ -5,3 +5,12 @@ pub mod m20241024_034305_insert_sys_role_menu;
 pub mod m20241024_034526_insert_sys_role;
 pub mod m20241024_034744_insert_sys_menu;
 pub mod m20241024_082926_insert_casbin_rule;
+pub mod m20261017_000002_insert_login_security_casbin_rule;
+pub mod m20261017_000006_insert_access_key_casbin_rule;
+pub mod m20261017_000009_insert_authorization_explain_casbin_rule;
+pub mod m20261017_000011_insert_log_export_casbin_rule;
+pub mod m20261017_000013_insert_job_casbin_rule;
+pub mod m20261017_000015_insert_organization_casbin_rule;
+pub mod m20261017_000017_detach_built_in_role_parents;
+pub mod m20261017_000019_insert_permission_code_casbin_rule;
+pub mod m20261017_000022_insert_sys_role_endpoint;
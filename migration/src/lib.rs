@@ -22,6 +22,19 @@ impl MigratorTrait for Migrator {
             Box::new(schemas::m20241023_091204_create_sys_tokens::Migration),
             Box::new(schemas::m20241023_091210_create_sys_user_role::Migration),
             Box::new(schemas::m20241023_091159_create_sys_role_menu::Migration),
+            Box::new(schemas::m20261017_000001_create_sys_login_security_policy::Migration),
+            Box::new(schemas::m20261017_000003_create_sys_user_totp::Migration),
+            Box::new(schemas::m20261017_000004_create_sys_user_external_identity::Migration),
+            Box::new(schemas::m20261017_000005_alter_sys_access_key_scope::Migration),
+            Box::new(schemas::m20261017_000007_alter_sys_access_key_signature_schemes::Migration),
+            Box::new(schemas::m20261017_000008_create_sys_authorization_decision::Migration),
+            Box::new(schemas::m20261017_000010_create_log_created_at_index::Migration),
+            Box::new(schemas::m20261017_000012_create_sys_job::Migration),
+            Box::new(schemas::m20261017_000014_create_sys_organization_membership::Migration),
+            Box::new(schemas::m20261017_000016_create_tenant_isolation::Migration),
+            Box::new(schemas::m20261017_000018_create_sys_permission_code::Migration),
+            Box::new(schemas::m20261017_000020_create_sys_user_password_history::Migration),
+            Box::new(schemas::m20261017_000021_create_sys_role_endpoint::Migration),
             // 数据迁移
             Box::new(datas::m20241023_102950_insert_sys_domain::Migration),
             Box::new(datas::m20241024_033005_insert_sys_user::Migration),
@@ -30,6 +43,15 @@ impl MigratorTrait for Migrator {
             Box::new(datas::m20241024_033933_insert_sys_user_role::Migration),
             Box::new(datas::m20241024_034305_insert_sys_role_menu::Migration),
             Box::new(datas::m20241024_082926_insert_casbin_rule::Migration),
+            Box::new(datas::m20261017_000002_insert_login_security_casbin_rule::Migration),
+            Box::new(datas::m20261017_000006_insert_access_key_casbin_rule::Migration),
+            Box::new(datas::m20261017_000009_insert_authorization_explain_casbin_rule::Migration),
+            Box::new(datas::m20261017_000011_insert_log_export_casbin_rule::Migration),
+            Box::new(datas::m20261017_000013_insert_job_casbin_rule::Migration),
+            Box::new(datas::m20261017_000015_insert_organization_casbin_rule::Migration),
+            Box::new(datas::m20261017_000017_detach_built_in_role_parents::Migration),
+            Box::new(datas::m20261017_000019_insert_permission_code_casbin_rule::Migration),
+            Box::new(datas::m20261017_000022_insert_sys_role_endpoint::Migration),
         ]
     }
 }
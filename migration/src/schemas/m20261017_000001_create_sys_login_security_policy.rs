@@ -0,0 +1,120 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysLoginSecurityPolicy::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::Domain)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::MaxAttempts)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::AttemptWindow)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::LockoutDuration)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::LockoutBackoffMultiplier)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::MaxLockoutDuration)
+                            .big_integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::IpAllowList)
+                            .json_binary()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::IpDenyList)
+                            .json_binary()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::LoginWindows)
+                            .json_binary()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::CreatedBy)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::UpdatedAt)
+                            .timestamp()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysLoginSecurityPolicy::UpdatedBy)
+                            .string()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(SysLoginSecurityPolicy::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysLoginSecurityPolicy {
+    Table,
+    Id,
+    Domain,
+    MaxAttempts,
+    AttemptWindow,
+    LockoutDuration,
+    LockoutBackoffMultiplier,
+    MaxLockoutDuration,
+    IpAllowList,
+    IpDenyList,
+    LoginWindows,
+    CreatedAt,
+    CreatedBy,
+    UpdatedAt,
+    UpdatedBy,
+}
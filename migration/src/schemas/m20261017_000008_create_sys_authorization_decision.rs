@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysAuthorizationDecision::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysAuthorizationDecision::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SysAuthorizationDecision::Subjects)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysAuthorizationDecision::Subject)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysAuthorizationDecision::Domain)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysAuthorizationDecision::Object)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysAuthorizationDecision::Action)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysAuthorizationDecision::Allowed)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysAuthorizationDecision::MatchedPolicy)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysAuthorizationDecision::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_authorization_decision_created_at")
+                    .table(SysAuthorizationDecision::Table)
+                    .col(SysAuthorizationDecision::CreatedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(SysAuthorizationDecision::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysAuthorizationDecision {
+    Table,
+    Id,
+    Subjects,
+    Subject,
+    Domain,
+    Object,
+    Action,
+    Allowed,
+    MatchedPolicy,
+    CreatedAt,
+}
@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_login_log_created_at")
+                    .table(SysLoginLog::Table)
+                    .col(SysLoginLog::CreatedAt)
+                    .col(SysLoginLog::Id)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_operation_log_created_at")
+                    .table(SysOperationLog::Table)
+                    .col(SysOperationLog::CreatedAt)
+                    .col(SysOperationLog::Id)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_sys_operation_log_created_at")
+                    .table(SysOperationLog::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_sys_login_log_created_at")
+                    .table(SysLoginLog::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysLoginLog {
+    Table,
+    Id,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum SysOperationLog {
+    Table,
+    Id,
+    CreatedAt,
+}
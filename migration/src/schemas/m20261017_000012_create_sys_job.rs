@@ -0,0 +1,105 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysJob::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysJob::Name)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SysJob::Cron).string().not_null())
+                    .col(ColumnDef::new(SysJob::Description).string().not_null())
+                    .col(
+                        ColumnDef::new(SysJob::Paused)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(SysJob::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysJobRun::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysJobRun::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SysJobRun::JobName).string().not_null())
+                    .col(ColumnDef::new(SysJobRun::Trigger).string().not_null())
+                    .col(ColumnDef::new(SysJobRun::Instance).string().not_null())
+                    .col(ColumnDef::new(SysJobRun::Status).string().not_null())
+                    .col(ColumnDef::new(SysJobRun::Error).text().null())
+                    .col(ColumnDef::new(SysJobRun::StartedAt).timestamp().not_null())
+                    .col(ColumnDef::new(SysJobRun::EndedAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_job_run_job_name_started_at")
+                    .table(SysJobRun::Table)
+                    .col(SysJobRun::JobName)
+                    .col(SysJobRun::StartedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysJobRun::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(SysJob::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysJob {
+    Table,
+    Name,
+    Cron,
+    Description,
+    Paused,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum SysJobRun {
+    Table,
+    Id,
+    JobName,
+    Trigger,
+    Instance,
+    Status,
+    Error,
+    StartedAt,
+    EndedAt,
+}
@@ -0,0 +1,151 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysUserOrganization::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysUserOrganization::UserId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysUserOrganization::OrgId)
+                            .string()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(SysUserOrganization::UserId)
+                            .col(SysUserOrganization::OrgId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_user_organization_user_id")
+                            .from(SysUserOrganization::Table, SysUserOrganization::UserId)
+                            .to(Alias::new("sys_user"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_user_organization_org_id")
+                            .from(SysUserOrganization::Table, SysUserOrganization::OrgId)
+                            .to(Alias::new("sys_organization"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_user_organization_org_id")
+                    .table(SysUserOrganization::Table)
+                    .col(SysUserOrganization::OrgId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // 已有角色默认保持全部数据权限
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysRole::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(SysRole::DataScope)
+                            .string()
+                            .not_null()
+                            .default("all"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysRoleDataScopeOrg::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysRoleDataScopeOrg::RoleId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysRoleDataScopeOrg::OrgId)
+                            .string()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(SysRoleDataScopeOrg::RoleId)
+                            .col(SysRoleDataScopeOrg::OrgId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_role_data_scope_org_role_id")
+                            .from(SysRoleDataScopeOrg::Table, SysRoleDataScopeOrg::RoleId)
+                            .to(SysRole::Table, SysRole::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_role_data_scope_org_org_id")
+                            .from(SysRoleDataScopeOrg::Table, SysRoleDataScopeOrg::OrgId)
+                            .to(Alias::new("sys_organization"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysRoleDataScopeOrg::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysRole::Table)
+                    .drop_column(SysRole::DataScope)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(SysUserOrganization::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysUserOrganization {
+    Table,
+    UserId,
+    OrgId,
+}
+
+#[derive(DeriveIden)]
+enum SysRole {
+    Table,
+    Id,
+    DataScope,
+}
+
+#[derive(DeriveIden)]
+enum SysRoleDataScopeOrg {
+    Table,
+    RoleId,
+    OrgId,
+}
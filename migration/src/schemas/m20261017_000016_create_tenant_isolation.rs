@@ -0,0 +1,167 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 已有角色和组织归属内置域
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysRole::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(SysRole::Domain)
+                            .string()
+                            .not_null()
+                            .default("built-in"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_role_domain")
+                    .table(SysRole::Table)
+                    .col(SysRole::Domain)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysOrganization::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(SysOrganization::Domain)
+                            .string()
+                            .not_null()
+                            .default("built-in"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_organization_domain")
+                    .table(SysOrganization::Table)
+                    .col(SysOrganization::Domain)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysTenantSwitchLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysTenantSwitchLog::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SysTenantSwitchLog::UserId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysTenantSwitchLog::Username)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysTenantSwitchLog::HomeDomain)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysTenantSwitchLog::TargetDomain)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysTenantSwitchLog::Method)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SysTenantSwitchLog::Url).string().not_null())
+                    .col(
+                        ColumnDef::new(SysTenantSwitchLog::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_tenant_switch_log_created_at")
+                    .table(SysTenantSwitchLog::Table)
+                    .col(SysTenantSwitchLog::CreatedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysTenantSwitchLog::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysOrganization::Table)
+                    .drop_column(SysOrganization::Domain)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SysRole::Table)
+                    .drop_column(SysRole::Domain)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysRole {
+    Table,
+    Domain,
+}
+
+#[derive(DeriveIden)]
+enum SysOrganization {
+    Table,
+    Domain,
+}
+
+#[derive(DeriveIden)]
+enum SysTenantSwitchLog {
+    Table,
+    Id,
+    UserId,
+    Username,
+    HomeDomain,
+    TargetDomain,
+    Method,
+    Url,
+    CreatedAt,
+}
@@ -0,0 +1,162 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysPermissionCode::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysPermissionCode::Id)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SysPermissionCode::MenuId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysPermissionCode::Code)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(SysPermissionCode::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(SysPermissionCode::EndpointId)
+                            .string()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysPermissionCode::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SysPermissionCode::CreatedBy)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysPermissionCode::UpdatedAt)
+                            .timestamp()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(SysPermissionCode::UpdatedBy).string().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_permission_code_menu_id")
+                            .from(SysPermissionCode::Table, SysPermissionCode::MenuId)
+                            .to(Alias::new("sys_menu"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_permission_code_endpoint_id")
+                            .from(SysPermissionCode::Table, SysPermissionCode::EndpointId)
+                            .to(Alias::new("sys_endpoint"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sys_permission_code_menu_id")
+                    .table(SysPermissionCode::Table)
+                    .col(SysPermissionCode::MenuId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysRolePermissionCode::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SysRolePermissionCode::RoleId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysRolePermissionCode::PermissionCodeId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SysRolePermissionCode::Domain)
+                            .string()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(SysRolePermissionCode::RoleId)
+                            .col(SysRolePermissionCode::PermissionCodeId)
+                            .col(SysRolePermissionCode::Domain),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_role_permission_code_role_id")
+                            .from(SysRolePermissionCode::Table, SysRolePermissionCode::RoleId)
+                            .to(Alias::new("sys_role"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_role_permission_code_permission_code_id")
+                            .from(
+                                SysRolePermissionCode::Table,
+                                SysRolePermissionCode::PermissionCodeId,
+                            )
+                            .to(SysPermissionCode::Table, SysPermissionCode::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysRolePermissionCode::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(SysPermissionCode::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysPermissionCode {
+    Table,
+    Id,
+    MenuId,
+    Code,
+    Name,
+    EndpointId,
+    CreatedAt,
+    CreatedBy,
+    UpdatedAt,
+    UpdatedBy,
+}
+
+#[derive(DeriveIden)]
+enum SysRolePermissionCode {
+    Table,
+    RoleId,
+    PermissionCodeId,
+    Domain,
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SysRoleEndpoint::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(SysRoleEndpoint::RoleId).string().not_null())
+                    .col(
+                        ColumnDef::new(SysRoleEndpoint::EndpointId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SysRoleEndpoint::Domain).string().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(SysRoleEndpoint::RoleId)
+                            .col(SysRoleEndpoint::EndpointId)
+                            .col(SysRoleEndpoint::Domain),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_role_endpoint_role_id")
+                            .from(SysRoleEndpoint::Table, SysRoleEndpoint::RoleId)
+                            .to(Alias::new("sys_role"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sys_role_endpoint_endpoint_id")
+                            .from(SysRoleEndpoint::Table, SysRoleEndpoint::EndpointId)
+                            .to(Alias::new("sys_endpoint"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SysRoleEndpoint::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SysRoleEndpoint {
+    Table,
+    RoleId,
+    EndpointId,
+    Domain,
+}
@@ -11,6 +11,19 @@ pub mod m20241023_091155_create_sys_organization;
 pub mod m20241023_091159_create_sys_role_menu;
 pub mod m20241023_091204_create_sys_tokens;
 pub mod m20241023_091210_create_sys_user_role;
+pub mod m20261017_000001_create_sys_login_security_policy;
+pub mod m20261017_000003_create_sys_user_totp;
+pub mod m20261017_000004_create_sys_user_external_identity;
+pub mod m20261017_000005_alter_sys_access_key_scope;
+pub mod m20261017_000007_alter_sys_access_key_signature_schemes;
+pub mod m20261017_000008_create_sys_authorization_decision;
+pub mod m20261017_000010_create_log_created_at_index;
+pub mod m20261017_000012_create_sys_job;
+pub mod m20261017_000014_create_sys_organization_membership;
+pub mod m20261017_000016_create_tenant_isolation;
+pub mod m20261017_000018_create_sys_permission_code;
+pub mod m20261017_000020_create_sys_user_password_history;
+pub mod m20261017_000021_create_sys_role_endpoint;
 
 // Web3 migrations
 pub mod m20260227_000001_create_web3_wallet;
@@ -2,7 +2,7 @@ use casbin::{error::AdapterError, Error as CasbinError, Filter, Result};
 use sea_orm::{
     ActiveModelTrait,
     ActiveValue::{NotSet, Set},
-    ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter,
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter, TransactionTrait,
 };
 
 use crate::entity::{self, Column, Entity};
@@ -91,6 +91,99 @@ pub(crate) async fn remove_filtered_policy<C: ConnectionTrait>(
         .map_err(|err| CasbinError::from(AdapterError(Box::new(err))))
 }
 
+pub(crate) async fn update_policy<'rule, C: ConnectionTrait>(
+    conn: &C,
+    old_rule: RuleWithType<'rule>,
+    new_rule: RuleWithType<'rule>,
+) -> Result<bool> {
+    let mut update = Entity::update_many()
+        .set(create_active_model(&new_rule))
+        .filter(Column::Ptype.eq(old_rule.ptype));
+    for (column, value) in COLUMNS.iter().zip(old_rule.rule.values.iter()) {
+        update = update.filter(column.eq(*value));
+    }
+    update
+        .exec(conn)
+        .await
+        .map(|result| result.rows_affected == 1)
+        .map_err(|err| CasbinError::from(AdapterError(Box::new(err))))
+}
+
+/// 在同一事务中逐条更新，任意一条旧规则不存在时整体回滚
+pub(crate) async fn update_policies<'rule, C: ConnectionTrait + TransactionTrait>(
+    conn: &C,
+    old_rules: Vec<RuleWithType<'rule>>,
+    new_rules: Vec<RuleWithType<'rule>>,
+) -> Result<bool> {
+    let txn = conn
+        .begin()
+        .await
+        .map_err(|err| CasbinError::from(AdapterError(Box::new(err))))?;
+
+    for (old_rule, new_rule) in old_rules.into_iter().zip(new_rules) {
+        if !update_policy(&txn, old_rule, new_rule).await? {
+            txn.rollback()
+                .await
+                .map_err(|err| CasbinError::from(AdapterError(Box::new(err))))?;
+            return Ok(false);
+        }
+    }
+
+    txn.commit()
+        .await
+        .map(|_| true)
+        .map_err(|err| CasbinError::from(AdapterError(Box::new(err))))
+}
+
+/// 在同一事务中将 `removed` 替换为 `added`
+///
+/// 按位置成对的规则原地更新，多出的旧规则删除、多出的新规则插入；任意一条旧规则不存在时整体回滚。
+pub(crate) async fn replace_policies<'rule, C: ConnectionTrait + TransactionTrait>(
+    conn: &C,
+    mut removed: Vec<RuleWithType<'rule>>,
+    mut added: Vec<RuleWithType<'rule>>,
+) -> Result<bool> {
+    let txn = conn
+        .begin()
+        .await
+        .map_err(|err| CasbinError::from(AdapterError(Box::new(err))))?;
+
+    let paired = removed.len().min(added.len());
+    let removed_rest = removed.split_off(paired);
+    let added_rest = added.split_off(paired);
+
+    let mut applied = true;
+    for (old_rule, new_rule) in removed.into_iter().zip(added) {
+        if !update_policy(&txn, old_rule, new_rule).await? {
+            applied = false;
+            break;
+        }
+    }
+    if applied {
+        for old_rule in removed_rest {
+            if !remove_policy(&txn, old_rule).await? {
+                applied = false;
+                break;
+            }
+        }
+    }
+    if applied && !added_rest.is_empty() {
+        add_policies(&txn, added_rest).await?;
+    }
+
+    if !applied {
+        txn.rollback()
+            .await
+            .map_err(|err| CasbinError::from(AdapterError(Box::new(err))))?;
+        return Ok(false);
+    }
+
+    txn.commit()
+        .await
+        .map(|_| true)
+        .map_err(|err| CasbinError::from(AdapterError(Box::new(err))))
+}
+
 pub(crate) async fn load_policy<C: ConnectionTrait>(conn: &C) -> Result<Vec<entity::Model>> {
     entity::Entity::find()
         .all(conn)
@@ -98,15 +191,21 @@ pub(crate) async fn load_policy<C: ConnectionTrait>(conn: &C) -> Result<Vec<enti
         .map_err(|err| CasbinError::from(AdapterError(Box::new(err))))
 }
 
+/// 按过滤器加载策略
+///
+/// `p_types`/`g_types` 为模型中定义的策略类型，以等值条件代替前缀匹配，
+/// 使 `(ptype, v1)`、`(ptype, v2)` 等索引可以命中。
 pub(crate) async fn load_filtered_policy<'conn, 'filter, C: ConnectionTrait>(
     conn: &'conn C,
     filter: Filter<'filter>,
+    p_types: Vec<String>,
+    g_types: Vec<String>,
 ) -> Result<Vec<entity::Model>> {
     let g_filter = Rule::from_slice(&filter.g);
     let p_filter = Rule::from_slice(&filter.p);
 
-    let g_condition = create_condition_from_rule("g", &g_filter);
-    let p_condition = create_condition_from_rule("p", &p_filter);
+    let g_condition = create_condition_from_rule(g_types, &g_filter);
+    let p_condition = create_condition_from_rule(p_types, &p_filter);
 
     Entity::find()
         .filter(Condition::any().add(g_condition).add(p_condition))
@@ -115,13 +214,13 @@ pub(crate) async fn load_filtered_policy<'conn, 'filter, C: ConnectionTrait>(
         .map_err(|err| CasbinError::from(AdapterError(Box::new(err))))
 }
 
-fn create_condition_from_rule(prefix: &str, rule: &Rule) -> Condition {
+fn create_condition_from_rule(ptypes: Vec<String>, rule: &Rule) -> Condition {
     rule.values
         .iter()
         .zip(COLUMNS.iter())
         .filter(|(value, _)| !value.is_empty())
         .fold(
-            Condition::all().add(Column::Ptype.starts_with(prefix)),
+            Condition::all().add(Column::Ptype.is_in(ptypes)),
             |acc, (value, column)| acc.add(column.eq(*value)),
         )
 }
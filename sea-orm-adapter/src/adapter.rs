@@ -1,12 +1,22 @@
 use async_trait::async_trait;
 use casbin::{error::AdapterError, Adapter, Error as CasbinError, Filter, Model, Result};
-use sea_orm::ConnectionTrait;
+use sea_orm::{ConnectionTrait, TransactionTrait};
 
 use crate::{
     action::{self, Rule, RuleWithType},
     entity, migration,
 };
 
+/// 只加载指定域的策略，适用于 `rbac_with_domains` 模型
+///
+/// 域位于 `p` 策略的第二列和 `g` 策略的第三列，配合 `Enforcer::load_filtered_policy` 使用。
+pub fn domain_filter(domain: &str) -> Filter<'_> {
+    Filter {
+        p: vec!["", domain],
+        g: vec!["", "", domain],
+    }
+}
+
 pub struct SeaOrmAdapter<C> {
     conn: C,
     is_filtered: bool,
@@ -25,6 +35,96 @@ impl<C: ConnectionTrait> SeaOrmAdapter<C> {
 }
 
 impl<C> SeaOrmAdapter<C> {
+    /// 基于已完成迁移的连接创建适配器，不再执行建表和建索引
+    pub fn without_migration(conn: C) -> Self {
+        Self {
+            conn,
+            is_filtered: false,
+        }
+    }
+}
+
+impl<C: ConnectionTrait + TransactionTrait> SeaOrmAdapter<C> {
+    /// 将 `old_rule` 原地更新为 `new_rule`，旧规则不存在时返回 `false`
+    pub async fn update_policy(
+        &mut self,
+        _sec: &str,
+        ptype: &str,
+        old_rule: Vec<String>,
+        new_rule: Vec<String>,
+    ) -> Result<bool> {
+        match (
+            Self::transform_policy_line(ptype, &old_rule),
+            Self::transform_policy_line(ptype, &new_rule),
+        ) {
+            (Some(old_rule), Some(new_rule)) => {
+                action::update_policy(&self.conn, old_rule, new_rule).await
+            },
+            _ => Ok(false),
+        }
+    }
+
+    /// 在同一事务中按位置将 `old_rules` 更新为 `new_rules`，任意一条失败时全部回滚
+    pub async fn update_policies(
+        &mut self,
+        _sec: &str,
+        ptype: &str,
+        old_rules: Vec<Vec<String>>,
+        new_rules: Vec<Vec<String>>,
+    ) -> Result<bool> {
+        if old_rules.is_empty() || old_rules.len() != new_rules.len() {
+            return Ok(false);
+        }
+
+        let old_rules: Vec<_> = old_rules
+            .iter()
+            .filter_map(|x| Self::transform_policy_line(ptype, x))
+            .collect();
+        let new_rules: Vec<_> = new_rules
+            .iter()
+            .filter_map(|x| Self::transform_policy_line(ptype, x))
+            .collect();
+
+        if old_rules.len() != new_rules.len() {
+            return Ok(false);
+        }
+
+        action::update_policies(&self.conn, old_rules, new_rules).await
+    }
+
+    /// 在同一事务中删除 `removed` 并写入 `added`，成对的规则原地更新，任意一条旧规则不存在时全部回滚
+    pub async fn replace_policies(
+        &mut self,
+        _sec: &str,
+        ptype: &str,
+        removed: Vec<Vec<String>>,
+        added: Vec<Vec<String>>,
+    ) -> Result<bool> {
+        let removed: Vec<_> = removed
+            .iter()
+            .filter_map(|x| Self::transform_policy_line(ptype, x))
+            .collect();
+        let added: Vec<_> = added
+            .iter()
+            .filter_map(|x| Self::transform_policy_line(ptype, x))
+            .collect();
+
+        if removed.is_empty() && added.is_empty() {
+            return Ok(false);
+        }
+
+        action::replace_policies(&self.conn, removed, added).await
+    }
+}
+
+impl<C> SeaOrmAdapter<C> {
+    fn policy_types(m: &dyn Model, sec: &str) -> Vec<String> {
+        m.get_model()
+            .get(sec)
+            .map(|ast_map| ast_map.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
     fn transform_policy_line<'a>(ptype: &'a str, rule: &'a [String]) -> Option<RuleWithType<'a>> {
         if ptype.trim().is_empty() || rule.is_empty() {
             return None;
@@ -71,7 +171,9 @@ impl<C: ConnectionTrait + Send + Sync> Adapter for SeaOrmAdapter<C> {
     }
 
     async fn load_filtered_policy<'a>(&mut self, m: &mut dyn Model, f: Filter<'a>) -> Result<()> {
-        let rules = action::load_filtered_policy(&self.conn, f).await?;
+        let p_types = Self::policy_types(m, "p");
+        let g_types = Self::policy_types(m, "g");
+        let rules = action::load_filtered_policy(&self.conn, f, p_types, g_types).await?;
         self.is_filtered = true;
 
         for rule in &rules {
@@ -404,7 +506,7 @@ mod tests {
             .remove_filtered_policy("", "g", 0, to_owned(vec!["carol"]),)
             .await
             .unwrap());
-        assert_eq!(vec![String::new(); 0], e.get_roles_for_user("carol", None));
+        assert_eq!(Vec::<String>::new(), e.get_roles_for_user("carol", None));
 
         // GitHub issue: https://github.com/casbin-rs/sqlx-adapter/pull/90
         // add policies:
@@ -481,4 +583,182 @@ mod tests {
         assert!(!e.enforce(("bob", "domain2", "data2", "read")).unwrap());
         assert!(!e.enforce(("bob", "domain2", "data2", "write")).unwrap());
     }
+
+    async fn sqlite_adapter() -> SeaOrmAdapter<sea_orm::DatabaseConnection> {
+        // 内存数据库每个连接相互独立，限制为单连接
+        let mut opt = ConnectOptions::new("sqlite::memory:");
+        opt.max_connections(1).min_connections(1);
+        let db = Database::connect(opt).await.unwrap();
+        SeaOrmAdapter::new(db).await.unwrap()
+    }
+
+    #[cfg_attr(
+        any(feature = "runtime-tokio-native-tls", feature = "runtime-tokio-rustls"),
+        tokio::test
+    )]
+    async fn test_update_policies() {
+        let mut adapter = sqlite_adapter().await;
+        assert!(adapter
+            .add_policies(
+                "p",
+                "p",
+                vec![
+                    to_owned(vec!["admin", "domain1", "/pen/1", "GET"]),
+                    to_owned(vec!["admin", "domain1", "/pen/2", "GET"]),
+                ]
+            )
+            .await
+            .unwrap());
+
+        assert!(adapter
+            .update_policy(
+                "p",
+                "p",
+                to_owned(vec!["admin", "domain1", "/pen/1", "GET"]),
+                to_owned(vec!["admin", "domain1", "/pen/1", "POST"]),
+            )
+            .await
+            .unwrap());
+        assert!(!adapter
+            .update_policy(
+                "p",
+                "p",
+                to_owned(vec!["admin", "domain1", "/pen/1", "GET"]),
+                to_owned(vec!["admin", "domain1", "/pen/1", "PUT"]),
+            )
+            .await
+            .unwrap());
+
+        // 第二条旧规则不存在，整批回滚
+        assert!(!adapter
+            .update_policies(
+                "p",
+                "p",
+                vec![
+                    to_owned(vec!["admin", "domain1", "/pen/2", "GET"]),
+                    to_owned(vec!["admin", "domain1", "/missing", "GET"]),
+                ],
+                vec![
+                    to_owned(vec!["admin", "domain1", "/pen/2", "POST"]),
+                    to_owned(vec!["admin", "domain1", "/missing", "POST"]),
+                ],
+            )
+            .await
+            .unwrap());
+        assert!(adapter
+            .remove_policy(
+                "p",
+                "p",
+                to_owned(vec!["admin", "domain1", "/pen/2", "GET"])
+            )
+            .await
+            .unwrap());
+
+        assert!(adapter
+            .update_policies(
+                "p",
+                "p",
+                vec![to_owned(vec!["admin", "domain1", "/pen/1", "POST"])],
+                vec![to_owned(vec!["admin", "domain2", "/pen/1", "POST"])],
+            )
+            .await
+            .unwrap());
+        assert!(adapter
+            .remove_policy(
+                "p",
+                "p",
+                to_owned(vec!["admin", "domain2", "/pen/1", "POST"])
+            )
+            .await
+            .unwrap());
+    }
+
+    #[cfg_attr(
+        any(feature = "runtime-tokio-native-tls", feature = "runtime-tokio-rustls"),
+        tokio::test
+    )]
+    async fn test_replace_policies() {
+        let mut adapter = sqlite_adapter().await;
+        assert!(adapter
+            .add_policies(
+                "p",
+                "p",
+                vec![
+                    to_owned(vec!["admin", "domain1", "/pen/1", "GET"]),
+                    to_owned(vec!["admin", "domain1", "/pen/2", "GET"]),
+                ]
+            )
+            .await
+            .unwrap());
+
+        // 旧规则不存在时不做任何修改
+        assert!(!adapter
+            .replace_policies(
+                "p",
+                "p",
+                vec![
+                    to_owned(vec!["admin", "domain1", "/pen/1", "GET"]),
+                    to_owned(vec!["admin", "domain1", "/missing", "GET"]),
+                ],
+                vec![to_owned(vec!["admin", "domain1", "/pen/3", "GET"])],
+            )
+            .await
+            .unwrap());
+
+        assert!(adapter
+            .replace_policies(
+                "p",
+                "p",
+                vec![to_owned(vec!["admin", "domain1", "/pen/1", "GET"])],
+                vec![
+                    to_owned(vec!["admin", "domain1", "/pen/3", "GET"]),
+                    to_owned(vec!["admin", "domain1", "/pen/4", "GET"]),
+                ],
+            )
+            .await
+            .unwrap());
+
+        for (path, exists) in [
+            ("/pen/1", false),
+            ("/pen/2", true),
+            ("/pen/3", true),
+            ("/pen/4", true),
+        ] {
+            assert_eq!(
+                adapter
+                    .remove_policy("p", "p", to_owned(vec!["admin", "domain1", path, "GET"]))
+                    .await
+                    .unwrap(),
+                exists
+            );
+        }
+    }
+
+    #[cfg_attr(
+        any(feature = "runtime-tokio-native-tls", feature = "runtime-tokio-rustls"),
+        tokio::test
+    )]
+    async fn test_load_domain_policy() {
+        use casbin::prelude::*;
+
+        let mut adapter = sqlite_adapter().await;
+        let mut e = Enforcer::new(
+            "examples/rbac_with_domains_model.conf",
+            "examples/rbac_with_domains_policy.csv",
+        )
+        .await
+        .unwrap();
+        adapter.save_policy(e.get_mut_model()).await.unwrap();
+        e.set_adapter(adapter).await.unwrap();
+
+        e.load_filtered_policy(crate::domain_filter("domain1"))
+            .await
+            .unwrap();
+        assert!(e.is_filtered());
+        assert!(e.enforce(("alice", "domain1", "data1", "read")).unwrap());
+        assert!(!e.enforce(("bob", "domain2", "data2", "read")).unwrap());
+        assert!(e
+            .get_filtered_policy(1, vec!["domain2".to_owned()])
+            .is_empty());
+    }
 }
@@ -1,4 +1,4 @@
-pub use adapter::SeaOrmAdapter;
+pub use adapter::{domain_filter, SeaOrmAdapter};
 pub use migration::{down, up};
 
 mod action;
@@ -1,6 +1,6 @@
 use sea_orm::{
     sea_query::{ColumnDef, Index, Table},
-    ConnectionTrait, DbErr, DeriveIden, ExecResult,
+    ConnectionTrait, DbBackend, DbErr, DeriveIden, ExecResult, Statement,
 };
 
 #[derive(DeriveIden)]
@@ -52,7 +52,46 @@ pub async fn up<C: ConnectionTrait>(conn: &C) -> Result<ExecResult, DbErr> {
         .to_owned();
 
     let builder = conn.get_database_backend();
-    conn.execute(builder.build(&create_table)).await
+    let result = conn.execute(builder.build(&create_table)).await?;
+
+    // 唯一索引已覆盖 (ptype, v0) 前缀，这里补充按域过滤时用到的 v1、v2 列
+    create_index_if_not_exists(conn, "idx_casbin_rule_ptype_v1", CasbinRule::V1).await?;
+    create_index_if_not_exists(conn, "idx_casbin_rule_ptype_v2", CasbinRule::V2).await?;
+
+    Ok(result)
+}
+
+/// MySQL 不支持 `CREATE INDEX IF NOT EXISTS`，需要先查询索引是否存在
+async fn create_index_if_not_exists<C: ConnectionTrait>(
+    conn: &C,
+    name: &str,
+    column: CasbinRule,
+) -> Result<(), DbErr> {
+    let builder = conn.get_database_backend();
+    if builder == DbBackend::MySql {
+        let exists = conn
+            .query_one(Statement::from_sql_and_values(
+                builder,
+                "SELECT 1 FROM information_schema.statistics \
+                 WHERE table_schema = DATABASE() AND table_name = 'casbin_rule' AND index_name = ?",
+                [name.into()],
+            ))
+            .await?
+            .is_some();
+        if exists {
+            return Ok(());
+        }
+    }
+
+    let create_index = Index::create()
+        .if_not_exists()
+        .name(name)
+        .table(CasbinRule::Table)
+        .col(CasbinRule::Ptype)
+        .col(column)
+        .to_owned();
+    conn.execute(builder.build(&create_index)).await?;
+    Ok(())
 }
 
 pub async fn down<C: ConnectionTrait>(conn: &C) -> Result<ExecResult, DbErr> {
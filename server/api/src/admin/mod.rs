@@ -1,23 +1,31 @@
 pub use sys_access_key_api::SysAccessKeyApi;
+pub use sys_account_api::SysAccountApi;
 pub use sys_authentication_api::SysAuthenticationApi;
 pub use sys_domain_api::SysDomainApi;
 pub use sys_endpoint_api::SysEndpointApi;
+pub use sys_job_api::SysJobApi;
 pub use sys_login_log_api::SysLoginLogApi;
+pub use sys_login_security_api::SysLoginSecurityApi;
 pub use sys_menu_api::SysMenuApi;
 pub use sys_operation_log_api::SysOperationLogApi;
 pub use sys_organization_api::SysOrganizationApi;
 pub use sys_role_api::SysRoleApi;
 pub use sys_sandbox_api::SysSandboxApi;
 pub use sys_user_api::SysUserApi;
+pub use sys_user_totp_api::SysUserTotpApi;
 
 mod sys_access_key_api;
+mod sys_account_api;
 mod sys_authentication_api;
 mod sys_domain_api;
 mod sys_endpoint_api;
+mod sys_job_api;
 mod sys_login_log_api;
+mod sys_login_security_api;
 mod sys_menu_api;
 mod sys_operation_log_api;
 mod sys_organization_api;
 mod sys_role_api;
 mod sys_sandbox_api;
 mod sys_user_api;
+mod sys_user_totp_api;
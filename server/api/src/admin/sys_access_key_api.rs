@@ -4,10 +4,15 @@ use axum::{
     extract::{Path, Query},
     Extension,
 };
-use server_core::web::{auth::User, error::AppError, page::PaginatedData, res::Res, validator::ValidatedForm};
+use axum_casbin::CasbinAxumLayer;
+use server_core::web::{
+    auth::User, error::AppError, page::PaginatedData, res::Res, tenant::TenantContext,
+    validator::ValidatedForm,
+};
 use server_service::admin::{
-    AccessKeyPageRequest, CreateAccessKeyInput, SysAccessKeyModel, SysAccessKeyService,
-    TAccessKeyService,
+    AccessKeyPageRequest, AssignAccessKeyPermissionInput, CreateAccessKeyInput,
+    RotateAccessKeyInput, SysAccessKeyModel, SysAccessKeyService, TAccessKeyService,
+    UpdateAccessKeyInput,
 };
 
 pub struct SysAccessKeyApi;
@@ -16,9 +21,10 @@ impl SysAccessKeyApi {
     pub async fn get_paginated_access_keys(
         Query(params): Query<AccessKeyPageRequest>,
         Extension(service): Extension<Arc<SysAccessKeyService>>,
+        Extension(tenant): Extension<TenantContext>,
     ) -> Result<Res<PaginatedData<SysAccessKeyModel>>, AppError> {
         service
-            .find_paginated_access_keys(params)
+            .find_paginated_access_keys(params, &tenant)
             .await
             .map(Res::new_data)
     }
@@ -26,15 +32,61 @@ impl SysAccessKeyApi {
     pub async fn create_access_key(
         Extension(service): Extension<Arc<SysAccessKeyService>>,
         Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
         ValidatedForm(input): ValidatedForm<CreateAccessKeyInput>,
     ) -> Result<Res<SysAccessKeyModel>, AppError> {
-        service.create_access_key(input, user).await.map(Res::new_data)
+        service
+            .create_access_key(input, user, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn update_access_key(
+        Extension(service): Extension<Arc<SysAccessKeyService>>,
+        Extension(tenant): Extension<TenantContext>,
+        ValidatedForm(input): ValidatedForm<UpdateAccessKeyInput>,
+    ) -> Result<Res<SysAccessKeyModel>, AppError> {
+        service
+            .update_access_key(input, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn rotate_access_key(
+        Extension(service): Extension<Arc<SysAccessKeyService>>,
+        Extension(tenant): Extension<TenantContext>,
+        ValidatedForm(input): ValidatedForm<RotateAccessKeyInput>,
+    ) -> Result<Res<SysAccessKeyModel>, AppError> {
+        service
+            .rotate_access_key(input, &tenant)
+            .await
+            .map(Res::new_data)
     }
 
     pub async fn delete_access_key(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysAccessKeyService>>,
+        Extension(tenant): Extension<TenantContext>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
+    ) -> Result<Res<()>, AppError> {
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .delete_access_key(&id, &tenant, enforcer)
+            .await
+            .map(Res::new_data)
+    }
+
+    /// 为访问密钥分配可访问的接口
+    pub async fn assign_permission(
+        Extension(service): Extension<Arc<SysAccessKeyService>>,
+        Extension(tenant): Extension<TenantContext>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
+        ValidatedForm(input): ValidatedForm<AssignAccessKeyPermissionInput>,
     ) -> Result<Res<()>, AppError> {
-        service.delete_access_key(&id).await.map(Res::new_data)
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .assign_permissions(input, &tenant, enforcer)
+            .await
+            .map(Res::new_data)
     }
 }
@@ -0,0 +1,96 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::{ConnectInfo, Multipart},
+    http::HeaderMap,
+    Extension,
+};
+use server_core::web::{
+    auth::User, error::AppError, res::Res, util::ClientIp, validator::ValidatedForm,
+};
+use server_service::admin::{
+    ChangePasswordInput, ForgotPasswordInput, ResetPasswordInput, SysAccountService,
+    TAccountService, UpdateProfileInput, UserWithoutPassword,
+};
+
+/// 当前用户的个人资料和密码管理
+pub struct SysAccountApi;
+
+impl SysAccountApi {
+    pub async fn get_profile(
+        Extension(service): Extension<Arc<SysAccountService>>,
+        Extension(user): Extension<User>,
+    ) -> Result<Res<UserWithoutPassword>, AppError> {
+        service.get_profile(user).await.map(Res::new_data)
+    }
+
+    pub async fn update_profile(
+        Extension(service): Extension<Arc<SysAccountService>>,
+        Extension(user): Extension<User>,
+        ValidatedForm(input): ValidatedForm<UpdateProfileInput>,
+    ) -> Result<Res<UserWithoutPassword>, AppError> {
+        service.update_profile(user, input).await.map(Res::new_data)
+    }
+
+    /// 修改成功后需要重新登录
+    pub async fn change_password(
+        Extension(service): Extension<Arc<SysAccountService>>,
+        Extension(user): Extension<User>,
+        ValidatedForm(input): ValidatedForm<ChangePasswordInput>,
+    ) -> Result<Res<()>, AppError> {
+        service
+            .change_password(user, input)
+            .await
+            .map(Res::new_data)
+    }
+
+    /// 上传头像，图片放在表单的 `file` 字段中
+    pub async fn upload_avatar(
+        Extension(service): Extension<Arc<SysAccountService>>,
+        Extension(user): Extension<User>,
+        mut multipart: Multipart,
+    ) -> Result<Res<UserWithoutPassword>, AppError> {
+        let bad_request = |message: String| AppError { code: 400, message };
+
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|e| bad_request(e.body_text()))?
+        {
+            if field.name() == Some("file") {
+                let data = field
+                    .bytes()
+                    .await
+                    .map_err(|e| bad_request(e.body_text()))?;
+                return service
+                    .upload_avatar(user, data.to_vec())
+                    .await
+                    .map(Res::new_data);
+            }
+        }
+
+        Err(bad_request("Missing file field".to_string()))
+    }
+
+    /// 按连接的对端地址限流，转发头可以由调用方伪造
+    pub async fn forgot_password(
+        ConnectInfo(addr): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+        Extension(service): Extension<Arc<SysAccountService>>,
+        ValidatedForm(input): ValidatedForm<ForgotPasswordInput>,
+    ) -> Result<Res<()>, AppError> {
+        let trusted_proxies = ClientIp::trusted_proxies().await;
+        let client_ip = ClientIp::resolve(addr.ip(), &headers, &trusted_proxies);
+        service
+            .forgot_password(input, &client_ip.to_string())
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn reset_password(
+        Extension(service): Extension<Arc<SysAccountService>>,
+        ValidatedForm(input): ValidatedForm<ResetPasswordInput>,
+    ) -> Result<Res<()>, AppError> {
+        service.reset_password(input).await.map(Res::new_data)
+    }
+}
@@ -1,15 +1,30 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 
-use axum::{extract::ConnectInfo, http::HeaderMap, Extension};
+use axum::{
+    extract::{ConnectInfo, Path},
+    http::{header::CACHE_CONTROL, HeaderMap},
+    response::IntoResponse,
+    Extension, Json,
+};
 use axum_casbin::CasbinAxumLayer;
-use axum_extra::{headers::UserAgent, TypedHeader};
+use axum_extra::{
+    headers::{authorization::Bearer, Authorization, UserAgent},
+    TypedHeader,
+};
 use server_core::web::{
-    auth::User, error::AppError, res::Res, util::ClientIp, validator::ValidatedForm, RequestId,
+    auth::User, error::AppError, jwt::JwtUtils, res::Res, tenant::TenantContext, util::ClientIp,
+    validator::ValidatedForm, RequestId,
 };
 use server_service::{
     admin::{
-        dto::sys_auth_dto::LoginContext, AssignPermissionDto, AssignRouteDto, AuthOutput,
-        LoginInput, SysAuthService, SysAuthorizationService, TAuthService, TAuthorizationService,
+        dto::sys_auth_dto::LoginContext, AssignPermissionCodeDto, AssignPermissionDto,
+        AssignRouteDto, AuthOutput, ExplainPermissionDto, ExternalAuthorizeOutput,
+        ExternalLoginCallbackInput, LoginInput, LoginOutput, PermissionExplanationOutput,
+        RefreshTokenInput, SiweLoginInput, SiweNonceOutput, SysAuthService,
+        SysAuthorizationService, TAuthService, TAuthorizationService, TwoFactorLoginInput,
         UserInfoOutput, UserRoute,
     },
     Audience,
@@ -18,6 +33,9 @@ use server_service::{
 pub struct SysAuthenticationApi;
 
 impl SysAuthenticationApi {
+    /// 密码登录
+    ///
+    /// 用户启用双因素认证时返回挑战令牌，需要再调用 `/auth/twoFactorLogin` 换取令牌。
     pub async fn login_handler(
         ConnectInfo(addr): ConnectInfo<SocketAddr>,
         headers: HeaderMap,
@@ -25,43 +43,155 @@ impl SysAuthenticationApi {
         Extension(request_id): Extension<RequestId>,
         Extension(service): Extension<Arc<SysAuthService>>,
         ValidatedForm(input): ValidatedForm<LoginInput>,
+    ) -> Result<Res<LoginOutput>, AppError> {
+        let login_context = Self::build_login_context(addr, &headers, &user_agent, &request_id);
+
+        service
+            .pwd_login(input, login_context)
+            .await
+            .map(Res::new_data)
+    }
+
+    /// 提交挑战令牌和动态码（或恢复码），完成双因素登录
+    pub async fn two_factor_login_handler(
+        ConnectInfo(addr): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+        TypedHeader(user_agent): TypedHeader<UserAgent>,
+        Extension(request_id): Extension<RequestId>,
+        Extension(service): Extension<Arc<SysAuthService>>,
+        ValidatedForm(input): ValidatedForm<TwoFactorLoginInput>,
     ) -> Result<Res<AuthOutput>, AppError> {
-        let client_ip = {
-            let header_ip = ClientIp::get_real_ip(&headers);
-            if header_ip == "unknown" {
-                addr.ip().to_string()
-            } else {
-                header_ip
-            }
-        };
+        let login_context = Self::build_login_context(addr, &headers, &user_agent, &request_id);
 
-        let address = xdb::searcher::search_by_ip(client_ip.as_str())
-            .unwrap_or_else(|_| "Unknown Location".to_string());
+        service
+            .two_factor_login(input, login_context)
+            .await
+            .map(Res::new_data)
+    }
 
-        let login_context = LoginContext {
-            client_ip,
-            client_port: Some(addr.port() as i32),
-            address,
-            user_agent: user_agent.as_str().to_string(),
-            request_id: request_id.to_string(),
-            audience: Audience::ManagementPlatform,
-            login_type: "PC".to_string(),
-            domain: "built-in".to_string(),
-        };
+    /// 获取外部登录提供方的授权地址
+    pub async fn external_authorize_handler(
+        Path(provider): Path<String>,
+        Extension(service): Extension<Arc<SysAuthService>>,
+    ) -> Result<Res<ExternalAuthorizeOutput>, AppError> {
+        service
+            .external_authorize(&provider)
+            .await
+            .map(Res::new_data)
+    }
+
+    /// 用外部登录提供方回调的授权码完成登录
+    ///
+    /// 与密码登录一样，启用双因素认证的用户会收到挑战令牌。
+    pub async fn external_login_handler(
+        Path(provider): Path<String>,
+        ConnectInfo(addr): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+        TypedHeader(user_agent): TypedHeader<UserAgent>,
+        Extension(request_id): Extension<RequestId>,
+        Extension(service): Extension<Arc<SysAuthService>>,
+        ValidatedForm(input): ValidatedForm<ExternalLoginCallbackInput>,
+    ) -> Result<Res<LoginOutput>, AppError> {
+        let login_context = Self::build_login_context(addr, &headers, &user_agent, &request_id);
 
         service
-            .pwd_login(input, login_context)
+            .external_login(&provider, input, login_context)
+            .await
+            .map(Res::new_data)
+    }
+
+    /// 签发以太坊钱包登录的一次性 nonce
+    pub async fn siwe_nonce_handler(
+        Extension(service): Extension<Arc<SysAuthService>>,
+    ) -> Result<Res<SiweNonceOutput>, AppError> {
+        service.siwe_nonce().await.map(Res::new_data)
+    }
+
+    /// 以太坊钱包登录（EIP-4361），钱包需要先关联到用户
+    ///
+    /// 与密码登录一样，启用双因素认证的用户会收到挑战令牌。
+    pub async fn siwe_login_handler(
+        ConnectInfo(addr): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+        TypedHeader(user_agent): TypedHeader<UserAgent>,
+        Extension(request_id): Extension<RequestId>,
+        Extension(service): Extension<Arc<SysAuthService>>,
+        ValidatedForm(input): ValidatedForm<SiweLoginInput>,
+    ) -> Result<Res<LoginOutput>, AppError> {
+        let login_context = Self::build_login_context(addr, &headers, &user_agent, &request_id);
+
+        service
+            .siwe_login(input, login_context)
+            .await
+            .map(Res::new_data)
+    }
+
+    /// 将签名证明持有的钱包关联到当前用户
+    pub async fn siwe_link_wallet_handler(
+        Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysAuthService>>,
+        ValidatedForm(input): ValidatedForm<SiweLoginInput>,
+    ) -> Result<Res<()>, AppError> {
+        service
+            .siwe_link_wallet(&user.user_id(), input)
+            .await
+            .map(Res::new_data)
+    }
+
+    /// 使用刷新令牌换取新的令牌对
+    ///
+    /// 旧的刷新令牌会被标记为已使用，重复使用将撤销该用户的所有会话。
+    pub async fn refresh_token_handler(
+        ConnectInfo(addr): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+        TypedHeader(user_agent): TypedHeader<UserAgent>,
+        Extension(request_id): Extension<RequestId>,
+        Extension(service): Extension<Arc<SysAuthService>>,
+        ValidatedForm(input): ValidatedForm<RefreshTokenInput>,
+    ) -> Result<Res<AuthOutput>, AppError> {
+        let login_context = Self::build_login_context(addr, &headers, &user_agent, &request_id);
+
+        service
+            .refresh_token(input, login_context)
             .await
             .map(Res::new_data)
     }
 
+    /// 注销当前会话
+    ///
+    /// 当前访问令牌会被加入撤销列表，在过期之前不能再使用。
+    pub async fn logout_handler(
+        TypedHeader(authorization): TypedHeader<Authorization<Bearer>>,
+        Extension(service): Extension<Arc<SysAuthService>>,
+    ) -> Result<Res<()>, AppError> {
+        service
+            .logout(authorization.token())
+            .await
+            .map(Res::new_data)
+    }
+
+    /// 公开 JWT 验证公钥集合
+    ///
+    /// 按 JWKS 标准格式直接返回，不使用统一响应包装，便于其他服务加载后自行验证令牌。
+    pub async fn jwks_handler() -> Result<impl IntoResponse, AppError> {
+        let jwks = JwtUtils::jwks().await?;
+        Ok(([(CACHE_CONTROL, "public, max-age=300")], Json(jwks)))
+    }
+
     pub async fn get_user_info(
         Extension(user): Extension<User>,
+        Extension(service): Extension<Arc<SysAuthService>>,
     ) -> Result<Res<UserInfoOutput>, AppError> {
+        let roles = user.subject();
+        let buttons = service
+            .get_user_permission_codes(&roles, &user.domain())
+            .await?;
+
         let user_info = UserInfoOutput {
             user_id: user.user_id(),
             user_name: user.username(),
-            roles: user.subject(),
+            roles,
+            buttons,
         };
 
         Ok(Res::new_data(user_info))
@@ -78,10 +208,68 @@ impl SysAuthenticationApi {
         Ok(Res::new_data(routes))
     }
 
+    fn build_login_context(
+        addr: SocketAddr,
+        headers: &HeaderMap,
+        user_agent: &UserAgent,
+        request_id: &RequestId,
+    ) -> LoginContext {
+        let client_ip = {
+            let header_ip = ClientIp::get_real_ip(headers);
+            if header_ip == "unknown" {
+                addr.ip().to_string()
+            } else {
+                header_ip
+            }
+        };
+
+        let address = client_ip
+            .parse::<IpAddr>()
+            .ok()
+            .and_then(|ip| xdb::search_by_ip(ip).ok())
+            .map(|region| region.to_string())
+            .unwrap_or_else(|| "Unknown Location".to_string());
+
+        LoginContext {
+            client_ip,
+            client_port: Some(addr.port() as i32),
+            address,
+            user_agent: user_agent.as_str().to_string(),
+            request_id: request_id.to_string(),
+            audience: Audience::ManagementPlatform,
+            login_type: "PC".to_string(),
+            domain: "built-in".to_string(),
+        }
+    }
+
+    /// 踢出指定会话
+    ///
+    /// 撤销 `sys_tokens` 中指定记录对应的访问令牌。
+    pub async fn kick_session(
+        Path(id): Path<String>,
+        Extension(tenant): Extension<TenantContext>,
+        Extension(service): Extension<Arc<SysAuthService>>,
+    ) -> Result<Res<()>, AppError> {
+        service.kick_session(&id, &tenant).await.map(Res::new_data)
+    }
+
+    /// 撤销指定用户的所有会话
+    pub async fn revoke_user_sessions(
+        Path(user_id): Path<String>,
+        Extension(tenant): Extension<TenantContext>,
+        Extension(service): Extension<Arc<SysAuthService>>,
+    ) -> Result<Res<()>, AppError> {
+        service
+            .revoke_user_sessions(&user_id, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
     /// 为角色分配权限
     ///
     /// 将指定的权限分配给指定域中的角色。
     pub async fn assign_permission(
+        Extension(tenant): Extension<TenantContext>,
         Extension(service): Extension<Arc<SysAuthorizationService>>,
         Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
         ValidatedForm(input): ValidatedForm<AssignPermissionDto>,
@@ -89,7 +277,13 @@ impl SysAuthenticationApi {
         let enforcer = cache_enforcer.get_enforcer();
 
         service
-            .assign_permission(input.domain, input.role_id, input.permissions, enforcer)
+            .assign_permission(
+                input.domain,
+                input.role_id,
+                input.permissions,
+                &tenant,
+                enforcer,
+            )
             .await?;
 
         Ok(Res::new_data(()))
@@ -99,13 +293,55 @@ impl SysAuthenticationApi {
     ///
     /// 将指定的路由分配给指定域中的角色。
     pub async fn assign_routes(
+        Extension(tenant): Extension<TenantContext>,
         Extension(service): Extension<Arc<SysAuthorizationService>>,
         ValidatedForm(input): ValidatedForm<AssignRouteDto>,
     ) -> Result<Res<()>, AppError> {
         service
-            .assign_routes(input.domain, input.role_id, input.route_ids)
+            .assign_routes(input.domain, input.role_id, input.route_ids, &tenant)
+            .await?;
+
+        Ok(Res::new_data(()))
+    }
+
+    /// 为角色分配按钮权限码
+    ///
+    /// 整体替换角色在指定域中的按钮权限码，关联了接口的权限码同时授予或回收接口访问策略。
+    pub async fn assign_permission_codes(
+        Extension(tenant): Extension<TenantContext>,
+        Extension(service): Extension<Arc<SysAuthorizationService>>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
+        ValidatedForm(input): ValidatedForm<AssignPermissionCodeDto>,
+    ) -> Result<Res<()>, AppError> {
+        let enforcer = cache_enforcer.get_enforcer();
+
+        service
+            .assign_permission_codes(
+                input.domain,
+                input.role_id,
+                input.permission_code_ids,
+                &tenant,
+                enforcer,
+            )
             .await?;
 
         Ok(Res::new_data(()))
     }
+
+    /// 解释授权决策
+    ///
+    /// 回答用户在指定域中能否访问某个接口，以及由哪个角色的哪条策略放行。
+    pub async fn explain_permission(
+        Extension(tenant): Extension<TenantContext>,
+        Extension(service): Extension<Arc<SysAuthorizationService>>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
+        ValidatedForm(input): ValidatedForm<ExplainPermissionDto>,
+    ) -> Result<Res<PermissionExplanationOutput>, AppError> {
+        let enforcer = cache_enforcer.get_enforcer();
+
+        service
+            .explain_permission(input, &tenant, enforcer)
+            .await
+            .map(Res::new_data)
+    }
 }
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query},
+    Extension,
+};
+use server_core::web::{error::AppError, page::PaginatedData, res::Res};
+use server_service::admin::{
+    JobInfo, JobRun, JobRunPageRequest, SysJobRunModel, SysJobService, TJobService,
+};
+
+pub struct SysJobApi;
+
+impl SysJobApi {
+    pub async fn list_jobs(
+        Extension(service): Extension<Arc<SysJobService>>,
+    ) -> Result<Res<Vec<JobInfo>>, AppError> {
+        service.list_jobs().await.map(Res::new_data)
+    }
+
+    pub async fn get_paginated_job_runs(
+        Query(params): Query<JobRunPageRequest>,
+        Extension(service): Extension<Arc<SysJobService>>,
+    ) -> Result<Res<PaginatedData<SysJobRunModel>>, AppError> {
+        service
+            .find_paginated_job_runs(params)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn pause_job(
+        Path(name): Path<String>,
+        Extension(service): Extension<Arc<SysJobService>>,
+    ) -> Result<Res<()>, AppError> {
+        service.pause_job(&name).await.map(Res::new_data)
+    }
+
+    pub async fn resume_job(
+        Path(name): Path<String>,
+        Extension(service): Extension<Arc<SysJobService>>,
+    ) -> Result<Res<()>, AppError> {
+        service.resume_job(&name).await.map(Res::new_data)
+    }
+
+    pub async fn trigger_job(
+        Path(name): Path<String>,
+        Extension(service): Extension<Arc<SysJobService>>,
+    ) -> Result<Res<JobRun>, AppError> {
+        service.trigger_job(&name).await.map(Res::new_data)
+    }
+}
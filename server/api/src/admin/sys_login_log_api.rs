@@ -1,21 +1,41 @@
 use std::sync::Arc;
 
-use axum::extract::{Extension, Query};
-use server_core::web::{error::AppError, page::PaginatedData, res::Res};
+use axum::{
+    extract::{Extension, Query},
+    response::Response,
+};
+use server_core::web::{
+    auth::User, error::AppError, page::PaginatedData, res::Res, tenant::TenantContext,
+};
 use server_service::admin::{
-    LoginLogPageRequest, SysLoginLogModel, SysLoginLogService, TLoginLogService,
+    LogExportRequest, LoginLogPageRequest, SysLoginLogModel, SysLoginLogService, TLoginLogService,
 };
 
+use super::sys_operation_log_api::log_export_response;
+
 pub struct SysLoginLogApi;
 
 impl SysLoginLogApi {
     pub async fn get_paginated_login_logs(
         Query(params): Query<LoginLogPageRequest>,
         Extension(service): Extension<Arc<SysLoginLogService>>,
+        Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
     ) -> Result<Res<PaginatedData<SysLoginLogModel>>, AppError> {
         service
-            .find_paginated_login_logs(params)
+            .find_paginated_login_logs(params, user, &tenant)
             .await
             .map(Res::new_data)
     }
+
+    pub async fn export_login_logs(
+        Query(params): Query<LogExportRequest>,
+        Extension(service): Extension<Arc<SysLoginLogService>>,
+        Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Response, AppError> {
+        let format = params.format;
+        let stream = service.export_login_logs(params, user, &tenant).await?;
+        Ok(log_export_response("login-log", format, stream))
+    }
 }
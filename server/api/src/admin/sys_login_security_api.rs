@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query},
+    Extension,
+};
+use server_core::web::{
+    auth::User, error::AppError, res::Res, tenant::TenantContext, validator::ValidatedForm,
+};
+use server_service::admin::{
+    dto::sys_login_security_dto::LoginSecurityPolicyOutput, LockedAccountOutput,
+    LockedAccountRequest, SysLoginSecurityPolicyModel, SysLoginSecurityService,
+    TLoginSecurityService, UnlockAccountInput, UpsertLoginSecurityPolicyInput,
+};
+
+pub struct SysLoginSecurityApi;
+
+impl SysLoginSecurityApi {
+    pub async fn get_policy(
+        Path(domain): Path<String>,
+        Extension(service): Extension<Arc<SysLoginSecurityService>>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Res<LoginSecurityPolicyOutput>, AppError> {
+        service
+            .get_policy(&domain, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn upsert_policy(
+        Extension(service): Extension<Arc<SysLoginSecurityService>>,
+        Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
+        ValidatedForm(input): ValidatedForm<UpsertLoginSecurityPolicyInput>,
+    ) -> Result<Res<SysLoginSecurityPolicyModel>, AppError> {
+        service
+            .upsert_policy(input, user, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn delete_policy(
+        Path(domain): Path<String>,
+        Extension(service): Extension<Arc<SysLoginSecurityService>>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Res<()>, AppError> {
+        service
+            .delete_policy(&domain, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn list_locked_accounts(
+        Query(params): Query<LockedAccountRequest>,
+        Extension(service): Extension<Arc<SysLoginSecurityService>>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Res<Vec<LockedAccountOutput>>, AppError> {
+        service
+            .list_locked_accounts(params, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn unlock_account(
+        Extension(service): Extension<Arc<SysLoginSecurityService>>,
+        Extension(tenant): Extension<TenantContext>,
+        ValidatedForm(input): ValidatedForm<UnlockAccountInput>,
+    ) -> Result<Res<()>, AppError> {
+        service
+            .unlock_account(input, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+}
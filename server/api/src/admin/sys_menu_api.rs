@@ -3,8 +3,8 @@ use std::sync::Arc;
 use axum::{extract::Path, Extension};
 use server_core::web::{auth::User, error::AppError, res::Res, validator::ValidatedForm};
 use server_service::admin::{
-    CreateMenuInput, MenuRoute, MenuTree, SysMenuModel, SysMenuService, TMenuService,
-    UpdateMenuInput,
+    CreateMenuInput, CreatePermissionCodeInput, MenuRoute, MenuTree, SysMenuModel, SysMenuService,
+    SysPermissionCodeModel, TMenuService, UpdateMenuInput, UpdatePermissionCodeInput,
 };
 
 pub struct SysMenuApi;
@@ -70,4 +70,54 @@ impl SysMenuApi {
             .await
             .map(Res::new_data)
     }
+
+    pub async fn get_permission_codes(
+        Path(menu_id): Path<i32>,
+        Extension(service): Extension<Arc<SysMenuService>>,
+    ) -> Result<Res<Vec<SysPermissionCodeModel>>, AppError> {
+        service
+            .find_permission_codes(menu_id)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn create_permission_code(
+        Extension(service): Extension<Arc<SysMenuService>>,
+        Extension(user): Extension<User>,
+        ValidatedForm(input): ValidatedForm<CreatePermissionCodeInput>,
+    ) -> Result<Res<SysPermissionCodeModel>, AppError> {
+        service
+            .create_permission_code(input, user)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn update_permission_code(
+        Extension(service): Extension<Arc<SysMenuService>>,
+        Extension(user): Extension<User>,
+        ValidatedForm(input): ValidatedForm<UpdatePermissionCodeInput>,
+    ) -> Result<Res<SysPermissionCodeModel>, AppError> {
+        service
+            .update_permission_code(input, user)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn delete_permission_code(
+        Path(id): Path<String>,
+        Extension(service): Extension<Arc<SysMenuService>>,
+    ) -> Result<Res<()>, AppError> {
+        service.delete_permission_code(&id).await.map(Res::new_data)
+    }
+
+    pub async fn get_auth_permission_codes(
+        Path(role_id): Path<String>,
+        Extension(service): Extension<Arc<SysMenuService>>,
+        Extension(user): Extension<User>,
+    ) -> Result<Res<Vec<String>>, AppError> {
+        service
+            .get_permission_code_ids_by_role_id(role_id, user.domain())
+            .await
+            .map(Res::new_data)
+    }
 }
@@ -1,9 +1,19 @@
 use std::sync::Arc;
 
-use axum::extract::{Extension, Query};
-use server_core::web::{error::AppError, page::PaginatedData, res::Res};
+use axum::{
+    body::Body,
+    extract::{Extension, Query},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chrono::Local;
+use futures::TryStreamExt;
+use server_core::web::{
+    auth::User, error::AppError, page::PaginatedData, res::Res, tenant::TenantContext,
+};
 use server_service::admin::{
-    OperationLogPageRequest, SysOperationLogModel, SysOperationLogService, TOperationLogService,
+    LogExportFormat, LogExportRequest, OperationLogPageRequest, SysOperationLogModel,
+    SysOperationLogService, TOperationLogService,
 };
 
 pub struct SysOperationLogApi;
@@ -12,10 +22,50 @@ impl SysOperationLogApi {
     pub async fn get_paginated_operation_logs(
         Query(params): Query<OperationLogPageRequest>,
         Extension(service): Extension<Arc<SysOperationLogService>>,
+        Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
     ) -> Result<Res<PaginatedData<SysOperationLogModel>>, AppError> {
         service
-            .find_paginated_operation_logs(params)
+            .find_paginated_operation_logs(params, user, &tenant)
             .await
             .map(Res::new_data)
     }
+
+    pub async fn export_operation_logs(
+        Query(params): Query<LogExportRequest>,
+        Extension(service): Extension<Arc<SysOperationLogService>>,
+        Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Response, AppError> {
+        let format = params.format;
+        let stream = service.export_operation_logs(params, user, &tenant).await?;
+        Ok(log_export_response("operation-log", format, stream))
+    }
+}
+
+/// 以附件形式流式返回导出的日志
+pub(super) fn log_export_response(
+    name: &str,
+    format: LogExportFormat,
+    stream: server_service::admin::LogExportStream,
+) -> Response {
+    let filename = format!(
+        "{}-{}.{}",
+        name,
+        Local::now().format("%Y%m%d%H%M%S"),
+        format.extension()
+    );
+    let body = Body::from_stream(stream.map_err(|e| std::io::Error::other(e.message)));
+
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
 }
@@ -1,9 +1,13 @@
 use std::sync::Arc;
 
-use axum::extract::{Extension, Query};
-use server_core::web::{error::AppError, page::PaginatedData, res::Res};
+use axum::extract::{Extension, Path, Query};
+use server_core::web::{
+    auth::User, error::AppError, page::PaginatedData, res::Res, tenant::TenantContext,
+    validator::ValidatedForm,
+};
 use server_service::admin::{
-    OrganizationPageRequest, SysOrganizationModel, SysOrganizationService, TOrganizationService,
+    CreateOrganizationInput, OrganizationPageRequest, OrganizationTree, SysOrganizationModel,
+    SysOrganizationService, TOrganizationService, UpdateOrganizationInput,
 };
 
 pub struct SysOrganizationApi;
@@ -12,9 +16,68 @@ impl SysOrganizationApi {
     pub async fn get_paginated_organizations(
         Query(params): Query<OrganizationPageRequest>,
         Extension(service): Extension<Arc<SysOrganizationService>>,
+        Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
     ) -> Result<Res<PaginatedData<SysOrganizationModel>>, AppError> {
         service
-            .find_paginated_organizations(params)
+            .find_paginated_organizations(params, user, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn tree_organization(
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+        Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Res<Vec<OrganizationTree>>, AppError> {
+        service
+            .tree_organization(user, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn create_organization(
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+        Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
+        ValidatedForm(input): ValidatedForm<CreateOrganizationInput>,
+    ) -> Result<Res<SysOrganizationModel>, AppError> {
+        service
+            .create_organization(input, user, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn get_organization(
+        Path(id): Path<String>,
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Res<SysOrganizationModel>, AppError> {
+        service
+            .get_organization(&id, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn update_organization(
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+        Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
+        ValidatedForm(input): ValidatedForm<UpdateOrganizationInput>,
+    ) -> Result<Res<SysOrganizationModel>, AppError> {
+        service
+            .update_organization(input, user, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn delete_organization(
+        Path(id): Path<String>,
+        Extension(service): Extension<Arc<SysOrganizationService>>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Res<()>, AppError> {
+        service
+            .delete_organization(&id, &tenant)
             .await
             .map(Res::new_data)
     }
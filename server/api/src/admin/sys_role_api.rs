@@ -4,9 +4,14 @@ use axum::{
     extract::{Path, Query},
     Extension,
 };
-use server_core::web::{auth::User, error::AppError, page::PaginatedData, res::Res, validator::ValidatedForm};
+use axum_casbin::CasbinAxumLayer;
+use server_core::web::{
+    auth::User, error::AppError, page::PaginatedData, res::Res, tenant::TenantContext,
+    validator::ValidatedForm,
+};
 use server_service::admin::{
-    CreateRoleInput, RolePageRequest, SysRoleModel, SysRoleService, TRoleService, UpdateRoleInput,
+    CreateRoleInput, RoleDataScopeOutput, RolePageRequest, SysRoleModel, SysRoleService,
+    TRoleService, UpdateRoleDataScopeInput, UpdateRoleInput,
 };
 
 pub struct SysRoleApi;
@@ -15,9 +20,10 @@ impl SysRoleApi {
     pub async fn get_paginated_roles(
         Query(params): Query<RolePageRequest>,
         Extension(service): Extension<Arc<SysRoleService>>,
+        Extension(tenant): Extension<TenantContext>,
     ) -> Result<Res<PaginatedData<SysRoleModel>>, AppError> {
         service
-            .find_paginated_roles(params)
+            .find_paginated_roles(params, &tenant)
             .await
             .map(Res::new_data)
     }
@@ -25,29 +31,70 @@ impl SysRoleApi {
     pub async fn create_role(
         Extension(service): Extension<Arc<SysRoleService>>,
         Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
         ValidatedForm(input): ValidatedForm<CreateRoleInput>,
     ) -> Result<Res<SysRoleModel>, AppError> {
-        service.create_role(input, user).await.map(Res::new_data)
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .create_role(input, user, &tenant, enforcer)
+            .await
+            .map(Res::new_data)
     }
 
     pub async fn get_role(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysRoleService>>,
+        Extension(tenant): Extension<TenantContext>,
     ) -> Result<Res<SysRoleModel>, AppError> {
-        service.get_role(&id).await.map(Res::new_data)
+        service.get_role(&id, &tenant).await.map(Res::new_data)
     }
 
     pub async fn update_role(
         Extension(service): Extension<Arc<SysRoleService>>,
+        Extension(tenant): Extension<TenantContext>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
         ValidatedForm(input): ValidatedForm<UpdateRoleInput>,
     ) -> Result<Res<SysRoleModel>, AppError> {
-        service.update_role(input).await.map(Res::new_data)
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .update_role(input, &tenant, enforcer)
+            .await
+            .map(Res::new_data)
     }
 
     pub async fn delete_role(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysRoleService>>,
+        Extension(tenant): Extension<TenantContext>,
+        Extension(mut cache_enforcer): Extension<CasbinAxumLayer>,
     ) -> Result<Res<()>, AppError> {
-        service.delete_role(&id).await.map(Res::new_data)
+        let enforcer = cache_enforcer.get_enforcer();
+        service
+            .delete_role(&id, &tenant, enforcer)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn get_role_data_scope(
+        Path(id): Path<String>,
+        Extension(service): Extension<Arc<SysRoleService>>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Res<RoleDataScopeOutput>, AppError> {
+        service
+            .get_role_data_scope(&id, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn update_role_data_scope(
+        Extension(service): Extension<Arc<SysRoleService>>,
+        Extension(tenant): Extension<TenantContext>,
+        ValidatedForm(input): ValidatedForm<UpdateRoleDataScopeInput>,
+    ) -> Result<Res<RoleDataScopeOutput>, AppError> {
+        service
+            .update_role_data_scope(input, &tenant)
+            .await
+            .map(Res::new_data)
     }
 }
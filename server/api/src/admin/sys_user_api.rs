@@ -6,11 +6,12 @@ use axum::{
 };
 use axum_casbin::{casbin::MgmtApi, CasbinAxumLayer};
 use server_core::web::{
-    auth::User, error::AppError, page::PaginatedData, res::Res, validator::ValidatedForm,
+    auth::User, error::AppError, page::PaginatedData, res::Res, tenant::TenantContext,
+    validator::ValidatedForm,
 };
 use server_service::admin::{
-    CreateUserInput, SysUserService, TUserService, UpdateUserInput, UserPageRequest,
-    UserWithoutPassword,
+    AssignUserOrganizationsInput, CreateUserInput, SysUserService, TUserService, UpdateUserInput,
+    UserPageRequest, UserWithoutPassword,
 };
 
 pub struct SysUserApi;
@@ -18,18 +19,21 @@ pub struct SysUserApi;
 impl SysUserApi {
     pub async fn get_all_users(
         Extension(service): Extension<Arc<SysUserService>>,
+        Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
     ) -> Result<Res<Vec<UserWithoutPassword>>, AppError> {
-        service.find_all().await.map(Res::new_data)
+        service.find_all(user, &tenant).await.map(Res::new_data)
     }
 
     pub async fn get_paginated_users(
         Query(params): Query<UserPageRequest>,
         Extension(service): Extension<Arc<SysUserService>>,
+        Extension(tenant): Extension<TenantContext>,
         user: User,
     ) -> Result<Res<PaginatedData<UserWithoutPassword>>, AppError> {
         print!("user is {:#?}", user);
         service
-            .find_paginated_users(params)
+            .find_paginated_users(params, user, &tenant)
             .await
             .map(Res::new_data)
     }
@@ -67,29 +71,58 @@ impl SysUserApi {
     pub async fn create_user(
         Extension(service): Extension<Arc<SysUserService>>,
         Extension(user): Extension<User>,
+        Extension(tenant): Extension<TenantContext>,
         ValidatedForm(input): ValidatedForm<CreateUserInput>,
     ) -> Result<Res<UserWithoutPassword>, AppError> {
-        service.create_user(input, user).await.map(Res::new_data)
+        service
+            .create_user(input, user, &tenant)
+            .await
+            .map(Res::new_data)
     }
 
     pub async fn get_user(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysUserService>>,
+        Extension(tenant): Extension<TenantContext>,
     ) -> Result<Res<UserWithoutPassword>, AppError> {
-        service.get_user(&id).await.map(Res::new_data)
+        service.get_user(&id, &tenant).await.map(Res::new_data)
     }
 
     pub async fn update_user(
         Extension(service): Extension<Arc<SysUserService>>,
+        Extension(tenant): Extension<TenantContext>,
         ValidatedForm(input): ValidatedForm<UpdateUserInput>,
     ) -> Result<Res<UserWithoutPassword>, AppError> {
-        service.update_user(input).await.map(Res::new_data)
+        service.update_user(input, &tenant).await.map(Res::new_data)
     }
 
     pub async fn delete_user(
         Path(id): Path<String>,
         Extension(service): Extension<Arc<SysUserService>>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Res<()>, AppError> {
+        service.delete_user(&id, &tenant).await.map(Res::new_data)
+    }
+
+    pub async fn get_user_organizations(
+        Path(id): Path<String>,
+        Extension(service): Extension<Arc<SysUserService>>,
+        Extension(tenant): Extension<TenantContext>,
+    ) -> Result<Res<Vec<String>>, AppError> {
+        service
+            .get_user_organization_ids(&id, &tenant)
+            .await
+            .map(Res::new_data)
+    }
+
+    pub async fn assign_user_organizations(
+        Extension(service): Extension<Arc<SysUserService>>,
+        Extension(tenant): Extension<TenantContext>,
+        ValidatedForm(input): ValidatedForm<AssignUserOrganizationsInput>,
     ) -> Result<Res<()>, AppError> {
-        service.delete_user(&id).await.map(Res::new_data)
+        service
+            .assign_user_organizations(input, &tenant)
+            .await
+            .map(Res::new_data)
     }
 }
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use axum::Extension;
+use server_core::web::{auth::User, error::AppError, res::Res, validator::ValidatedForm};
+use server_service::admin::{
+    RecoveryCodesOutput, SysUserTotpService, TUserTotpService, TotpCodeInput, TotpSetupOutput,
+    TotpStatusOutput,
+};
+
+/// 当前用户的双因素认证管理
+pub struct SysUserTotpApi;
+
+impl SysUserTotpApi {
+    pub async fn get_status(
+        Extension(service): Extension<Arc<SysUserTotpService>>,
+        Extension(user): Extension<User>,
+    ) -> Result<Res<TotpStatusOutput>, AppError> {
+        service.get_status(user).await.map(Res::new_data)
+    }
+
+    /// 生成新的密钥，需要再提交一次动态码确认后才会启用
+    pub async fn setup(
+        Extension(service): Extension<Arc<SysUserTotpService>>,
+        Extension(user): Extension<User>,
+    ) -> Result<Res<TotpSetupOutput>, AppError> {
+        service.setup(user).await.map(Res::new_data)
+    }
+
+    /// 确认绑定并返回恢复码，恢复码只展示这一次
+    pub async fn confirm(
+        Extension(service): Extension<Arc<SysUserTotpService>>,
+        Extension(user): Extension<User>,
+        ValidatedForm(input): ValidatedForm<TotpCodeInput>,
+    ) -> Result<Res<RecoveryCodesOutput>, AppError> {
+        service.confirm(user, input).await.map(Res::new_data)
+    }
+
+    pub async fn disable(
+        Extension(service): Extension<Arc<SysUserTotpService>>,
+        Extension(user): Extension<User>,
+        ValidatedForm(input): ValidatedForm<TotpCodeInput>,
+    ) -> Result<Res<()>, AppError> {
+        service.disable(user, input).await.map(Res::new_data)
+    }
+
+    pub async fn regenerate_recovery_codes(
+        Extension(service): Extension<Arc<SysUserTotpService>>,
+        Extension(user): Extension<User>,
+        ValidatedForm(input): ValidatedForm<TotpCodeInput>,
+    ) -> Result<Res<RecoveryCodesOutput>, AppError> {
+        service
+            .regenerate_recovery_codes(user, input)
+            .await
+            .map(Res::new_data)
+    }
+}
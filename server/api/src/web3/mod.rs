@@ -1677,7 +1677,7 @@ impl Web3Api {
             ("0x8798249c2e607446efb7ad49ec89dd1865f39f02", "SUSHI", "SushiSwap", 18),
         ];
         
-        let common_spenders = vec![
+        let common_spenders = [
             "0x7a250d5630b4cf539739df2c5dacb4c659f2488d", // Uniswap V2
             "0xe592427a0aece92de3edee1f18e0157c05861564", // Uniswap V3
             "0xd9e1ce17f2641f24ae83637ab66a2cca9c378b9f", // SushiSwap
@@ -37,12 +37,15 @@ async fn main() {
     server_initialize::init_primary_connection().await;
     server_initialize::init_db_pools().await;
     server_initialize::initialize_keys_and_validation().await;
-    server_initialize::initialize_event_channel().await;
 
     server_initialize::init_primary_redis().await;
     server_initialize::init_redis_pools().await;
+    server_initialize::initialize_event_channel().await;
     server_initialize::init_primary_mongo().await;
     server_initialize::init_mongo_pools().await;
+    server_initialize::init_primary_s3().await;
+    server_initialize::init_s3_pools().await;
+    server_initialize::initialize_scheduler().await;
 
     // build our application with a route
     let app = server_initialize::initialize_admin_router().await;
@@ -7,9 +7,11 @@ use crate::{
     env_config::{load_config_with_env, EnvConfigLoader},
     model::{Config, OptionalConfigs},
     multi_instance_env::MultiInstanceEnvProcessor,
-    project_error, project_info, DatabaseConfig, DatabasesInstancesConfig, JwtConfig, MongoConfig,
-    MongoInstancesConfig, RedisConfig, RedisInstancesConfig, S3Config, S3InstancesConfig,
-    ServerConfig,
+    project_error, project_info, AccessKeyConfig, AccountConfig, DatabaseConfig,
+    DatabasesInstancesConfig, EventBusConfig, JwtConfig, LogRetentionConfig, LoginSecurityConfig,
+    MailConfig, MongoConfig, MongoInstancesConfig, OidcProviderConfig, OperationLogConfig,
+    RedisConfig, RedisInstancesConfig, S3Config, S3InstancesConfig, SchedulerConfig, ServerConfig,
+    SiweConfig, TotpConfig,
 };
 
 #[derive(Debug, Error)]
@@ -64,6 +66,23 @@ pub async fn init_from_file(file_path: &str) -> Result<(), ConfigError> {
 
     global::init_config::<ServerConfig>(config.server).await;
     global::init_config::<JwtConfig>(config.jwt).await;
+    global::init_config::<LoginSecurityConfig>(config.login_security).await;
+    global::init_config::<TotpConfig>(config.totp).await;
+    global::init_config::<AccountConfig>(config.account).await;
+    global::init_config::<MailConfig>(config.mail).await;
+    global::init_config::<EventBusConfig>(config.event_bus).await;
+    global::init_config::<OperationLogConfig>(config.operation_log).await;
+    global::init_config::<LogRetentionConfig>(config.log_retention).await;
+    global::init_config::<SchedulerConfig>(config.scheduler).await;
+    global::init_config::<OptionalConfigs<OidcProviderConfig>>(config.oidc_providers.into()).await;
+
+    if let Some(siwe_config) = config.siwe {
+        global::init_config::<SiweConfig>(siwe_config).await;
+    }
+
+    if let Some(access_key_config) = config.access_key {
+        global::init_config::<AccessKeyConfig>(access_key_config).await;
+    }
 
     if let Some(redis_config) = config.redis {
         global::init_config::<RedisConfig>(redis_config).await;
@@ -406,6 +425,19 @@ async fn init_global_config(config: Config) {
 
     global::init_config::<ServerConfig>(config.server).await;
     global::init_config::<JwtConfig>(config.jwt).await;
+    global::init_config::<LoginSecurityConfig>(config.login_security).await;
+    global::init_config::<TotpConfig>(config.totp).await;
+    global::init_config::<AccountConfig>(config.account).await;
+    global::init_config::<MailConfig>(config.mail).await;
+    global::init_config::<OptionalConfigs<OidcProviderConfig>>(config.oidc_providers.into()).await;
+
+    if let Some(siwe_config) = config.siwe {
+        global::init_config::<SiweConfig>(siwe_config).await;
+    }
+
+    if let Some(access_key_config) = config.access_key {
+        global::init_config::<AccessKeyConfig>(access_key_config).await;
+    }
 
     if let Some(redis_config) = config.redis {
         global::init_config::<RedisConfig>(redis_config).await;
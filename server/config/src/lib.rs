@@ -4,9 +4,12 @@ pub use config_init::{
 };
 pub use env_config::{load_config_from_env, load_config_with_env, EnvConfigLoader};
 pub use model::{
-    Config, DatabaseConfig, DatabasesInstancesConfig, JwtConfig, MongoConfig, MongoInstancesConfig,
-    OptionalConfigs, RedisConfig, RedisInstancesConfig, RedisMode, S3Config, S3InstancesConfig,
-    ServerConfig,
+    AccessKeyConfig, AccountConfig, CasbinAuditSink, CasbinConfig, Config, DatabaseConfig,
+    DatabasesInstancesConfig, EventBusConfig, EventTransport, JwtConfig, JwtKeyConfig,
+    LogRetentionConfig, LoginSecurityConfig, LoginWindowConfig, MailConfig, MailTransport,
+    MongoConfig, MongoInstancesConfig, OidcProviderConfig, OperationLogConfig, OptionalConfigs,
+    RedisConfig, RedisInstancesConfig, RedisMode, S3Config, S3InstancesConfig, SchedulerConfig,
+    ServerConfig, SiweConfig, TotpConfig,
 };
 pub use server_global::{project_error, project_info};
 
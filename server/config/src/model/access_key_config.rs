@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// API 访问密钥配置
+///
+/// 支持的环境变量：
+/// - APP_ACCESS_KEY_ENCRYPTION_KEY: 加密 `access_key_secret` 的口令
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AccessKeyConfig {
+    /// 加密落库的 `access_key_secret` 所用的口令，修改后已有密钥将无法解密
+    /// 环境变量: APP_ACCESS_KEY_ENCRYPTION_KEY
+    pub encryption_key: String,
+
+    /// 可信的反向代理地址或网段，只有请求来自这些地址时才按转发头识别客户端 IP，
+    /// 为空时密钥的 IP 白名单和找回密码的 IP 限流始终使用连接的对端地址
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+}
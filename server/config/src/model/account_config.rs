@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+/// 用户自助服务配置：修改密码、找回密码和上传头像
+///
+/// 支持的环境变量：
+/// - APP_ACCOUNT_PASSWORD_HISTORY: 不允许重复使用的最近密码数量
+/// - APP_ACCOUNT_PASSWORD_RESET_EXPIRE: 找回密码令牌有效期（秒）
+/// - APP_ACCOUNT_PASSWORD_RESET_URL: 找回密码邮件中的链接模板
+/// - APP_ACCOUNT_PASSWORD_RESET_EMAIL_LIMIT: 每个邮箱在限流窗口内的找回密码次数上限
+/// - APP_ACCOUNT_PASSWORD_RESET_IP_LIMIT: 每个 IP 在限流窗口内的找回密码次数上限
+/// - APP_ACCOUNT_PASSWORD_RESET_LIMIT_WINDOW: 找回密码限流窗口（秒）
+/// - APP_ACCOUNT_AVATAR_S3_INSTANCE: 头像使用的 S3 实例名
+/// - APP_ACCOUNT_AVATAR_BUCKET: 头像写入的存储桶
+/// - APP_ACCOUNT_AVATAR_KEY_PREFIX: 头像对象的键前缀
+/// - APP_ACCOUNT_AVATAR_URL_PREFIX: 头像的公开访问地址前缀
+/// - APP_ACCOUNT_AVATAR_MAX_SIZE: 头像文件大小上限（字节）
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AccountConfig {
+    /// 修改或重置密码时不允许与最近几次使用过的密码相同，0 表示只校验当前密码，默认 5
+    /// 环境变量: APP_ACCOUNT_PASSWORD_HISTORY
+    #[serde(default = "default_password_history")]
+    pub password_history: usize,
+
+    /// 找回密码令牌有效期（秒），默认 30 分钟
+    /// 环境变量: APP_ACCOUNT_PASSWORD_RESET_EXPIRE
+    #[serde(default = "default_password_reset_expire")]
+    pub password_reset_expire: u64,
+
+    /// 找回密码邮件中的链接模板，`{token}` 会被替换为令牌
+    /// 环境变量: APP_ACCOUNT_PASSWORD_RESET_URL
+    #[serde(default = "default_password_reset_url")]
+    pub password_reset_url: String,
+
+    /// 每个邮箱在限流窗口内最多申请找回密码的次数，默认 3
+    /// 环境变量: APP_ACCOUNT_PASSWORD_RESET_EMAIL_LIMIT
+    #[serde(default = "default_password_reset_email_limit")]
+    pub password_reset_email_limit: u64,
+
+    /// 每个 IP 在限流窗口内最多申请找回密码的次数，默认 20
+    /// 环境变量: APP_ACCOUNT_PASSWORD_RESET_IP_LIMIT
+    #[serde(default = "default_password_reset_ip_limit")]
+    pub password_reset_ip_limit: u64,
+
+    /// 找回密码限流窗口（秒），默认 1 小时
+    /// 环境变量: APP_ACCOUNT_PASSWORD_RESET_LIMIT_WINDOW
+    #[serde(default = "default_password_reset_limit_window")]
+    pub password_reset_limit_window: u64,
+
+    /// 头像使用的 S3 实例名，对应 `s3_instances` 中的 name，未配置时使用主 S3
+    /// 环境变量: APP_ACCOUNT_AVATAR_S3_INSTANCE
+    pub avatar_s3_instance: Option<String>,
+
+    /// 头像写入的存储桶，未配置时不开放头像上传
+    /// 环境变量: APP_ACCOUNT_AVATAR_BUCKET
+    pub avatar_bucket: Option<String>,
+
+    /// 头像对象的键前缀，对象键为 `{前缀}/{用户 ID}/{ID}.{扩展名}`，默认 `avatars`
+    /// 环境变量: APP_ACCOUNT_AVATAR_KEY_PREFIX
+    #[serde(default = "default_avatar_key_prefix")]
+    pub avatar_key_prefix: String,
+
+    /// 头像的公开访问地址前缀（如 CDN 地址），保存到用户资料的地址为 `{前缀}/{对象键}`；
+    /// 未配置时保存 `s3://{存储桶}/{对象键}`
+    /// 环境变量: APP_ACCOUNT_AVATAR_URL_PREFIX
+    pub avatar_url_prefix: Option<String>,
+
+    /// 头像文件大小上限（字节），默认 2 MiB
+    /// 环境变量: APP_ACCOUNT_AVATAR_MAX_SIZE
+    #[serde(default = "default_avatar_max_size")]
+    pub avatar_max_size: usize,
+}
+
+impl Default for AccountConfig {
+    fn default() -> Self {
+        Self {
+            password_history: default_password_history(),
+            password_reset_expire: default_password_reset_expire(),
+            password_reset_url: default_password_reset_url(),
+            password_reset_email_limit: default_password_reset_email_limit(),
+            password_reset_ip_limit: default_password_reset_ip_limit(),
+            password_reset_limit_window: default_password_reset_limit_window(),
+            avatar_s3_instance: None,
+            avatar_bucket: None,
+            avatar_key_prefix: default_avatar_key_prefix(),
+            avatar_url_prefix: None,
+            avatar_max_size: default_avatar_max_size(),
+        }
+    }
+}
+
+fn default_password_history() -> usize {
+    5
+}
+
+fn default_password_reset_expire() -> u64 {
+    30 * 60
+}
+
+fn default_password_reset_url() -> String {
+    "http://localhost:9527/#/reset-password?token={token}".to_string()
+}
+
+fn default_password_reset_email_limit() -> u64 {
+    3
+}
+
+fn default_password_reset_ip_limit() -> u64 {
+    20
+}
+
+fn default_password_reset_limit_window() -> u64 {
+    60 * 60
+}
+
+fn default_avatar_key_prefix() -> String {
+    "avatars".to_string()
+}
+
+fn default_avatar_max_size() -> usize {
+    2 * 1024 * 1024
+}
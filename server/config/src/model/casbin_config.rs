@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Casbin 授权配置
+///
+/// 支持的环境变量：
+/// - APP_CASBIN_AUDIT: 是否记录每次授权决策
+/// - APP_CASBIN_AUDIT_SINK: 决策的记录方式，`tracing` 或 `database`
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct CasbinConfig {
+    /// 是否记录每次授权决策（主体、域、资源、操作、命中的策略和结果），默认关闭
+    /// 环境变量: APP_CASBIN_AUDIT
+    #[serde(default)]
+    pub audit: bool,
+
+    /// 决策的记录方式，默认输出到 tracing 日志
+    /// 环境变量: APP_CASBIN_AUDIT_SINK
+    #[serde(default)]
+    pub audit_sink: CasbinAuditSink,
+}
+
+/// 授权决策的记录方式
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CasbinAuditSink {
+    /// 输出到 tracing 日志
+    #[default]
+    Tracing,
+    /// 写入 `sys_authorization_decision` 表
+    Database,
+}
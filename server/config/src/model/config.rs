@@ -1,8 +1,11 @@
 use serde::Deserialize;
 
 use super::{
-    DatabaseConfig, DatabasesInstancesConfig, JwtConfig, MongoConfig, MongoInstancesConfig,
-    RedisConfig, RedisInstancesConfig, S3Config, S3InstancesConfig, ServerConfig,
+    AccessKeyConfig, AccountConfig, CasbinConfig, DatabaseConfig, DatabasesInstancesConfig,
+    EventBusConfig, JwtConfig, LogRetentionConfig, LoginSecurityConfig, MailConfig, MongoConfig,
+    MongoInstancesConfig, OidcProviderConfig, OperationLogConfig, RedisConfig,
+    RedisInstancesConfig, S3Config, S3InstancesConfig, SchedulerConfig, ServerConfig, SiweConfig,
+    TotpConfig,
 };
 
 /// 应用程序配置结构
@@ -53,6 +56,18 @@ use super::{
 /// - `database_instances`: 可选的数据库连接池配置，用于配置多个命名的数据库连接
 /// - `server`: HTTP 服务器配置，包含监听地址和端口等
 /// - `jwt`: JWT 认证配置，包含密钥和过期时间等
+/// - `login_security`: 登录安全策略，包含失败锁定、IP 访问控制和登录时间窗口
+/// - `totp`: 双因素认证配置，包含签发方名称和挑战令牌有效期等
+/// - `oidc_providers`: 可选的 OIDC 外部登录提供方
+/// - `siwe`: 可选的以太坊钱包登录（EIP-4361）配置
+/// - `access_key`: API 访问密钥配置，包含密钥落库加密的口令
+/// - `account`: 用户自助服务配置，包含密码历史、找回密码和头像上传
+/// - `mail`: 邮件发送配置，支持 SMTP、写文件和写日志
+/// - `casbin`: 授权配置，包含授权决策审计
+/// - `event_bus`: 事件总线配置，包含通道容量、重试与 Redis Streams 传输
+/// - `operation_log`: 操作日志记录策略，包含字段脱敏、大小限制和路由排除
+/// - `log_retention`: 登录日志与操作日志的保留天数和 S3 归档
+/// - `scheduler`: 定时任务配置
 /// - `redis`: 主 Redis 配置，用于配置默认的 Redis 连接
 /// - `redis_instances`: 可选的 Redis 连接池配置，用于配置多个命名的 Redis 连接
 /// - `mongo`: 主 MongoDB 配置，用于配置默认的 MongoDB 连接
@@ -102,6 +117,51 @@ pub struct Config {
     /// JWT 认证配置
     pub jwt: JwtConfig,
 
+    /// 登录安全策略，未配置时使用默认值
+    #[serde(default)]
+    pub login_security: LoginSecurityConfig,
+
+    /// 双因素认证配置，未配置时使用默认值
+    #[serde(default)]
+    pub totp: TotpConfig,
+
+    /// 可选的 OIDC 外部登录提供方
+    pub oidc_providers: Option<Vec<OidcProviderConfig>>,
+
+    /// 可选的以太坊钱包登录配置，未配置时不开放钱包登录
+    pub siwe: Option<SiweConfig>,
+
+    /// API 访问密钥配置，创建访问密钥前必须配置加密口令
+    pub access_key: Option<AccessKeyConfig>,
+
+    /// 用户自助服务配置，未配置时使用默认值
+    #[serde(default)]
+    pub account: AccountConfig,
+
+    /// 邮件发送配置，未配置时邮件只写入日志
+    #[serde(default)]
+    pub mail: MailConfig,
+
+    /// 授权配置，未配置时不记录授权决策
+    #[serde(default)]
+    pub casbin: CasbinConfig,
+
+    /// 事件总线配置，未配置时使用默认值
+    #[serde(default)]
+    pub event_bus: EventBusConfig,
+
+    /// 操作日志记录策略，未配置时使用默认值
+    #[serde(default)]
+    pub operation_log: OperationLogConfig,
+
+    /// 日志保留与归档配置，未配置时不清理日志
+    #[serde(default)]
+    pub log_retention: LogRetentionConfig,
+
+    /// 定时任务配置，未配置时使用默认值
+    #[serde(default)]
+    pub scheduler: SchedulerConfig,
+
     /// 主 Redis 配置
     pub redis: Option<RedisConfig>,
 
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+/// 事件总线配置
+///
+/// 支持的环境变量：
+/// - APP_EVENT_BUS_CAPACITY: 每个订阅者通道的容量
+/// - APP_EVENT_BUS_MAX_ATTEMPTS: 事件处理的最大尝试次数
+/// - APP_EVENT_BUS_RETRY_BACKOFF_MS: 首次重试前的等待时间（毫秒）
+/// - APP_EVENT_BUS_TRANSPORT: 事件传输方式，`local` 或 `redis_streams`
+/// - APP_EVENT_BUS_STREAM_GROUP: Redis Streams 消费组
+/// - APP_EVENT_BUS_STREAM_CONSUMER: Redis Streams 消费者名
+/// - APP_EVENT_BUS_STREAM_MAX_LEN: Redis Stream 的近似最大长度
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EventBusConfig {
+    /// 每个订阅者通道的容量，写满时发布方等待，默认 1024
+    /// 环境变量: APP_EVENT_BUS_CAPACITY
+    #[serde(default = "default_capacity")]
+    pub capacity: usize,
+
+    /// 事件处理的最大尝试次数，耗尽后写入死信，默认 3
+    /// 环境变量: APP_EVENT_BUS_MAX_ATTEMPTS
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+
+    /// 首次重试前的等待时间（毫秒），之后每次翻倍，默认 200
+    /// 环境变量: APP_EVENT_BUS_RETRY_BACKOFF_MS
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+
+    /// 登录日志与操作日志的传输方式，默认仅在进程内传递
+    /// 环境变量: APP_EVENT_BUS_TRANSPORT
+    #[serde(default)]
+    pub transport: EventTransport,
+
+    /// Redis Streams 消费组，同组实例共同消费事件，默认 `soybean-admin`
+    /// 环境变量: APP_EVENT_BUS_STREAM_GROUP
+    #[serde(default = "default_stream_group")]
+    pub stream_group: String,
+
+    /// Redis Streams 消费者名，需在实例重启后保持不变，默认取主机名
+    /// 环境变量: APP_EVENT_BUS_STREAM_CONSUMER
+    pub stream_consumer: Option<String>,
+
+    /// Redis Stream 的近似最大长度，默认 100000
+    /// 环境变量: APP_EVENT_BUS_STREAM_MAX_LEN
+    #[serde(default = "default_stream_max_len")]
+    pub stream_max_len: usize,
+}
+
+/// 事件传输方式
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventTransport {
+    /// 进程内有界通道
+    #[default]
+    Local,
+    /// 经主 Redis 的 Streams 传递，可由其他实例消费
+    RedisStreams,
+}
+
+impl Default for EventBusConfig {
+    fn default() -> Self {
+        Self {
+            capacity: default_capacity(),
+            max_attempts: default_max_attempts(),
+            retry_backoff_ms: default_retry_backoff_ms(),
+            transport: EventTransport::default(),
+            stream_group: default_stream_group(),
+            stream_consumer: None,
+            stream_max_len: default_stream_max_len(),
+        }
+    }
+}
+
+fn default_capacity() -> usize {
+    1024
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    200
+}
+
+fn default_stream_group() -> String {
+    "soybean-admin".to_string()
+}
+
+fn default_stream_max_len() -> usize {
+    100_000
+}
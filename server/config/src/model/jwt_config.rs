@@ -6,9 +6,12 @@ use serde::Deserialize;
 /// - APP_JWT_JWT_SECRET: JWT 密钥
 /// - APP_JWT_ISSUER: JWT 签发者
 /// - APP_JWT_EXPIRE: JWT 过期时间（秒）
+/// - APP_JWT_REFRESH_EXPIRE: 刷新令牌过期时间（秒）
+/// - APP_JWT_ALGORITHM: 签名算法
+/// - APP_JWT_ACTIVE_KID: 当前用于签名的密钥 ID
 #[derive(Deserialize, Debug, Clone)]
 pub struct JwtConfig {
-    /// JWT 密钥
+    /// JWT 密钥，仅在 HS256/HS384/HS512 算法下使用
     /// 环境变量: APP_JWT_JWT_SECRET
     pub jwt_secret: String,
 
@@ -19,4 +22,48 @@ pub struct JwtConfig {
     /// JWT 过期时间（秒）
     /// 环境变量: APP_JWT_EXPIRE
     pub expire: i64,
+
+    /// 刷新令牌过期时间（秒），默认 7 天
+    /// 环境变量: APP_JWT_REFRESH_EXPIRE
+    #[serde(default = "default_refresh_expire")]
+    pub refresh_expire: i64,
+
+    /// 签名算法，默认 HS256
+    /// 可选值: HS256, HS384, HS512, RS256, RS384, RS512, PS256, PS384, PS512, ES256, ES384, EdDSA
+    /// 环境变量: APP_JWT_ALGORITHM
+    #[serde(default = "default_algorithm")]
+    pub algorithm: String,
+
+    /// 当前用于签名的密钥 ID，未配置时使用 `keys` 中第一个带私钥的密钥
+    /// 环境变量: APP_JWT_ACTIVE_KID
+    #[serde(default)]
+    pub active_kid: Option<String>,
+
+    /// 非对称算法使用的密钥列表
+    ///
+    /// 轮换密钥时先加入新密钥并切换 `active_kid`，旧密钥保留公钥直到其签发的令牌全部过期。
+    #[serde(default)]
+    pub keys: Vec<JwtKeyConfig>,
+}
+
+/// JWT 非对称密钥配置
+#[derive(Deserialize, Debug, Clone)]
+pub struct JwtKeyConfig {
+    /// 密钥 ID，写入令牌头部的 `kid`
+    pub kid: String,
+
+    /// PEM 格式私钥文件路径，仅用于验证的旧密钥可以不配置
+    #[serde(default)]
+    pub private_key_path: Option<String>,
+
+    /// PEM 格式公钥文件路径
+    pub public_key_path: String,
+}
+
+fn default_refresh_expire() -> i64 {
+    7 * 24 * 60 * 60
+}
+
+fn default_algorithm() -> String {
+    "HS256".to_string()
 }
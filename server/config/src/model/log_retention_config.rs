@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+/// 登录日志与操作日志保留配置
+///
+/// 过期的日志按天写成 gzip 压缩的 NDJSON 归档到 S3，上传成功后才从数据库删除
+///
+/// 支持的环境变量：
+/// - APP_LOG_RETENTION_ENABLED: 是否启用定时清理
+/// - APP_LOG_RETENTION_CRON: 清理任务的 cron 表达式
+/// - APP_LOG_RETENTION_OPERATION_LOG_DAYS: 操作日志保留天数
+/// - APP_LOG_RETENTION_LOGIN_LOG_DAYS: 登录日志保留天数
+/// - APP_LOG_RETENTION_S3_INSTANCE: 归档使用的 S3 实例名
+/// - APP_LOG_RETENTION_BUCKET: 归档写入的存储桶
+/// - APP_LOG_RETENTION_KEY_PREFIX: 归档对象的键前缀
+/// - APP_LOG_RETENTION_BATCH_SIZE: 每次从数据库读取的行数
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LogRetentionConfig {
+    /// 是否启用定时清理，默认关闭
+    /// 环境变量: APP_LOG_RETENTION_ENABLED
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// 清理任务的 cron 表达式，默认每小时整点执行
+    /// 环境变量: APP_LOG_RETENTION_CRON
+    #[serde(default = "default_cron")]
+    pub cron: String,
+
+    /// 操作日志保留天数，0 表示永久保留，默认 90
+    /// 环境变量: APP_LOG_RETENTION_OPERATION_LOG_DAYS
+    #[serde(default = "default_operation_log_days")]
+    pub operation_log_days: u32,
+
+    /// 登录日志保留天数，0 表示永久保留，默认 180
+    /// 环境变量: APP_LOG_RETENTION_LOGIN_LOG_DAYS
+    #[serde(default = "default_login_log_days")]
+    pub login_log_days: u32,
+
+    /// 归档使用的 S3 实例名，对应 `s3_instances` 中的 name，未配置时使用主 S3
+    /// 环境变量: APP_LOG_RETENTION_S3_INSTANCE
+    pub s3_instance: Option<String>,
+
+    /// 归档写入的存储桶，未配置时不清理任何日志
+    /// 环境变量: APP_LOG_RETENTION_BUCKET
+    pub bucket: Option<String>,
+
+    /// 归档对象的键前缀，对象键为 `{前缀}/{表名}/dt={日期}/{ID}.ndjson.gz`，默认 `log-archive`
+    /// 环境变量: APP_LOG_RETENTION_KEY_PREFIX
+    #[serde(default = "default_key_prefix")]
+    pub key_prefix: String,
+
+    /// 每次从数据库读取的行数，默认 1000
+    /// 环境变量: APP_LOG_RETENTION_BATCH_SIZE
+    #[serde(default = "default_batch_size")]
+    pub batch_size: u64,
+}
+
+impl Default for LogRetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cron: default_cron(),
+            operation_log_days: default_operation_log_days(),
+            login_log_days: default_login_log_days(),
+            s3_instance: None,
+            bucket: None,
+            key_prefix: default_key_prefix(),
+            batch_size: default_batch_size(),
+        }
+    }
+}
+
+fn default_cron() -> String {
+    "0 * * * *".to_string()
+}
+
+fn default_operation_log_days() -> u32 {
+    90
+}
+
+fn default_login_log_days() -> u32 {
+    180
+}
+
+fn default_key_prefix() -> String {
+    "log-archive".to_string()
+}
+
+fn default_batch_size() -> u64 {
+    1000
+}
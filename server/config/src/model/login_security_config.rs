@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+/// 登录安全策略配置
+///
+/// 作为全局默认值使用，各域可以在数据库中覆盖其中的任意字段。
+///
+/// 支持的环境变量：
+/// - APP_LOGIN_SECURITY_MAX_ATTEMPTS: 锁定前允许的连续失败次数
+/// - APP_LOGIN_SECURITY_ATTEMPT_WINDOW: 失败次数统计窗口（秒）
+/// - APP_LOGIN_SECURITY_LOCKOUT_DURATION: 首次锁定时长（秒）
+/// - APP_LOGIN_SECURITY_LOCKOUT_BACKOFF_MULTIPLIER: 再次锁定时的时长倍数
+/// - APP_LOGIN_SECURITY_MAX_LOCKOUT_DURATION: 锁定时长上限（秒）
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LoginSecurityConfig {
+    /// 锁定前允许的连续失败次数，默认 5
+    /// 环境变量: APP_LOGIN_SECURITY_MAX_ATTEMPTS
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+
+    /// 失败次数统计窗口（秒），默认 15 分钟
+    /// 环境变量: APP_LOGIN_SECURITY_ATTEMPT_WINDOW
+    #[serde(default = "default_attempt_window")]
+    pub attempt_window: u64,
+
+    /// 首次锁定时长（秒），默认 30 分钟
+    /// 环境变量: APP_LOGIN_SECURITY_LOCKOUT_DURATION
+    #[serde(default = "default_lockout_duration")]
+    pub lockout_duration: u64,
+
+    /// 解锁后再次被锁定时，锁定时长按该倍数递增，默认 2
+    /// 环境变量: APP_LOGIN_SECURITY_LOCKOUT_BACKOFF_MULTIPLIER
+    #[serde(default = "default_lockout_backoff_multiplier")]
+    pub lockout_backoff_multiplier: u32,
+
+    /// 锁定时长上限（秒），默认 24 小时
+    /// 环境变量: APP_LOGIN_SECURITY_MAX_LOCKOUT_DURATION
+    #[serde(default = "default_max_lockout_duration")]
+    pub max_lockout_duration: u64,
+
+    /// 允许登录的 IP 段（CIDR 或单个 IP），为空表示不限制
+    #[serde(default)]
+    pub ip_allow_list: Vec<String>,
+
+    /// 禁止登录的 IP 段（CIDR 或单个 IP），优先于允许列表
+    #[serde(default)]
+    pub ip_deny_list: Vec<String>,
+
+    /// 允许登录的时间窗口，满足任意一个即可，为空表示不限制
+    #[serde(default)]
+    pub login_windows: Vec<LoginWindowConfig>,
+}
+
+/// 登录时间窗口
+///
+/// 结束时间早于开始时间时表示跨越午夜，例如 22:00 - 06:00。
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct LoginWindowConfig {
+    /// 时区，支持 `UTC`、`Local`（服务器本地时区）以及 `+08:00` 形式的固定偏移
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+
+    /// 开始时间，格式 HH:MM
+    pub start: String,
+
+    /// 结束时间（不含），格式 HH:MM
+    pub end: String,
+}
+
+impl Default for LoginSecurityConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            attempt_window: default_attempt_window(),
+            lockout_duration: default_lockout_duration(),
+            lockout_backoff_multiplier: default_lockout_backoff_multiplier(),
+            max_lockout_duration: default_max_lockout_duration(),
+            ip_allow_list: Vec::new(),
+            ip_deny_list: Vec::new(),
+            login_windows: Vec::new(),
+        }
+    }
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_attempt_window() -> u64 {
+    15 * 60
+}
+
+fn default_lockout_duration() -> u64 {
+    30 * 60
+}
+
+fn default_lockout_backoff_multiplier() -> u32 {
+    2
+}
+
+fn default_max_lockout_duration() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_timezone() -> String {
+    "Local".to_string()
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+/// 邮件发送配置
+///
+/// 支持的环境变量：
+/// - APP_MAIL_TRANSPORT: 发送方式，`disabled`、`log`、`file` 或 `smtp`
+/// - APP_MAIL_FROM: 发件人
+/// - APP_MAIL_SMTP_HOST: SMTP 服务器地址
+/// - APP_MAIL_SMTP_PORT: SMTP 服务器端口
+/// - APP_MAIL_SMTP_USERNAME: SMTP 登录用户名
+/// - APP_MAIL_SMTP_PASSWORD: SMTP 登录密码
+/// - APP_MAIL_SMTP_STARTTLS: 是否使用 STARTTLS
+/// - APP_MAIL_FILE_DIR: `file` 方式下邮件写入的目录
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MailConfig {
+    /// 发送方式，默认不发送邮件
+    /// 环境变量: APP_MAIL_TRANSPORT
+    #[serde(default)]
+    pub transport: MailTransport,
+
+    /// 发件人，如 `Soybean Admin <noreply@example.com>`
+    /// 环境变量: APP_MAIL_FROM
+    #[serde(default = "default_from")]
+    pub from: String,
+
+    /// SMTP 服务器地址，`smtp` 方式下必须配置
+    /// 环境变量: APP_MAIL_SMTP_HOST
+    pub smtp_host: Option<String>,
+
+    /// SMTP 服务器端口，默认 587
+    /// 环境变量: APP_MAIL_SMTP_PORT
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+
+    /// SMTP 登录用户名，未配置时不登录
+    /// 环境变量: APP_MAIL_SMTP_USERNAME
+    pub smtp_username: Option<String>,
+
+    /// SMTP 登录密码
+    /// 环境变量: APP_MAIL_SMTP_PASSWORD
+    pub smtp_password: Option<String>,
+
+    /// 是否通过 STARTTLS 加密连接，默认开启；关闭后以明文连接，仅用于本地调试
+    /// 环境变量: APP_MAIL_SMTP_STARTTLS
+    #[serde(default = "default_smtp_starttls")]
+    pub smtp_starttls: bool,
+
+    /// `file` 方式下每封邮件写成一个 `.eml` 文件的目录，默认 `mail-outbox`
+    /// 环境变量: APP_MAIL_FILE_DIR
+    #[serde(default = "default_file_dir")]
+    pub file_dir: String,
+}
+
+/// 邮件发送方式
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MailTransport {
+    /// 不发送邮件，依赖邮件的功能（如找回密码）不可用
+    #[default]
+    Disabled,
+    /// 只把收件人和主题写入日志，用于开发和测试
+    Log,
+    /// 写入本地目录，用于测试
+    File,
+    /// 通过 SMTP 服务器发送
+    Smtp,
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            transport: MailTransport::default(),
+            from: default_from(),
+            smtp_host: None,
+            smtp_port: default_smtp_port(),
+            smtp_username: None,
+            smtp_password: None,
+            smtp_starttls: default_smtp_starttls(),
+            file_dir: default_file_dir(),
+        }
+    }
+}
+
+fn default_from() -> String {
+    "soybean-admin <noreply@localhost>".to_string()
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_starttls() -> bool {
+    true
+}
+
+fn default_file_dir() -> String {
+    "mail-outbox".to_string()
+}
@@ -1,10 +1,22 @@
+pub use access_key_config::AccessKeyConfig;
+pub use account_config::AccountConfig;
+pub use casbin_config::{CasbinAuditSink, CasbinConfig};
 pub use config::Config;
 pub use database_config::{DatabaseConfig, DatabasesInstancesConfig};
-pub use jwt_config::JwtConfig;
+pub use event_bus_config::{EventBusConfig, EventTransport};
+pub use jwt_config::{JwtConfig, JwtKeyConfig};
+pub use log_retention_config::LogRetentionConfig;
+pub use login_security_config::{LoginSecurityConfig, LoginWindowConfig};
+pub use mail_config::{MailConfig, MailTransport};
 pub use mongo_config::{MongoConfig, MongoInstancesConfig};
+pub use oidc_config::OidcProviderConfig;
+pub use operation_log_config::OperationLogConfig;
 pub use redis_config::{RedisConfig, RedisInstancesConfig, RedisMode};
 pub use s3_config::{S3Config, S3InstancesConfig};
+pub use scheduler_config::SchedulerConfig;
 pub use server_config::ServerConfig;
+pub use siwe_config::SiweConfig;
+pub use totp_config::TotpConfig;
 
 /// 可选配置集合的包装类
 #[allow(dead_code)]
@@ -19,10 +31,22 @@ impl<T> From<Option<Vec<T>>> for OptionalConfigs<T> {
     }
 }
 
+mod access_key_config;
+mod account_config;
+mod casbin_config;
 mod config;
 mod database_config;
+mod event_bus_config;
 mod jwt_config;
+mod log_retention_config;
+mod login_security_config;
+mod mail_config;
 mod mongo_config;
+mod oidc_config;
+mod operation_log_config;
 mod redis_config;
 mod s3_config;
+mod scheduler_config;
 mod server_config;
+mod siwe_config;
+mod totp_config;
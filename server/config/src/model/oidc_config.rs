@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+/// OIDC 外部登录提供方配置
+///
+/// 使用授权码 + PKCE 流程登录，ID Token 通过提供方的 JWKS 验证，
+/// 端点从 `{issuer}/.well-known/openid-configuration` 自动发现。
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OidcProviderConfig {
+    /// 提供方名称，用于登录地址 `/auth/external/{name}/...`
+    pub name: String,
+
+    /// 签发方地址，需与 ID Token 中的 `iss` 一致
+    pub issuer: String,
+
+    pub client_id: String,
+
+    /// 机密客户端的密钥，公共客户端只使用 PKCE 时可不配置
+    #[serde(default)]
+    pub client_secret: Option<String>,
+
+    /// 提供方回调的前端地址，需在提供方处登记
+    pub redirect_uri: String,
+
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+
+    /// 外部用户登录到的域
+    #[serde(default = "default_domain")]
+    pub domain: String,
+
+    /// 映射为用户名的声明
+    #[serde(default = "default_username_claim")]
+    pub username_claim: String,
+
+    /// 映射为昵称的声明
+    #[serde(default = "default_nick_name_claim")]
+    pub nick_name_claim: String,
+
+    /// 映射为邮箱的声明
+    #[serde(default = "default_email_claim")]
+    pub email_claim: String,
+
+    /// 首次登录时按用户名关联域内已有用户，只应对可信的提供方开启
+    #[serde(default)]
+    pub link_by_username: bool,
+
+    /// 首次登录且没有可关联的用户时自动创建用户
+    #[serde(default)]
+    pub auto_create_user: bool,
+
+    /// 自动创建的用户分配的角色编码，按 `domain` 域内的角色查找
+    #[serde(default)]
+    pub default_role: Option<String>,
+}
+
+fn default_scopes() -> Vec<String> {
+    vec![
+        "openid".to_string(),
+        "profile".to_string(),
+        "email".to_string(),
+    ]
+}
+
+fn default_domain() -> String {
+    "built-in".to_string()
+}
+
+fn default_username_claim() -> String {
+    "preferred_username".to_string()
+}
+
+fn default_nick_name_claim() -> String {
+    "name".to_string()
+}
+
+fn default_email_claim() -> String {
+    "email".to_string()
+}
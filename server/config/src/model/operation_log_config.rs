@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// 操作日志记录策略
+///
+/// 支持的环境变量：
+/// - APP_OPERATION_LOG_MAX_BODY_SIZE: 记录的请求体和响应体的最大字节数
+/// - APP_OPERATION_LOG_SAMPLE_RATE: 记录操作日志的采样比例
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OperationLogConfig {
+    /// 需要脱敏的字段名，不区分大小写并忽略 `_` 与 `-`，`privateKey` 与 `private_key` 等价
+    #[serde(default = "default_redact_keys")]
+    pub redact_keys: Vec<String>,
+
+    /// 需要脱敏的 JSON 路径，如 `$.data.token`、`$.items.*.secret`，`*` 匹配任意字段或数组下标
+    #[serde(default)]
+    pub redact_paths: Vec<String>,
+
+    /// 记录的请求体和响应体的最大字节数，超出时只记录截断标记，默认 32KB
+    /// 环境变量: APP_OPERATION_LOG_MAX_BODY_SIZE
+    #[serde(default = "default_max_body_size")]
+    pub max_body_size: usize,
+
+    /// 记录操作日志的采样比例，取值 0 到 1，默认 1 即全部记录
+    /// 环境变量: APP_OPERATION_LOG_SAMPLE_RATE
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: f64,
+
+    /// 不记录操作日志的路由，`*` 匹配一段路径，`**` 匹配剩余的所有路径
+    #[serde(default)]
+    pub exclude_routes: Vec<String>,
+}
+
+impl Default for OperationLogConfig {
+    fn default() -> Self {
+        Self {
+            redact_keys: default_redact_keys(),
+            redact_paths: Vec::new(),
+            max_body_size: default_max_body_size(),
+            sample_rate: default_sample_rate(),
+            exclude_routes: Vec::new(),
+        }
+    }
+}
+
+fn default_redact_keys() -> Vec<String> {
+    [
+        "password",
+        "oldPassword",
+        "newPassword",
+        "confirmPassword",
+        "secret",
+        "clientSecret",
+        "accessKeySecret",
+        "privateKey",
+        "mnemonic",
+        "token",
+        "accessToken",
+        "refreshToken",
+        "apiKey",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_max_body_size() -> usize {
+    32 * 1024
+}
+
+fn default_sample_rate() -> f64 {
+    1.0
+}
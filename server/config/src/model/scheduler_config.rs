@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// 定时任务配置
+///
+/// 支持的环境变量：
+/// - APP_SCHEDULER_ENABLED: 是否启用定时任务
+/// - APP_SCHEDULER_ACCESS_KEY_EXPIRY_CRON: 禁用过期访问密钥任务的 cron 表达式
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SchedulerConfig {
+    /// 是否启用定时任务，关闭后所有任务都不会定时触发，默认开启
+    /// 环境变量: APP_SCHEDULER_ENABLED
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// 禁用过期访问密钥任务的 cron 表达式，默认每分钟执行
+    /// 环境变量: APP_SCHEDULER_ACCESS_KEY_EXPIRY_CRON
+    #[serde(default = "default_access_key_expiry_cron")]
+    pub access_key_expiry_cron: String,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            access_key_expiry_cron: default_access_key_expiry_cron(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_access_key_expiry_cron() -> String {
+    "* * * * *".to_string()
+}
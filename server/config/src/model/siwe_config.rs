@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// 以太坊钱包登录（Sign-In with Ethereum，EIP-4361）配置
+///
+/// 消息中的 `domain`、`uri` 和 `chain_id` 必须与这里的配置一致，
+/// 防止其他站点诱导用户签名的消息被拿来登录本系统。
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SiweConfig {
+    /// 发起签名请求的站点，即前端页面的 host[:port]
+    pub domain: String,
+
+    /// 登录请求的资源地址，通常为前端站点的根地址
+    pub uri: String,
+
+    /// 允许登录的链 ID，默认只允许以太坊主网
+    #[serde(default = "default_chain_ids")]
+    pub chain_ids: Vec<u64>,
+}
+
+fn default_chain_ids() -> Vec<u64> {
+    vec![1]
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// 双因素认证（TOTP）配置
+///
+/// 支持的环境变量：
+/// - APP_TOTP_ISSUER: 身份验证器中显示的签发方名称
+/// - APP_TOTP_CHALLENGE_EXPIRE: 登录挑战令牌有效期（秒）
+/// - APP_TOTP_SKEW: 允许的时间步偏差
+/// - APP_TOTP_RECOVERY_CODE_COUNT: 每次生成的恢复码数量
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TotpConfig {
+    /// 身份验证器中显示的签发方名称
+    /// 环境变量: APP_TOTP_ISSUER
+    #[serde(default = "default_issuer")]
+    pub issuer: String,
+
+    /// 密码验证通过后签发的挑战令牌有效期（秒），默认 5 分钟
+    /// 环境变量: APP_TOTP_CHALLENGE_EXPIRE
+    #[serde(default = "default_challenge_expire")]
+    pub challenge_expire: i64,
+
+    /// 允许前后偏差的时间步数量，用于容忍客户端时钟误差，默认 1
+    /// 环境变量: APP_TOTP_SKEW
+    #[serde(default = "default_skew")]
+    pub skew: u64,
+
+    /// 每次生成的恢复码数量，默认 10
+    /// 环境变量: APP_TOTP_RECOVERY_CODE_COUNT
+    #[serde(default = "default_recovery_code_count")]
+    pub recovery_code_count: usize,
+}
+
+impl Default for TotpConfig {
+    fn default() -> Self {
+        Self {
+            issuer: default_issuer(),
+            challenge_expire: default_challenge_expire(),
+            skew: default_skew(),
+            recovery_code_count: default_recovery_code_count(),
+        }
+    }
+}
+
+fn default_issuer() -> String {
+    "soybean-admin-rust".to_string()
+}
+
+fn default_challenge_expire() -> i64 {
+    5 * 60
+}
+
+fn default_skew() -> u64 {
+    1
+}
+
+fn default_recovery_code_count() -> usize {
+    10
+}
@@ -9,6 +9,9 @@ pub enum Audience {
     MobileApp,
     /// Audience for mini-programs or widgets.
     MiniProgram,
+    /// Audience for the short-lived token issued between the password and
+    /// second-factor steps of a two-factor login.
+    TwoFactorChallenge,
 }
 
 impl Audience {
@@ -19,6 +22,7 @@ impl Audience {
             Audience::ManagementPlatform => "management_platform",
             Audience::MobileApp => "mobile_app",
             Audience::MiniProgram => "mini_program",
+            Audience::TwoFactorChallenge => "two_factor_challenge",
         }
     }
 }
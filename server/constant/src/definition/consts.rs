@@ -32,4 +32,10 @@ pub enum SystemEvent {
     AuditOperationLoggedEvent,
     /// API密钥验证事件
     AuthApiKeyValidatedEvent,
+    /// 授权决策审计事件
+    AuthorizationDecisionEvent,
+    /// JWT签发事件
+    JwtCreatedEvent,
+    /// 超级管理员切换租户域事件
+    TenantSwitchedEvent,
 }
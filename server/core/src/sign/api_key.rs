@@ -2,13 +2,15 @@ use md5::{Digest, Md5};
 use parking_lot::RwLock;
 use ring::{digest, hmac};
 use std::{
-    borrow::Cow,
     collections::HashMap,
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::sign::nonce_store::{create_memory_store_factory, NonceStore, NonceStoreFactory};
+use crate::sign::{
+    canonical_request::string_to_sign,
+    nonce_store::{create_memory_store_factory, NonceStore, NonceStoreFactory},
+};
 
 /// Supported signature algorithms for API key validation.
 ///
@@ -129,6 +131,8 @@ impl Default for SimpleApiKeyValidator {
 #[derive(Clone)]
 pub struct ComplexApiKeyValidator {
     secrets: Arc<RwLock<HashMap<String, String>>>,
+    /// 轮换前的密钥及其失效时间（毫秒时间戳），宽限期内仍可用于签名
+    previous_secrets: Arc<RwLock<HashMap<String, (String, i64)>>>,
     nonce_store: NonceStore,
     nonce_store_factory: NonceStoreFactory,
     config: ApiKeyConfig,
@@ -156,6 +160,7 @@ impl ComplexApiKeyValidator {
     ) -> Self {
         Self {
             secrets: Arc::new(RwLock::new(HashMap::with_capacity(DEFAULT_CAPACITY))),
+            previous_secrets: Arc::new(RwLock::new(HashMap::new())),
             nonce_store: (nonce_store_factory)(),
             nonce_store_factory,
             config: config.unwrap_or_default(),
@@ -171,11 +176,7 @@ impl ComplexApiKeyValidator {
     /// Validates if a timestamp is within the allowed 5-minute window.
     #[inline]
     fn validate_timestamp(&self, timestamp: i64) -> bool {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as i64;
-        (now - timestamp).abs() < TIMESTAMP_DISPARITY_MS
+        (current_millis() - timestamp).abs() < TIMESTAMP_DISPARITY_MS
     }
 
     /// Calculates signature for a signing string using the configured algorithm.
@@ -233,25 +234,10 @@ impl ComplexApiKeyValidator {
         timestamp: i64,
         nonce: &str,
     ) -> bool {
-        if !self.validate_timestamp(timestamp) {
+        if !self.check_replay(timestamp, nonce) {
             return false;
         }
 
-        let check_result = tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current()
-                .block_on(async { self.nonce_store.check_and_set(nonce).await })
-        });
-
-        if !check_result {
-            return false;
-        }
-
-        let secrets_guard = self.secrets.read();
-        let secret = match secrets_guard.get(api_key) {
-            Some(s) => Cow::Borrowed(s),
-            None => return false,
-        };
-
         // Pre-allocate with capacity to avoid reallocations
         let mut sorted_params: Vec<_> = Vec::with_capacity(params.len());
         sorted_params.extend_from_slice(params);
@@ -277,7 +263,73 @@ impl ComplexApiKeyValidator {
             }
         }
 
-        self.calculate_signature(&signing_string, &secret) == signature
+        self.verify_with_secrets(api_key, |secret| {
+            self.calculate_signature(&signing_string, secret) == signature
+        })
+    }
+
+    /// Validates a request signed with the canonical request scheme.
+    ///
+    /// # Arguments
+    /// * `api_key` - The API key to validate
+    /// * `canonical_request` - Canonical request built from the received request
+    /// * `signature` - The hex encoded HMAC-SHA256 signature to validate
+    /// * `timestamp` - Request timestamp in milliseconds since UNIX epoch
+    /// * `nonce` - Unique request identifier to prevent replay attacks
+    ///
+    /// # Returns
+    /// * `true` if the request is valid
+    /// * `false` if any validation check fails
+    pub fn validate_canonical_signature(
+        &self,
+        api_key: &str,
+        canonical_request: &str,
+        signature: &str,
+        timestamp: i64,
+        nonce: &str,
+    ) -> bool {
+        let signature = match hex::decode(signature) {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+
+        if !self.check_replay(timestamp, nonce) {
+            return false;
+        }
+
+        let string_to_sign = string_to_sign(timestamp, nonce, canonical_request);
+        self.verify_with_secrets(api_key, |secret| {
+            let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+            hmac::verify(&key, string_to_sign.as_bytes(), &signature).is_ok()
+        })
+    }
+
+    /// Checks the timestamp window and records the nonce.
+    fn check_replay(&self, timestamp: i64, nonce: &str) -> bool {
+        if !self.validate_timestamp(timestamp) {
+            return false;
+        }
+
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current()
+                .block_on(async { self.nonce_store.check_and_set(nonce).await })
+        })
+    }
+
+    /// Verifies a signature against the current secret, or the previous secret
+    /// while its rotation grace period has not ended.
+    fn verify_with_secrets(&self, api_key: &str, verify: impl Fn(&str) -> bool) -> bool {
+        match self.secrets.read().get(api_key) {
+            Some(secret) if verify(secret) => return true,
+            Some(_) => {},
+            None => return false,
+        }
+
+        // 轮换宽限期内，旧密钥计算的签名仍然有效
+        match self.previous_secrets.read().get(api_key) {
+            Some((previous, valid_until)) if current_millis() < *valid_until => verify(previous),
+            _ => false,
+        }
     }
 
     /// Adds a new API key and its corresponding secret.
@@ -297,6 +349,26 @@ impl ComplexApiKeyValidator {
     #[inline]
     pub fn remove_key(&self, key: &str) {
         self.secrets.write().remove(key);
+        self.previous_secrets.write().remove(key);
+    }
+
+    /// 设置轮换前的旧密钥，在 `valid_until`（毫秒时间戳）之前仍可用于签名
+    ///
+    /// # Arguments
+    /// * `key` - The API key
+    /// * `secret` - The secret used before rotation
+    /// * `valid_until` - End of the grace period in milliseconds since UNIX epoch
+    #[inline]
+    pub fn set_previous_secret(&self, key: String, secret: String, valid_until: i64) {
+        self.previous_secrets
+            .write()
+            .insert(key, (secret, valid_until));
+    }
+
+    /// 移除轮换前的旧密钥
+    #[inline]
+    pub fn remove_previous_secret(&self, key: &str) {
+        self.previous_secrets.write().remove(key);
     }
 
     /// Updates the API key validation configuration.
@@ -309,6 +381,15 @@ impl ComplexApiKeyValidator {
     }
 }
 
+/// Current time in milliseconds since UNIX epoch.
+#[inline]
+fn current_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,6 +414,15 @@ mod tests {
         assert!(store.check_and_set("nonce1").await);
     }
 
+    #[tokio::test]
+    async fn test_issued_nonce_is_consumed_once() {
+        let store = crate::sign::nonce_store::create_memory_store();
+        assert!(!store.consume("never-issued").await);
+        assert!(store.issue("nonce1").await);
+        assert!(store.consume("nonce1").await);
+        assert!(!store.consume("nonce1").await);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_complex_validator() {
         let validator = ComplexApiKeyValidator::new(Some(ApiKeyConfig {
@@ -358,6 +448,79 @@ mod tests {
         assert!(validator.validate_signature("test-key", &params, &signature, now, "test-nonce"));
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_previous_secret_during_grace_period() {
+        let validator = ComplexApiKeyValidator::new(None);
+        validator.add_key_secret("test-key".to_string(), "new-secret".to_string());
+
+        let now = current_millis();
+        let params = vec![("timestamp".to_string(), now.to_string())];
+        let signing_string = format!("timestamp={}", now);
+        let old_signature = validator.calculate_signature(&signing_string, "old-secret");
+
+        assert!(!validator.validate_signature("test-key", &params, &old_signature, now, "n1"));
+
+        validator.set_previous_secret(
+            "test-key".to_string(),
+            "old-secret".to_string(),
+            now + 60_000,
+        );
+        assert!(validator.validate_signature("test-key", &params, &old_signature, now, "n2"));
+
+        validator.set_previous_secret("test-key".to_string(), "old-secret".to_string(), now);
+        assert!(!validator.validate_signature("test-key", &params, &old_signature, now, "n3"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_canonical_validator() {
+        use crate::sign::{canonical_request, sha256_hex, CanonicalAuthorization, RequestSigner};
+        use axum::http::{header::AUTHORIZATION, HeaderMap, Method, Uri};
+
+        let validator = ComplexApiKeyValidator::new(None);
+        validator.add_key_secret("test-key".to_string(), "test-secret".to_string());
+
+        let uri: Uri = "http://localhost/orders?id=1".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        let now = current_millis();
+        RequestSigner::new("test-key", "test-secret")
+            .sign_with(
+                &Method::POST,
+                &uri,
+                &mut headers,
+                b"{}",
+                now,
+                "canonical-nonce",
+            )
+            .unwrap();
+
+        let authorization =
+            CanonicalAuthorization::parse(headers[AUTHORIZATION].to_str().unwrap()).unwrap();
+        let canonical = canonical_request(
+            &Method::POST,
+            &uri,
+            &headers,
+            &authorization.signed_headers,
+            &sha256_hex(b"{}"),
+        )
+        .unwrap();
+
+        assert!(validator.validate_canonical_signature(
+            "test-key",
+            &canonical,
+            &authorization.signature,
+            now,
+            "canonical-nonce"
+        ));
+        // 相同 nonce 不能重放
+        assert!(!validator.validate_canonical_signature(
+            "test-key",
+            &canonical,
+            &authorization.signature,
+            now,
+            "canonical-nonce"
+        ));
+    }
+
     #[test]
     fn test_concurrent_access() {
         let validator = Arc::new(ComplexApiKeyValidator::new(None));
@@ -1,18 +1,31 @@
 use axum::{
     body::Body,
-    extract::Request,
-    http::{HeaderMap, StatusCode, Uri},
+    extract::{ConnectInfo, Request},
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode, Uri},
     middleware::Next,
     response::IntoResponse,
 };
+use axum_casbin::CasbinVals;
+use chrono::Utc;
+use ipnet::IpNet;
 use once_cell::sync::Lazy;
-use server_constant::definition::consts::SystemEvent;
 use server_global::global;
-use std::{collections::HashSet, sync::RwLock};
+use std::{
+    collections::HashSet,
+    net::{IpAddr, SocketAddr},
+    sync::RwLock,
+};
+
+use crate::web::{res::Res, util::ClientIp};
 
-use crate::web::res::Res;
+use super::{
+    access_key_subject, canonical_request, get_key_scope, sha256_hex, ApiKeyEvent,
+    CanonicalAuthorization, ComplexApiKeyValidator, SignatureScheme, SimpleApiKeyValidator,
+    CANONICAL_SIGNATURE_ALGORITHM, SIGN_DATE_HEADER, SIGN_NONCE_HEADER,
+};
 
-use super::{ApiKeyEvent, ComplexApiKeyValidator, SimpleApiKeyValidator};
+/// 规范请求签名时允许读取的最大请求体（字节）
+const MAX_SIGNED_BODY_SIZE: usize = 10 * 1024 * 1024;
 
 /// Global set of protected paths.
 ///
@@ -113,25 +126,96 @@ fn is_protected_path(uri: &Uri) -> bool {
 /// API key validation middleware.
 ///
 /// This middleware checks if the API key is valid for the given request.
+/// 复杂校验时，带有 `SOYBEAN-HMAC-SHA256` 认证头的请求按规范请求签名校验，否则按 URL 参数签名校验。
+/// 校验通过后还会检查密钥的过期时间、来源 IP 和允许的签名方式，并以 `ak:<AccessKeyId>` 作为 casbin
+/// 主体写入请求扩展，由后续的 `CasbinAxumLayer` 校验该密钥可访问的接口。
 #[inline]
 pub async fn api_key_middleware(
     validator: ApiKeyValidation,
-    req: Request<Body>,
+    mut req: Request<Body>,
     next: Next,
 ) -> impl IntoResponse {
     if !is_protected_path(req.uri()) {
         return next.run(req).await.into_response();
     }
 
-    match validate_request(&validator, &req) {
-        Ok(true) => next.run(req).await.into_response(),
-        Ok(false) => Res::<()>::new_error(
-            StatusCode::UNAUTHORIZED.as_u16(),
-            "Invalid API key or signature",
-        )
-        .into_response(),
-        Err(e) => Res::<()>::new_error(StatusCode::BAD_REQUEST.as_u16(), e).into_response(),
+    // 规范请求签名覆盖请求体，需要先读出请求体再放回
+    let body = if is_canonical_request(&validator, req.headers()) {
+        let (parts, body) = req.into_parts();
+        match axum::body::to_bytes(body, MAX_SIGNED_BODY_SIZE).await {
+            Ok(bytes) => {
+                req = Request::from_parts(parts, Body::from(bytes.clone()));
+                Some(bytes)
+            },
+            Err(_) => {
+                return Res::<()>::new_error(
+                    StatusCode::PAYLOAD_TOO_LARGE.as_u16(),
+                    "Request body is too large to verify",
+                )
+                .into_response()
+            },
+        }
+    } else {
+        None
+    };
+
+    let (api_key, scheme) = match validate_request(&validator, &req, body.as_deref()) {
+        Ok(Some(validated)) => validated,
+        Ok(None) => {
+            return Res::<()>::new_error(
+                StatusCode::UNAUTHORIZED.as_u16(),
+                "Invalid API key or signature",
+            )
+            .into_response()
+        },
+        Err(e) => return Res::<()>::new_error(StatusCode::BAD_REQUEST.as_u16(), e).into_response(),
+    };
+
+    if let Some(scope) = get_key_scope(&api_key) {
+        if scope.is_expired(Utc::now().timestamp_millis()) {
+            return Res::<()>::new_error(StatusCode::UNAUTHORIZED.as_u16(), "API key has expired")
+                .into_response();
+        }
+        if scheme.is_some_and(|scheme| !scope.allows_scheme(scheme)) {
+            return Res::<()>::new_error(
+                StatusCode::UNAUTHORIZED.as_u16(),
+                "Signature scheme is not allowed for this API key",
+            )
+            .into_response();
+        }
+        let trusted_proxies = ClientIp::trusted_proxies().await;
+        if !scope.is_ip_allowed(client_ip(&req, &trusted_proxies)) {
+            return Res::<()>::new_error(
+                StatusCode::FORBIDDEN.as_u16(),
+                "Client IP is not allowed to use this API key",
+            )
+            .into_response();
+        }
+        req.extensions_mut().insert(CasbinVals {
+            subject: vec![access_key_subject(&api_key)],
+            domain: Some(scope.domain),
+        });
     }
+
+    global::emit_event(ApiKeyEvent { api_key });
+    next.run(req).await.into_response()
+}
+
+/// 解析访问密钥 IP 白名单使用的客户端地址
+///
+/// 只有连接的对端是 `access_key.trusted_proxies` 中的代理时才采用转发头。
+fn client_ip(req: &Request<Body>, trusted_proxies: &[IpNet]) -> Option<IpAddr> {
+    req.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| ClientIp::resolve(addr.ip(), req.headers(), trusted_proxies))
+}
+
+/// Whether the request uses the canonical request signing scheme.
+#[inline]
+fn is_canonical_request(validator: &ApiKeyValidation, headers: &HeaderMap) -> bool {
+    matches!(validator, ApiKeyValidation::Complex(..))
+        && get_header_value(headers, AUTHORIZATION.as_str())
+            .is_some_and(|value| value.starts_with(CANONICAL_SIGNATURE_ALGORITHM))
 }
 
 /// Get value from request headers.
@@ -155,12 +239,15 @@ fn get_query_value<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a
 
 /// Validate API key in request.
 ///
-/// This function validates the API key in the given request.
+/// This function validates the API key in the given request and returns the
+/// API key with the signature scheme used when the request is valid.
+/// `body` is only present for requests using the canonical request scheme.
 #[inline]
 fn validate_request(
     validator: &ApiKeyValidation,
     req: &Request<Body>,
-) -> Result<bool, &'static str> {
+    body: Option<&[u8]>,
+) -> Result<Option<(String, Option<SignatureScheme>)>, &'static str> {
     let headers = req.headers();
     let query = req.uri().query().unwrap_or("");
     let params = if !query.is_empty() {
@@ -177,13 +264,12 @@ fn validate_request(
             }
             .ok_or("Missing API key")?;
 
-            global::send_dyn_event(
-                SystemEvent::AuthApiKeyValidatedEvent.as_ref(),
-                Box::new(ApiKeyEvent {
-                    api_key: api_key.to_owned(),
-                }),
-            );
-            Ok(validator.validate_key(api_key))
+            Ok(validator
+                .validate_key(api_key)
+                .then(|| (api_key.to_owned(), None)))
+        },
+        ApiKeyValidation::Complex(validator, _) if body.is_some() => {
+            validate_canonical_request(validator, req, body.unwrap_or_default())
         },
         ApiKeyValidation::Complex(validator, config) => {
             let api_key =
@@ -205,23 +291,55 @@ fn validate_request(
                 .map(|(k, v)| (k.clone(), v.clone()))
                 .collect();
 
-            global::send_dyn_event(
-                SystemEvent::AuthApiKeyValidatedEvent.as_ref(),
-                Box::new(ApiKeyEvent {
-                    api_key: api_key.to_owned(),
-                }),
-            );
-            Ok(validator.validate_signature(
-                api_key,
-                &params_for_signing,
-                signature,
-                timestamp,
-                nonce,
-            ))
+            Ok(validator
+                .validate_signature(api_key, &params_for_signing, signature, timestamp, nonce)
+                .then(|| (api_key.to_owned(), Some(SignatureScheme::Query))))
         },
     }
 }
 
+/// Validate a request signed with the canonical request scheme.
+#[inline]
+fn validate_canonical_request(
+    validator: &ComplexApiKeyValidator,
+    req: &Request<Body>,
+    body: &[u8],
+) -> Result<Option<(String, Option<SignatureScheme>)>, &'static str> {
+    let headers = req.headers();
+    let authorization = get_header_value(headers, AUTHORIZATION.as_str())
+        .and_then(CanonicalAuthorization::parse)
+        .ok_or("Malformed Authorization header")?;
+
+    let timestamp = get_header_value(headers, SIGN_DATE_HEADER)
+        .ok_or("Missing x-sign-date")?
+        .parse::<i64>()
+        .map_err(|_| "Invalid x-sign-date")?;
+
+    let nonce = get_header_value(headers, SIGN_NONCE_HEADER).ok_or("Missing x-sign-nonce")?;
+
+    let canonical_request = canonical_request(
+        req.method(),
+        req.uri(),
+        headers,
+        &authorization.signed_headers,
+        &sha256_hex(body),
+    )
+    .ok_or("Missing signed header")?;
+
+    Ok(validator
+        .validate_canonical_signature(
+            &authorization.access_key_id,
+            &canonical_request,
+            &authorization.signature,
+            timestamp,
+            nonce,
+        )
+        .then_some((
+            authorization.access_key_id,
+            Some(SignatureScheme::Canonical),
+        )))
+}
+
 /// Parse query string into key-value pairs.
 ///
 /// This function parses a query string into a vector of key-value pairs.
@@ -257,7 +375,7 @@ mod tests {
             .as_millis() as i64;
         let nonce = format!("nonce_{}", timestamp);
 
-        let mut params = vec![
+        let mut params = [
             ("AccessKeyId".to_string(), "test-access-key".to_string()),
             ("param1".to_string(), "value1".to_string()),
             ("param2".to_string(), "value2".to_string()),
@@ -0,0 +1,103 @@
+use ipnet::IpNet;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::{collections::HashMap, net::IpAddr};
+
+use super::SignatureScheme;
+
+/// casbin 策略中访问密钥主体的前缀
+pub const ACCESS_KEY_SUBJECT_PREFIX: &str = "ak:";
+
+/// 已加载访问密钥的使用范围，按 AccessKeyId 索引
+static API_KEY_SCOPES: Lazy<RwLock<HashMap<String, ApiKeyScope>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 访问密钥的使用范围
+///
+/// 过期时间和来源 IP 由 API 密钥中间件校验，可访问的接口由 casbin 以 `ak:<AccessKeyId>` 为主体校验。
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeyScope {
+    /// 密钥所属域，作为 casbin 校验的 domain
+    pub domain: String,
+    /// 过期时间（毫秒时间戳），为空表示永不过期
+    pub expires_at: Option<i64>,
+    /// 允许的来源 IP 段，为空表示不限制
+    pub allowed_ips: Vec<IpNet>,
+    /// 允许的签名方式，为空表示不限制
+    pub signature_schemes: Vec<SignatureScheme>,
+}
+
+impl ApiKeyScope {
+    /// 密钥在 `now`（毫秒时间戳）时是否已过期
+    #[inline]
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// 来源 IP 是否允许使用该密钥；配置了 IP 段但无法识别来源 IP 时拒绝
+    pub fn is_ip_allowed(&self, ip: Option<IpAddr>) -> bool {
+        if self.allowed_ips.is_empty() {
+            return true;
+        }
+        ip.is_some_and(|ip| self.allowed_ips.iter().any(|net| net.contains(&ip)))
+    }
+
+    /// 是否允许使用该签名方式
+    #[inline]
+    pub fn allows_scheme(&self, scheme: SignatureScheme) -> bool {
+        self.signature_schemes.is_empty() || self.signature_schemes.contains(&scheme)
+    }
+}
+
+/// 访问密钥在 casbin 策略中的主体
+#[inline]
+pub fn access_key_subject(access_key_id: &str) -> String {
+    format!("{}{}", ACCESS_KEY_SUBJECT_PREFIX, access_key_id)
+}
+
+/// 设置访问密钥的使用范围
+pub fn set_key_scope(access_key_id: &str, scope: ApiKeyScope) {
+    API_KEY_SCOPES
+        .write()
+        .insert(access_key_id.to_string(), scope);
+}
+
+/// 移除访问密钥的使用范围
+pub fn remove_key_scope(access_key_id: &str) {
+    API_KEY_SCOPES.write().remove(access_key_id);
+}
+
+/// 获取访问密钥的使用范围
+pub fn get_key_scope(access_key_id: &str) -> Option<ApiKeyScope> {
+    API_KEY_SCOPES.read().get(access_key_id).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_scope() {
+        let scope = ApiKeyScope {
+            domain: "built-in".to_string(),
+            expires_at: Some(1_000),
+            allowed_ips: vec!["10.0.0.0/8".parse().unwrap(), "::1/128".parse().unwrap()],
+            signature_schemes: vec![SignatureScheme::Canonical],
+        };
+
+        assert!(!scope.is_expired(999));
+        assert!(scope.is_expired(1_000));
+        assert!(scope.is_ip_allowed("10.1.2.3".parse().ok()));
+        assert!(scope.is_ip_allowed("::1".parse().ok()));
+        assert!(!scope.is_ip_allowed("192.168.1.1".parse().ok()));
+        assert!(!scope.is_ip_allowed(None));
+        assert!(scope.allows_scheme(SignatureScheme::Canonical));
+        assert!(!scope.allows_scheme(SignatureScheme::Query));
+
+        let unrestricted = ApiKeyScope::default();
+        assert!(!unrestricted.is_expired(i64::MAX));
+        assert!(unrestricted.is_ip_allowed(None));
+        assert!(unrestricted.allows_scheme(SignatureScheme::Query));
+        assert_eq!(access_key_subject("AK01"), "ak:AK01");
+    }
+}
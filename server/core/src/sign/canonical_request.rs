@@ -0,0 +1,355 @@
+use axum::http::{
+    header::{AUTHORIZATION, HOST},
+    HeaderMap, HeaderValue, Method, Uri,
+};
+use chrono::Utc;
+use ring::{digest, hmac};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use ulid::Ulid;
+
+/// 规范请求签名的算法标识，同时作为 `Authorization` 头的认证方案
+pub const CANONICAL_SIGNATURE_ALGORITHM: &str = "SOYBEAN-HMAC-SHA256";
+/// 签名时间（毫秒时间戳）请求头
+pub const SIGN_DATE_HEADER: &str = "x-sign-date";
+/// 签名随机数请求头
+pub const SIGN_NONCE_HEADER: &str = "x-sign-nonce";
+
+/// 客户端默认参与签名的请求头，请求中不存在的会被忽略
+const DEFAULT_SIGNED_HEADERS: [&str; 4] =
+    ["content-type", "host", SIGN_DATE_HEADER, SIGN_NONCE_HEADER];
+
+/// API 密钥的签名方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureScheme {
+    /// 对 URL 参数签名，签名放在查询参数中
+    Query,
+    /// 对方法、路径、查询参数、指定请求头和请求体摘要签名，签名放在 `Authorization` 头中
+    Canonical,
+}
+
+/// 规范请求签名的 `Authorization` 头
+///
+/// 格式为 `SOYBEAN-HMAC-SHA256 Credential=<AccessKeyId>, SignedHeaders=<h1;h2>, Signature=<hex>`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalAuthorization {
+    pub access_key_id: String,
+    /// 参与签名的请求头，小写并按字典序排列
+    pub signed_headers: Vec<String>,
+    pub signature: String,
+}
+
+impl CanonicalAuthorization {
+    /// 解析 `Authorization` 头，认证方案不匹配或字段缺失时返回 `None`
+    pub fn parse(value: &str) -> Option<Self> {
+        let credentials = value
+            .strip_prefix(CANONICAL_SIGNATURE_ALGORITHM)?
+            .strip_prefix(' ')?;
+
+        let mut access_key_id = None;
+        let mut signed_headers = None;
+        let mut signature = None;
+        for part in credentials.split(',') {
+            let (name, value) = part.trim().split_once('=')?;
+            match name {
+                "Credential" => access_key_id = Some(value.to_string()),
+                "SignedHeaders" => {
+                    signed_headers = Some(
+                        value
+                            .split(';')
+                            .filter(|header| !header.is_empty())
+                            .map(str::to_ascii_lowercase)
+                            .collect::<Vec<_>>(),
+                    )
+                },
+                "Signature" => signature = Some(value.to_string()),
+                _ => return None,
+            }
+        }
+
+        let mut signed_headers = signed_headers?;
+        signed_headers.sort_unstable();
+        signed_headers.dedup();
+
+        Some(Self {
+            access_key_id: access_key_id.filter(|id| !id.is_empty())?,
+            signed_headers,
+            signature: signature.filter(|signature| !signature.is_empty())?,
+        })
+    }
+}
+
+impl fmt::Display for CanonicalAuthorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} Credential={}, SignedHeaders={}, Signature={}",
+            CANONICAL_SIGNATURE_ALGORITHM,
+            self.access_key_id,
+            self.signed_headers.join(";"),
+            self.signature
+        )
+    }
+}
+
+/// 计算数据的 SHA-256 摘要（十六进制）
+#[inline]
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(digest::digest(&digest::SHA256, data))
+}
+
+/// 规范化查询字符串：解码后按参数名和值排序，再统一编码
+pub fn canonical_query(query: &str) -> String {
+    let mut pairs: Vec<(String, String)> = form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+    pairs.sort_unstable();
+
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// 构造规范请求
+///
+/// 依次为方法、路径、规范化查询字符串、参与签名的请求头、请求头名称列表和请求体摘要，以换行分隔。
+/// 参与签名的请求头缺失或不是合法字符串时返回 `None`。
+pub fn canonical_request(
+    method: &Method,
+    uri: &Uri,
+    headers: &HeaderMap,
+    signed_headers: &[String],
+    body_hash: &str,
+) -> Option<String> {
+    let mut canonical_headers = String::new();
+    for name in signed_headers {
+        let values = headers
+            .get_all(name.as_str())
+            .iter()
+            .map(|value| value.to_str().map(str::trim))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        if values.is_empty() {
+            return None;
+        }
+        canonical_headers.push_str(name);
+        canonical_headers.push(':');
+        canonical_headers.push_str(&values.join(","));
+        canonical_headers.push('\n');
+    }
+
+    Some(format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method.as_str(),
+        uri.path(),
+        canonical_query(uri.query().unwrap_or("")),
+        canonical_headers,
+        signed_headers.join(";"),
+        body_hash
+    ))
+}
+
+/// 待签名字符串，包含算法、时间戳、随机数和规范请求摘要
+pub fn string_to_sign(timestamp: i64, nonce: &str, canonical_request: &str) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        CANONICAL_SIGNATURE_ALGORITHM,
+        timestamp,
+        nonce,
+        sha256_hex(canonical_request.as_bytes())
+    )
+}
+
+/// 使用 HMAC-SHA256 计算签名（十六进制）
+#[inline]
+pub fn calculate_canonical_signature(secret: &str, string_to_sign: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hex::encode(hmac::sign(&key, string_to_sign.as_bytes()))
+}
+
+/// 客户端规范请求签名器
+///
+/// 为请求写入 `x-sign-date`、`x-sign-nonce` 和 `Authorization` 头。
+///
+/// # 示例
+/// ```
+/// use axum::http::{HeaderMap, Method, Uri};
+/// use server_core::sign::RequestSigner;
+///
+/// let signer = RequestSigner::new("AK01", "SK01");
+/// let uri: Uri = "https://api.example.com/sandbox/complex-api-key?a=1".parse().unwrap();
+/// let mut headers = HeaderMap::new();
+/// signer.sign(&Method::GET, &uri, &mut headers, b"").unwrap();
+/// assert!(headers.contains_key("authorization"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RequestSigner {
+    access_key_id: String,
+    secret: String,
+    signed_headers: Vec<String>,
+}
+
+impl RequestSigner {
+    pub fn new(access_key_id: impl Into<String>, secret: impl Into<String>) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret: secret.into(),
+            signed_headers: DEFAULT_SIGNED_HEADERS
+                .iter()
+                .map(|h| h.to_string())
+                .collect(),
+        }
+    }
+
+    /// 追加参与签名的请求头
+    pub fn with_signed_headers(mut self, headers: &[&str]) -> Self {
+        self.signed_headers
+            .extend(headers.iter().map(|h| h.to_ascii_lowercase()));
+        self
+    }
+
+    /// 使用当前时间和随机 nonce 签名请求
+    pub fn sign(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &mut HeaderMap,
+        body: &[u8],
+    ) -> Result<(), http::header::InvalidHeaderValue> {
+        self.sign_with(
+            method,
+            uri,
+            headers,
+            body,
+            Utc::now().timestamp_millis(),
+            &Ulid::new().to_string(),
+        )
+    }
+
+    /// 使用指定的时间戳（毫秒）和 nonce 签名请求
+    pub fn sign_with(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &mut HeaderMap,
+        body: &[u8],
+        timestamp: i64,
+        nonce: &str,
+    ) -> Result<(), http::header::InvalidHeaderValue> {
+        if !headers.contains_key(HOST) {
+            if let Some(authority) = uri.authority() {
+                headers.insert(HOST, HeaderValue::from_str(authority.as_str())?);
+            }
+        }
+        headers.insert(SIGN_DATE_HEADER, HeaderValue::from(timestamp));
+        headers.insert(SIGN_NONCE_HEADER, HeaderValue::from_str(nonce)?);
+
+        let mut signed_headers: Vec<String> = self
+            .signed_headers
+            .iter()
+            .filter(|name| {
+                let values = headers.get_all(name.as_str());
+                values.iter().next().is_some() && values.iter().all(|v| v.to_str().is_ok())
+            })
+            .cloned()
+            .collect();
+        signed_headers.sort_unstable();
+        signed_headers.dedup();
+
+        let canonical_request =
+            canonical_request(method, uri, headers, &signed_headers, &sha256_hex(body))
+                .unwrap_or_default();
+        let signature = calculate_canonical_signature(
+            &self.secret,
+            &string_to_sign(timestamp, nonce, &canonical_request),
+        );
+
+        let authorization = CanonicalAuthorization {
+            access_key_id: self.access_key_id.clone(),
+            signed_headers,
+            signature,
+        };
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&authorization.to_string())?,
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_query() {
+        assert_eq!(canonical_query("b=2&a=%20x&a=1"), "a=%20x&a=1&b=2");
+        assert_eq!(canonical_query(""), "");
+    }
+
+    #[test]
+    fn test_authorization_round_trip() {
+        let authorization = CanonicalAuthorization {
+            access_key_id: "AK01".to_string(),
+            signed_headers: vec!["host".to_string(), SIGN_DATE_HEADER.to_string()],
+            signature: "abcd".to_string(),
+        };
+        let value = authorization.to_string();
+        assert_eq!(
+            value,
+            "SOYBEAN-HMAC-SHA256 Credential=AK01, SignedHeaders=host;x-sign-date, Signature=abcd"
+        );
+        assert_eq!(CanonicalAuthorization::parse(&value), Some(authorization));
+        assert_eq!(CanonicalAuthorization::parse("Bearer token"), None);
+        assert_eq!(
+            CanonicalAuthorization::parse("SOYBEAN-HMAC-SHA256 Credential=AK01, Signature=ab"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_signer_covers_body() {
+        let signer = RequestSigner::new("AK01", "SK01");
+        let uri: Uri = "https://api.example.com/orders?b=2&a=1".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        signer
+            .sign_with(
+                &Method::POST,
+                &uri,
+                &mut headers,
+                b"{}",
+                1_700_000_000_000,
+                "n1",
+            )
+            .unwrap();
+
+        let authorization =
+            CanonicalAuthorization::parse(headers.get(AUTHORIZATION).unwrap().to_str().unwrap())
+                .unwrap();
+        assert_eq!(
+            authorization.signed_headers,
+            ["host", "x-sign-date", "x-sign-nonce"]
+        );
+
+        let verify = |method: &Method, body: &[u8]| {
+            let canonical = canonical_request(
+                method,
+                &uri,
+                &headers,
+                &authorization.signed_headers,
+                &sha256_hex(body),
+            )
+            .unwrap();
+            calculate_canonical_signature(
+                "SK01",
+                &string_to_sign(1_700_000_000_000, "n1", &canonical),
+            ) == authorization.signature
+        };
+        assert!(verify(&Method::POST, b"{}"));
+        assert!(!verify(&Method::POST, b"{\"amount\":1}"));
+        assert!(!verify(&Method::PUT, b"{}"));
+    }
+}
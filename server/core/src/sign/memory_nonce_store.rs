@@ -40,6 +40,19 @@ impl MemoryNonceStore {
             true
         }
     }
+
+    /// Stores a server-issued nonce until it is consumed or expires.
+    #[inline]
+    pub async fn issue(&self, nonce: &str) -> bool {
+        self.nonces.insert(nonce.to_string(), ());
+        true
+    }
+
+    /// Removes an issued nonce, returning `true` only for the first call.
+    #[inline]
+    pub async fn consume(&self, nonce: &str) -> bool {
+        self.nonces.remove(nonce).is_some()
+    }
 }
 
 impl Default for MemoryNonceStore {
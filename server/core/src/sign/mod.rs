@@ -1,5 +1,7 @@
 mod api_key;
 mod api_key_middleware;
+mod api_key_scope;
+mod canonical_request;
 mod memory_nonce_store;
 mod nonce_store;
 mod redis_nonce_store;
@@ -11,11 +13,23 @@ pub use api_key_middleware::{
     api_key_middleware, protect_route, ApiKeySource, ApiKeyValidation, ComplexApiKeyConfig,
     SimpleApiKeyConfig,
 };
+pub use api_key_scope::{
+    access_key_subject, get_key_scope, remove_key_scope, set_key_scope, ApiKeyScope,
+    ACCESS_KEY_SUBJECT_PREFIX,
+};
+pub use canonical_request::{
+    calculate_canonical_signature, canonical_query, canonical_request, sha256_hex,
+    string_to_sign, CanonicalAuthorization, RequestSigner, SignatureScheme,
+    CANONICAL_SIGNATURE_ALGORITHM, SIGN_DATE_HEADER, SIGN_NONCE_HEADER,
+};
 pub use memory_nonce_store::{create_memory_nonce_store_factory, MemoryNonceStore};
 pub use nonce_store::{NonceStore, NonceStoreFactory};
 pub use redis_nonce_store::{create_redis_nonce_store_factory, RedisNonceStore};
 
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use server_constant::definition::consts::SystemEvent;
+use server_global::event_bus::Event;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -71,6 +85,20 @@ pub async fn remove_key(validator_type: ValidatorType, key: &str) {
     }
 }
 
+/// 为复杂验证器设置轮换前的旧密钥，`valid_until` 为宽限期结束的毫秒时间戳
+pub async fn set_previous_secret(key: &str, secret: &str, valid_until: i64) {
+    API_KEY_VALIDATORS.1.read().await.set_previous_secret(
+        key.to_string(),
+        secret.to_string(),
+        valid_until,
+    );
+}
+
+/// 移除复杂验证器中轮换前的旧密钥
+pub async fn remove_previous_secret(key: &str) {
+    API_KEY_VALIDATORS.1.read().await.remove_previous_secret(key);
+}
+
 pub async fn init_validators(config: Option<ApiKeyConfig>) {
     // 使用默认的内存 nonce 存储
     init_validators_with_nonce_store(config, create_memory_nonce_store_factory()).await;
@@ -89,7 +117,13 @@ pub async fn init_validators_with_nonce_store(
     *API_KEY_VALIDATORS.1.write().await = complex_validator;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiKeyEvent {
     pub api_key: String,
 }
+
+impl Event for ApiKeyEvent {
+    fn name() -> &'static str {
+        SystemEvent::AuthApiKeyValidatedEvent.as_ref()
+    }
+}
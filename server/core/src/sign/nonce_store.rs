@@ -24,6 +24,30 @@ impl NonceStore {
             NonceStore::Redis(store) => store.check_and_set(nonce).await,
         }
     }
+
+    /// Stores a server-issued nonce so that it can later be consumed once
+    ///
+    /// # Returns
+    /// * `true` - If the nonce was stored
+    /// * `false` - If the storage backend is unavailable
+    pub async fn issue(&self, nonce: &str) -> bool {
+        match self {
+            NonceStore::Memory(store) => store.issue(nonce).await,
+            NonceStore::Redis(store) => store.issue(nonce).await,
+        }
+    }
+
+    /// Consumes a nonce previously stored with [`NonceStore::issue`]
+    ///
+    /// # Returns
+    /// * `true` - If the nonce was issued, has not expired and has not been consumed yet
+    /// * `false` - Otherwise
+    pub async fn consume(&self, nonce: &str) -> bool {
+        match self {
+            NonceStore::Memory(store) => store.consume(nonce).await,
+            NonceStore::Redis(store) => store.consume(nonce).await,
+        }
+    }
 }
 
 /// Factory function type for creating NonceStore instances
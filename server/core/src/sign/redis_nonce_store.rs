@@ -33,51 +33,50 @@ impl Default for RedisNonceStore {
 
 impl RedisNonceStore {
     pub async fn check_and_set(&self, nonce: &str) -> bool {
-        let redis_connection = match server_global::global::GLOBAL_PRIMARY_REDIS
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(self.get_key(nonce))
+            .arg("1")
+            .arg("NX")
+            .arg("EX")
+            .arg(NONCE_TTL_SECS);
+
+        Self::execute::<bool>(&cmd).await.unwrap_or(false)
+    }
+
+    /// Stores a server-issued nonce until it is consumed or expires
+    pub async fn issue(&self, nonce: &str) -> bool {
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(self.get_key(nonce))
+            .arg("1")
+            .arg("EX")
+            .arg(NONCE_TTL_SECS);
+
+        Self::execute::<()>(&cmd).await.is_some()
+    }
+
+    /// Deletes an issued nonce, returning `true` only for the first call
+    pub async fn consume(&self, nonce: &str) -> bool {
+        let mut cmd = redis::cmd("DEL");
+        cmd.arg(self.get_key(nonce));
+
+        Self::execute::<i64>(&cmd).await == Some(1)
+    }
+
+    /// Runs a command on the primary Redis connection, returning `None` on any failure
+    async fn execute<T: redis::FromRedisValue>(cmd: &redis::Cmd) -> Option<T> {
+        let redis_connection = server_global::global::GLOBAL_PRIMARY_REDIS
             .read()
             .await
-            .clone()
-        {
-            Some(conn) => conn,
-            None => return false,
-        };
-
-        let key = self.get_key(nonce);
+            .clone()?;
 
         match redis_connection {
             RedisConnection::Single(client) => {
-                if let Ok(mut conn) = client.get_multiplexed_async_connection().await {
-                    let result: Option<bool> = redis::cmd("SET")
-                        .arg(&key)
-                        .arg("1")
-                        .arg("NX")
-                        .arg("EX")
-                        .arg(NONCE_TTL_SECS)
-                        .query_async(&mut conn)
-                        .await
-                        .ok();
-
-                    result.unwrap_or(false)
-                } else {
-                    false
-                }
+                let mut conn = client.get_multiplexed_async_connection().await.ok()?;
+                cmd.query_async(&mut conn).await.ok()
             },
             RedisConnection::Cluster(client) => {
-                if let Ok(mut conn) = client.get_async_connection().await {
-                    let result: Option<bool> = redis::cmd("SET")
-                        .arg(&key)
-                        .arg("1")
-                        .arg("NX")
-                        .arg("EX")
-                        .arg(NONCE_TTL_SECS)
-                        .query_async(&mut conn)
-                        .await
-                        .ok();
-
-                    result.unwrap_or(false)
-                } else {
-                    false
-                }
+                let mut conn = client.get_async_connection().await.ok()?;
+                cmd.query_async(&mut conn).await.ok()
             },
         }
     }
@@ -67,6 +67,18 @@ impl Claims {
     pub fn set_jti(&mut self, jti: String) {
         self.jti = Some(jti);
     }
+
+    pub fn sub(&self) -> &str {
+        &self.sub
+    }
+
+    pub fn exp(&self) -> Option<usize> {
+        self.exp
+    }
+
+    pub fn jti(&self) -> Option<&str> {
+        self.jti.as_deref()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -0,0 +1,363 @@
+use std::{collections::HashMap, fs, str::FromStr};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use jsonwebtoken::{
+    jwk::{
+        AlgorithmParameters, CommonParameters, EllipticCurve, EllipticCurveKeyParameters,
+        EllipticCurveKeyType, Jwk, JwkSet, KeyAlgorithm, OctetKeyPairParameters, OctetKeyPairType,
+        PublicKeyUse, RSAKeyParameters, RSAKeyType,
+    },
+    Algorithm, DecodingKey, EncodingKey,
+};
+use server_config::{JwtConfig, JwtKeyConfig};
+use server_global::global::Keys;
+use simple_asn1::{from_der, ASN1Block};
+
+use crate::web::jwt::JwtError;
+
+/// 根据 JWT 配置构建签名和验证密钥
+///
+/// HMAC 算法直接使用 `jwt_secret`；非对称算法从 `keys` 中加载 PEM 文件，
+/// 按 kid 建立验证密钥表，并导出公钥集合供 `/.well-known/jwks.json` 使用。
+pub fn load_keys(config: &JwtConfig) -> Result<Keys, JwtError> {
+    let algorithm = Algorithm::from_str(&config.algorithm)
+        .map_err(|_| key_error(format!("unsupported algorithm: {}", config.algorithm)))?;
+
+    if matches!(
+        algorithm,
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+    ) {
+        let mut keys = Keys::new(config.jwt_secret.as_bytes());
+        keys.algorithm = algorithm;
+        return Ok(keys);
+    }
+
+    if config.keys.is_empty() {
+        return Err(key_error(format!(
+            "algorithm {:?} requires at least one key in jwt.keys",
+            algorithm
+        )));
+    }
+
+    let active = match &config.active_kid {
+        Some(kid) => config
+            .keys
+            .iter()
+            .find(|key| &key.kid == kid)
+            .ok_or_else(|| key_error(format!("active kid '{}' not found in jwt.keys", kid)))?,
+        None => config
+            .keys
+            .iter()
+            .find(|key| key.private_key_path.is_some())
+            .ok_or_else(|| key_error("no key with private_key_path configured".to_string()))?,
+    };
+    let private_key_path = active
+        .private_key_path
+        .as_deref()
+        .ok_or_else(|| key_error(format!("active kid '{}' has no private key", active.kid)))?;
+
+    let encoding = encoding_key_from_pem(algorithm, &read_pem(private_key_path)?)?;
+
+    let mut verifying = HashMap::with_capacity(config.keys.len());
+    let mut jwks = JwkSet {
+        keys: Vec::with_capacity(config.keys.len()),
+    };
+    for key in &config.keys {
+        if verifying.contains_key(&key.kid) {
+            return Err(key_error(format!(
+                "duplicate kid '{}' in jwt.keys",
+                key.kid
+            )));
+        }
+        let (decoding, jwk) = load_public_key(algorithm, key)?;
+        verifying.insert(key.kid.clone(), decoding);
+        jwks.keys.push(jwk);
+    }
+
+    let decoding = verifying
+        .get(&active.kid)
+        .cloned()
+        .ok_or_else(|| key_error(format!("active kid '{}' has no public key", active.kid)))?;
+
+    Ok(Keys {
+        algorithm,
+        kid: Some(active.kid.clone()),
+        encoding,
+        decoding,
+        verifying,
+        jwks,
+    })
+}
+
+fn load_public_key(
+    algorithm: Algorithm,
+    key: &JwtKeyConfig,
+) -> Result<(DecodingKey, Jwk), JwtError> {
+    let pem_bytes = read_pem(&key.public_key_path)?;
+    let decoding = match algorithm {
+        Algorithm::ES256 | Algorithm::ES384 => DecodingKey::from_ec_pem(&pem_bytes),
+        Algorithm::EdDSA => DecodingKey::from_ed_pem(&pem_bytes),
+        _ => DecodingKey::from_rsa_pem(&pem_bytes),
+    }
+    .map_err(|e| key_error(format!("invalid public key '{}': {}", key.kid, e)))?;
+
+    let parsed = pem::parse(&pem_bytes)
+        .map_err(|e| key_error(format!("invalid public key '{}': {}", key.kid, e)))?;
+    let parameters = public_key_parameters(algorithm, parsed.tag(), parsed.contents())
+        .map_err(|e| key_error(format!("invalid public key '{}': {}", key.kid, e)))?;
+
+    let jwk = Jwk {
+        common: CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(key_algorithm(algorithm)),
+            key_id: Some(key.kid.clone()),
+            ..Default::default()
+        },
+        algorithm: parameters,
+    };
+
+    Ok((decoding, jwk))
+}
+
+fn encoding_key_from_pem(algorithm: Algorithm, pem_bytes: &[u8]) -> Result<EncodingKey, JwtError> {
+    match algorithm {
+        Algorithm::ES256 | Algorithm::ES384 => EncodingKey::from_ec_pem(pem_bytes),
+        Algorithm::EdDSA => EncodingKey::from_ed_pem(pem_bytes),
+        _ => EncodingKey::from_rsa_pem(pem_bytes),
+    }
+    .map_err(|e| key_error(format!("invalid private key: {}", e)))
+}
+
+/// 从 PEM 公钥中提取 JWK 所需的参数
+///
+/// 支持 SubjectPublicKeyInfo（`PUBLIC KEY`）以及 PKCS#1（`RSA PUBLIC KEY`）格式。
+fn public_key_parameters(
+    algorithm: Algorithm,
+    tag: &str,
+    der: &[u8],
+) -> Result<AlgorithmParameters, String> {
+    let key_bytes = if tag == "RSA PUBLIC KEY" {
+        der.to_vec()
+    } else {
+        subject_public_key(der)?
+    };
+
+    match algorithm {
+        Algorithm::ES256 | Algorithm::ES384 => {
+            let (curve, size) = if algorithm == Algorithm::ES256 {
+                (EllipticCurve::P256, 32)
+            } else {
+                (EllipticCurve::P384, 48)
+            };
+            // 未压缩的椭圆曲线点：0x04 || x || y
+            if key_bytes.len() != 1 + 2 * size || key_bytes[0] != 0x04 {
+                return Err("expected an uncompressed EC point".to_string());
+            }
+            Ok(AlgorithmParameters::EllipticCurve(
+                EllipticCurveKeyParameters {
+                    key_type: EllipticCurveKeyType::EC,
+                    curve,
+                    x: URL_SAFE_NO_PAD.encode(&key_bytes[1..=size]),
+                    y: URL_SAFE_NO_PAD.encode(&key_bytes[1 + size..]),
+                },
+            ))
+        },
+        Algorithm::EdDSA => {
+            if key_bytes.len() != 32 {
+                return Err("expected a 32-byte Ed25519 public key".to_string());
+            }
+            Ok(AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                key_type: OctetKeyPairType::OctetKeyPair,
+                curve: EllipticCurve::Ed25519,
+                x: URL_SAFE_NO_PAD.encode(&key_bytes),
+            }))
+        },
+        _ => {
+            let blocks = from_der(&key_bytes).map_err(|e| e.to_string())?;
+            match blocks.first() {
+                Some(ASN1Block::Sequence(_, items)) => match items.as_slice() {
+                    [ASN1Block::Integer(_, n), ASN1Block::Integer(_, e), ..] => {
+                        Ok(AlgorithmParameters::RSA(RSAKeyParameters {
+                            key_type: RSAKeyType::RSA,
+                            n: URL_SAFE_NO_PAD.encode(n.to_bytes_be().1),
+                            e: URL_SAFE_NO_PAD.encode(e.to_bytes_be().1),
+                        }))
+                    },
+                    _ => Err("expected RSA modulus and exponent".to_string()),
+                },
+                _ => Err("expected an RSA public key sequence".to_string()),
+            }
+        },
+    }
+}
+
+/// 取出 SubjectPublicKeyInfo 中的公钥位串
+fn subject_public_key(der: &[u8]) -> Result<Vec<u8>, String> {
+    let blocks = from_der(der).map_err(|e| e.to_string())?;
+    match blocks.first() {
+        Some(ASN1Block::Sequence(_, items)) => match items.as_slice() {
+            [ASN1Block::Sequence(..), ASN1Block::BitString(_, _, bits)] => Ok(bits.clone()),
+            _ => Err("malformed SubjectPublicKeyInfo".to_string()),
+        },
+        _ => Err("malformed SubjectPublicKeyInfo".to_string()),
+    }
+}
+
+fn key_algorithm(algorithm: Algorithm) -> KeyAlgorithm {
+    match algorithm {
+        Algorithm::HS256 => KeyAlgorithm::HS256,
+        Algorithm::HS384 => KeyAlgorithm::HS384,
+        Algorithm::HS512 => KeyAlgorithm::HS512,
+        Algorithm::ES256 => KeyAlgorithm::ES256,
+        Algorithm::ES384 => KeyAlgorithm::ES384,
+        Algorithm::RS256 => KeyAlgorithm::RS256,
+        Algorithm::RS384 => KeyAlgorithm::RS384,
+        Algorithm::RS512 => KeyAlgorithm::RS512,
+        Algorithm::PS256 => KeyAlgorithm::PS256,
+        Algorithm::PS384 => KeyAlgorithm::PS384,
+        Algorithm::PS512 => KeyAlgorithm::PS512,
+        Algorithm::EdDSA => KeyAlgorithm::EdDSA,
+    }
+}
+
+fn read_pem(path: &str) -> Result<Vec<u8>, JwtError> {
+    fs::read(path).map_err(|e| key_error(format!("failed to read '{}': {}", path, e)))
+}
+
+fn key_error(message: String) -> JwtError {
+    JwtError::KeyLoadError(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::{decode, decode_header, encode, Header, Validation};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct TestClaims {
+        sub: String,
+        exp: usize,
+    }
+
+    fn fixture(name: &str) -> String {
+        format!("{}/tests/fixtures/jwt/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    fn key_config(kid: &str, prefix: &str, with_private: bool) -> JwtKeyConfig {
+        JwtKeyConfig {
+            kid: kid.to_string(),
+            private_key_path: with_private.then(|| fixture(&format!("{}-private.pem", prefix))),
+            public_key_path: fixture(&format!("{}-public.pem", prefix)),
+        }
+    }
+
+    fn jwt_config(algorithm: &str, active_kid: Option<&str>, keys: Vec<JwtKeyConfig>) -> JwtConfig {
+        JwtConfig {
+            jwt_secret: "secret".to_string(),
+            issuer: "issuer".to_string(),
+            expire: 3600,
+            refresh_expire: 7200,
+            algorithm: algorithm.to_string(),
+            active_kid: active_kid.map(str::to_string),
+            keys,
+        }
+    }
+
+    fn sign(keys: &Keys) -> String {
+        let mut header = Header::new(keys.algorithm);
+        header.kid = keys.kid.clone();
+        let claims = TestClaims {
+            sub: "user".to_string(),
+            exp: 4_102_444_800,
+        };
+        encode(&header, &claims, &keys.encoding).unwrap()
+    }
+
+    /// 使用导出的 JWK 验证令牌，模拟其他服务的验证方式
+    fn verify_with_jwks(keys: &Keys, token: &str) -> bool {
+        let kid = decode_header(token).unwrap().kid.unwrap();
+        let jwk = keys.jwks.find(&kid).unwrap();
+        let key = DecodingKey::from_jwk(jwk).unwrap();
+        decode::<TestClaims>(token, &key, &Validation::new(keys.algorithm)).is_ok()
+    }
+
+    #[test]
+    fn test_hmac_keeps_secret_private() {
+        let keys = load_keys(&jwt_config("HS256", None, vec![])).unwrap();
+        assert_eq!(keys.algorithm, Algorithm::HS256);
+        assert!(keys.kid.is_none());
+        assert!(keys.jwks.keys.is_empty());
+        assert!(keys.decoding_key(None).is_some());
+    }
+
+    #[test]
+    fn test_asymmetric_algorithms_round_trip_through_jwks() {
+        for (algorithm, prefix) in [("RS256", "rs256"), ("ES256", "es256"), ("EdDSA", "eddsa")] {
+            let keys = load_keys(&jwt_config(
+                algorithm,
+                None,
+                vec![key_config("k1", prefix, true)],
+            ))
+            .unwrap();
+            let token = sign(&keys);
+
+            let decoding = keys.decoding_key(Some("k1")).unwrap();
+            assert!(
+                decode::<TestClaims>(&token, decoding, &Validation::new(keys.algorithm)).is_ok()
+            );
+            assert!(
+                verify_with_jwks(&keys, &token),
+                "{} jwk mismatch",
+                algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn test_rotation_verifies_previous_kid() {
+        let previous = load_keys(&jwt_config(
+            "RS256",
+            None,
+            vec![key_config("2024", "rs256-previous", true)],
+        ))
+        .unwrap();
+        let old_token = sign(&previous);
+
+        let keys = load_keys(&jwt_config(
+            "RS256",
+            Some("2025"),
+            vec![
+                key_config("2025", "rs256", true),
+                key_config("2024", "rs256-previous", false),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(keys.kid.as_deref(), Some("2025"));
+        assert_eq!(keys.jwks.keys.len(), 2);
+
+        let validation = Validation::new(keys.algorithm);
+        let old_key = keys.decoding_key(Some("2024")).unwrap();
+        assert!(decode::<TestClaims>(&old_token, old_key, &validation).is_ok());
+        assert!(verify_with_jwks(&keys, &old_token));
+
+        let new_key = keys.decoding_key(Some("2025")).unwrap();
+        assert!(decode::<TestClaims>(&old_token, new_key, &validation).is_err());
+        assert!(keys.decoding_key(Some("unknown")).is_none());
+        assert!(keys.decoding_key(None).is_none());
+    }
+
+    #[test]
+    fn test_active_kid_requires_private_key() {
+        let result = load_keys(&jwt_config(
+            "RS256",
+            Some("2024"),
+            vec![
+                key_config("2025", "rs256", true),
+                key_config("2024", "rs256-previous", false),
+            ],
+        ));
+        assert!(matches!(result, Err(JwtError::KeyLoadError(_))));
+    }
+}
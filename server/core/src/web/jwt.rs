@@ -1,9 +1,14 @@
 use std::{error::Error, fmt};
 
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, Header, TokenData};
+use jsonwebtoken::{decode, decode_header, encode, jwk::JwkSet, Header, TokenData, Validation};
+use serde::{Deserialize, Serialize};
 use server_config::JwtConfig;
-use server_global::global;
+use server_constant::definition::consts::SystemEvent;
+use server_global::{
+    event_bus::Event,
+    global::{self, Keys},
+};
 use ulid::Ulid;
 
 use crate::web::auth::Claims;
@@ -29,6 +34,7 @@ pub enum JwtError {
     ValidationNotInitialized,
     TokenCreationError(String),
     TokenValidationError(String),
+    KeyLoadError(String),
 }
 
 impl fmt::Display for JwtError {
@@ -38,16 +44,38 @@ impl fmt::Display for JwtError {
             JwtError::ValidationNotInitialized => write!(f, "Validation not initialized"),
             JwtError::TokenCreationError(err) => write!(f, "Token creation error: {}", err),
             JwtError::TokenValidationError(err) => write!(f, "Token validation error: {}", err),
+            JwtError::KeyLoadError(err) => write!(f, "Key load error: {}", err),
         }
     }
 }
 
 impl Error for JwtError {}
 
+/// 签发 JWT 后发布的事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JwtCreatedEvent {
+    pub token: String,
+}
+
+impl Event for JwtCreatedEvent {
+    fn name() -> &'static str {
+        SystemEvent::JwtCreatedEvent.as_ref()
+    }
+}
+
 pub struct JwtUtils;
 
 impl JwtUtils {
     pub async fn generate_token(claims: &Claims) -> Result<String, JwtError> {
+        let jwt_config = global::get_config::<JwtConfig>().await.unwrap();
+        Self::generate_token_with_expire(claims, jwt_config.expire).await
+    }
+
+    /// 按指定有效期（秒）签发令牌，用于双因素挑战等短期令牌
+    pub async fn generate_token_with_expire(
+        claims: &Claims,
+        expire: i64,
+    ) -> Result<String, JwtError> {
         let keys_arc = global::KEYS.get().ok_or(JwtError::KeysNotInitialized)?;
 
         let keys = keys_arc.lock().await;
@@ -57,17 +85,19 @@ impl JwtUtils {
         let now = Utc::now();
         let timestamp = now.timestamp() as usize;
         let jwt_config = global::get_config::<JwtConfig>().await.unwrap();
-        claims_clone.set_exp((now + Duration::seconds(jwt_config.expire)).timestamp() as usize);
+        claims_clone.set_exp((now + Duration::seconds(expire)).timestamp() as usize);
         claims_clone.set_iss(jwt_config.issuer.to_string());
         claims_clone.set_iat(timestamp);
         claims_clone.set_nbf(timestamp);
         claims_clone.set_jti(Ulid::new().to_string());
 
-        let token = encode(&Header::default(), &claims_clone, &keys.encoding)
+        let mut header = Header::new(keys.algorithm);
+        header.kid = keys.kid.clone();
+        let token = encode(&header, &claims_clone, &keys.encoding)
             .map_err(|e| JwtError::TokenCreationError(e.to_string()));
 
         if let Ok(ref tok) = token {
-            global::send_string_event(tok.clone());
+            global::emit_event(JwtCreatedEvent { token: tok.clone() });
         }
 
         token
@@ -87,7 +117,46 @@ impl JwtUtils {
 
         let mut validation_clone = validation.clone();
         validation_clone.set_audience(&[audience.to_string()]);
-        decode::<Claims>(token, &keys.decoding, &validation_clone)
-            .map_err(|e| JwtError::TokenValidationError(e.to_string()))
+        decode_with_kid(token, &keys, &validation_clone)
+    }
+
+    /// 校验签名和签发者并解析声明，不检查过期时间和受众
+    ///
+    /// 用于撤销已签发的令牌，此时令牌可能已过期或属于其他受众。
+    pub async fn inspect_token(token: &str) -> Result<Claims, JwtError> {
+        let keys_arc = global::KEYS.get().ok_or(JwtError::KeysNotInitialized)?;
+
+        let keys = keys_arc.lock().await;
+        let validation_arc = global::VALIDATION
+            .get()
+            .ok_or(JwtError::ValidationNotInitialized)?;
+        let validation = validation_arc.lock().await;
+
+        let mut validation_clone = validation.clone();
+        validation_clone.validate_exp = false;
+        validation_clone.validate_aud = false;
+        decode_with_kid(token, &keys, &validation_clone).map(|data| data.claims)
     }
+
+    /// 获取对外公开的验证公钥集合，HMAC 算法下为空集合
+    pub async fn jwks() -> Result<JwkSet, JwtError> {
+        let keys_arc = global::KEYS.get().ok_or(JwtError::KeysNotInitialized)?;
+
+        let keys = keys_arc.lock().await;
+        Ok(keys.jwks.clone())
+    }
+}
+
+/// 按令牌头部的 kid 选择验证密钥并解码
+fn decode_with_kid(
+    token: &str,
+    keys: &Keys,
+    validation: &Validation,
+) -> Result<TokenData<Claims>, JwtError> {
+    let header = decode_header(token).map_err(|e| JwtError::TokenValidationError(e.to_string()))?;
+    let decoding = keys.decoding_key(header.kid.as_deref()).ok_or_else(|| {
+        JwtError::TokenValidationError(format!("Unknown key id: {:?}", header.kid))
+    })?;
+    decode::<Claims>(token, decoding, validation)
+        .map_err(|e| JwtError::TokenValidationError(e.to_string()))
 }
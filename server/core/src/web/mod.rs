@@ -1,12 +1,16 @@
 pub mod auth;
 pub mod error;
+pub mod jwk;
 pub mod jwt;
 pub mod page;
 pub mod res;
+pub mod revocation;
+pub mod tenant;
 pub mod util;
 pub mod validator;
 
 pub use request_id::{RequestId, RequestIdLayer};
 
 pub mod operation_log;
+pub mod operation_log_policy;
 mod request_id;
@@ -2,26 +2,30 @@ use std::{
     collections::HashMap,
     convert::Infallible,
     net::SocketAddr,
+    sync::Arc,
     task::{Context, Poll},
 };
 
 use axum::{
-    body::{to_bytes, Body, Bytes},
-    extract::{ConnectInfo, Request},
+    body::{Body, Bytes, HttpBody},
+    extract::{ConnectInfo, OriginalUri, Request},
     response::Response,
     Extension,
 };
 use bytes::BytesMut;
 use chrono::Local;
 use futures::{future::BoxFuture, StreamExt};
-use http::{Extensions, HeaderMap, Uri};
+use http::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    Extensions, HeaderMap, Uri,
+};
 use serde_json::Value;
-use server_constant::definition::consts::SystemEvent;
+use server_config::OperationLogConfig;
 use server_global::global::{self, OperationLogContext};
 use tower_layer::Layer;
 use tower_service::Service;
 
-use super::{auth::User, RequestId};
+use super::{auth::User, operation_log_policy::OperationLogPolicy, RequestId};
 
 const USER_AGENT_HEADER: &str = "user-agent";
 const UNKNOWN_REQUEST_ID: &str = "unknown";
@@ -30,11 +34,29 @@ const DEFAULT_BODY_CAPACITY: usize = 1024 * 16; // 16KB 默认缓冲区大小
 #[derive(Clone)]
 pub struct OperationLogLayer {
     pub enabled: bool,
+    policy: Arc<OperationLogPolicy>,
 }
 
 impl OperationLogLayer {
     pub fn new(enabled: bool) -> Self {
-        Self { enabled }
+        Self {
+            enabled,
+            policy: Arc::new(OperationLogPolicy::default()),
+        }
+    }
+
+    /// 使用全局 [`OperationLogConfig`] 构建记录策略，未配置时使用默认值
+    pub async fn from_config(enabled: bool) -> Self {
+        let policy = match global::get_config::<OperationLogConfig>().await {
+            Some(config) => OperationLogPolicy::new(&config),
+            None => OperationLogPolicy::default(),
+        };
+        Self::new(enabled).with_policy(policy)
+    }
+
+    pub fn with_policy(mut self, policy: OperationLogPolicy) -> Self {
+        self.policy = Arc::new(policy);
+        self
     }
 }
 
@@ -51,6 +73,7 @@ where
         OperationLogMiddleware {
             inner: service,
             enabled: self.enabled,
+            policy: self.policy.clone(),
         }
     }
 }
@@ -59,6 +82,7 @@ where
 pub struct OperationLogMiddleware<S> {
     inner: S,
     enabled: bool,
+    policy: Arc<OperationLogPolicy>,
 }
 
 impl<S> Service<Request<Body>> for OperationLogMiddleware<S>
@@ -78,12 +102,16 @@ where
     }
 
     fn call(&mut self, req: Request<Body>) -> Self::Future {
-        if !self.enabled {
-            let mut inner = self.inner.clone();
+        let mut inner = self.inner.clone();
+        let path = req
+            .extensions()
+            .get::<OriginalUri>()
+            .map_or_else(|| req.uri().path(), |uri| uri.path());
+        if !self.enabled || self.policy.is_excluded(path) || !self.policy.sample() {
             return Box::pin(async move { inner.call(req).await });
         }
 
-        let mut inner = self.inner.clone();
+        let policy = self.policy.clone();
         Box::pin(async move {
             let start_time = Local::now().naive_local();
             let (parts, body) = req.into_parts();
@@ -97,49 +125,93 @@ where
                 .map(ToString::to_string)
                 .unwrap_or_else(|| UNKNOWN_REQUEST_ID.to_string());
 
-            if let Ok(bytes) = buffer_body(body).await {
-                let method = parts.method.to_string();
-                let uri = parts.uri.to_string();
-                let ip = get_client_ip(extensions, headers);
-                let user_agent = get_user_agent(headers);
-                let params = parse_query_params(&parts.uri);
-
-                let req = Request::from_parts(parts, Body::from(bytes.clone()));
-                let response = inner.call(req).await?;
+            let method = parts.method.to_string();
+            let uri = parts.uri.to_string();
+            let url = policy.redact_uri(&parts.uri);
+            let ip = get_client_ip(extensions, headers);
+            let user_agent = get_user_agent(headers);
+            let params = parse_query_params(&parts.uri).map(|mut params| {
+                policy.redact(&mut params);
+                params
+            });
+
+            let (body, captured_body) = capture_body(headers, body, policy.max_body_size()).await;
+            let response = inner.call(Request::from_parts(parts, body)).await?;
+
+            let (response_parts, response_body) = response.into_parts();
+            let (response_body, captured_response) = capture_body(
+                &response_parts.headers,
+                response_body,
+                policy.max_body_size(),
+            )
+            .await;
+
+            let end_time = Local::now().naive_local();
+            let duration = (end_time - start_time).num_milliseconds() as i32;
+
+            let context = OperationLogContext {
+                user_id,
+                username,
+                domain,
+                module_name: extract_module_name(&uri),
+                description: generate_description(&method, &uri),
+                request_id,
+                method,
+                url,
+                ip,
+                user_agent,
+                params,
+                body: captured_body.into_value(&policy),
+                response: captured_response.into_value(&policy),
+                start_time,
+                end_time,
+                duration,
+                created_at: start_time,
+            };
 
-                let (response_parts, response_body) = response.into_parts();
-                let response_bytes = to_bytes(response_body, usize::MAX)
-                    .await
-                    .unwrap_or_default();
+            global::publish_event(context).await;
 
-                let end_time = Local::now().naive_local();
-                let duration = (end_time - start_time).num_milliseconds() as i32;
+            Ok(Response::from_parts(response_parts, response_body))
+        })
+    }
+}
 
 /// 从URI路径中提取模块名
 /// 例如: /api/admin/sys_user/list -> sys_user
 fn extract_module_name(uri: &str) -> String {
     // 移除查询参数
     let path = uri.split('?').next().unwrap_or(uri);
-    
+
     // 分割路径并查找有意义的模块名
     // 常见模式: /api/admin/{module_name}/... 或 /api/{module_name}/...
     let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
-    
+
     // 尝试找到模块名 (通常在 admin, api, web3 等之后)
-    let module_keywords = ["admin", "api", "web3", "system", "auth", "user", "role", "menu", "domain"];
-    
+    let module_keywords = [
+        "admin", "api", "web3", "system", "auth", "user", "role", "menu", "domain",
+    ];
+
     for (i, segment) in segments.iter().enumerate() {
         if module_keywords.contains(segment) && i + 1 < segments.len() {
             let next = segments[i + 1];
             // 跳过纯数字ID或特殊路径
-            if !next.chars().all(|c| c.is_ascii_digit()) && next != "list" && next != "detail" && next != "create" && next != "edit" && next != "delete" {
+            if !next.chars().all(|c| c.is_ascii_digit())
+                && next != "list"
+                && next != "detail"
+                && next != "create"
+                && next != "edit"
+                && next != "delete"
+            {
                 return next.to_string();
             }
         }
     }
-    
+
     // 如果找不到，返回最后有意义的段
-    segments.last().map(|s| s.to_string()).unwrap_or_else(|| "unknown".to_string())
+    segments
+        .last()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
 }
 
 /// 根据HTTP方法和路径生成描述
@@ -147,7 +219,7 @@ fn generate_description(method: &str, uri: &str) -> String {
     let path = uri.split('?').next().unwrap_or(uri);
     let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
     let last_segment = segments.last().unwrap_or(&"");
-    
+
     let action = match method.to_uppercase().as_str() {
         "GET" if *last_segment == "list" || last_segment.contains("List") => "查询列表",
         "GET" => "查询详情",
@@ -156,73 +228,100 @@ fn generate_description(method: &str, uri: &str) -> String {
         "DELETE" => "删除",
         _ => "操作",
     };
-    
+
     format!("{} {}", action, extract_module_name(uri))
 }
 
-                let context = OperationLogContext {
-                    user_id,
-                    username,
-                    domain,
-                    module_name: extract_module_name(&uri),
-                    description: generate_description(&method, &uri),
-                    request_id,
-                    method,
-                    url: uri,
-                    ip,
-                    user_agent,
-                    params,
-                    body: (!bytes.is_empty())
-                        .then(|| serde_json::from_slice(&bytes).ok())
-                        .flatten(),
-                    response: serde_json::from_slice(&response_bytes).ok(),
-                    start_time,
-                    end_time,
-                    duration,
-                    created_at: start_time,
-                };
-
-                global::send_dyn_event(
-                    SystemEvent::AuditOperationLoggedEvent.as_ref(),
-                    Box::new(context),
-                );
-
-                Ok(Response::from_parts(
-                    response_parts,
-                    Body::from(response_bytes),
-                ))
-            } else {
-                let mut inner = inner;
-                inner.call(Request::from_parts(parts, Body::empty())).await
-            }
-        })
+/// 消息体的捕获结果
+#[derive(Debug)]
+enum CapturedBody {
+    /// 完整缓冲的 JSON 消息体
+    Complete(Bytes),
+    /// 超过大小限制，已知大小时记录字节数
+    Truncated(Option<u64>),
+    /// 空消息体、非 JSON 或无法读取
+    Skipped,
+}
+
+impl CapturedBody {
+    /// 转换为记录的 JSON 值，完整消息体会先脱敏
+    fn into_value(self, policy: &OperationLogPolicy) -> Option<Value> {
+        match self {
+            Self::Complete(bytes) => {
+                let mut value = serde_json::from_slice(&bytes).ok()?;
+                policy.redact(&mut value);
+                Some(value)
+            },
+            Self::Truncated(size) => Some(policy.truncation_marker(size)),
+            Self::Skipped => None,
+        }
     }
 }
 
-/// 缓冲请求体，带容量限制
+/// 捕获 JSON 消息体用于记录
 ///
 /// # 参数
-/// * `body` - 请求体，类型为 axum 的 Body
+/// * `headers` - 消息头，用于判断内容类型和长度
+/// * `body` - 原始消息体
+/// * `limit` - 最大缓冲字节数
 ///
 /// # 返回值
-/// * `Result<Bytes, Box<dyn std::error::Error + Send + Sync>>` -
-///   成功返回缓冲的字节数据，失败返回错误
+/// * `(Body, CapturedBody)` - 继续传递的消息体和捕获结果
+///
+/// 非 JSON 的消息体以及已知大小超过 `limit` 的消息体不做缓冲直接传递，
+/// 读取过程中超过 `limit` 时，已读取的部分与剩余的流一起传递
+async fn capture_body(headers: &HeaderMap, body: Body, limit: usize) -> (Body, CapturedBody) {
+    if body.size_hint().exact() == Some(0) || !is_json(headers) {
+        return (body, CapturedBody::Skipped);
+    }
+
+    let size = headers
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .or_else(|| body.size_hint().exact());
+    if size.is_some_and(|size| size > limit as u64) {
+        return (body, CapturedBody::Truncated(size));
+    }
+
+    match buffer_body(body, limit).await {
+        Ok(bytes) => (Body::from(bytes.clone()), CapturedBody::Complete(bytes)),
+        Err(body) => (body, CapturedBody::Truncated(size)),
+    }
+}
+
+/// 判断内容类型是否为 JSON，包括 `application/*+json`
+#[inline]
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<mime::Mime>().ok())
+        .is_some_and(|m| m.subtype() == mime::JSON || m.suffix() == Some(mime::JSON))
+}
+
+/// 缓冲消息体，带容量限制
 ///
-/// # 错误
-/// * 当请求体大小超过 MAX_SIZE (32KB) 时返回错误
-/// * 当读取请求体流失败时返回错误
+/// # 参数
+/// * `body` - 消息体，类型为 axum 的 Body
+/// * `limit` - 最大缓冲字节数
+///
+/// # 返回值
+/// * `Result<Bytes, Body>` - 成功返回缓冲的字节数据；超过 `limit`
+///   或读取失败时返回由已读取部分和剩余流组成的消息体，读取错误会继续传递给下游
 #[inline]
-async fn buffer_body(body: Body) -> Result<Bytes, Box<dyn std::error::Error + Send + Sync>> {
-    const MAX_SIZE: usize = DEFAULT_BODY_CAPACITY * 2;
-    let mut bytes = BytesMut::with_capacity(DEFAULT_BODY_CAPACITY);
+async fn buffer_body(body: Body, limit: usize) -> Result<Bytes, Body> {
+    let mut bytes = BytesMut::with_capacity(DEFAULT_BODY_CAPACITY.min(limit));
 
     let mut stream = body.into_data_stream();
     while let Some(chunk) = stream.next().await {
-        let chunk = chunk?;
-        if bytes.len() + chunk.len() > MAX_SIZE {
-            return Err("Request body too large".into());
+        match chunk {
+            Ok(chunk) if bytes.len() + chunk.len() <= limit => bytes.extend_from_slice(&chunk),
+            chunk => {
+                let head = futures::stream::iter([Ok(bytes.freeze()), chunk]);
+                return Err(Body::from_stream(head.chain(stream)));
+            },
         }
-        bytes.extend_from_slice(&chunk);
     }
 
     Ok(bytes.freeze())
@@ -320,13 +419,16 @@ mod tests {
     use std::convert::Infallible;
 
     use axum::{
-        body::{Body, HttpBody},
+        body::{to_bytes, Body, HttpBody},
         http::{Method, Request, StatusCode},
     };
     use serde_json::json;
 
     use super::*;
-    use crate::web::auth::{Claims, User};
+    use crate::web::{
+        auth::{Claims, User},
+        operation_log_policy,
+    };
 
     /// 创建测试用户
     fn create_test_user() -> User {
@@ -442,14 +544,15 @@ mod tests {
             }
 
             let mut middleware = OperationLogMiddleware {
-                inner: service.clone(),
+                inner: service,
                 enabled: true,
+                policy: Default::default(),
             };
 
             let request = create_request(method.clone(), uri, body.clone());
             let _ = middleware.call(request).await.unwrap();
 
-            assert_context(&method.to_string(), uri, params, body).await;
+            assert_context(method.as_ref(), uri, params, body).await;
         }
     }
 
@@ -489,8 +592,9 @@ mod tests {
             println!("\n▶ 测试错误场景: {:?}", expected_status);
 
             let mut middleware = OperationLogMiddleware {
-                inner: service.clone(),
+                inner: service,
                 enabled: true,
+                policy: Default::default(),
             };
 
             let mut request = create_request(method, uri, body);
@@ -507,6 +611,72 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_capture_body() {
+        let policy = OperationLogPolicy::default();
+        let mut json = HeaderMap::new();
+        json.insert(CONTENT_TYPE, "application/json".parse().unwrap());
+        let mut text = HeaderMap::new();
+        text.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+
+        let body = Body::from(r#"{"userName":"admin","password":"123456"}"#);
+        let (_, captured) = capture_body(&json, body, 1024).await;
+        assert_eq!(
+            captured.into_value(&policy),
+            Some(json!({"userName": "admin", "password": operation_log_policy::REDACTED}))
+        );
+
+        let (_, captured) = capture_body(&text, Body::from("password=123456"), 1024).await;
+        assert_eq!(captured.into_value(&policy), None);
+
+        let (_, captured) = capture_body(&json, Body::from("x".repeat(2048)), 1024).await;
+        assert_eq!(
+            captured.into_value(&policy),
+            Some(json!({"truncated": true, "size": 2048, "limit": policy.max_body_size()}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_bodies_pass_through() {
+        const LIMIT: usize = 1024;
+
+        // 大小未知的流式请求体在读取中超出限制，下游仍需收到完整内容
+        let stream = futures::stream::iter(
+            (0..4).map(|_| Ok::<_, Infallible>(Bytes::from(vec![b'a'; LIMIT / 2]))),
+        );
+        let mut json = HeaderMap::new();
+        json.insert(CONTENT_TYPE, "application/json".parse().unwrap());
+        let (body, captured) = capture_body(&json, Body::from_stream(stream), LIMIT).await;
+        assert!(matches!(captured, CapturedBody::Truncated(None)));
+        assert_eq!(to_bytes(body, usize::MAX).await.unwrap().len(), LIMIT * 2);
+
+        let service = tower::service_fn(|req: Request<Body>| async move {
+            let len = to_bytes(req.into_body(), usize::MAX).await.unwrap().len();
+            Ok::<_, Infallible>(
+                Response::builder()
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(format!("[\"{}\"]", "x".repeat(len))))
+                    .unwrap(),
+            )
+        });
+        let config = OperationLogConfig {
+            max_body_size: LIMIT,
+            ..Default::default()
+        };
+        let mut middleware = OperationLogLayer::new(true)
+            .with_policy(OperationLogPolicy::new(&config))
+            .layer(service);
+
+        let body = json!({"data": "y".repeat(LIMIT)});
+        let expected = serde_json::to_vec(&body).unwrap().len();
+        let request = create_request(Method::POST, "/test", Some(body));
+        let response = middleware.call(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(response.len(), expected + 4);
+    }
+
     #[tokio::test]
     async fn test_disabled_middleware() {
         println!("\n=== Testing Disabled Middleware ===");
@@ -519,6 +689,7 @@ mod tests {
         let mut middleware = OperationLogMiddleware {
             inner: service,
             enabled: false,
+            policy: Default::default(),
         };
 
         let request = create_request(Method::POST, "/test", Some(json!({"test": true})));
@@ -0,0 +1,293 @@
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use http::Uri;
+use serde_json::{json, Value};
+use server_config::OperationLogConfig;
+
+/// 脱敏后替换字段值的占位符
+pub const REDACTED: &str = "******";
+
+/// 操作日志记录策略，决定哪些请求需要记录以及记录前如何处理消息体
+///
+/// - 字段名或 JSON 路径命中时，字段值替换为 [`REDACTED`]
+/// - 消息体超过 `max_body_size` 时只记录截断标记
+/// - 命中 `exclude_routes` 的请求不记录
+/// - 按 `sample_rate` 均匀采样
+#[derive(Debug)]
+pub struct OperationLogPolicy {
+    redact_keys: HashSet<String>,
+    redact_paths: Vec<Vec<String>>,
+    max_body_size: usize,
+    sample_rate: f64,
+    exclude_routes: Vec<Vec<String>>,
+    requests: AtomicU64,
+}
+
+impl OperationLogPolicy {
+    pub fn new(config: &OperationLogConfig) -> Self {
+        Self {
+            redact_keys: config
+                .redact_keys
+                .iter()
+                .map(|k| normalize_key(k))
+                .collect(),
+            redact_paths: config.redact_paths.iter().map(|p| parse_path(p)).collect(),
+            max_body_size: config.max_body_size,
+            sample_rate: config.sample_rate,
+            exclude_routes: config
+                .exclude_routes
+                .iter()
+                .map(|r| split_route(r).map(str::to_string).collect())
+                .collect(),
+            requests: AtomicU64::new(0),
+        }
+    }
+
+    /// 记录的请求体和响应体的最大字节数
+    pub fn max_body_size(&self) -> usize {
+        self.max_body_size
+    }
+
+    /// 路由是否被排除，`*` 匹配一段路径，`**` 匹配剩余的所有路径
+    pub fn is_excluded(&self, path: &str) -> bool {
+        let segments: Vec<&str> = split_route(path).collect();
+        self.exclude_routes
+            .iter()
+            .any(|pattern| match_route(pattern, &segments))
+    }
+
+    /// 本次请求是否需要记录，按采样比例均匀放行
+    pub fn sample(&self) -> bool {
+        if self.sample_rate >= 1.0 {
+            return true;
+        }
+        if self.sample_rate <= 0.0 {
+            return false;
+        }
+        let n = self.requests.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * self.sample_rate).floor() > (n * self.sample_rate).floor()
+    }
+
+    /// 对 JSON 值中命中字段名或路径的字段脱敏
+    pub fn redact(&self, value: &mut Value) {
+        if self.redact_keys.is_empty() && self.redact_paths.is_empty() {
+            return;
+        }
+        self.redact_value(value, &mut Vec::new());
+    }
+
+    /// 对 URI 查询参数中需要脱敏的值进行替换，未命中时原样返回
+    pub fn redact_uri(&self, uri: &Uri) -> String {
+        let Some(query) = uri.query() else {
+            return uri.to_string();
+        };
+
+        let mut redacted = false;
+        let pairs: Vec<(String, String)> = form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .map(|(k, v)| {
+                if self.is_redacted(std::slice::from_ref(&k)) {
+                    redacted = true;
+                    (k, REDACTED.to_string())
+                } else {
+                    (k, v)
+                }
+            })
+            .collect();
+        if !redacted {
+            return uri.to_string();
+        }
+
+        let query = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(pairs)
+            .finish();
+        format!("{}?{}", uri.path(), query)
+    }
+
+    /// 消息体超过大小限制时记录的截断标记，`size` 未知时为 null
+    pub fn truncation_marker(&self, size: Option<u64>) -> Value {
+        json!({
+            "truncated": true,
+            "size": size,
+            "limit": self.max_body_size,
+        })
+    }
+
+    fn redact_value(&self, value: &mut Value, path: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                for (key, field) in map.iter_mut() {
+                    path.push(key.clone());
+                    if self.is_redacted(path) {
+                        *field = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_value(field, path);
+                    }
+                    path.pop();
+                }
+            },
+            Value::Array(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    path.push(index.to_string());
+                    if self.is_redacted(path) {
+                        *item = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_value(item, path);
+                    }
+                    path.pop();
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn is_redacted(&self, path: &[String]) -> bool {
+        let key_matched = path
+            .last()
+            .is_some_and(|key| self.redact_keys.contains(&normalize_key(key)));
+
+        key_matched
+            || self.redact_paths.iter().any(|pattern| {
+                pattern.len() == path.len()
+                    && pattern
+                        .iter()
+                        .zip(path)
+                        .all(|(expected, actual)| expected == "*" || expected == actual)
+            })
+    }
+}
+
+impl Default for OperationLogPolicy {
+    fn default() -> Self {
+        Self::new(&OperationLogConfig::default())
+    }
+}
+
+/// 字段名忽略大小写以及 `_`、`-`，`private_key` 与 `privateKey` 等价
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 解析 `$.data.items.*.token` 形式的路径
+fn parse_path(path: &str) -> Vec<String> {
+    let path = path.trim();
+    let path = path
+        .strip_prefix("$.")
+        .or_else(|| path.strip_prefix('$'))
+        .unwrap_or(path);
+    path.split('.')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn split_route(route: &str) -> impl Iterator<Item = &str> {
+    route.split('/').filter(|s| !s.is_empty())
+}
+
+fn match_route(pattern: &[String], segments: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => segments.is_empty(),
+        Some((first, _)) if first == "**" => true,
+        Some((first, rest)) => segments.split_first().is_some_and(|(segment, tail)| {
+            (first == "*" || first == segment) && match_route(rest, tail)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(config: OperationLogConfig) -> OperationLogPolicy {
+        OperationLogPolicy::new(&config)
+    }
+
+    #[test]
+    fn test_redact_by_key_and_path() {
+        let policy = policy(OperationLogConfig {
+            redact_paths: vec!["$.data.*.code".to_string(), "wallet.0".to_string()],
+            ..Default::default()
+        });
+
+        let mut value = json!({
+            "userName": "admin",
+            "password": "123456",
+            "private_key": "0xabc",
+            "nested": { "clientSecret": "s", "name": "n" },
+            "data": [{ "code": "c1", "id": 1 }, { "code": "c2" }],
+            "wallet": ["w0", "w1"],
+            "code": "menu"
+        });
+        policy.redact(&mut value);
+
+        assert_eq!(
+            value,
+            json!({
+                "userName": "admin",
+                "password": REDACTED,
+                "private_key": REDACTED,
+                "nested": { "clientSecret": REDACTED, "name": "n" },
+                "data": [{ "code": REDACTED, "id": 1 }, { "code": REDACTED }],
+                "wallet": [REDACTED, "w1"],
+                "code": "menu"
+            })
+        );
+    }
+
+    #[test]
+    fn test_redact_uri() {
+        let policy = OperationLogPolicy::default();
+
+        let uri: Uri = "/auth/callback?token=abc&state=x%20y".parse().unwrap();
+        assert_eq!(
+            policy.redact_uri(&uri),
+            "/auth/callback?token=******&state=x+y"
+        );
+
+        let uri: Uri = "/test?key=hello%20world".parse().unwrap();
+        assert_eq!(policy.redact_uri(&uri), "/test?key=hello%20world");
+    }
+
+    #[test]
+    fn test_exclude_routes() {
+        let policy = policy(OperationLogConfig {
+            exclude_routes: vec![
+                "/auth/login".to_string(),
+                "/web3/key/**".to_string(),
+                "/user/*/password".to_string(),
+            ],
+            ..Default::default()
+        });
+
+        assert!(policy.is_excluded("/auth/login"));
+        assert!(policy.is_excluded("/auth/login/"));
+        assert!(policy.is_excluded("/web3/key"));
+        assert!(policy.is_excluded("/web3/key/import/1"));
+        assert!(policy.is_excluded("/user/1/password"));
+        assert!(!policy.is_excluded("/auth/login/extra"));
+        assert!(!policy.is_excluded("/user/1/2/password"));
+        assert!(!policy.is_excluded("/route/getConstantRoutes"));
+    }
+
+    #[test]
+    fn test_sample_rate() {
+        let sampled = |rate: f64| {
+            let policy = policy(OperationLogConfig {
+                sample_rate: rate,
+                ..Default::default()
+            });
+            (0..100).filter(|_| policy.sample()).count()
+        };
+
+        assert_eq!(sampled(1.0), 100);
+        assert_eq!(sampled(0.25), 25);
+        assert_eq!(sampled(0.0), 0);
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use moka::{sync::Cache, Expiry};
+use once_cell::sync::Lazy;
+use server_global::global::{RedisConnection, GLOBAL_PRIMARY_REDIS};
+
+/// 已撤销令牌 ID 在 Redis 中的键前缀
+const REVOKED_KEY_PREFIX: &str = "jwt_revoked:";
+
+/// 撤销记录在令牌本身过期的时刻一并过期
+struct RevokedUntil;
+
+impl Expiry<String, Instant> for RevokedUntil {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        value: &Instant,
+        created_at: Instant,
+    ) -> Option<Duration> {
+        Some(value.saturating_duration_since(created_at))
+    }
+}
+
+/// 主 Redis 不可用时使用的进程内撤销列表
+static MEMORY_REVOKED: Lazy<Cache<String, Instant>> =
+    Lazy::new(|| Cache::builder().expire_after(RevokedUntil).build());
+
+/// 以 `jti` 为键的 JWT 撤销列表
+///
+/// 记录保存在主 Redis 中，所有实例共享；未配置 Redis 或访问失败时退回进程内缓存。
+/// 记录只需比被撤销的令牌活得更久，因此在令牌的 `exp` 时过期。
+pub struct TokenRevocation;
+
+impl TokenRevocation {
+    /// 撤销 `jti` 对应的令牌，直到 `exp`（UNIX 时间戳，秒）
+    pub async fn revoke(jti: &str, exp: usize) {
+        let ttl = Self::remaining_ttl(exp);
+        if ttl == 0 {
+            return;
+        }
+
+        if !Self::redis_revoke(jti, ttl).await {
+            MEMORY_REVOKED.insert(jti.to_string(), Instant::now() + Duration::from_secs(ttl));
+        }
+    }
+
+    /// `jti` 对应的令牌是否已被撤销
+    pub async fn is_revoked(jti: &str) -> bool {
+        if MEMORY_REVOKED.contains_key(jti) {
+            return true;
+        }
+        Self::redis_is_revoked(jti).await.unwrap_or(false)
+    }
+
+    fn remaining_ttl(exp: usize) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        (exp as u64).saturating_sub(now)
+    }
+
+    fn key(jti: &str) -> String {
+        format!("{}{}", REVOKED_KEY_PREFIX, jti)
+    }
+
+    async fn redis_revoke(jti: &str, ttl: u64) -> bool {
+        let Some(connection) = GLOBAL_PRIMARY_REDIS.read().await.clone() else {
+            return false;
+        };
+
+        let cmd = redis::cmd("SET")
+            .arg(Self::key(jti))
+            .arg("1")
+            .arg("EX")
+            .arg(ttl)
+            .to_owned();
+
+        match connection {
+            RedisConnection::Single(client) => {
+                match client.get_multiplexed_async_connection().await {
+                    Ok(mut conn) => cmd.query_async::<()>(&mut conn).await.is_ok(),
+                    Err(_) => false,
+                }
+            },
+            RedisConnection::Cluster(client) => match client.get_async_connection().await {
+                Ok(mut conn) => cmd.query_async::<()>(&mut conn).await.is_ok(),
+                Err(_) => false,
+            },
+        }
+    }
+
+    async fn redis_is_revoked(jti: &str) -> Option<bool> {
+        let connection = GLOBAL_PRIMARY_REDIS.read().await.clone()?;
+        let cmd = redis::cmd("EXISTS").arg(Self::key(jti)).to_owned();
+
+        match connection {
+            RedisConnection::Single(client) => {
+                let mut conn = client.get_multiplexed_async_connection().await.ok()?;
+                cmd.query_async::<bool>(&mut conn).await.ok()
+            },
+            RedisConnection::Cluster(client) => {
+                let mut conn = client.get_async_connection().await.ok()?;
+                cmd.query_async::<bool>(&mut conn).await.ok()
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exp_in(secs: u64) -> usize {
+        (SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + secs) as usize
+    }
+
+    #[tokio::test]
+    async fn test_memory_fallback_revocation() {
+        assert!(!TokenRevocation::is_revoked("jti-memory").await);
+        TokenRevocation::revoke("jti-memory", exp_in(60)).await;
+        assert!(TokenRevocation::is_revoked("jti-memory").await);
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_is_not_recorded() {
+        TokenRevocation::revoke("jti-expired", exp_in(0)).await;
+        assert!(!TokenRevocation::is_revoked("jti-expired").await);
+    }
+}
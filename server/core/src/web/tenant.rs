@@ -0,0 +1,136 @@
+//! 请求级租户上下文，服务层按其中的域隔离各租户的数据
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use server_constant::definition::consts::SystemEvent;
+use server_global::event_bus::Event;
+
+use crate::web::auth::User;
+
+/// 内置超级管理员切换到其他域时携带的请求头
+pub const TENANT_DOMAIN_HEADER: &str = "x-tenant-domain";
+
+/// 内置域，其中的超级管理员可以切换到任意域
+pub const BUILT_IN_DOMAIN: &str = "built-in";
+
+/// 允许切换域的角色编码
+pub const SUPER_ROLE: &str = "ROLE_SUPER";
+
+/// 当前请求生效的域
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenantContext {
+    domain: String,
+    home_domain: String,
+}
+
+impl TenantContext {
+    pub fn new(domain: impl Into<String>) -> Self {
+        let domain = domain.into();
+        Self {
+            home_domain: domain.clone(),
+            domain,
+        }
+    }
+
+    /// 确定请求的生效域
+    ///
+    /// 未请求切换或请求的就是所属域时使用用户所属域；
+    /// 只有内置域的超级管理员可以切换到其他域，其他用户请求切换时返回 None。
+    pub fn resolve(user: &User, requested: Option<&str>) -> Option<Self> {
+        let home_domain = user.domain();
+        let target = requested
+            .map(str::trim)
+            .filter(|domain| !domain.is_empty() && *domain != home_domain);
+
+        match target {
+            None => Some(Self::new(home_domain)),
+            Some(domain) if can_switch_domain(user) => Some(Self {
+                domain: domain.to_string(),
+                home_domain,
+            }),
+            Some(_) => None,
+        }
+    }
+
+    /// 查询和写入使用的域
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// 用户所属的域
+    pub fn home_domain(&self) -> &str {
+        &self.home_domain
+    }
+
+    pub fn is_switched(&self) -> bool {
+        self.domain != self.home_domain
+    }
+}
+
+fn can_switch_domain(user: &User) -> bool {
+    user.domain() == BUILT_IN_DOMAIN && user.subject().iter().any(|role| role == SUPER_ROLE)
+}
+
+/// 超级管理员以其他域身份发起请求时发布，用于审计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantSwitchEvent {
+    pub user_id: String,
+    pub username: String,
+    pub home_domain: String,
+    pub target_domain: String,
+    pub method: String,
+    pub url: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl Event for TenantSwitchEvent {
+    fn name() -> &'static str {
+        SystemEvent::TenantSwitchedEvent.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::auth::Claims;
+
+    fn user(domain: &str, roles: &[&str]) -> User {
+        User::from(Claims::new(
+            "u1".to_string(),
+            "management_platform".to_string(),
+            "alice".to_string(),
+            roles.iter().map(|role| role.to_string()).collect(),
+            domain.to_string(),
+            None,
+        ))
+    }
+
+    #[test]
+    fn test_resolve_uses_home_domain_by_default() {
+        let user = user("tenant-a", &["ROLE_ADMIN"]);
+
+        for requested in [None, Some(""), Some("tenant-a")] {
+            let tenant = TenantContext::resolve(&user, requested).unwrap();
+            assert_eq!(tenant.domain(), "tenant-a");
+            assert!(!tenant.is_switched());
+        }
+    }
+
+    #[test]
+    fn test_only_built_in_super_admin_can_switch() {
+        let tenant =
+            TenantContext::resolve(&user(BUILT_IN_DOMAIN, &[SUPER_ROLE]), Some("tenant-a"))
+                .unwrap();
+        assert_eq!(tenant.domain(), "tenant-a");
+        assert_eq!(tenant.home_domain(), BUILT_IN_DOMAIN);
+        assert!(tenant.is_switched());
+
+        assert!(
+            TenantContext::resolve(&user(BUILT_IN_DOMAIN, &["ROLE_ADMIN"]), Some("tenant-a"))
+                .is_none()
+        );
+        assert!(
+            TenantContext::resolve(&user("tenant-b", &[SUPER_ROLE]), Some("tenant-a")).is_none()
+        );
+    }
+}
@@ -1,4 +1,9 @@
+use std::{net::IpAddr, str::FromStr};
+
 use axum::http::HeaderMap;
+use ipnet::IpNet;
+use server_config::AccessKeyConfig;
+use server_global::global;
 
 /// 客户端 IP 地址处理工具
 ///
@@ -123,6 +128,48 @@ impl ClientIp {
             .map(|ip_str| ip_str.split(',').map(|ip| ip.trim().to_string()).collect())
             .unwrap_or_default()
     }
+
+    /// 按可信代理解析客户端地址
+    ///
+    /// 转发头可以由调用方任意伪造，只有连接的对端 `peer` 是可信代理时才读取
+    /// X-Forwarded-For（或 X-Real-IP），否则直接返回 `peer`。
+    pub fn resolve(peer: IpAddr, headers: &HeaderMap, trusted_proxies: &[IpNet]) -> IpAddr {
+        let is_trusted = |ip: &IpAddr| trusted_proxies.iter().any(|net| net.contains(ip));
+        if !is_trusted(&peer) {
+            return peer;
+        }
+
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        // 代理在 X-Forwarded-For 末尾追加地址，从右往左跳过可信代理，左侧的地址可能是调用方伪造的
+        let forwarded = match header("X-Forwarded-For") {
+            Some(value) => value
+                .rsplit(',')
+                .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+                .find(|ip| !is_trusted(ip)),
+            None => header("X-Real-IP").and_then(|ip| ip.trim().parse().ok()),
+        };
+        forwarded.unwrap_or(peer)
+    }
+
+    /// 解析可信代理的地址或网段，忽略无法识别的条目
+    pub fn parse_trusted_proxies(values: &[String]) -> Vec<IpNet> {
+        values
+            .iter()
+            .filter_map(|value| {
+                IpNet::from_str(value)
+                    .or_else(|_| IpAddr::from_str(value).map(IpNet::from))
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// 读取 `access_key.trusted_proxies` 中配置的可信代理
+    pub async fn trusted_proxies() -> Vec<IpNet> {
+        global::get_config::<AccessKeyConfig>()
+            .await
+            .map(|config| Self::parse_trusted_proxies(&config.trusted_proxies))
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -154,4 +201,27 @@ mod tests {
         assert_eq!(chain.len(), 3);
         assert_eq!(chain[0], "192.168.1.1");
     }
+
+    #[test]
+    fn test_forwarded_ip_only_trusted_from_proxies() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-Forwarded-For",
+            "192.0.2.1, 203.0.113.7, 10.0.0.2".parse().unwrap(),
+        );
+        let proxies =
+            ClientIp::parse_trusted_proxies(&["10.0.0.0/8".to_string(), "bad".to_string()]);
+        let proxy: IpAddr = "10.1.2.3".parse().unwrap();
+        let caller: IpAddr = "198.51.100.1".parse().unwrap();
+
+        assert_eq!(proxies.len(), 1);
+        assert_eq!(
+            ClientIp::resolve(proxy, &headers, &proxies),
+            "203.0.113.7".parse::<IpAddr>().unwrap()
+        );
+        // 非可信代理伪造的转发头被忽略
+        assert_eq!(ClientIp::resolve(caller, &headers, &proxies), caller);
+        assert_eq!(ClientIp::resolve(proxy, &headers, &[]), proxy);
+        assert_eq!(ClientIp::resolve(proxy, &HeaderMap::new(), &proxies), proxy);
+    }
 }
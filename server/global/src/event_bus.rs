@@ -0,0 +1,727 @@
+//! 类型化事件总线
+//!
+//! 每个订阅者拥有独立的有界通道，通道写满时发布方等待，从而对生产方施加背压。
+//! 订阅者由监督任务运行，处理函数 panic 或返回错误时按指数退避重试，重试耗尽后
+//! 写入死信存储。为事件配置 Redis Streams 传输后，事件先写入 Stream，再由消费组
+//! 投递给各实例的订阅者，进程重启后未确认的事件会重新投递。
+
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex as StdMutex, RwLock as StdRwLock},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{Local, NaiveDateTime};
+use redis::{
+    aio::MultiplexedConnection, cluster_async::ClusterConnection, streams::StreamReadReply, Cmd,
+    FromRedisValue, RedisResult,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::{mpsc, Mutex};
+
+use crate::{global::RedisConnection, project_error, project_info};
+
+/// 可在事件总线上传递的事件
+pub trait Event: Serialize + DeserializeOwned + Clone + Send + Sync + 'static {
+    /// 事件名，用于日志、死信和 Redis Stream 的键
+    fn name() -> &'static str;
+}
+
+#[derive(Debug, Error)]
+pub enum EventBusError {
+    #[error("Event channel for '{0}' is full")]
+    ChannelFull(&'static str),
+    #[error("Event channel for '{0}' is closed")]
+    ChannelClosed(&'static str),
+    #[error("Failed to serialize event '{0}': {1}")]
+    Serialize(&'static str, serde_json::Error),
+    #[error("Redis stream error: {0}")]
+    Redis(#[from] redis::RedisError),
+}
+
+/// 事件总线参数
+#[derive(Debug, Clone)]
+pub struct EventBusOptions {
+    /// 每个订阅者通道的容量
+    pub capacity: usize,
+    /// 处理函数的最大尝试次数，包含首次处理
+    pub max_attempts: u32,
+    /// 首次重试前的等待时间，之后每次翻倍
+    pub retry_backoff: Duration,
+}
+
+impl Default for EventBusOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            max_attempts: 3,
+            retry_backoff: Duration::from_millis(200),
+        }
+    }
+}
+
+/// 处理失败或无法投递的事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetter {
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub error: String,
+    pub attempts: u32,
+    pub failed_at: NaiveDateTime,
+}
+
+impl DeadLetter {
+    fn new<E: Event>(event: &E, error: String, attempts: u32) -> Self {
+        Self {
+            event: E::name().to_string(),
+            payload: serde_json::to_value(event).unwrap_or_default(),
+            error,
+            attempts,
+            failed_at: Local::now().naive_local(),
+        }
+    }
+}
+
+/// 死信存储
+#[async_trait]
+pub trait DeadLetterStore: Send + Sync {
+    async fn store(&self, letter: DeadLetter);
+}
+
+/// 保存在内存中的死信，超出容量时丢弃最早的记录
+pub struct MemoryDeadLetterStore {
+    capacity: usize,
+    letters: StdMutex<VecDeque<DeadLetter>>,
+}
+
+impl MemoryDeadLetterStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            letters: StdMutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn letters(&self) -> Vec<DeadLetter> {
+        self.letters.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+impl Default for MemoryDeadLetterStore {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+#[async_trait]
+impl DeadLetterStore for MemoryDeadLetterStore {
+    async fn store(&self, letter: DeadLetter) {
+        project_error!(
+            "Event '{}' dead-lettered after {} attempts: {}",
+            letter.event,
+            letter.attempts,
+            letter.error
+        );
+        let mut letters = self.letters.lock().unwrap();
+        if letters.len() >= self.capacity {
+            letters.pop_front();
+        }
+        letters.push_back(letter);
+    }
+}
+
+/// 将死信追加到 Redis 列表 `{key}`
+pub struct RedisDeadLetterStore {
+    connection: RedisConnection,
+    key: String,
+}
+
+impl RedisDeadLetterStore {
+    pub fn new(connection: RedisConnection, key: impl Into<String>) -> Self {
+        Self {
+            connection,
+            key: key.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl DeadLetterStore for RedisDeadLetterStore {
+    async fn store(&self, letter: DeadLetter) {
+        project_error!(
+            "Event '{}' dead-lettered after {} attempts: {}",
+            letter.event,
+            letter.attempts,
+            letter.error
+        );
+        let result = async {
+            let payload = serde_json::to_string(&letter).map_err(|e| e.to_string())?;
+            let mut conn = StreamConnection::open(&self.connection)
+                .await
+                .map_err(|e| e.to_string())?;
+            conn.query::<i64>(redis::cmd("RPUSH").arg(&self.key).arg(payload))
+                .await
+                .map_err(|e| e.to_string())
+        }
+        .await;
+        if let Err(e) = result {
+            project_error!("Failed to store dead letter in Redis: {}", e);
+        }
+    }
+}
+
+/// Redis Streams 传输参数
+#[derive(Debug, Clone)]
+pub struct RedisStreamOptions {
+    /// Stream 键前缀，完整的键为 `{prefix}{event name}`
+    pub key_prefix: String,
+    /// 消费组，同一消费组内每个事件只被一个实例处理
+    pub group: String,
+    /// 消费者名，应在实例重启后保持不变，以便重新投递未确认的事件
+    pub consumer: String,
+    /// Stream 的近似最大长度
+    pub max_len: usize,
+}
+
+type DynFuture = dyn Future<Output = Result<(), String>> + Send + 'static;
+type Handler<E> = Arc<dyn Fn(E) -> Pin<Box<DynFuture>> + Send + Sync>;
+
+/// 投递给订阅者的事件，来自 Redis Stream 时在所有订阅者处理完成后确认
+struct Envelope<E> {
+    event: E,
+    _ack: Option<Arc<StreamAck>>,
+}
+
+struct Topic<E> {
+    subscribers: StdRwLock<Vec<mpsc::Sender<Envelope<E>>>>,
+    stream: StdRwLock<Option<StreamProducer>>,
+}
+
+impl<E> Default for Topic<E> {
+    fn default() -> Self {
+        Self {
+            subscribers: StdRwLock::new(Vec::new()),
+            stream: StdRwLock::new(None),
+        }
+    }
+}
+
+struct Inner {
+    options: EventBusOptions,
+    topics: StdRwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+    dead_letters: StdRwLock<Arc<dyn DeadLetterStore>>,
+}
+
+/// 类型化事件总线
+#[derive(Clone)]
+pub struct EventBus {
+    inner: Arc<Inner>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(EventBusOptions::default())
+    }
+}
+
+impl EventBus {
+    pub fn new(options: EventBusOptions) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                options,
+                topics: StdRwLock::new(HashMap::new()),
+                dead_letters: StdRwLock::new(Arc::new(MemoryDeadLetterStore::default())),
+            }),
+        }
+    }
+
+    pub fn set_dead_letter_store(&self, store: Arc<dyn DeadLetterStore>) {
+        *self.inner.dead_letters.write().unwrap() = store;
+    }
+
+    fn topic<E: Event>(&self) -> Arc<Topic<E>> {
+        if let Some(topic) = self.inner.topics.read().unwrap().get(&TypeId::of::<E>()) {
+            return topic.clone().downcast::<Topic<E>>().unwrap();
+        }
+        self.inner
+            .topics
+            .write()
+            .unwrap()
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Arc::new(Topic::<E>::default()))
+            .clone()
+            .downcast::<Topic<E>>()
+            .unwrap()
+    }
+
+    /// 订阅事件，处理函数在受监督的任务中按发布顺序执行
+    pub fn subscribe<E, F, Fut, Err>(&self, handler: F)
+    where
+        E: Event,
+        F: Fn(E) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), Err>> + Send + 'static,
+        Err: Display + Send + 'static,
+    {
+        let handler: Handler<E> = Arc::new(move |event| {
+            let fut = handler(event);
+            Box::pin(async move { fut.await.map_err(|e| e.to_string()) })
+        });
+        let (tx, rx) = mpsc::channel(self.inner.options.capacity);
+        self.topic::<E>().subscribers.write().unwrap().push(tx);
+        tokio::spawn(supervise(
+            self.inner.clone(),
+            Arc::new(Mutex::new(rx)),
+            handler,
+        ));
+        project_info!("Event listener for '{}' spawned", E::name());
+    }
+
+    /// 发布事件，订阅者通道已满时等待
+    pub async fn publish<E: Event>(&self, event: E) -> Result<(), EventBusError> {
+        let topic = self.topic::<E>();
+        let producer = topic.stream.read().unwrap().clone();
+        if let Some(producer) = producer {
+            return producer.append(&event).await;
+        }
+        dispatch(&topic, event, None).await
+    }
+
+    /// 不等待地发布事件，订阅者通道已满时返回错误
+    pub fn try_publish<E: Event>(&self, event: E) -> Result<(), EventBusError> {
+        let topic = self.topic::<E>();
+        if topic.stream.read().unwrap().is_some() {
+            let bus = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = bus.publish(event).await {
+                    project_error!("Failed to publish event '{}': {}", E::name(), e);
+                }
+            });
+            return Ok(());
+        }
+        for tx in topic.subscribers.read().unwrap().iter() {
+            tx.try_send(Envelope {
+                event: event.clone(),
+                _ack: None,
+            })
+            .map_err(|e| match e {
+                mpsc::error::TrySendError::Full(_) => EventBusError::ChannelFull(E::name()),
+                mpsc::error::TrySendError::Closed(_) => EventBusError::ChannelClosed(E::name()),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// 在同步上下文中发布事件，无法投递时写入死信而不是丢弃
+    pub fn emit<E: Event>(&self, event: E) {
+        if let Err(e) = self.try_publish(event.clone()) {
+            let bus = self.clone();
+            tokio::spawn(async move { bus.dead_letter(&event, e.to_string()).await });
+        }
+    }
+
+    /// 将未能投递的事件写入死信存储
+    pub async fn dead_letter<E: Event>(&self, event: &E, error: String) {
+        self.dead_letter_store()
+            .store(DeadLetter::new(event, error, 0))
+            .await;
+    }
+
+    fn dead_letter_store(&self) -> Arc<dyn DeadLetterStore> {
+        self.inner.dead_letters.read().unwrap().clone()
+    }
+
+    /// 通过 Redis Streams 传递事件，并在本实例启动消费者
+    ///
+    /// 此后发布的事件写入 Stream，由消费组中的某个实例投递给其订阅者。
+    pub async fn bridge_redis_stream<E: Event>(
+        &self,
+        connection: RedisConnection,
+        options: RedisStreamOptions,
+    ) -> Result<(), EventBusError> {
+        let key = format!("{}{}", options.key_prefix, E::name());
+        let mut conn = StreamConnection::open(&connection).await?;
+        let created = conn
+            .query::<()>(
+                redis::cmd("XGROUP")
+                    .arg("CREATE")
+                    .arg(&key)
+                    .arg(&options.group)
+                    .arg("0")
+                    .arg("MKSTREAM"),
+            )
+            .await;
+        match created {
+            Err(e) if e.code() != Some("BUSYGROUP") => return Err(e.into()),
+            _ => {},
+        }
+
+        let producer = StreamProducer {
+            conn: conn.clone(),
+            key: key.clone(),
+            max_len: options.max_len,
+        };
+        *self.topic::<E>().stream.write().unwrap() = Some(producer);
+
+        // 阻塞读取需要独占连接
+        let reader = StreamConnection::open(&connection).await?;
+        tokio::spawn(consume_stream::<E>(
+            self.clone(),
+            reader,
+            conn,
+            key.clone(),
+            options,
+        ));
+        project_info!("Event '{}' bridged to Redis stream '{}'", E::name(), key);
+        Ok(())
+    }
+}
+
+async fn dispatch<E: Event>(
+    topic: &Topic<E>,
+    event: E,
+    ack: Option<Arc<StreamAck>>,
+) -> Result<(), EventBusError> {
+    let subscribers = topic.subscribers.read().unwrap().clone();
+    for tx in subscribers {
+        tx.send(Envelope {
+            event: event.clone(),
+            _ack: ack.clone(),
+        })
+        .await
+        .map_err(|_| EventBusError::ChannelClosed(E::name()))?;
+    }
+    Ok(())
+}
+
+/// 运行订阅者，任务异常退出时重新启动
+async fn supervise<E: Event>(
+    inner: Arc<Inner>,
+    rx: Arc<Mutex<mpsc::Receiver<Envelope<E>>>>,
+    handler: Handler<E>,
+) {
+    loop {
+        let worker = tokio::spawn(listen(inner.clone(), rx.clone(), handler.clone()));
+        match worker.await {
+            Ok(()) => return,
+            Err(e) => {
+                project_error!(
+                    "Event listener for '{}' crashed, restarting: {}",
+                    E::name(),
+                    e
+                );
+                tokio::time::sleep(inner.options.retry_backoff).await;
+            },
+        }
+    }
+}
+
+async fn listen<E: Event>(
+    inner: Arc<Inner>,
+    rx: Arc<Mutex<mpsc::Receiver<Envelope<E>>>>,
+    handler: Handler<E>,
+) {
+    let mut rx = rx.lock().await;
+    while let Some(envelope) = rx.recv().await {
+        deliver(&inner, &handler, envelope).await;
+    }
+}
+
+/// 处理单个事件，panic 与错误同样计入重试次数
+async fn deliver<E: Event>(inner: &Inner, handler: &Handler<E>, envelope: Envelope<E>) {
+    let max_attempts = inner.options.max_attempts.max(1);
+    let mut backoff = inner.options.retry_backoff;
+    let mut error = String::new();
+
+    for attempt in 1..=max_attempts {
+        match tokio::spawn(handler(envelope.event.clone())).await {
+            Ok(Ok(())) => return,
+            Ok(Err(e)) => error = e,
+            Err(e) => error = format!("handler panicked: {}", e),
+        }
+        if attempt < max_attempts {
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
+    let store = inner.dead_letters.read().unwrap().clone();
+    store
+        .store(DeadLetter::new(&envelope.event, error, max_attempts))
+        .await;
+}
+
+/// 单机或集群 Redis 连接
+#[derive(Clone)]
+enum StreamConnection {
+    Single(MultiplexedConnection),
+    Cluster(ClusterConnection),
+}
+
+impl StreamConnection {
+    async fn open(connection: &RedisConnection) -> RedisResult<Self> {
+        match connection {
+            RedisConnection::Single(client) => client
+                .get_multiplexed_async_connection()
+                .await
+                .map(StreamConnection::Single),
+            RedisConnection::Cluster(client) => client
+                .get_async_connection()
+                .await
+                .map(StreamConnection::Cluster),
+        }
+    }
+
+    async fn query<T: FromRedisValue>(&mut self, cmd: &Cmd) -> RedisResult<T> {
+        match self {
+            StreamConnection::Single(conn) => cmd.query_async(conn).await,
+            StreamConnection::Cluster(conn) => cmd.query_async(conn).await,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct StreamProducer {
+    conn: StreamConnection,
+    key: String,
+    max_len: usize,
+}
+
+impl StreamProducer {
+    async fn append<E: Event>(&self, event: &E) -> Result<(), EventBusError> {
+        let payload =
+            serde_json::to_string(event).map_err(|e| EventBusError::Serialize(E::name(), e))?;
+        self.conn
+            .clone()
+            .query::<String>(
+                redis::cmd("XADD")
+                    .arg(&self.key)
+                    .arg("MAXLEN")
+                    .arg("~")
+                    .arg(self.max_len)
+                    .arg("*")
+                    .arg("payload")
+                    .arg(payload),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// 所有订阅者处理完成（或写入死信）后确认 Stream 中的事件
+struct StreamAck {
+    conn: StreamConnection,
+    key: String,
+    group: String,
+    id: String,
+}
+
+impl Drop for StreamAck {
+    fn drop(&mut self) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let mut conn = self.conn.clone();
+        let mut cmd = redis::cmd("XACK");
+        cmd.arg(&self.key).arg(&self.group).arg(&self.id);
+        handle.spawn(async move {
+            if let Err(e) = conn.query::<i64>(&cmd).await {
+                project_error!("Failed to acknowledge stream event: {}", e);
+            }
+        });
+    }
+}
+
+/// 从消费组读取事件并投递给本实例的订阅者
+///
+/// 启动时先读取本消费者未确认的事件，再读取新事件。
+async fn consume_stream<E: Event>(
+    bus: EventBus,
+    mut reader: StreamConnection,
+    acker: StreamConnection,
+    key: String,
+    options: RedisStreamOptions,
+) {
+    let topic = bus.topic::<E>();
+    // 读取未确认事件时的起始 ID，读完后改为只读取新事件
+    let mut pending_from = Some("0".to_string());
+
+    loop {
+        let read = reader
+            .query::<Option<StreamReadReply>>(
+                redis::cmd("XREADGROUP")
+                    .arg("GROUP")
+                    .arg(&options.group)
+                    .arg(&options.consumer)
+                    .arg("COUNT")
+                    .arg(100)
+                    .arg("BLOCK")
+                    .arg(5000)
+                    .arg("STREAMS")
+                    .arg(&key)
+                    .arg(pending_from.as_deref().unwrap_or(">")),
+            )
+            .await;
+
+        let entries = match read {
+            Ok(reply) => reply
+                .into_iter()
+                .flat_map(|reply| reply.keys)
+                .flat_map(|stream| stream.ids)
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                project_error!("Failed to read Redis stream '{}': {}", key, e);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            },
+        };
+        if pending_from.is_some() {
+            pending_from = entries.last().map(|entry| entry.id.clone());
+        }
+        if entries.is_empty() {
+            continue;
+        }
+
+        for entry in entries {
+            let ack = Arc::new(StreamAck {
+                conn: acker.clone(),
+                key: key.clone(),
+                group: options.group.clone(),
+                id: entry.id.clone(),
+            });
+            let payload = entry.get::<String>("payload").unwrap_or_default();
+            match serde_json::from_str::<E>(&payload) {
+                Ok(event) => {
+                    if let Err(e) = dispatch(&topic, event, Some(ack)).await {
+                        project_error!("Failed to dispatch stream event '{}': {}", E::name(), e);
+                    }
+                },
+                Err(e) => {
+                    bus.dead_letter_store()
+                        .store(DeadLetter {
+                            event: E::name().to_string(),
+                            payload: serde_json::Value::String(payload),
+                            error: e.to_string(),
+                            attempts: 0,
+                            failed_at: Local::now().naive_local(),
+                        })
+                        .await;
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct Ping(u32);
+
+    impl Event for Ping {
+        fn name() -> &'static str {
+            "ping"
+        }
+    }
+
+    fn bus(capacity: usize) -> (EventBus, Arc<MemoryDeadLetterStore>) {
+        let bus = EventBus::new(EventBusOptions {
+            capacity,
+            max_attempts: 3,
+            retry_backoff: Duration::from_millis(1),
+        });
+        let store = Arc::new(MemoryDeadLetterStore::default());
+        bus.set_dead_letter_store(store.clone());
+        (bus, store)
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let (bus, store) = bus(8);
+        let attempts = Arc::new(AtomicU32::new(0));
+        let (done_tx, mut done_rx) = mpsc::unbounded_channel();
+
+        let counter = attempts.clone();
+        bus.subscribe(move |ping: Ping| {
+            let counter = counter.clone();
+            let done_tx = done_tx.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    return Err("not yet");
+                }
+                done_tx.send(ping.0).unwrap();
+                Ok(())
+            }
+        });
+
+        bus.publish(Ping(7)).await.unwrap();
+        assert_eq!(done_rx.recv().await, Some(7));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert!(store.letters().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_panic_is_dead_lettered_and_listener_survives() {
+        let (bus, store) = bus(8);
+        let (done_tx, mut done_rx) = mpsc::unbounded_channel();
+
+        bus.subscribe(move |ping: Ping| {
+            let done_tx = done_tx.clone();
+            async move {
+                if ping.0 == 0 {
+                    panic!("boom");
+                }
+                done_tx.send(ping.0).unwrap();
+                Ok::<(), String>(())
+            }
+        });
+
+        bus.publish(Ping(0)).await.unwrap();
+        bus.publish(Ping(1)).await.unwrap();
+        assert_eq!(done_rx.recv().await, Some(1));
+
+        let letters = store.letters();
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].event, "ping");
+        assert_eq!(letters[0].payload, serde_json::json!(0));
+        assert_eq!(letters[0].attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_bounded_channel_applies_backpressure() {
+        let (bus, store) = bus(1);
+        let (release_tx, release_rx) = tokio::sync::watch::channel(false);
+
+        bus.subscribe(move |_: Ping| {
+            let mut release_rx = release_rx.clone();
+            async move {
+                release_rx.wait_for(|released| *released).await.unwrap();
+                Ok::<(), String>(())
+            }
+        });
+
+        // 第一个事件正在处理，第二个占满通道
+        bus.publish(Ping(1)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        bus.publish(Ping(2)).await.unwrap();
+        assert!(matches!(
+            bus.try_publish(Ping(3)),
+            Err(EventBusError::ChannelFull("ping"))
+        ));
+
+        bus.emit(Ping(4));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(store.letters()[0].payload, serde_json::json!(4));
+
+        release_tx.send(true).unwrap();
+    }
+}
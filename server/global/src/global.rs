@@ -1,23 +1,25 @@
 use std::{
     any::{Any, TypeId},
     collections::HashMap,
-    future::Future,
-    pin::Pin,
     sync::Arc,
 };
 
 use aws_sdk_s3::Client as S3Client;
 use chrono::NaiveDateTime;
 use http::Method;
-use jsonwebtoken::{DecodingKey, EncodingKey, Validation};
+use jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, EncodingKey, Validation};
 use mongodb::Client as MongoClient;
 use once_cell::sync::Lazy;
 use redis::{cluster::ClusterClient, Client};
 use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tokio::sync::{mpsc, Mutex, OnceCell, RwLock};
+use tokio::sync::{Mutex, OnceCell, RwLock};
 
-use crate::project_info;
+use crate::{
+    event_bus::{Event, EventBus},
+    project_error,
+};
 
 //*****************************************************************************
 // 全局配置
@@ -83,15 +85,38 @@ pub static GLOBAL_S3_POOL: Lazy<RwLock<HashMap<String, Arc<S3Client>>>> =
 //*****************************************************************************
 
 pub struct Keys {
+    /// 签名算法
+    pub algorithm: Algorithm,
+    /// 当前签名密钥 ID，HMAC 密钥没有 ID
+    pub kid: Option<String>,
     pub encoding: EncodingKey,
     pub decoding: DecodingKey,
+    /// 按 kid 索引的验证密钥，包含当前签名密钥和轮换中保留的旧密钥
+    pub verifying: HashMap<String, DecodingKey>,
+    /// 对外公开的公钥集合
+    pub jwks: JwkSet,
 }
 
 impl Keys {
     pub fn new(secret: &[u8]) -> Self {
         Self {
+            algorithm: Algorithm::HS256,
+            kid: None,
             encoding: EncodingKey::from_secret(secret),
             decoding: DecodingKey::from_secret(secret),
+            verifying: HashMap::new(),
+            jwks: JwkSet { keys: Vec::new() },
+        }
+    }
+
+    /// 根据令牌头部的 kid 选择验证密钥
+    ///
+    /// 配置了 kid 时令牌必须携带已知的 kid，否则只接受不带 kid 的令牌。
+    pub fn decoding_key(&self, kid: Option<&str>) -> Option<&DecodingKey> {
+        match kid {
+            Some(kid) => self.verifying.get(kid),
+            None if self.kid.is_none() => Some(&self.decoding),
+            None => None,
         }
     }
 }
@@ -100,81 +125,21 @@ pub static KEYS: OnceCell<Arc<Mutex<Keys>>> = OnceCell::const_new();
 pub static VALIDATION: OnceCell<Arc<Mutex<Validation>>> = OnceCell::const_new();
 
 //*****************************************************************************
-// 事件通道
+// 事件总线
 //*****************************************************************************
 
-/// 事件通道条目
-struct DynChannelEntry {
-    name: String,
-    tx: mpsc::UnboundedSender<Box<dyn Any + Send>>,
-}
-
-/// 事件通道管理器
-struct EventChannels {
-    string_tx: mpsc::UnboundedSender<String>,
-    dyn_channels: Vec<DynChannelEntry>,
-}
-
-static EVENT_CHANNELS: Lazy<Arc<Mutex<EventChannels>>> = Lazy::new(|| {
-    let (string_tx, _) = mpsc::unbounded_channel();
-    Arc::new(Mutex::new(EventChannels {
-        string_tx,
-        dyn_channels: Vec::new(),
-    }))
-});
-
-type DynFuture = dyn Future<Output = ()> + Send + 'static;
-type StringListener = Box<dyn FnOnce(mpsc::UnboundedReceiver<String>) -> Pin<Box<DynFuture>>>;
-type DynListener = (
-    String,
-    Box<dyn Fn(mpsc::UnboundedReceiver<Box<dyn Any + Send>>) -> Pin<Box<DynFuture>>>,
-);
-
-/// 获取字符串事件发送器
-#[inline]
-pub async fn get_string_sender() -> mpsc::UnboundedSender<String> {
-    EVENT_CHANNELS.lock().await.string_tx.clone()
-}
+static EVENT_BUS: once_cell::sync::OnceCell<EventBus> = once_cell::sync::OnceCell::new();
 
-/// 获取动态类型事件发送器
-#[inline]
-pub async fn get_dyn_sender(name: &str) -> Option<mpsc::UnboundedSender<Box<dyn Any + Send>>> {
-    let channels = EVENT_CHANNELS.lock().await;
-    channels
-        .dyn_channels
-        .iter()
-        .find(|entry| entry.name == name)
-        .map(|entry| entry.tx.clone())
+/// 设置全局事件总线，需在订阅和发布事件之前调用
+pub fn init_event_bus(bus: EventBus) {
+    if EVENT_BUS.set(bus).is_err() {
+        project_error!("Event bus already initialized");
+    }
 }
 
-/// 注册事件监听器
-pub async fn register_event_listeners(
-    string_listener: StringListener,
-    dyn_listeners: &[DynListener],
-) {
-    let mut channels = EVENT_CHANNELS.lock().await;
-
-    // 设置字符串事件通道
-    let (string_tx, string_rx) = mpsc::unbounded_channel();
-    channels.string_tx = string_tx;
-
-    // 启动字符串事件监听器
-    tokio::spawn(string_listener(string_rx));
-    project_info!("String event listener spawned");
-
-    // 清空旧的发送器
-    channels.dyn_channels.clear();
-
-    // 为每个监听器创建独立通道
-    for (name, listener) in dyn_listeners {
-        let (tx, rx) = mpsc::unbounded_channel();
-        channels.dyn_channels.push(DynChannelEntry {
-            name: name.clone(),
-            tx,
-        });
-        tokio::spawn(listener(rx));
-        project_info!("Dynamic event listener '{}' spawned", name);
-    }
+/// 获取全局事件总线，未初始化时使用默认参数
+pub fn event_bus() -> &'static EventBus {
+    EVENT_BUS.get_or_init(EventBus::default)
 }
 
 //*****************************************************************************
@@ -218,7 +183,7 @@ pub async fn clear_routes() {
 // 操作日志
 //*****************************************************************************
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OperationLogContext {
     pub user_id: Option<String>,
     pub username: Option<String>,
@@ -242,6 +207,12 @@ pub struct OperationLogContext {
 static OPERATION_LOG_CONTEXT: Lazy<Arc<RwLock<Option<OperationLogContext>>>> =
     Lazy::new(|| Arc::new(RwLock::new(None)));
 
+impl Event for OperationLogContext {
+    fn name() -> &'static str {
+        "audit_operation_logged_event"
+    }
+}
+
 impl OperationLogContext {
     pub async fn set(context: OperationLogContext) {
         let mut writer = OPERATION_LOG_CONTEXT.write().await;
@@ -258,21 +229,16 @@ impl OperationLogContext {
     }
 }
 
-/// 异步发送字符串事件
-#[inline]
-pub fn send_string_event(msg: String) {
-    tokio::spawn(async move {
-        let sender = get_string_sender().await;
-        let _ = sender.send(msg);
-    });
+/// 发布事件，订阅者通道已满时等待，无法投递时写入死信
+pub async fn publish_event<E: Event>(event: E) {
+    let bus = event_bus();
+    if let Err(e) = bus.publish(event.clone()).await {
+        bus.dead_letter(&event, e.to_string()).await;
+    }
 }
 
-/// 异步发送动态类型事件
+/// 在同步上下文中发布事件，无法立即投递时写入死信
 #[inline]
-pub fn send_dyn_event(event_name: &'static str, event: Box<dyn Any + Send>) {
-    tokio::spawn(async move {
-        if let Some(sender) = get_dyn_sender(event_name).await {
-            let _ = sender.send(event);
-        }
-    });
+pub fn emit_event<E: Event>(event: E) {
+    event_bus().emit(event);
 }
@@ -1,5 +1,6 @@
 pub use jsonwebtoken::Validation;
 
+pub mod event_bus;
 pub mod global;
 
 #[macro_export]
@@ -1,10 +1,15 @@
-use server_global::project_info;
-use server_service::admin::{SysAccessKeyService, TAccessKeyService};
+use server_global::{project_error, project_info};
+use server_service::admin::{access_key_sync_listener, SysAccessKeyService, TAccessKeyService};
 
+/// 从 `sys_access_key` 加载启用的访问密钥，并订阅其他实例的变更
 pub async fn initialize_access_key() {
     let access_key_service = SysAccessKeyService;
 
-    let _ = access_key_service.initialize_access_key().await;
+    if let Err(e) = access_key_service.initialize_access_key().await {
+        project_error!("Failed to load access keys: {:?}", e);
+    }
+
+    tokio::spawn(access_key_sync_listener());
 
     project_info!("Access key initialization completed successfully")
 }
@@ -1,11 +1,14 @@
-use std::error::Error;
+use std::{error::Error, sync::Arc};
 
-use axum_casbin::CasbinAxumLayer;
+use axum_casbin::{CasbinAxumLayer, PolicyWatcher};
 use casbin::DefaultModel;
-use sea_orm::Database;
+use sea_orm::{Database, DatabaseConnection};
 use sea_orm_adapter::SeaOrmAdapter;
+use server_config::{CasbinAuditSink, Config, RedisConfig};
+use server_global::global::{get_config, RedisConnection};
+use server_service::admin::DatabaseDecisionSink;
 
-use crate::project_info;
+use crate::{project_error, project_info, redis_initialization::get_primary_redis};
 
 pub async fn initialize_casbin(
     model_path: &str,
@@ -14,9 +17,64 @@ pub async fn initialize_casbin(
     project_info!("Initializing Casbin with model: {}", model_path);
     let model = DefaultModel::from_file(model_path).await?;
     let db = Database::connect(db_url).await?;
+    let watcher = create_policy_watcher(&db).await;
     let adapter = SeaOrmAdapter::new(db).await?;
 
-    let casbin_axum_layer = CasbinAxumLayer::new(model, adapter).await?;
+    let mut casbin_axum_layer = CasbinAxumLayer::new(model, adapter).await?;
+    let casbin_config = get_config::<Config>()
+        .await
+        .map(|config| config.casbin.clone())
+        .unwrap_or_default();
+    if casbin_config.audit {
+        project_info!(
+            "Casbin decision audit enabled with {:?} sink",
+            casbin_config.audit_sink
+        );
+        casbin_axum_layer = match casbin_config.audit_sink {
+            CasbinAuditSink::Tracing => casbin_axum_layer.with_audit(),
+            CasbinAuditSink::Database => {
+                casbin_axum_layer.with_decision_sink(Arc::new(DatabaseDecisionSink))
+            },
+        };
+    }
+    if let Some(watcher) = watcher {
+        project_info!(
+            "Casbin policy changes synchronized via {}",
+            watcher.channel()
+        );
+        casbin_axum_layer.set_watcher(watcher).await;
+    }
     project_info!("Casbin initialization completed successfully");
     Ok(casbin_axum_layer)
 }
+
+/// 创建策略同步 watcher，优先使用主 Redis，未配置时使用 Postgres LISTEN/NOTIFY
+async fn create_policy_watcher(db: &DatabaseConnection) -> Option<PolicyWatcher> {
+    match get_primary_redis().await {
+        Some(RedisConnection::Single(client)) => {
+            return Some(PolicyWatcher::redis(client.as_ref().clone()))
+        },
+        Some(RedisConnection::Cluster(_)) => {
+            // 集群模式下 PUBLISH 会广播到所有节点，订阅第一个节点即可
+            let url = get_config::<RedisConfig>()
+                .await
+                .and_then(|config| config.get_urls())
+                .and_then(|urls| urls.into_iter().next());
+            match url.map(|url| redis::Client::open(url.as_str())) {
+                Some(Ok(client)) => return Some(PolicyWatcher::redis(client)),
+                Some(Err(e)) => project_error!("Failed to create Casbin watcher client: {}", e),
+                None => project_error!("Redis cluster URLs not configured for Casbin watcher"),
+            }
+        },
+        None => {},
+    }
+
+    if let DatabaseConnection::SqlxPostgresPoolConnection(_) = db {
+        return Some(PolicyWatcher::postgres(
+            db.get_postgres_connection_pool().clone(),
+        ));
+    }
+
+    project_info!("No Casbin watcher backend available, policy changes are not synchronized");
+    None
+}
@@ -1,28 +1,82 @@
-use server_constant::definition::consts::SystemEvent;
-use server_global::global;
+use std::{sync::Arc, time::Duration};
 
+use server_config::{EventBusConfig, EventTransport};
+use server_global::{
+    event_bus::{Event, EventBus, EventBusOptions, RedisDeadLetterStore, RedisStreamOptions},
+    global::{self, OperationLogContext, RedisConnection},
+};
+
+use crate::{project_error, project_info, redis_initialization::get_primary_redis};
+
+/// 死信在主 Redis 中的列表键
+const DEAD_LETTER_KEY: &str = "event_bus:dead_letters";
+/// 事件 Stream 的键前缀
+const STREAM_KEY_PREFIX: &str = "event_bus:stream:";
+
+/// 初始化事件总线并注册监听器，需在主 Redis 初始化之后调用
 pub async fn initialize_event_channel() {
     use server_service::admin::{
-        api_key_validate_listener, auth_login_listener, jwt_created_listener,
-        sys_operation_log_listener,
+        api_key_validate_listener, auth_login_listener, authorization_decision_listener,
+        jwt_created_listener, sys_operation_log_listener, tenant_switch_listener, AuthEvent,
     };
 
-    global::register_event_listeners(
-        Box::new(|rx| Box::pin(jwt_created_listener(rx))),
-        &[
-            (
-                SystemEvent::AuthLoggedInEvent.to_string(),
-                Box::new(|rx| Box::pin(auth_login_listener(rx))),
-            ),
-            (
-                SystemEvent::AuditOperationLoggedEvent.to_string(),
-                Box::new(|rx| Box::pin(sys_operation_log_listener(rx))),
-            ),
-            (
-                SystemEvent::AuthApiKeyValidatedEvent.to_string(),
-                Box::new(|rx| Box::pin(api_key_validate_listener(rx))),
-            ),
-        ],
-    )
-    .await;
+    let config = global::get_config::<EventBusConfig>()
+        .await
+        .map(|config| config.as_ref().clone())
+        .unwrap_or_default();
+    let bus = EventBus::new(EventBusOptions {
+        capacity: config.capacity,
+        max_attempts: config.max_attempts,
+        retry_backoff: Duration::from_millis(config.retry_backoff_ms),
+    });
+
+    let redis = get_primary_redis().await;
+    if let Some(redis) = redis.clone() {
+        bus.set_dead_letter_store(Arc::new(RedisDeadLetterStore::new(redis, DEAD_LETTER_KEY)));
+    }
+    global::init_event_bus(bus.clone());
+
+    bus.subscribe(jwt_created_listener);
+    bus.subscribe(auth_login_listener);
+    bus.subscribe(sys_operation_log_listener);
+    bus.subscribe(api_key_validate_listener);
+    bus.subscribe(authorization_decision_listener);
+    bus.subscribe(tenant_switch_listener);
+
+    if config.transport == EventTransport::RedisStreams {
+        let Some(redis) = redis else {
+            project_error!("Redis streams transport requires the primary Redis, events stay local");
+            return;
+        };
+        let options = RedisStreamOptions {
+            key_prefix: STREAM_KEY_PREFIX.to_string(),
+            group: config.stream_group.clone(),
+            consumer: config
+                .stream_consumer
+                .clone()
+                .or_else(|| std::env::var("HOSTNAME").ok())
+                .unwrap_or_else(|| "soybean-admin".to_string()),
+            max_len: config.stream_max_len,
+        };
+        bridge_redis_stream::<AuthEvent>(&bus, &redis, &options).await;
+        bridge_redis_stream::<OperationLogContext>(&bus, &redis, &options).await;
+    }
+}
+
+async fn bridge_redis_stream<E: Event>(
+    bus: &EventBus,
+    redis: &RedisConnection,
+    options: &RedisStreamOptions,
+) {
+    match bus
+        .bridge_redis_stream::<E>(redis.clone(), options.clone())
+        .await
+    {
+        Ok(()) => project_info!("Event '{}' delivered via Redis streams", E::name()),
+        Err(e) => project_error!(
+            "Failed to bridge event '{}' to Redis streams, events stay local: {}",
+            E::name(),
+            e
+        ),
+    }
 }
@@ -1,14 +1,66 @@
-use std::error::Error;
+use std::{error::Error, path::Path, time::Duration};
 
 use xdb::searcher;
 
-use crate::project_info;
+use crate::{project_error, project_info};
+
+const XDB_FILEPATH: &str = "server/resources/ip2region.xdb";
+const XDB_V6_FILEPATH: &str = "server/resources/ip2region_v6.xdb";
+
+/// 检查 xdb 文件是否更新的间隔
+const XDB_RELOAD_INTERVAL: Duration = Duration::from_secs(60);
 
 pub async fn init_xdb() -> Result<(), Box<dyn Error>> {
     tokio::task::spawn_blocking(|| {
-        searcher::searcher_init(Some("server/resources/ip2region.xdb".to_string()));
+        if let Err(e) = searcher::searcher_init(Some(XDB_FILEPATH.to_string())) {
+            project_error!("Failed to load XDB {}: {}", XDB_FILEPATH, e);
+        }
+        // IPv6 数据库是可选的，缺失时 IPv6 地址无法解析归属地
+        if let Err(e) = searcher::searcher_init_v6(Some(XDB_V6_FILEPATH.to_string())) {
+            project_info!("IPv6 XDB not loaded: {}", e);
+        }
     })
     .await?;
+
+    tokio::spawn(watch_xdb_files());
     project_info!("XDB initialized successfully");
     Ok(())
 }
+
+/// 定期检查 xdb 文件的修改时间，文件更新后原地重新加载，无需重启服务
+async fn watch_xdb_files() {
+    let mut modified = [XDB_FILEPATH, XDB_V6_FILEPATH].map(modified_time);
+    let mut interval = tokio::time::interval(XDB_RELOAD_INTERVAL);
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        for (path, last_modified) in [XDB_FILEPATH, XDB_V6_FILEPATH]
+            .into_iter()
+            .zip(modified.iter_mut())
+        {
+            let current = modified_time(path);
+            if current.is_none() || current == *last_modified {
+                continue;
+            }
+            *last_modified = current;
+
+            match tokio::task::spawn_blocking(move || {
+                searcher::searcher_reload(path).map_err(|e| e.to_string())
+            })
+            .await
+            {
+                Ok(Ok(version)) => project_info!("XDB {} reloaded ({:?})", path, version),
+                Ok(Err(e)) => project_error!("Failed to reload XDB {}: {}", path, e),
+                Err(e) => project_error!("XDB reload task failed: {}", e),
+            }
+        }
+    }
+}
+
+fn modified_time(path: &str) -> Option<std::time::SystemTime> {
+    Path::new(path)
+        .metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
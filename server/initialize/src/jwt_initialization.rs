@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use server_config::JwtConfig;
+use server_core::web::jwk::load_keys;
 use server_global::{global, Validation};
 use tokio::sync::Mutex;
 
@@ -15,12 +16,20 @@ pub async fn initialize_keys_and_validation() {
         },
     };
 
-    let keys = global::Keys::new(jwt_config.jwt_secret.as_bytes());
+    let keys = match load_keys(&jwt_config) {
+        Ok(keys) => keys,
+        Err(e) => {
+            project_error!("Failed to load JWT keys: {}", e);
+            return;
+        },
+    };
+    let algorithm = keys.algorithm;
+    let kid = keys.kid.clone();
     if global::KEYS.set(Arc::new(Mutex::new(keys))).is_err() {
         project_error!("Failed to set KEYS");
     }
 
-    let mut validation = Validation::default();
+    let mut validation = Validation::new(algorithm);
     validation.leeway = 60;
     validation.set_issuer(&[&jwt_config.issuer]);
     if global::VALIDATION
@@ -30,5 +39,9 @@ pub async fn initialize_keys_and_validation() {
         project_error!("Failed to set VALIDATION");
     }
 
-    project_info!("JWT keys and validation initialized");
+    project_info!(
+        "JWT keys and validation initialized (algorithm: {:?}, kid: {})",
+        algorithm,
+        kid.as_deref().unwrap_or("-")
+    );
 }
@@ -13,6 +13,7 @@ pub use log_tracing_init::initialize_log_tracing;
 pub use mongo_initialization::{init_mongo_pools, init_primary_mongo};
 pub use redis_initialization::{init_primary_redis, init_redis_pools};
 pub use router_initialization::initialize_admin_router;
+pub use scheduler_initialization::initialize_scheduler;
 pub use server_global::{project_error, project_info};
 pub use server_initialization::get_server_address;
 
@@ -28,6 +29,7 @@ mod log_tracing_init;
 mod mongo_initialization;
 mod redis_initialization;
 mod router_initialization;
+mod scheduler_initialization;
 mod server_initialization;
 
 // axum-test-helpers不兼容axum 0.8.x，已移除依赖，改用tower::ServiceExt自定义测试客户端
@@ -8,22 +8,24 @@ use server_config::Config;
 use server_constant::definition::Audience;
 use server_core::sign::{
     api_key_middleware, protect_route, ApiKeySource, ApiKeyValidation, ComplexApiKeyConfig,
-    SimpleApiKeyConfig, ValidatorType,
+    SimpleApiKeyConfig,
 };
 use server_core::web::{RequestId, RequestIdLayer};
 use server_global::global::{clear_routes, get_collected_routes, get_config};
-use server_middleware::jwt_auth_middleware;
+use server_middleware::{jwt_auth_middleware, tenant_middleware};
 use server_router::admin::{
-    SysAccessKeyRouter, SysAuthenticationRouter, SysDomainRouter, SysEndpointRouter,
-    SysLoginLogRouter, SysMenuRouter, SysOperationLogRouter, SysOrganizationRouter, SysRoleRouter,
-    SysSandboxRouter, SysUserRouter,
+    SysAccessKeyRouter, SysAccountRouter, SysAuthenticationRouter, SysDomainRouter,
+    SysEndpointRouter, SysJobRouter, SysLoginLogRouter, SysLoginSecurityRouter, SysMenuRouter,
+    SysOperationLogRouter, SysOrganizationRouter, SysRoleRouter, SysSandboxRouter, SysUserRouter,
+    SysUserTotpRouter,
 };
 use server_router::web3::Web3Router;
 use server_service::{
     admin::{
-        SysAccessKeyService, SysAuthService, SysAuthorizationService, SysDomainService,
-        SysEndpointService, SysLoginLogService, SysMenuService, SysOperationLogService,
-        SysOrganizationService, SysRoleService, SysUserService, TEndpointService,
+        SysAccessKeyService, SysAccountService, SysAuthService, SysAuthorizationService,
+        SysDomainService, SysEndpointService, SysJobService, SysLoginLogService,
+        SysLoginSecurityService, SysMenuService, SysOperationLogService, SysOrganizationService,
+        SysRoleService, SysUserService, SysUserTotpService, TEndpointService,
     },
     web3::{Web3WalletService, Web3MarketDataService, GasAnalyticsService, Web3Provider, alloy_provider::ChainConfig},
     SysEndpoint,
@@ -84,9 +86,12 @@ async fn apply_layers<T: Send + Sync + 'static>(
     }
 
     if need_auth {
-        router = router.layer(axum::middleware::from_fn(move |req, next| {
-            jwt_auth_middleware(req, next, audience.as_str())
-        }));
+        // 租户上下文依赖 JWT 写入的用户信息，因此放在认证层内侧
+        router = router
+            .layer(axum::middleware::from_fn(tenant_middleware))
+            .layer(axum::middleware::from_fn(move |req, next| {
+                jwt_auth_middleware(req, next, audience.as_str())
+            }));
     }
 
     router
@@ -121,7 +126,6 @@ pub async fn initialize_admin_router() -> Router {
 
     let simple_validation = {
         let validator = server_core::sign::get_simple_validator().await;
-        server_core::sign::add_key(ValidatorType::Simple, "test-api-key", None).await;
         ApiKeyValidation::Simple(
             validator,
             SimpleApiKeyConfig {
@@ -133,12 +137,6 @@ pub async fn initialize_admin_router() -> Router {
 
     let complex_validation = {
         let validator = server_core::sign::get_complex_validator().await;
-        server_core::sign::add_key(
-            ValidatorType::Complex,
-            "test-access-key",
-            Some("test-secret-key"),
-        )
-        .await;
         ApiKeyValidation::Complex(
             validator,
             ComplexApiKeyConfig {
@@ -196,6 +194,13 @@ pub async fn initialize_admin_router() -> Router {
         false,
         None
     );
+    merge_router!(
+        SysAuthenticationRouter::init_well_known_router().await,
+        None,
+        false,
+        false,
+        None
+    );
 
     let auth_router = SysAuthenticationRouter::init_authorization_router()
         .await
@@ -224,6 +229,27 @@ pub async fn initialize_admin_router() -> Router {
         true,
         None
     );
+    merge_router!(
+        SysUserTotpRouter::init_totp_router().await,
+        SysUserTotpService,
+        false,
+        true,
+        None
+    );
+    merge_router!(
+        SysAccountRouter::init_account_router().await,
+        SysAccountService,
+        false,
+        true,
+        None
+    );
+    merge_router!(
+        SysAccountRouter::init_password_reset_router().await,
+        SysAccountService,
+        false,
+        false,
+        None
+    );
 
     merge_router!(
         SysMenuRouter::init_menu_router().await,
@@ -283,6 +309,13 @@ pub async fn initialize_admin_router() -> Router {
         true,
         None
     );
+    merge_router!(
+        SysLoginSecurityRouter::init_login_security_router().await,
+        SysLoginSecurityService,
+        true,
+        true,
+        None
+    );
     merge_router!(
         SysOperationLogRouter::init_operation_log_router().await,
         SysOperationLogService,
@@ -290,6 +323,13 @@ pub async fn initialize_admin_router() -> Router {
         true,
         None
     );
+    merge_router!(
+        SysJobRouter::init_job_router().await,
+        SysJobService,
+        true,
+        true,
+        None
+    );
 
     merge_router!(
         SysOrganizationRouter::init_organization_router().await,
@@ -300,17 +340,18 @@ pub async fn initialize_admin_router() -> Router {
     );
 
     // sandbox
+    // 访问密钥可访问的接口由 casbin 以 `ak:<AccessKeyId>` 为主体校验
     merge_router!(
         SysSandboxRouter::init_simple_sandbox_router().await,
         None,
-        false,
+        true,
         false,
         Some(simple_validation)
     );
     merge_router!(
         SysSandboxRouter::init_complex_sandbox_router().await,
         None,
-        false,
+        true,
         false,
         Some(complex_validation)
     );
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use server_config::{LogRetentionConfig, SchedulerConfig};
+use server_global::global;
+use server_scheduler::{JobLock, JobSpec, NoopJobLock, RedisJobLock, Scheduler, GLOBAL_SCHEDULER};
+use server_service::admin::{DatabaseJobStore, SysAccessKeyService, SysLogRetentionService};
+
+use crate::{project_error, project_info};
+
+/// 注册内置定时任务并启动调度器
+///
+/// 需在数据库、主 Redis 和 S3 初始化之后调用。配置主 Redis 时通过 Redis 锁保证同一任务
+/// 只在一个实例上执行，否则按单实例执行
+///
+/// 价格告警和 nonce 清理不注册为定时任务：`web3::price_alert` 的告警与价格只保存在各自的
+/// `PriceAlertService` 实例中，没有可供任务读取的共享存储和价格来源；签名、外部登录使用的 nonce
+/// 在内存缓存和 Redis 中都带有 TTL，到期自动删除，无需额外清理
+pub async fn initialize_scheduler() {
+    if GLOBAL_SCHEDULER.get().is_some() {
+        return;
+    }
+    let config = global::get_config::<SchedulerConfig>()
+        .await
+        .map(|config| config.as_ref().clone())
+        .unwrap_or_default();
+
+    let lock: Arc<dyn JobLock> = match global::GLOBAL_PRIMARY_REDIS.read().await.clone() {
+        Some(redis) => Arc::new(RedisJobLock::new(redis)),
+        None => {
+            project_info!("Redis not configured, scheduled jobs are not locked across instances");
+            Arc::new(NoopJobLock)
+        },
+    };
+    let scheduler = Scheduler::new(Arc::new(DatabaseJobStore), lock);
+
+    register_access_key_expiry(&scheduler, &config).await;
+    register_log_retention(&scheduler).await;
+
+    if config.enabled {
+        scheduler.start();
+        project_info!("Scheduler started on instance {}", scheduler.instance());
+    } else {
+        project_info!("Scheduler is disabled, jobs only run when triggered manually");
+    }
+
+    if GLOBAL_SCHEDULER.set(scheduler).is_err() {
+        project_error!("Scheduler is already initialized");
+    }
+}
+
+async fn register_access_key_expiry(scheduler: &Scheduler, config: &SchedulerConfig) {
+    let spec = JobSpec::new(
+        "access_key_expiry",
+        &config.access_key_expiry_cron,
+        "禁用已过期的访问密钥",
+    );
+
+    let result = scheduler
+        .register(spec, || async {
+            SysAccessKeyService
+                .disable_expired_access_keys()
+                .await
+                .map(|_| ())
+                .map_err(|e| e.message)
+        })
+        .await;
+    if let Err(e) = result {
+        project_error!("Failed to register access key expiry job: {}", e);
+    }
+}
+
+async fn register_log_retention(scheduler: &Scheduler) {
+    let Some(config) = global::get_config::<LogRetentionConfig>().await else {
+        return;
+    };
+    if !config.enabled {
+        return;
+    }
+    if config.bucket.is_none() {
+        project_error!("Log retention is enabled but no archive bucket is configured, skipped");
+        return;
+    }
+
+    let spec = JobSpec::new(
+        "log_retention",
+        &config.cron,
+        format!(
+            "归档并删除过期日志（操作日志 {} 天，登录日志 {} 天）",
+            config.operation_log_days, config.login_log_days
+        ),
+    );
+    let config = config.as_ref().clone();
+
+    let result = scheduler
+        .register(spec, move || {
+            let config = config.clone();
+            async move {
+                let report = SysLogRetentionService::run(&config)
+                    .await
+                    .map_err(|e| e.message)?;
+                if report.operation_logs > 0 || report.login_logs > 0 {
+                    project_info!(
+                        "Log retention archived {} operation logs and {} login logs",
+                        report.operation_logs,
+                        report.login_logs
+                    );
+                }
+                Ok::<_, String>(())
+            }
+        })
+        .await;
+    if let Err(e) = result {
+        project_error!("Failed to register log retention job: {}", e);
+    }
+}
@@ -1,3 +1,5 @@
 mod jwt;
+mod tenant;
 
 pub use jwt::jwt_auth_middleware;
+pub use tenant::tenant_middleware;
@@ -0,0 +1,49 @@
+use axum::{
+    body::Body, extract::Request, http::StatusCode, middleware::Next, response::IntoResponse,
+};
+use chrono::Local;
+use server_core::web::{
+    auth::User,
+    res::Res,
+    tenant::{TenantContext, TenantSwitchEvent, TENANT_DOMAIN_HEADER},
+};
+use server_global::global;
+
+/// 根据登录用户和 `x-tenant-domain` 请求头写入 [`TenantContext`]，须位于 JWT 认证之后
+///
+/// 超级管理员切换到其他域时每个请求都会发布 [`TenantSwitchEvent`] 留作审计。
+pub async fn tenant_middleware(mut req: Request<Body>, next: Next) -> impl IntoResponse {
+    let Some(user) = req.extensions().get::<User>().cloned() else {
+        return Res::<String>::new_error(StatusCode::UNAUTHORIZED.as_u16(), "Unauthorized")
+            .into_response();
+    };
+
+    let requested = req
+        .headers()
+        .get(TENANT_DOMAIN_HEADER)
+        .and_then(|value| value.to_str().ok());
+
+    let Some(tenant) = TenantContext::resolve(&user, requested) else {
+        return Res::<String>::new_error(
+            StatusCode::FORBIDDEN.as_u16(),
+            "Switching to another domain is not allowed",
+        )
+        .into_response();
+    };
+
+    if tenant.is_switched() {
+        global::publish_event(TenantSwitchEvent {
+            user_id: user.user_id(),
+            username: user.username(),
+            home_domain: tenant.home_domain().to_string(),
+            target_domain: tenant.domain().to_string(),
+            method: req.method().to_string(),
+            url: req.uri().to_string(),
+            created_at: Local::now().naive_local(),
+        })
+        .await;
+    }
+
+    req.extensions_mut().insert(tenant);
+    next.run(req).await.into_response()
+}
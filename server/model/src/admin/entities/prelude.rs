@@ -2,10 +2,20 @@
 
 pub use super::{
     casbin_rule::Entity as CasbinRule, sys_access_key::Entity as SysAccessKey,
+    sys_authorization_decision::Entity as SysAuthorizationDecision,
     sys_domain::Entity as SysDomain, sys_endpoint::Entity as SysEndpoint,
-    sys_login_log::Entity as SysLoginLog, sys_menu::Entity as SysMenu,
+    sys_job::Entity as SysJob, sys_job_run::Entity as SysJobRun,
+    sys_login_log::Entity as SysLoginLog,
+    sys_login_security_policy::Entity as SysLoginSecurityPolicy, sys_menu::Entity as SysMenu,
     sys_operation_log::Entity as SysOperationLog, sys_organization::Entity as SysOrganization,
-    sys_role::Entity as SysRole, sys_role_menu::Entity as SysRoleMenu,
-    sys_tokens::Entity as SysTokens, sys_user::Entity as SysUser,
-    sys_user_role::Entity as SysUserRole,
+    sys_permission_code::Entity as SysPermissionCode, sys_role::Entity as SysRole,
+    sys_role_data_scope_org::Entity as SysRoleDataScopeOrg,
+    sys_role_endpoint::Entity as SysRoleEndpoint, sys_role_menu::Entity as SysRoleMenu,
+    sys_role_permission_code::Entity as SysRolePermissionCode,
+    sys_tenant_switch_log::Entity as SysTenantSwitchLog, sys_tokens::Entity as SysTokens,
+    sys_user::Entity as SysUser, sys_user_external_identity::Entity as SysUserExternalIdentity,
+    sys_user_organization::Entity as SysUserOrganization,
+    sys_user_password_history::Entity as SysUserPasswordHistory,
+    sys_user_recovery_code::Entity as SysUserRecoveryCode, sys_user_role::Entity as SysUserRole,
+    sys_user_totp::Entity as SysUserTotp,
 };
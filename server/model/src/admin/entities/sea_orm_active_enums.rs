@@ -28,3 +28,31 @@ pub enum Status {
     #[serde(rename = "enabled")]
     Enabled,
 }
+/// 角色的数据权限范围，多个角色时取并集
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, EnumIter, DeriveActiveEnum, Deserialize, Serialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)")]
+pub enum DataScope {
+    /// 全部数据
+    #[default]
+    #[sea_orm(string_value = "all")]
+    #[serde(rename = "all")]
+    All,
+    /// 所属组织
+    #[sea_orm(string_value = "org")]
+    #[serde(rename = "org")]
+    Org,
+    /// 所属组织及其下级组织
+    #[sea_orm(string_value = "org_and_children")]
+    #[serde(rename = "org_and_children")]
+    OrgAndChildren,
+    /// 仅本人
+    #[sea_orm(string_value = "self_only")]
+    #[serde(rename = "self_only")]
+    SelfOnly,
+    /// 自定义组织列表
+    #[sea_orm(string_value = "custom")]
+    #[serde(rename = "custom")]
+    Custom,
+}
@@ -2,6 +2,7 @@
 
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
+use serde_json::Value as JsonValue;
 
 use super::sea_orm_active_enums::Status;
 
@@ -22,6 +23,16 @@ pub struct Model {
     pub created_at: DateTime,
     #[sea_orm(column_type = "Text")]
     pub created_by: String,
+    pub expires_at: Option<DateTime>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub allowed_ips: Option<JsonValue>,
+    pub last_used_at: Option<DateTime>,
+    #[serde(skip)]
+    #[sea_orm(column_type = "Text", nullable)]
+    pub previous_access_key_secret: Option<String>,
+    pub previous_secret_expires_at: Option<DateTime>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub signature_schemes: Option<JsonValue>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
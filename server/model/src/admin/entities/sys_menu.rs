@@ -40,10 +40,18 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::sys_permission_code::Entity")]
+    SysPermissionCode,
     #[sea_orm(has_many = "super::sys_role_menu::Entity")]
     SysRoleMenu,
 }
 
+impl Related<super::sys_permission_code::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SysPermissionCode.def()
+    }
+}
+
 impl Related<super::sys_role_menu::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::SysRoleMenu.def()
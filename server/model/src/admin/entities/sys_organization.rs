@@ -19,6 +19,8 @@ pub struct Model {
     #[sea_orm(column_type = "Text")]
     pub pid: String,
     pub status: Status,
+    #[sea_orm(column_type = "Text")]
+    pub domain: String,
     pub created_at: DateTime,
     #[sea_orm(column_type = "Text")]
     pub created_by: String,
@@ -3,7 +3,7 @@
 use sea_orm::entity::prelude::*;
 use serde::Serialize;
 
-use super::sea_orm_active_enums::Status;
+use super::sea_orm_active_enums::{DataScope, Status};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "sys_role")]
@@ -19,6 +19,9 @@ pub struct Model {
     #[sea_orm(column_type = "Text")]
     pub pid: String,
     pub status: Status,
+    #[sea_orm(column_type = "Text")]
+    pub domain: String,
+    pub data_scope: DataScope,
     pub created_at: DateTime,
     #[sea_orm(column_type = "Text")]
     pub created_by: String,
@@ -29,12 +32,20 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::sys_role_data_scope_org::Entity")]
+    SysRoleDataScopeOrg,
     #[sea_orm(has_many = "super::sys_role_menu::Entity")]
     SysRoleMenu,
     #[sea_orm(has_many = "super::sys_user_role::Entity")]
     SysUserRole,
 }
 
+impl Related<super::sys_role_data_scope_org::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SysRoleDataScopeOrg.def()
+    }
+}
+
 impl Related<super::sys_role_menu::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::SysRoleMenu.def()
@@ -1,23 +1,51 @@
-pub use sys_access_key::{AccessKeyPageRequest, CreateAccessKeyInput};
-pub use sys_authentication::LoginInput;
-pub use sys_authorization::{AssignPermissionDto, AssignRouteDto, AssignUserDto};
+pub use sys_access_key::{
+    AccessKeyInput, AccessKeyPageRequest, AssignAccessKeyPermissionInput, CreateAccessKeyInput,
+    RotateAccessKeyInput, UpdateAccessKeyInput,
+};
+pub use sys_account::{
+    ChangePasswordInput, ForgotPasswordInput, ResetPasswordInput, UpdateProfileInput,
+};
+pub use sys_authentication::{
+    ExternalLoginCallbackInput, LoginInput, RefreshTokenInput, SiweLoginInput, TwoFactorLoginInput,
+};
+pub use sys_authorization::{
+    AssignPermissionCodeDto, AssignPermissionDto, AssignRouteDto, AssignUserDto,
+    ExplainPermissionDto,
+};
 pub use sys_domain::{CreateDomainInput, DomainPageRequest, UpdateDomainInput};
 pub use sys_endpoint::EndpointPageRequest;
+pub use sys_job::JobRunPageRequest;
+pub use sys_log_export::{LogExportFormat, LogExportRequest};
 pub use sys_login_log::LoginLogPageRequest;
-pub use sys_menu::{CreateMenuInput, UpdateMenuInput};
+pub use sys_login_security::{
+    LockedAccountRequest, LoginWindowInput, UnlockAccountInput, UpsertLoginSecurityPolicyInput,
+};
+pub use sys_menu::{
+    CreateMenuInput, CreatePermissionCodeInput, UpdateMenuInput, UpdatePermissionCodeInput,
+};
 pub use sys_operation_log::OperationLogPageRequest;
-pub use sys_organization::OrganizationPageRequest;
-pub use sys_role::{CreateRoleInput, RolePageRequest, UpdateRoleInput};
-pub use sys_user::{CreateUserInput, UpdateUserInput, UserPageRequest};
+pub use sys_organization::{
+    CreateOrganizationInput, OrganizationPageRequest, UpdateOrganizationInput,
+};
+pub use sys_role::{CreateRoleInput, RolePageRequest, UpdateRoleDataScopeInput, UpdateRoleInput};
+pub use sys_user::{
+    AssignUserOrganizationsInput, CreateUserInput, UpdateUserInput, UserPageRequest,
+};
+pub use sys_user_totp::TotpCodeInput;
 
 mod sys_access_key;
+mod sys_account;
 mod sys_authentication;
 mod sys_authorization;
 mod sys_domain;
 mod sys_endpoint;
+mod sys_job;
+mod sys_log_export;
 mod sys_login_log;
+mod sys_login_security;
 mod sys_menu;
 mod sys_operation_log;
 mod sys_organization;
 mod sys_role;
 mod sys_user;
+mod sys_user_totp;
@@ -1,5 +1,6 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use server_core::web::page::PageRequest;
+use server_core::{sign::SignatureScheme, web::page::PageRequest};
 use validator::Validate;
 
 use crate::admin::entities::sea_orm_active_enums::Status;
@@ -17,6 +18,39 @@ pub struct AccessKeyInput {
     pub status: Status,
     #[validate(length(max = 200, message = "Description must not exceed 200 characters"))]
     pub description: Option<String>,
+    /// 过期时间，为空表示永不过期
+    pub expires_at: Option<NaiveDateTime>,
+    /// 允许的来源 IP 或 CIDR，为空表示不限制
+    pub allowed_ips: Option<Vec<String>>,
+    /// 允许的签名方式，为空表示不限制
+    pub signature_schemes: Option<Vec<SignatureScheme>>,
 }
 
 pub type CreateAccessKeyInput = AccessKeyInput;
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateAccessKeyInput {
+    pub id: String,
+    #[serde(flatten)]
+    pub access_key: AccessKeyInput,
+}
+
+/// 轮换访问密钥，旧密钥在宽限期内仍可用于签名
+#[derive(Deserialize, Validate)]
+pub struct RotateAccessKeyInput {
+    pub id: String,
+    /// 旧密钥的宽限期（秒），为空时使用默认值
+    #[validate(range(
+        min = 0,
+        max = 604800,
+        message = "Grace period must be between 0 and 604800 seconds"
+    ))]
+    pub grace_period: Option<i64>,
+}
+
+/// 为访问密钥分配可访问的接口，传入空列表表示收回全部权限
+#[derive(Deserialize, Validate)]
+pub struct AssignAccessKeyPermissionInput {
+    pub id: String,
+    pub permissions: Vec<String>,
+}
@@ -0,0 +1,54 @@
+use serde::Deserialize;
+use validator::Validate;
+
+/// 当前用户修改自己的资料
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProfileInput {
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Nick name must be between 1 and 50 characters"
+    ))]
+    pub nick_name: String,
+    #[validate(email(message = "Invalid email format"))]
+    pub email: Option<String>,
+    #[validate(length(max = 20, message = "Phone number must not exceed 20 characters"))]
+    pub phone_number: Option<String>,
+    /// 修改邮箱时必填，邮箱可用于找回密码
+    pub current_password: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePasswordInput {
+    #[validate(length(min = 1, message = "Current password cannot be empty"))]
+    pub current_password: String,
+    #[validate(length(
+        min = 6,
+        max = 100,
+        message = "Password must be between 6 and 100 characters"
+    ))]
+    pub new_password: String,
+}
+
+/// 按邮箱申请找回密码
+#[derive(Deserialize, Validate)]
+pub struct ForgotPasswordInput {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+}
+
+/// 用找回密码邮件中的令牌设置新密码
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetPasswordInput {
+    #[validate(length(min = 1, message = "Token cannot be empty"))]
+    pub token: String,
+    #[validate(length(
+        min = 6,
+        max = 100,
+        message = "Password must be between 6 and 100 characters"
+    ))]
+    pub new_password: String,
+}
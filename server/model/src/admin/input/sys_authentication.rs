@@ -8,3 +8,44 @@ pub struct LoginInput {
     #[validate(length(min = 6, message = "Password cannot be empty"))]
     pub password: String,
 }
+
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshTokenInput {
+    #[validate(length(min = 1, message = "Refresh token cannot be empty"))]
+    pub refresh_token: String,
+}
+
+/// 双因素认证登录的第二步
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorLoginInput {
+    #[validate(length(min = 1, message = "Challenge token cannot be empty"))]
+    pub challenge_token: String,
+    /// 身份验证器中的 6 位动态码或一次性恢复码
+    #[validate(length(
+        min = 6,
+        max = 32,
+        message = "Code must be between 6 and 32 characters"
+    ))]
+    pub code: String,
+}
+
+/// 外部登录提供方回调到前端后，由前端提交的授权码和 state
+#[derive(Deserialize, Validate)]
+pub struct ExternalLoginCallbackInput {
+    #[validate(length(min = 1, message = "Code cannot be empty"))]
+    pub code: String,
+    #[validate(length(min = 1, message = "State cannot be empty"))]
+    pub state: String,
+}
+
+/// 以太坊钱包登录，提交钱包签名的 EIP-4361 消息和签名
+#[derive(Deserialize, Validate)]
+pub struct SiweLoginInput {
+    #[validate(length(min = 1, max = 4096, message = "Message cannot be empty"))]
+    pub message: String,
+    /// 十六进制编码的 65 字节签名
+    #[validate(length(min = 130, max = 132, message = "Invalid signature length"))]
+    pub signature: String,
+}
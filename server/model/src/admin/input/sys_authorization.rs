@@ -27,6 +27,19 @@ pub struct AssignRouteDto {
     pub route_ids: Vec<i32>,
 }
 
+/// 整体替换角色的按钮权限码，传空数组即清空
+#[derive(Debug, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignPermissionCodeDto {
+    #[validate(length(min = 1, message = "domain cannot be empty"))]
+    pub domain: String,
+
+    #[validate(length(min = 1, message = "Role ID cannot be empty"))]
+    pub role_id: String,
+
+    pub permission_code_ids: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct AssignUserDto {
@@ -36,3 +49,20 @@ pub struct AssignUserDto {
     #[validate(length(min = 1, message = "Users array cannot be empty"))]
     pub user_ids: Vec<String>,
 }
+
+/// 解释某个用户能否访问指定接口
+#[derive(Debug, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainPermissionDto {
+    #[validate(length(min = 1, message = "User ID cannot be empty"))]
+    pub user_id: String,
+
+    #[validate(length(min = 1, message = "domain cannot be empty"))]
+    pub domain: String,
+
+    #[validate(length(min = 1, message = "method cannot be empty"))]
+    pub method: String,
+
+    #[validate(length(min = 1, message = "path cannot be empty"))]
+    pub path: String,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use server_core::web::page::PageRequest;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRunPageRequest {
+    #[serde(flatten)]
+    pub page_details: PageRequest,
+    pub job_name: Option<String>,
+    /// 按执行状态过滤：running、succeeded、failed
+    pub status: Option<String>,
+}
@@ -0,0 +1,39 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// 日志导出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogExportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+impl LogExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            LogExportFormat::Csv => "text/csv; charset=utf-8",
+            LogExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            LogExportFormat::Csv => "csv",
+            LogExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+/// 登录日志和操作日志的导出条件，按创建时间升序输出
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogExportRequest {
+    #[serde(default)]
+    pub format: LogExportFormat,
+    pub keywords: Option<String>,
+    /// 创建时间下限（包含）
+    pub start_time: Option<NaiveDateTime>,
+    /// 创建时间上限（不包含）
+    pub end_time: Option<NaiveDateTime>,
+}
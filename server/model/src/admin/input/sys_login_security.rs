@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 域级登录安全策略，未填写的字段沿用全局配置
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertLoginSecurityPolicyInput {
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Domain must be between 1 and 50 characters"
+    ))]
+    pub domain: String,
+    #[validate(range(min = 1, max = 100, message = "Max attempts must be between 1 and 100"))]
+    pub max_attempts: Option<i32>,
+    #[validate(range(min = 1, message = "Attempt window must be positive"))]
+    pub attempt_window: Option<i64>,
+    #[validate(range(min = 1, message = "Lockout duration must be positive"))]
+    pub lockout_duration: Option<i64>,
+    #[validate(range(
+        min = 1,
+        max = 10,
+        message = "Backoff multiplier must be between 1 and 10"
+    ))]
+    pub lockout_backoff_multiplier: Option<i32>,
+    #[validate(range(min = 1, message = "Max lockout duration must be positive"))]
+    pub max_lockout_duration: Option<i64>,
+    pub ip_allow_list: Option<Vec<String>>,
+    pub ip_deny_list: Option<Vec<String>>,
+    #[validate(nested)]
+    pub login_windows: Option<Vec<LoginWindowInput>>,
+}
+
+/// 登录时间窗口，字段与全局配置中的 `login_windows` 一致
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct LoginWindowInput {
+    #[validate(length(min = 1, message = "Timezone cannot be empty"))]
+    pub timezone: String,
+    #[validate(length(equal = 5, message = "Start time must be in HH:MM format"))]
+    pub start: String,
+    #[validate(length(equal = 5, message = "End time must be in HH:MM format"))]
+    pub end: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LockedAccountRequest {
+    pub domain: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct UnlockAccountInput {
+    #[validate(length(min = 1, message = "Domain cannot be empty"))]
+    pub domain: String,
+    #[validate(length(min = 1, message = "Username cannot be empty"))]
+    pub username: String,
+}
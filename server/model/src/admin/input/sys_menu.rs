@@ -69,3 +69,33 @@ pub struct UpdateMenuInput {
     #[serde(flatten)]
     pub menu: MenuInput,
 }
+
+/// 菜单下的按钮权限码
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionCodeInput {
+    pub menu_id: i32,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Permission code must be between 1 and 100 characters"
+    ))]
+    pub code: String,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Permission code name must be between 1 and 100 characters"
+    ))]
+    pub name: String,
+    /// 关联的接口，分配按钮时同时授予该接口的访问策略
+    pub endpoint_id: Option<String>,
+}
+
+pub type CreatePermissionCodeInput = PermissionCodeInput;
+
+#[derive(Deserialize, Validate)]
+pub struct UpdatePermissionCodeInput {
+    pub id: String,
+    #[serde(flatten)]
+    pub permission_code: PermissionCodeInput,
+}
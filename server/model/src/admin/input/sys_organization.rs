@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use server_core::web::page::PageRequest;
+use validator::Validate;
+
+use crate::admin::entities::sea_orm_active_enums::Status;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OrganizationPageRequest {
@@ -7,3 +10,33 @@ pub struct OrganizationPageRequest {
     pub page_details: PageRequest,
     pub keywords: Option<String>,
 }
+
+#[derive(Deserialize, Validate)]
+pub struct OrganizationInput {
+    /// 上级组织 ID，顶级组织为 "0"
+    pub pid: String,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Code must be between 1 and 50 characters"
+    ))]
+    pub code: String,
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Name must be between 1 and 50 characters"
+    ))]
+    pub name: String,
+    pub status: Status,
+    #[validate(length(max = 200, message = "Description must not exceed 200 characters"))]
+    pub description: Option<String>,
+}
+
+pub type CreateOrganizationInput = OrganizationInput;
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateOrganizationInput {
+    pub id: String,
+    #[serde(flatten)]
+    pub organization: OrganizationInput,
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use server_core::web::page::PageRequest;
 use validator::Validate;
 
-use crate::admin::entities::sea_orm_active_enums::Status;
+use crate::admin::entities::sea_orm_active_enums::{DataScope, Status};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RolePageRequest {
@@ -39,3 +39,13 @@ pub struct UpdateRoleInput {
     #[serde(flatten)]
     pub role: RoleInput,
 }
+
+/// 设置角色的数据权限，`orgIds` 仅在自定义范围下生效
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRoleDataScopeInput {
+    pub id: String,
+    pub data_scope: DataScope,
+    #[serde(default)]
+    pub org_ids: Vec<String>,
+}
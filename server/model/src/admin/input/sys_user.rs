@@ -49,3 +49,11 @@ pub struct UpdateUserInput {
     #[serde(flatten)]
     pub user: UserInput,
 }
+
+/// 设置用户所属组织，传入空列表表示移出全部组织
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignUserOrganizationsInput {
+    pub user_id: String,
+    pub org_ids: Vec<String>,
+}
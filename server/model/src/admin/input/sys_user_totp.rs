@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use validator::Validate;
+
+/// 动态码或恢复码
+#[derive(Deserialize, Validate)]
+pub struct TotpCodeInput {
+    #[validate(length(
+        min = 6,
+        max = 32,
+        message = "Code must be between 6 and 32 characters"
+    ))]
+    pub code: String,
+}
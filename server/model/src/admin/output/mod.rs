@@ -1,11 +1,24 @@
-pub use sys_authentication::{AuthOutput, UserInfoOutput, UserRoute};
+pub use sys_authentication::{
+    AuthOutput, ExternalAuthorizeOutput, LoginOutput, SiweNonceOutput, TwoFactorChallengeOutput,
+    UserInfoOutput, UserRoute,
+};
+pub use sys_authorization::PermissionExplanationOutput;
 pub use sys_domain::DomainOutput;
 pub use sys_endpoint::EndpointTree;
+pub use sys_login_security::LockedAccountOutput;
 pub use sys_menu::{MenuRoute, MenuTree, RouteMeta};
+pub use sys_organization::OrganizationTree;
+pub use sys_role::RoleDataScopeOutput;
 pub use sys_user::{UserWithDomainAndOrgOutput, UserWithoutPassword};
+pub use sys_user_totp::{RecoveryCodesOutput, TotpSetupOutput, TotpStatusOutput};
 
 mod sys_authentication;
+mod sys_authorization;
 mod sys_domain;
 mod sys_endpoint;
+mod sys_login_security;
 mod sys_menu;
+mod sys_organization;
+mod sys_role;
 mod sys_user;
+mod sys_user_totp;
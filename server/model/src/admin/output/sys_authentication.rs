@@ -10,6 +10,43 @@ pub struct AuthOutput {
     pub refresh_token: String,
 }
 
+/// 密码登录的结果
+///
+/// 未启用双因素认证时直接返回令牌；启用后返回挑战令牌，需要再提交动态码换取令牌。
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum LoginOutput {
+    Authenticated(AuthOutput),
+    TwoFactorRequired(TwoFactorChallengeOutput),
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorChallengeOutput {
+    pub two_factor_required: bool,
+    pub challenge_token: String,
+    /// 挑战令牌有效期（秒）
+    pub expires_in: i64,
+}
+
+/// 外部登录的授权地址，前端跳转到该地址完成登录
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalAuthorizeOutput {
+    pub authorize_url: String,
+    pub state: String,
+}
+
+/// 钱包登录的 nonce 以及构造 EIP-4361 消息所需的站点信息
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiweNonceOutput {
+    pub nonce: String,
+    pub domain: String,
+    pub uri: String,
+    pub chain_ids: Vec<u64>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct UserInfoOutput {
     #[serde(rename = "userId")]
@@ -17,6 +54,8 @@ pub struct UserInfoOutput {
     #[serde(rename = "userName")]
     pub user_name: String,
     pub roles: Vec<String>,
+    /// 按钮权限码，包含从上级角色继承的
+    pub buttons: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
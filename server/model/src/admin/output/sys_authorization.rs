@@ -0,0 +1,16 @@
+use serde::Serialize;
+
+/// 授权决策的解释
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionExplanationOutput {
+    pub allowed: bool,
+    /// 参与判定的用户角色
+    pub roles: Vec<String>,
+    /// 放行请求的角色，拒绝时为空
+    pub granted_by: Option<String>,
+    /// 命中的策略，拒绝时为空
+    pub matched_policy: Option<Vec<String>>,
+    /// 用户角色在该域下拥有的全部策略，用于排查拒绝原因
+    pub role_policies: Vec<Vec<String>>,
+}
@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedAccountOutput {
+    pub domain: String,
+    pub username: String,
+    /// 当前统计窗口内的失败次数
+    pub failed_attempts: u32,
+    /// 近期被锁定的次数，用于计算递增的锁定时长
+    pub lockout_count: u32,
+    /// 剩余锁定时间（秒）
+    pub remaining_seconds: i64,
+}
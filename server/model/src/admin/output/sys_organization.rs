@@ -0,0 +1,42 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::admin::entities::{
+    sea_orm_active_enums::Status, sys_organization::Model as SysOrganizationModel,
+};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct OrganizationTree {
+    pub id: String,
+    pub pid: String,
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: Status,
+    pub domain: String,
+    pub created_at: NaiveDateTime,
+    pub created_by: String,
+    pub updated_at: Option<NaiveDateTime>,
+    pub updated_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<OrganizationTree>>,
+}
+
+impl From<SysOrganizationModel> for OrganizationTree {
+    fn from(model: SysOrganizationModel) -> Self {
+        Self {
+            id: model.id,
+            pid: model.pid,
+            code: model.code,
+            name: model.name,
+            description: model.description,
+            status: model.status,
+            domain: model.domain,
+            created_at: model.created_at,
+            created_by: model.created_by,
+            updated_at: model.updated_at,
+            updated_by: model.updated_by,
+            children: None,
+        }
+    }
+}
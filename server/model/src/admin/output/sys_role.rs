@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+use crate::admin::entities::sea_orm_active_enums::DataScope;
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleDataScopeOutput {
+    pub data_scope: DataScope,
+    pub org_ids: Vec<String>,
+}
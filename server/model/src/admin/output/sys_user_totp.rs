@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+/// 开始绑定身份验证器时返回的密钥，确认前不会生效
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpSetupOutput {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpStatusOutput {
+    pub enabled: bool,
+    pub remaining_recovery_codes: u64,
+}
+
+/// 恢复码明文只在生成时返回一次
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryCodesOutput {
+    pub recovery_codes: Vec<String>,
+}
@@ -0,0 +1,46 @@
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{get, post, put},
+    Router,
+};
+use server_api::admin::SysAccountApi;
+use server_config::AccountConfig;
+use server_global::global;
+
+/// multipart 边界和字段头预留的空间
+const MULTIPART_OVERHEAD: usize = 64 * 1024;
+
+pub struct SysAccountRouter;
+
+impl SysAccountRouter {
+    /// 当前登录用户维护自己的资料，只需登录，不做权限校验
+    pub async fn init_account_router() -> Router {
+        let avatar_max_size = global::get_config::<AccountConfig>()
+            .await
+            .map(|config| config.avatar_max_size)
+            .unwrap_or_else(|| AccountConfig::default().avatar_max_size);
+
+        let router = Router::new()
+            .route(
+                "/profile",
+                get(SysAccountApi::get_profile).put(SysAccountApi::update_profile),
+            )
+            .route("/password", put(SysAccountApi::change_password))
+            .route(
+                "/avatar",
+                post(SysAccountApi::upload_avatar)
+                    .layer(DefaultBodyLimit::max(avatar_max_size + MULTIPART_OVERHEAD)),
+            );
+
+        Router::new().nest("/auth/account", router)
+    }
+
+    /// 找回密码，无需登录
+    pub async fn init_password_reset_router() -> Router {
+        let router = Router::new()
+            .route("/forgot", post(SysAccountApi::forgot_password))
+            .route("/reset", post(SysAccountApi::reset_password));
+
+        Router::new().nest("/auth/password", router)
+    }
+}
@@ -10,14 +10,48 @@ pub struct SysAuthenticationRouter;
 
 impl SysAuthenticationRouter {
     pub async fn init_authentication_router() -> Router {
-        let router = Router::new().route("/login", post(SysAuthenticationApi::login_handler));
+        let router = Router::new()
+            .route("/login", post(SysAuthenticationApi::login_handler))
+            .route(
+                "/twoFactorLogin",
+                post(SysAuthenticationApi::two_factor_login_handler),
+            )
+            .route(
+                "/refreshToken",
+                post(SysAuthenticationApi::refresh_token_handler),
+            )
+            .route(
+                "/external/{provider}/authorize",
+                get(SysAuthenticationApi::external_authorize_handler),
+            )
+            .route(
+                "/external/{provider}/callback",
+                post(SysAuthenticationApi::external_login_handler),
+            )
+            .route("/siwe/nonce", get(SysAuthenticationApi::siwe_nonce_handler))
+            .route(
+                "/siwe/login",
+                post(SysAuthenticationApi::siwe_login_handler),
+            );
         Router::new().nest("/auth", router)
     }
 
+    pub async fn init_well_known_router() -> Router {
+        Router::new().route(
+            "/.well-known/jwks.json",
+            get(SysAuthenticationApi::jwks_handler),
+        )
+    }
+
     pub async fn init_protected_router() -> Router {
         let router = Router::new()
+            .route("/logout", post(SysAuthenticationApi::logout_handler))
             .route("/getUserInfo", get(SysAuthenticationApi::get_user_info))
-            .route("/getUserRoutes", get(SysAuthenticationApi::get_user_routes));
+            .route("/getUserRoutes", get(SysAuthenticationApi::get_user_routes))
+            .route(
+                "/siwe/link",
+                post(SysAuthenticationApi::siwe_link_wallet_handler),
+            );
 
         Router::new().nest("/auth", router)
     }
@@ -39,6 +73,30 @@ impl SysAuthenticationRouter {
                 service_name,
                 "分配路由",
             ),
+            RouteInfo::new(
+                &format!("{}/assign-permission-codes", base_path),
+                Method::POST,
+                service_name,
+                "分配按钮权限码",
+            ),
+            RouteInfo::new(
+                &format!("{}/explain", base_path),
+                Method::POST,
+                service_name,
+                "解释授权决策",
+            ),
+            RouteInfo::new(
+                &format!("{}/kick-session/:id", base_path),
+                Method::POST,
+                service_name,
+                "踢出会话",
+            ),
+            RouteInfo::new(
+                &format!("{}/revoke-user-sessions/:userId", base_path),
+                Method::POST,
+                service_name,
+                "撤销用户所有会话",
+            ),
         ];
 
         for route in routes {
@@ -51,7 +109,20 @@ impl SysAuthenticationRouter {
                 "/assign-permission",
                 post(SysAuthenticationApi::assign_permission),
             )
-            .route("/assign-routes", post(SysAuthenticationApi::assign_routes));
+            .route("/assign-routes", post(SysAuthenticationApi::assign_routes))
+            .route(
+                "/assign-permission-codes",
+                post(SysAuthenticationApi::assign_permission_codes),
+            )
+            .route("/explain", post(SysAuthenticationApi::explain_permission))
+            .route(
+                "/kick-session/{id}",
+                post(SysAuthenticationApi::kick_session),
+            )
+            .route(
+                "/revoke-user-sessions/{userId}",
+                post(SysAuthenticationApi::revoke_user_sessions),
+            );
 
         Router::new().nest(base_path, authorization_router)
     }
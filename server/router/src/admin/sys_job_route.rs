@@ -0,0 +1,57 @@
+use axum::{
+    http::Method,
+    routing::{get, post, put},
+    Router,
+};
+use server_api::admin::SysJobApi;
+use server_global::global::{add_route, RouteInfo};
+
+pub struct SysJobRouter;
+
+impl SysJobRouter {
+    pub async fn init_job_router() -> Router {
+        let base_path = "/job";
+        let service_name = "SysJobApi";
+
+        let routes = vec![
+            RouteInfo::new(base_path, Method::GET, service_name, "获取定时任务列表"),
+            RouteInfo::new(
+                &format!("{}/runs", base_path),
+                Method::GET,
+                service_name,
+                "获取定时任务执行记录",
+            ),
+            RouteInfo::new(
+                &format!("{}/:name/pause", base_path),
+                Method::PUT,
+                service_name,
+                "暂停定时任务",
+            ),
+            RouteInfo::new(
+                &format!("{}/:name/resume", base_path),
+                Method::PUT,
+                service_name,
+                "恢复定时任务",
+            ),
+            RouteInfo::new(
+                &format!("{}/:name/trigger", base_path),
+                Method::POST,
+                service_name,
+                "手动执行定时任务",
+            ),
+        ];
+
+        for route in routes {
+            add_route(route).await;
+        }
+
+        let router = Router::new()
+            .route("/", get(SysJobApi::list_jobs))
+            .route("/runs", get(SysJobApi::get_paginated_job_runs))
+            .route("/{name}/pause", put(SysJobApi::pause_job))
+            .route("/{name}/resume", put(SysJobApi::resume_job))
+            .route("/{name}/trigger", post(SysJobApi::trigger_job));
+
+        Router::new().nest(base_path, router)
+    }
+}
@@ -9,18 +9,23 @@ impl SysLoginLogRouter {
         let base_path = "/login-log";
         let service_name = "SysLoginLogApi";
 
-        let routes = vec![RouteInfo::new(
-            base_path,
-            Method::GET,
-            service_name,
-            "获取登录日志列表",
-        )];
+        let routes = vec![
+            RouteInfo::new(base_path, Method::GET, service_name, "获取登录日志列表"),
+            RouteInfo::new(
+                &format!("{}/export", base_path),
+                Method::GET,
+                service_name,
+                "导出登录日志",
+            ),
+        ];
 
         for route in routes {
             add_route(route).await;
         }
 
-        let router = Router::new().route("/", get(SysLoginLogApi::get_paginated_login_logs));
+        let router = Router::new()
+            .route("/", get(SysLoginLogApi::get_paginated_login_logs))
+            .route("/export", get(SysLoginLogApi::export_login_logs));
 
         Router::new().nest(base_path, router)
     }
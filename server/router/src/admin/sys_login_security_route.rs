@@ -0,0 +1,68 @@
+use axum::{
+    http::Method,
+    routing::{delete, get, post, put},
+    Router,
+};
+use server_api::admin::SysLoginSecurityApi;
+use server_global::global::{add_route, RouteInfo};
+
+pub struct SysLoginSecurityRouter;
+
+impl SysLoginSecurityRouter {
+    pub async fn init_login_security_router() -> Router {
+        let base_path = "/login-security";
+        let service_name = "SysLoginSecurityApi";
+
+        let routes = vec![
+            RouteInfo::new(
+                &format!("{}/policy/:domain", base_path),
+                Method::GET,
+                service_name,
+                "获取域登录安全策略",
+            ),
+            RouteInfo::new(
+                &format!("{}/policy", base_path),
+                Method::PUT,
+                service_name,
+                "保存域登录安全策略",
+            ),
+            RouteInfo::new(
+                &format!("{}/policy/:domain", base_path),
+                Method::DELETE,
+                service_name,
+                "删除域登录安全策略",
+            ),
+            RouteInfo::new(
+                &format!("{}/locked-accounts", base_path),
+                Method::GET,
+                service_name,
+                "获取锁定账号列表",
+            ),
+            RouteInfo::new(
+                &format!("{}/unlock", base_path),
+                Method::POST,
+                service_name,
+                "解锁账号",
+            ),
+        ];
+
+        for route in routes {
+            add_route(route).await;
+        }
+
+        let router = Router::new()
+            .route("/policy/{domain}", get(SysLoginSecurityApi::get_policy))
+            .route("/policy", put(SysLoginSecurityApi::upsert_policy))
+            .route(
+                "/policy/{domain}",
+                delete(SysLoginSecurityApi::delete_policy),
+            )
+            .route(
+                "/locked-accounts",
+                get(SysLoginSecurityApi::list_locked_accounts),
+            )
+            .route("/unlock", post(SysLoginSecurityApi::unlock_account));
+
+        Router::new().nest(base_path, router)
+    }
+}
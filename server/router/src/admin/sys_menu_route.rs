@@ -13,7 +13,7 @@ impl SysMenuRouter {
     pub async fn init_menu_router() -> Router {
         let router = Router::new().route(
             "/getConstantRoutes",
-            get(SysMenuApi::get_constant_routes).layer(OperationLogLayer::new(true)),
+            get(SysMenuApi::get_constant_routes).layer(OperationLogLayer::from_config(true).await),
         );
         Router::new().nest("/route", router)
     }
@@ -50,6 +50,36 @@ impl SysMenuRouter {
                 service_name,
                 "获取角色菜单",
             ),
+            RouteInfo::new(
+                &format!("{}/:id/permission-codes", base_path),
+                Method::GET,
+                service_name,
+                "获取菜单按钮权限码",
+            ),
+            RouteInfo::new(
+                &format!("{}/permission-code", base_path),
+                Method::POST,
+                service_name,
+                "创建按钮权限码",
+            ),
+            RouteInfo::new(
+                &format!("{}/permission-code", base_path),
+                Method::PUT,
+                service_name,
+                "更新按钮权限码",
+            ),
+            RouteInfo::new(
+                &format!("{}/permission-code/:id", base_path),
+                Method::DELETE,
+                service_name,
+                "删除按钮权限码",
+            ),
+            RouteInfo::new(
+                &format!("{}/auth-permission-code/:roleId", base_path),
+                Method::GET,
+                service_name,
+                "获取角色按钮权限码",
+            ),
         ];
 
         for route in routes {
@@ -63,7 +93,21 @@ impl SysMenuRouter {
             .route("/{id}", get(SysMenuApi::get_menu))
             .route("/", put(SysMenuApi::update_menu))
             .route("/{id}", delete(SysMenuApi::delete_menu))
-            .route("/auth-route/{roleId}", get(SysMenuApi::get_auth_routes));
+            .route("/auth-route/{roleId}", get(SysMenuApi::get_auth_routes))
+            .route(
+                "/{id}/permission-codes",
+                get(SysMenuApi::get_permission_codes),
+            )
+            .route("/permission-code", post(SysMenuApi::create_permission_code))
+            .route("/permission-code", put(SysMenuApi::update_permission_code))
+            .route(
+                "/permission-code/{id}",
+                delete(SysMenuApi::delete_permission_code),
+            )
+            .route(
+                "/auth-permission-code/{roleId}",
+                get(SysMenuApi::get_auth_permission_codes),
+            );
 
         Router::new().nest(base_path, router)
     }
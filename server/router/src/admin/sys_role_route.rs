@@ -29,6 +29,18 @@ impl SysRoleRouter {
                 service_name,
                 "删除角色",
             ),
+            RouteInfo::new(
+                &format!("{}/:id/data-scope", base_path),
+                Method::GET,
+                service_name,
+                "获取角色数据权限",
+            ),
+            RouteInfo::new(
+                &format!("{}/data-scope", base_path),
+                Method::PUT,
+                service_name,
+                "设置角色数据权限",
+            ),
         ];
 
         for route in routes {
@@ -40,7 +52,9 @@ impl SysRoleRouter {
             .route("/", post(SysRoleApi::create_role))
             .route("/{id}", get(SysRoleApi::get_role))
             .route("/", put(SysRoleApi::update_role))
-            .route("/{id}", delete(SysRoleApi::delete_role));
+            .route("/{id}", delete(SysRoleApi::delete_role))
+            .route("/{id}/data-scope", get(SysRoleApi::get_role_data_scope))
+            .route("/data-scope", put(SysRoleApi::update_role_data_scope));
 
         Router::new().nest(base_path, router)
     }
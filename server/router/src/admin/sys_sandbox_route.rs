@@ -1,11 +1,22 @@
-use axum::{routing::get, Router};
+use axum::{http::Method, routing::get, Router};
 use server_api::admin::SysSandboxApi;
+use server_global::global::{add_route, RouteInfo};
 
 pub struct SysSandboxRouter;
 
 impl SysSandboxRouter {
     const BASE_PATH: &str = "/sandbox";
+    const SERVICE_NAME: &str = "SysSandboxApi";
+
     pub async fn init_simple_sandbox_router() -> Router {
+        add_route(RouteInfo::new(
+            &format!("{}/simple-api-key", Self::BASE_PATH),
+            Method::GET,
+            Self::SERVICE_NAME,
+            "简单 API 密钥测试",
+        ))
+        .await;
+
         let router =
             Router::new().route("/simple-api-key", get(SysSandboxApi::test_simple_api_key));
 
@@ -13,6 +24,14 @@ impl SysSandboxRouter {
     }
 
     pub async fn init_complex_sandbox_router() -> Router {
+        add_route(RouteInfo::new(
+            &format!("{}/complex-api-key", Self::BASE_PATH),
+            Method::GET,
+            Self::SERVICE_NAME,
+            "签名 API 密钥测试",
+        ))
+        .await;
+
         let router =
             Router::new().route("/complex-api-key", get(SysSandboxApi::test_complex_api_key));
         Router::new().nest(Self::BASE_PATH, router)
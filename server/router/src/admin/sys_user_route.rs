@@ -47,6 +47,18 @@ impl SysUserRouter {
                 service_name,
                 "删除用户策略",
             ),
+            RouteInfo::new(
+                &format!("{}/:id/organizations", base_path),
+                Method::GET,
+                service_name,
+                "获取用户所属组织",
+            ),
+            RouteInfo::new(
+                &format!("{}/organizations", base_path),
+                Method::PUT,
+                service_name,
+                "设置用户所属组织",
+            ),
         ];
 
         for route in routes {
@@ -61,7 +73,12 @@ impl SysUserRouter {
             .route("/", put(SysUserApi::update_user))
             .route("/{id}", delete(SysUserApi::delete_user))
             .route("/add_policies", get(SysUserApi::add_policies))
-            .route("/remove_policies", get(SysUserApi::remove_policies));
+            .route("/remove_policies", get(SysUserApi::remove_policies))
+            .route(
+                "/{id}/organizations",
+                get(SysUserApi::get_user_organizations),
+            )
+            .route("/organizations", put(SysUserApi::assign_user_organizations));
 
         Router::new().nest(base_path, router)
     }
@@ -0,0 +1,24 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use server_api::admin::SysUserTotpApi;
+
+pub struct SysUserTotpRouter;
+
+impl SysUserTotpRouter {
+    /// 当前登录用户管理自己的双因素认证，只需登录，不做权限校验
+    pub async fn init_totp_router() -> Router {
+        let router = Router::new()
+            .route("/status", get(SysUserTotpApi::get_status))
+            .route("/setup", post(SysUserTotpApi::setup))
+            .route("/confirm", post(SysUserTotpApi::confirm))
+            .route("/disable", post(SysUserTotpApi::disable))
+            .route(
+                "/recoveryCodes",
+                post(SysUserTotpApi::regenerate_recovery_codes),
+            );
+
+        Router::new().nest("/auth/totp", router)
+    }
+}
@@ -361,15 +361,15 @@ impl Web3Router {
 
         // Add all routes
         for route in wallet_routes.into_iter()
-            .chain(contract_routes.into_iter())
-            .chain(transaction_routes.into_iter())
-            .chain(market_data_routes.into_iter())
-            .chain(key_routes.into_iter()) 
-            .chain(block_scanner_routes.into_iter())
-            .chain(nft_routes.into_iter())
-            .chain(bridge_routes.into_iter())
-            .chain(approval_routes.into_iter())
-            .chain(oracle_routes.into_iter())
+            .chain(contract_routes)
+            .chain(transaction_routes)
+            .chain(market_data_routes)
+            .chain(key_routes) 
+            .chain(block_scanner_routes)
+            .chain(nft_routes)
+            .chain(bridge_routes)
+            .chain(approval_routes)
+            .chain(oracle_routes)
         {
             add_route(route).await;
         }
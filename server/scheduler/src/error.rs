@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SchedulerError {
+    #[error("Invalid cron expression '{0}': {1}")]
+    InvalidCron(String, String),
+    #[error("Job '{0}' is already registered")]
+    AlreadyRegistered(String),
+    #[error("Job '{0}' not found")]
+    JobNotFound(String),
+    #[error("Job '{0}' is already running")]
+    JobRunning(String),
+    #[error("Job lock error: {0}")]
+    Lock(String),
+    #[error("Job store error: {0}")]
+    Store(String),
+}
@@ -0,0 +1,155 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use chrono::{Local, NaiveDateTime};
+use cron::Schedule;
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+use crate::SchedulerError;
+
+/// 任务处理函数，返回的错误信息写入执行记录
+pub type JobHandler = Arc<dyn Fn() -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+/// 任务定义
+#[derive(Debug, Clone)]
+pub struct JobSpec {
+    /// 任务名，全局唯一，同时作为分布式锁的键
+    pub name: String,
+    /// cron 表达式，支持 5 段（分 时 日 月 周）和 6、7 段（秒 分 时 日 月 周 [年]）
+    pub cron: String,
+    pub description: String,
+    /// 分布式锁的过期时间，执行期间每过一半时间续期一次，实例崩溃后锁在此时间后释放
+    pub lock_ttl: Duration,
+}
+
+impl JobSpec {
+    pub fn new(
+        name: impl Into<String>,
+        cron: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            cron: cron.into(),
+            description: description.into(),
+            lock_ttl: Duration::from_secs(300),
+        }
+    }
+
+    pub fn with_lock_ttl(mut self, lock_ttl: Duration) -> Self {
+        self.lock_ttl = lock_ttl;
+        self
+    }
+}
+
+/// 任务的触发方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobTrigger {
+    /// 按 cron 表达式定时触发
+    Schedule,
+    /// 通过管理接口手动触发
+    Manual,
+}
+
+impl JobTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobTrigger::Schedule => "schedule",
+            JobTrigger::Manual => "manual",
+        }
+    }
+}
+
+/// 执行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+        }
+    }
+}
+
+/// 一次任务执行的记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRun {
+    pub id: String,
+    pub job_name: String,
+    pub trigger: JobTrigger,
+    /// 执行任务的实例
+    pub instance: String,
+    pub status: JobStatus,
+    pub error: Option<String>,
+    pub started_at: NaiveDateTime,
+    pub ended_at: Option<NaiveDateTime>,
+}
+
+/// 已注册任务的当前状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobInfo {
+    pub name: String,
+    pub cron: String,
+    pub description: String,
+    pub paused: bool,
+    /// 本实例是否正在执行该任务
+    pub running: bool,
+    pub next_run_at: Option<NaiveDateTime>,
+}
+
+/// 解析 cron 表达式，5 段表达式补齐秒字段为 0
+pub fn parse_cron(expr: &str) -> Result<Schedule, SchedulerError> {
+    let expr = expr.trim();
+    let normalized = if expr.split_whitespace().count() == 5 {
+        format!("0 {}", expr)
+    } else {
+        expr.to_string()
+    };
+
+    Schedule::from_str(&normalized)
+        .map_err(|e| SchedulerError::InvalidCron(expr.to_string(), e.to_string()))
+}
+
+/// 当前时间之后的下一次触发时间
+pub(crate) fn next_run(schedule: &Schedule) -> Option<NaiveDateTime> {
+    schedule
+        .upcoming(Local)
+        .next()
+        .map(|time| time.naive_local())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Timelike};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_cron() {
+        let schedule = parse_cron("*/15 * * * *").unwrap();
+        let start = Local.with_ymd_and_hms(2026, 10, 17, 8, 1, 30).unwrap();
+        let next: Vec<_> = schedule.after(&start).take(2).collect();
+        assert_eq!((next[0].minute(), next[0].second()), (15, 0));
+        assert_eq!(next[1].minute(), 30);
+
+        let schedule = parse_cron("30 0 3 * * *").unwrap();
+        let next = schedule.after(&start).next().unwrap();
+        assert_eq!((next.hour(), next.minute(), next.second()), (3, 0, 30));
+
+        assert!(matches!(
+            parse_cron("every minute"),
+            Err(SchedulerError::InvalidCron(..))
+        ));
+    }
+}
@@ -0,0 +1,17 @@
+//! 定时任务调度
+//!
+//! 任务在初始化阶段通过 [`Scheduler::register`] 注册，按 cron 表达式触发。多实例部署时
+//! 通过主 Redis 上的 [`RedisJobLock`] 保证同一任务只在一个实例上执行，暂停状态和执行
+//! 记录由 [`JobStore`] 持久化。
+
+pub use error::SchedulerError;
+pub use job::{parse_cron, JobHandler, JobInfo, JobRun, JobSpec, JobStatus, JobTrigger};
+pub use lock::{JobLock, NoopJobLock, RedisJobLock};
+pub use scheduler::{Scheduler, GLOBAL_SCHEDULER};
+pub use store::{JobStore, MemoryJobStore};
+
+mod error;
+mod job;
+mod lock;
+mod scheduler;
+mod store;
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use redis::{Cmd, FromRedisValue, RedisResult};
+use server_global::global::RedisConnection;
+
+use crate::SchedulerError;
+
+/// 仅当锁仍由自己持有时删除
+const RELEASE_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+
+/// 仅当锁仍由自己持有时续期
+const RENEW_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("PEXPIRE", KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+/// 任务执行锁，保证多实例部署时同一任务同一时间只在一个实例上执行
+#[async_trait]
+pub trait JobLock: Send + Sync {
+    /// 以 `token` 的身份获取锁，锁已被持有时返回 false
+    async fn acquire(&self, job: &str, token: &str, ttl: Duration) -> Result<bool, SchedulerError>;
+
+    /// 延长自己持有的锁，锁已过期或被他人持有时返回 false
+    async fn renew(&self, job: &str, token: &str, ttl: Duration) -> Result<bool, SchedulerError>;
+
+    async fn release(&self, job: &str, token: &str) -> Result<(), SchedulerError>;
+}
+
+/// 不做任何互斥的锁，用于单实例部署
+pub struct NoopJobLock;
+
+#[async_trait]
+impl JobLock for NoopJobLock {
+    async fn acquire(
+        &self,
+        _job: &str,
+        _token: &str,
+        _ttl: Duration,
+    ) -> Result<bool, SchedulerError> {
+        Ok(true)
+    }
+
+    async fn renew(
+        &self,
+        _job: &str,
+        _token: &str,
+        _ttl: Duration,
+    ) -> Result<bool, SchedulerError> {
+        Ok(true)
+    }
+
+    async fn release(&self, _job: &str, _token: &str) -> Result<(), SchedulerError> {
+        Ok(())
+    }
+}
+
+/// 基于 Redis `SET NX PX` 的锁，键为 `{prefix}{任务名}`，值为本次执行的 ID
+pub struct RedisJobLock {
+    connection: RedisConnection,
+    key_prefix: String,
+}
+
+impl RedisJobLock {
+    pub fn new(connection: RedisConnection) -> Self {
+        Self {
+            connection,
+            key_prefix: "scheduler:lock:".to_string(),
+        }
+    }
+
+    fn key(&self, job: &str) -> String {
+        format!("{}{}", self.key_prefix, job)
+    }
+
+    async fn query<T: FromRedisValue>(&self, cmd: &Cmd) -> Result<T, SchedulerError> {
+        let result: RedisResult<T> = match &self.connection {
+            RedisConnection::Single(client) => {
+                match client.get_multiplexed_async_connection().await {
+                    Ok(mut conn) => cmd.query_async(&mut conn).await,
+                    Err(e) => Err(e),
+                }
+            },
+            RedisConnection::Cluster(client) => match client.get_async_connection().await {
+                Ok(mut conn) => cmd.query_async(&mut conn).await,
+                Err(e) => Err(e),
+            },
+        };
+        result.map_err(|e| SchedulerError::Lock(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl JobLock for RedisJobLock {
+    async fn acquire(&self, job: &str, token: &str, ttl: Duration) -> Result<bool, SchedulerError> {
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(self.key(job))
+            .arg(token)
+            .arg("NX")
+            .arg("PX")
+            .arg(millis(ttl));
+        let reply: Option<String> = self.query(&cmd).await?;
+        Ok(reply.is_some())
+    }
+
+    async fn renew(&self, job: &str, token: &str, ttl: Duration) -> Result<bool, SchedulerError> {
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(RENEW_SCRIPT)
+            .arg(1)
+            .arg(self.key(job))
+            .arg(token)
+            .arg(millis(ttl));
+        let renewed: i64 = self.query(&cmd).await?;
+        Ok(renewed == 1)
+    }
+
+    async fn release(&self, job: &str, token: &str) -> Result<(), SchedulerError> {
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(RELEASE_SCRIPT).arg(1).arg(self.key(job)).arg(token);
+        self.query::<i64>(&cmd).await?;
+        Ok(())
+    }
+}
+
+fn millis(ttl: Duration) -> u64 {
+    (ttl.as_millis() as u64).max(1)
+}
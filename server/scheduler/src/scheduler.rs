@@ -0,0 +1,495 @@
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt::Display,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+use chrono::Local;
+use cron::Schedule;
+use futures::FutureExt;
+use once_cell::sync::OnceCell;
+use server_global::{project_error, project_info};
+use ulid::Ulid;
+
+use crate::{
+    job::{next_run, parse_cron},
+    JobHandler, JobInfo, JobLock, JobRun, JobSpec, JobStatus, JobStore, JobTrigger, SchedulerError,
+};
+
+/// 全局调度器，在初始化阶段设置，供管理接口使用
+pub static GLOBAL_SCHEDULER: OnceCell<Scheduler> = OnceCell::new();
+
+struct JobEntry {
+    spec: JobSpec,
+    schedule: Schedule,
+    handler: JobHandler,
+    /// 最近一次从存储读取的暂停状态，存储不可用时使用
+    paused: AtomicBool,
+    /// 本实例是否正在执行
+    running: AtomicBool,
+}
+
+struct Inner {
+    jobs: RwLock<BTreeMap<String, Arc<JobEntry>>>,
+    store: Arc<dyn JobStore>,
+    lock: Arc<dyn JobLock>,
+    instance: String,
+    started: AtomicBool,
+}
+
+/// 定时任务调度器
+///
+/// 每个任务按 cron 表达式在独立的循环中触发。触发时先获取任务锁，锁被其他实例持有时
+/// 跳过本次执行；执行期间定期续期，结束后写入执行记录并释放锁。暂停状态保存在
+/// [`JobStore`] 中，每次触发前重新读取，因此在任一实例上暂停对所有实例生效。
+#[derive(Clone)]
+pub struct Scheduler {
+    inner: Arc<Inner>,
+}
+
+impl Scheduler {
+    pub fn new(store: Arc<dyn JobStore>, lock: Arc<dyn JobLock>) -> Self {
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+        Self {
+            inner: Arc::new(Inner {
+                jobs: RwLock::new(BTreeMap::new()),
+                store,
+                lock,
+                instance: format!("{}-{}", host, std::process::id()),
+                started: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// 当前实例的标识，写入执行记录
+    pub fn instance(&self) -> &str {
+        &self.inner.instance
+    }
+
+    /// 注册任务，调度器已启动时立即开始调度
+    pub async fn register<F, Fut, E>(&self, spec: JobSpec, handler: F) -> Result<(), SchedulerError>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        let schedule = parse_cron(&spec.cron)?;
+        if self.inner.jobs.read().unwrap().contains_key(&spec.name) {
+            return Err(SchedulerError::AlreadyRegistered(spec.name));
+        }
+        let paused = self.inner.store.register(&spec).await?;
+
+        let handler: JobHandler = Arc::new(move || {
+            let future = handler();
+            async move { future.await.map_err(|e| e.to_string()) }.boxed()
+        });
+        let entry = Arc::new(JobEntry {
+            spec,
+            schedule,
+            handler,
+            paused: AtomicBool::new(paused),
+            running: AtomicBool::new(false),
+        });
+
+        {
+            let mut jobs = self.inner.jobs.write().unwrap();
+            if jobs.contains_key(&entry.spec.name) {
+                return Err(SchedulerError::AlreadyRegistered(entry.spec.name.clone()));
+            }
+            jobs.insert(entry.spec.name.clone(), entry.clone());
+        }
+
+        project_info!(
+            "Job '{}' registered with cron '{}'{}",
+            entry.spec.name,
+            entry.spec.cron,
+            if paused { " (paused)" } else { "" }
+        );
+        if self.inner.started.load(Ordering::Acquire) {
+            self.spawn_loop(entry);
+        }
+        Ok(())
+    }
+
+    /// 启动所有已注册任务的调度循环，重复调用无效
+    pub fn start(&self) {
+        if self.inner.started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let entries: Vec<_> = self.inner.jobs.read().unwrap().values().cloned().collect();
+        for entry in entries {
+            self.spawn_loop(entry);
+        }
+    }
+
+    /// 按任务名排列的所有任务
+    pub async fn list(&self) -> Vec<JobInfo> {
+        let entries: Vec<_> = self.inner.jobs.read().unwrap().values().cloned().collect();
+
+        let mut jobs = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let paused = self.refresh_paused(&entry).await;
+            jobs.push(JobInfo {
+                name: entry.spec.name.clone(),
+                cron: entry.spec.cron.clone(),
+                description: entry.spec.description.clone(),
+                paused,
+                running: entry.running.load(Ordering::Acquire),
+                next_run_at: next_run(&entry.schedule),
+            });
+        }
+        jobs
+    }
+
+    /// 暂停定时触发，正在执行的任务不受影响
+    pub async fn pause(&self, name: &str) -> Result<(), SchedulerError> {
+        self.set_paused(name, true).await
+    }
+
+    pub async fn resume(&self, name: &str) -> Result<(), SchedulerError> {
+        self.set_paused(name, false).await
+    }
+
+    /// 立即执行一次任务，不受暂停状态影响
+    ///
+    /// 获取锁并写入开始记录后返回，任务在后台继续执行
+    pub async fn trigger(&self, name: &str) -> Result<JobRun, SchedulerError> {
+        let entry = self.entry(name)?;
+        let run = self.begin(&entry, JobTrigger::Manual).await?;
+
+        let scheduler = self.clone();
+        let started = run.clone();
+        tokio::spawn(async move { scheduler.finish(&entry, run).await });
+        Ok(started)
+    }
+
+    fn entry(&self, name: &str) -> Result<Arc<JobEntry>, SchedulerError> {
+        self.inner
+            .jobs
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| SchedulerError::JobNotFound(name.to_string()))
+    }
+
+    async fn set_paused(&self, name: &str, paused: bool) -> Result<(), SchedulerError> {
+        let entry = self.entry(name)?;
+        self.inner.store.set_paused(name, paused).await?;
+        entry.paused.store(paused, Ordering::Release);
+        project_info!(
+            "Job '{}' {}",
+            name,
+            if paused { "paused" } else { "resumed" }
+        );
+        Ok(())
+    }
+
+    async fn refresh_paused(&self, entry: &JobEntry) -> bool {
+        match self.inner.store.is_paused(&entry.spec.name).await {
+            Ok(paused) => {
+                entry.paused.store(paused, Ordering::Release);
+                paused
+            },
+            Err(e) => {
+                project_error!("Failed to load state of job '{}': {}", entry.spec.name, e);
+                entry.paused.load(Ordering::Acquire)
+            },
+        }
+    }
+
+    fn spawn_loop(&self, entry: Arc<JobEntry>) {
+        let scheduler = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let Some(next) = entry.schedule.upcoming(Local).next() else {
+                    project_info!("Job '{}' has no upcoming run, stopped", entry.spec.name);
+                    break;
+                };
+                let delay = (next - Local::now()).to_std().unwrap_or_default();
+                tokio::time::sleep(delay).await;
+
+                if scheduler.refresh_paused(&entry).await {
+                    continue;
+                }
+                match scheduler.begin(&entry, JobTrigger::Schedule).await {
+                    Ok(run) => scheduler.finish(&entry, run).await,
+                    // 上一次执行尚未结束，或其他实例正在执行
+                    Err(SchedulerError::JobRunning(_)) => {},
+                    Err(e) => project_error!("Failed to start job '{}': {}", entry.spec.name, e),
+                }
+            }
+        });
+    }
+
+    /// 获取任务锁并写入开始记录
+    async fn begin(&self, entry: &JobEntry, trigger: JobTrigger) -> Result<JobRun, SchedulerError> {
+        let name = &entry.spec.name;
+        if entry.running.swap(true, Ordering::AcqRel) {
+            return Err(SchedulerError::JobRunning(name.clone()));
+        }
+
+        let run = JobRun {
+            id: Ulid::new().to_string(),
+            job_name: name.clone(),
+            trigger,
+            instance: self.inner.instance.clone(),
+            status: JobStatus::Running,
+            error: None,
+            started_at: Local::now().naive_local(),
+            ended_at: None,
+        };
+
+        match self
+            .inner
+            .lock
+            .acquire(name, &run.id, entry.spec.lock_ttl)
+            .await
+        {
+            Ok(true) => {},
+            Ok(false) => {
+                entry.running.store(false, Ordering::Release);
+                return Err(SchedulerError::JobRunning(name.clone()));
+            },
+            Err(e) => {
+                entry.running.store(false, Ordering::Release);
+                return Err(e);
+            },
+        }
+
+        if let Err(e) = self.inner.store.record_start(&run).await {
+            project_error!("Failed to record start of job '{}': {}", name, e);
+        }
+        Ok(run)
+    }
+
+    /// 执行任务并在结束后写入结果、释放锁
+    async fn finish(&self, entry: &JobEntry, mut run: JobRun) {
+        let name = &entry.spec.name;
+        let ttl = entry.spec.lock_ttl;
+
+        let mut handle = tokio::spawn((entry.handler)());
+        let mut renewal = tokio::time::interval((ttl / 2).max(Duration::from_millis(1)));
+        renewal.tick().await;
+
+        let result = loop {
+            tokio::select! {
+                joined = &mut handle => {
+                    break joined.unwrap_or_else(|e| match e.try_into_panic() {
+                        Ok(payload) => Err(format!("Job panicked: {}", panic_message(payload))),
+                        Err(e) => Err(e.to_string()),
+                    });
+                },
+                _ = renewal.tick() => {
+                    match self.inner.lock.renew(name, &run.id, ttl).await {
+                        Ok(true) => {},
+                        Ok(false) => project_error!("Lock of job '{}' was lost while running", name),
+                        Err(e) => project_error!("Failed to renew lock of job '{}': {}", name, e),
+                    }
+                },
+            }
+        };
+
+        run.ended_at = Some(Local::now().naive_local());
+        match result {
+            Ok(()) => run.status = JobStatus::Succeeded,
+            Err(e) => {
+                project_error!("Job '{}' failed: {}", name, e);
+                run.status = JobStatus::Failed;
+                run.error = Some(e);
+            },
+        }
+
+        if let Err(e) = self.inner.store.record_finish(&run).await {
+            project_error!("Failed to record result of job '{}': {}", name, e);
+        }
+        if let Err(e) = self.inner.lock.release(name, &run.id).await {
+            project_error!("Failed to release lock of job '{}': {}", name, e);
+        }
+        entry.running.store(false, Ordering::Release);
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::{MemoryJobStore, NoopJobLock};
+
+    /// 一年触发一次，测试中只通过手动触发执行
+    const YEARLY: &str = "0 0 1 1 *";
+
+    fn scheduler() -> (Scheduler, Arc<MemoryJobStore>) {
+        let store = Arc::new(MemoryJobStore::new());
+        (Scheduler::new(store.clone(), Arc::new(NoopJobLock)), store)
+    }
+
+    async fn wait_for_runs(store: &MemoryJobStore, finished: usize) -> Vec<JobRun> {
+        for _ in 0..300 {
+            let runs = store.runs();
+            if runs
+                .iter()
+                .filter(|r| r.status != JobStatus::Running)
+                .count()
+                >= finished
+            {
+                return runs;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job did not finish in time");
+    }
+
+    #[tokio::test]
+    async fn test_pause_resume_and_trigger() {
+        let (scheduler, store) = scheduler();
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        scheduler
+            .register(JobSpec::new("count", YEARLY, "计数"), move || {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok::<_, String>(())
+                }
+            })
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            scheduler
+                .register(JobSpec::new("count", YEARLY, ""), || async {
+                    Ok::<_, String>(())
+                })
+                .await,
+            Err(SchedulerError::AlreadyRegistered(_))
+        ));
+        assert!(matches!(
+            scheduler.pause("missing").await,
+            Err(SchedulerError::JobNotFound(_))
+        ));
+
+        scheduler.pause("count").await.unwrap();
+        assert!(scheduler.list().await[0].paused);
+
+        // 手动触发不受暂停影响
+        let run = scheduler.trigger("count").await.unwrap();
+        assert_eq!(run.trigger, JobTrigger::Manual);
+        let runs = wait_for_runs(&store, 1).await;
+        assert_eq!(runs[0].status, JobStatus::Succeeded);
+        assert!(runs[0].ended_at.is_some());
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        scheduler.resume("count").await.unwrap();
+        let jobs = scheduler.list().await;
+        assert!(!jobs[0].paused);
+        assert!(jobs[0].next_run_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failed_and_panicked_runs_are_recorded() {
+        let (scheduler, store) = scheduler();
+        scheduler
+            .register(JobSpec::new("fail", YEARLY, ""), || async {
+                Err::<(), _>("boom")
+            })
+            .await
+            .unwrap();
+        scheduler
+            .register(JobSpec::new("panic", YEARLY, ""), || async {
+                if true {
+                    panic!("oops");
+                }
+                Ok::<_, String>(())
+            })
+            .await
+            .unwrap();
+
+        scheduler.trigger("fail").await.unwrap();
+        scheduler.trigger("panic").await.unwrap();
+        let runs = wait_for_runs(&store, 2).await;
+
+        let error = |job: &str| {
+            let run = runs.iter().find(|r| r.job_name == job).unwrap();
+            assert_eq!(run.status, JobStatus::Failed);
+            run.error.clone().unwrap()
+        };
+        assert_eq!(error("fail"), "boom");
+        assert_eq!(error("panic"), "Job panicked: oops");
+
+        // panic 后任务可以再次执行
+        scheduler.trigger("panic").await.unwrap();
+        wait_for_runs(&store, 3).await;
+    }
+
+    struct HeldLock;
+
+    #[async_trait]
+    impl JobLock for HeldLock {
+        async fn acquire(&self, _: &str, _: &str, _: Duration) -> Result<bool, SchedulerError> {
+            Ok(false)
+        }
+
+        async fn renew(&self, _: &str, _: &str, _: Duration) -> Result<bool, SchedulerError> {
+            Ok(false)
+        }
+
+        async fn release(&self, _: &str, _: &str) -> Result<(), SchedulerError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_skip_when_lock_is_held_elsewhere() {
+        let store = Arc::new(MemoryJobStore::new());
+        let scheduler = Scheduler::new(store.clone(), Arc::new(HeldLock));
+        scheduler
+            .register(JobSpec::new("locked", YEARLY, ""), || async {
+                Ok::<_, String>(())
+            })
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            scheduler.trigger("locked").await,
+            Err(SchedulerError::JobRunning(_))
+        ));
+        assert!(store.runs().is_empty());
+        assert!(!scheduler.list().await[0].running);
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_run() {
+        let (scheduler, store) = scheduler();
+        scheduler.start();
+        scheduler
+            .register(JobSpec::new("tick", "* * * * * *", ""), || async {
+                Ok::<_, String>(())
+            })
+            .await
+            .unwrap();
+
+        let runs = wait_for_runs(&store, 1).await;
+        assert_eq!(runs[0].trigger, JobTrigger::Schedule);
+        assert_eq!(runs[0].instance, scheduler.instance());
+    }
+}
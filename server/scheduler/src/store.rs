@@ -0,0 +1,81 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+
+use crate::{JobRun, JobSpec, SchedulerError};
+
+/// 任务状态和执行记录的持久化，暂停状态由所有实例共享
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    /// 注册时写入任务定义，返回已保存的暂停状态
+    async fn register(&self, spec: &JobSpec) -> Result<bool, SchedulerError>;
+
+    async fn is_paused(&self, job: &str) -> Result<bool, SchedulerError>;
+
+    async fn set_paused(&self, job: &str, paused: bool) -> Result<(), SchedulerError>;
+
+    /// 记录开始执行
+    async fn record_start(&self, run: &JobRun) -> Result<(), SchedulerError>;
+
+    /// 记录执行结果
+    async fn record_finish(&self, run: &JobRun) -> Result<(), SchedulerError>;
+}
+
+/// 保存在内存中的任务状态，用于测试或未配置数据库的场景
+#[derive(Default)]
+pub struct MemoryJobStore {
+    paused: Mutex<HashMap<String, bool>>,
+    runs: Mutex<Vec<JobRun>>,
+}
+
+impl MemoryJobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按开始时间排列的执行记录
+    pub fn runs(&self) -> Vec<JobRun> {
+        self.runs.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl JobStore for MemoryJobStore {
+    async fn register(&self, spec: &JobSpec) -> Result<bool, SchedulerError> {
+        Ok(*self
+            .paused
+            .lock()
+            .unwrap()
+            .entry(spec.name.clone())
+            .or_default())
+    }
+
+    async fn is_paused(&self, job: &str) -> Result<bool, SchedulerError> {
+        Ok(self
+            .paused
+            .lock()
+            .unwrap()
+            .get(job)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn set_paused(&self, job: &str, paused: bool) -> Result<(), SchedulerError> {
+        self.paused.lock().unwrap().insert(job.to_string(), paused);
+        Ok(())
+    }
+
+    async fn record_start(&self, run: &JobRun) -> Result<(), SchedulerError> {
+        self.runs.lock().unwrap().push(run.clone());
+        Ok(())
+    }
+
+    async fn record_finish(&self, run: &JobRun) -> Result<(), SchedulerError> {
+        let mut runs = self.runs.lock().unwrap();
+        match runs.iter_mut().find(|r| r.id == run.id) {
+            Some(existing) => *existing = run.clone(),
+            None => runs.push(run.clone()),
+        }
+        Ok(())
+    }
+}
@@ -1 +1,2 @@
 pub mod sys_auth_dto;
+pub mod sys_login_security_dto;
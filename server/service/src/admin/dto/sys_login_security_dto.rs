@@ -0,0 +1,13 @@
+use serde::Serialize;
+use server_config::LoginSecurityConfig;
+use server_model::admin::entities::sys_login_security_policy::Model as SysLoginSecurityPolicyModel;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginSecurityPolicyOutput {
+    pub domain: String,
+    /// 数据库中保存的域级覆盖，未配置时为空
+    pub overrides: Option<SysLoginSecurityPolicyModel>,
+    /// 合并全局配置后实际生效的策略
+    pub effective: LoginSecurityConfig,
+}
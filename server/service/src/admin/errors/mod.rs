@@ -1,5 +1,15 @@
 pub mod sys_access_key_error;
+pub mod sys_account_error;
+pub mod sys_auth_error;
 pub mod sys_domain_error;
+pub mod sys_external_login_error;
+pub mod sys_job_error;
+pub mod sys_log_archive_error;
+pub mod sys_login_security_error;
+pub mod sys_mail_error;
 pub mod sys_menu_error;
+pub mod sys_organization_error;
 pub mod sys_role_error;
+pub mod sys_siwe_error;
+pub mod sys_tenant_error;
 pub mod sys_user_error;
@@ -5,12 +5,33 @@ use thiserror::Error;
 pub enum AccessKeyError {
     #[error("Access key not found")]
     AccessKeyNotFound,
+    #[error("Access key encryption key is not configured")]
+    EncryptionKeyNotConfigured,
+    #[error("Failed to encrypt or decrypt access key secret: {0}")]
+    SecretCipherFailed(String),
+    #[error("Invalid IP address or CIDR: {0}")]
+    InvalidAllowedIp(String),
+    #[error("Expiration time must be in the future")]
+    InvalidExpiration,
+    #[error("One or more permissions not found")]
+    PermissionsNotFound,
+    #[error("Failed to update access key permissions: {0}")]
+    PolicyUpdateFailed(String),
+    #[error("Invalid signature scheme: {0}")]
+    InvalidSignatureScheme(String),
 }
 
 impl ApiError for AccessKeyError {
     fn code(&self) -> u16 {
         match self {
             AccessKeyError::AccessKeyNotFound => 5001,
+            AccessKeyError::EncryptionKeyNotConfigured => 5002,
+            AccessKeyError::SecretCipherFailed(_) => 5003,
+            AccessKeyError::InvalidAllowedIp(_) => 5004,
+            AccessKeyError::InvalidExpiration => 5005,
+            AccessKeyError::PermissionsNotFound => 5006,
+            AccessKeyError::PolicyUpdateFailed(_) => 5007,
+            AccessKeyError::InvalidSignatureScheme(_) => 5008,
         }
     }
 
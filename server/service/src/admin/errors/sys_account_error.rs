@@ -0,0 +1,62 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AccountError {
+    #[error("New password must differ from recently used passwords")]
+    PasswordReused,
+    #[error("Password reset token is invalid or expired")]
+    InvalidResetToken,
+    #[error("Email already exists")]
+    EmailAlreadyExists,
+    #[error("Phone number already exists")]
+    PhoneNumberAlreadyExists,
+    #[error("Avatar upload is not configured")]
+    AvatarNotConfigured,
+    #[error("S3 client '{0}' not found")]
+    S3ClientNotFound(String),
+    #[error("Avatar must not exceed {0} bytes")]
+    AvatarTooLarge(usize),
+    #[error("Unsupported avatar type, expected PNG, JPEG, GIF or WebP")]
+    UnsupportedAvatarType,
+    #[error("Failed to upload avatar: {0}")]
+    AvatarUpload(String),
+    #[error("Too many password reset requests, please try again later")]
+    TooManyResetRequests,
+    #[error("Current password is required to change the email")]
+    CurrentPasswordRequired,
+    #[error("Password reset is not available")]
+    ResetUnavailable,
+}
+
+impl ApiError for AccountError {
+    fn code(&self) -> u16 {
+        match self {
+            AccountError::PasswordReused => 16001,
+            AccountError::InvalidResetToken => 16002,
+            AccountError::EmailAlreadyExists => 16003,
+            AccountError::PhoneNumberAlreadyExists => 16004,
+            AccountError::AvatarNotConfigured => 16005,
+            AccountError::S3ClientNotFound(_) => 16006,
+            AccountError::AvatarTooLarge(_) => 16007,
+            AccountError::UnsupportedAvatarType => 16008,
+            AccountError::AvatarUpload(_) => 16009,
+            AccountError::TooManyResetRequests => 16010,
+            AccountError::CurrentPasswordRequired => 16011,
+            AccountError::ResetUnavailable => 16012,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<AccountError> for AppError {
+    fn from(err: AccountError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Invalid refresh token")]
+    InvalidRefreshToken,
+    #[error("Refresh token has expired")]
+    RefreshTokenExpired,
+    #[error("Refresh token reuse detected, all sessions have been revoked")]
+    RefreshTokenReused,
+    #[error("Session not found")]
+    SessionNotFound,
+    #[error("Invalid or expired two-factor challenge")]
+    InvalidTwoFactorChallenge,
+    #[error("Invalid two-factor code")]
+    InvalidTwoFactorCode,
+    #[error("Two-factor authentication is already enabled")]
+    TwoFactorAlreadyEnabled,
+    #[error("Two-factor authentication is not enabled")]
+    TwoFactorNotEnabled,
+    #[error("Two-factor setup has not been started")]
+    TwoFactorSetupNotStarted,
+}
+
+impl ApiError for AuthError {
+    fn code(&self) -> u16 {
+        match self {
+            AuthError::InvalidRefreshToken => 6001,
+            AuthError::RefreshTokenExpired => 6002,
+            AuthError::RefreshTokenReused => 6003,
+            AuthError::SessionNotFound => 6004,
+            AuthError::InvalidTwoFactorChallenge => 6005,
+            AuthError::InvalidTwoFactorCode => 6006,
+            AuthError::TwoFactorAlreadyEnabled => 6007,
+            AuthError::TwoFactorNotEnabled => 6008,
+            AuthError::TwoFactorSetupNotStarted => 6009,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<AuthError> for AppError {
+    fn from(err: AuthError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
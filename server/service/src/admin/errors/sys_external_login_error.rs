@@ -0,0 +1,53 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ExternalLoginError {
+    #[error("External login provider not found: {0}")]
+    ProviderNotFound(String),
+    #[error("Invalid or expired login state")]
+    InvalidState,
+    #[error("Provider discovery failed: {0}")]
+    DiscoveryFailed(String),
+    #[error("Authorization code exchange failed: {0}")]
+    TokenExchangeFailed(String),
+    #[error("Invalid ID token: {0}")]
+    InvalidIdToken(String),
+    #[error("External account is not linked to any user")]
+    AccountNotLinked,
+    #[error("Username already exists: {0}")]
+    UsernameConflict(String),
+    #[error("Default role not found: {0}")]
+    DefaultRoleNotFound(String),
+    #[error("Missing claim: {0}")]
+    MissingClaim(String),
+}
+
+impl ApiError for ExternalLoginError {
+    fn code(&self) -> u16 {
+        match self {
+            ExternalLoginError::ProviderNotFound(_) => 8001,
+            ExternalLoginError::InvalidState => 8002,
+            ExternalLoginError::DiscoveryFailed(_) => 8003,
+            ExternalLoginError::TokenExchangeFailed(_) => 8004,
+            ExternalLoginError::InvalidIdToken(_) => 8005,
+            ExternalLoginError::AccountNotLinked => 8006,
+            ExternalLoginError::UsernameConflict(_) => 8007,
+            ExternalLoginError::DefaultRoleNotFound(_) => 8008,
+            ExternalLoginError::MissingClaim(_) => 8009,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<ExternalLoginError> for AppError {
+    fn from(err: ExternalLoginError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use server_core::web::error::{ApiError, AppError};
+use server_scheduler::SchedulerError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum JobError {
+    #[error("Job '{0}' not found")]
+    JobNotFound(String),
+    #[error("Job '{0}' is already running")]
+    JobRunning(String),
+    #[error("Scheduler is not initialized")]
+    SchedulerNotInitialized,
+    #[error("Scheduler error: {0}")]
+    Scheduler(String),
+}
+
+impl From<SchedulerError> for JobError {
+    fn from(err: SchedulerError) -> Self {
+        match err {
+            SchedulerError::JobNotFound(name) => JobError::JobNotFound(name),
+            SchedulerError::JobRunning(name) => JobError::JobRunning(name),
+            other => JobError::Scheduler(other.to_string()),
+        }
+    }
+}
+
+impl ApiError for JobError {
+    fn code(&self) -> u16 {
+        match self {
+            JobError::JobNotFound(_) => 12001,
+            JobError::JobRunning(_) => 12002,
+            JobError::SchedulerNotInitialized => 12003,
+            JobError::Scheduler(_) => 12004,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<JobError> for AppError {
+    fn from(err: JobError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LogArchiveError {
+    #[error("Log archive bucket is not configured")]
+    BucketNotConfigured,
+    #[error("S3 client '{0}' not found")]
+    S3ClientNotFound(String),
+    #[error("Failed to encode logs: {0}")]
+    Encode(String),
+    #[error("Failed to upload log archive: {0}")]
+    Upload(String),
+}
+
+impl ApiError for LogArchiveError {
+    fn code(&self) -> u16 {
+        match self {
+            LogArchiveError::BucketNotConfigured => 11001,
+            LogArchiveError::S3ClientNotFound(_) => 11002,
+            LogArchiveError::Encode(_) => 11003,
+            LogArchiveError::Upload(_) => 11004,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<LogArchiveError> for AppError {
+    fn from(err: LogArchiveError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LoginSecurityError {
+    #[error("Invalid IP or CIDR: {0}")]
+    InvalidCidr(String),
+    #[error("Invalid timezone: {0}")]
+    InvalidTimezone(String),
+    #[error("Invalid login window time: {0}")]
+    InvalidLoginWindow(String),
+    #[error("Login security policy not found")]
+    PolicyNotFound,
+    #[error("Account is not locked")]
+    AccountNotLocked,
+}
+
+impl ApiError for LoginSecurityError {
+    fn code(&self) -> u16 {
+        match self {
+            LoginSecurityError::InvalidCidr(_) => 7001,
+            LoginSecurityError::InvalidTimezone(_) => 7002,
+            LoginSecurityError::InvalidLoginWindow(_) => 7003,
+            LoginSecurityError::PolicyNotFound => 7004,
+            LoginSecurityError::AccountNotLocked => 7005,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<LoginSecurityError> for AppError {
+    fn from(err: LoginSecurityError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MailError {
+    #[error("Mail delivery is disabled")]
+    Disabled,
+    #[error("SMTP host is not configured")]
+    SmtpHostNotConfigured,
+    #[error("Invalid mail address: {0}")]
+    InvalidAddress(String),
+    #[error("Failed to build mail: {0}")]
+    Build(String),
+    #[error("Failed to send mail: {0}")]
+    Send(String),
+}
+
+impl ApiError for MailError {
+    fn code(&self) -> u16 {
+        match self {
+            MailError::Disabled => 15005,
+            MailError::SmtpHostNotConfigured => 15001,
+            MailError::InvalidAddress(_) => 15002,
+            MailError::Build(_) => 15003,
+            MailError::Send(_) => 15004,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<MailError> for AppError {
+    fn from(err: MailError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
@@ -8,6 +8,18 @@ pub enum MenuError {
 
     #[error("Duplicate route name")]
     DuplicateRouteName,
+
+    #[error("Permission code not found")]
+    PermissionCodeNotFound,
+
+    #[error("Duplicate permission code")]
+    DuplicatePermissionCode,
+
+    #[error("Endpoint not found")]
+    EndpointNotFound,
+
+    #[error("Permission code is assigned to roles")]
+    PermissionCodeInUse,
 }
 
 impl ApiError for MenuError {
@@ -15,6 +27,10 @@ impl ApiError for MenuError {
         match self {
             MenuError::MenuNotFound => 3001,
             MenuError::DuplicateRouteName => 3002,
+            MenuError::PermissionCodeNotFound => 3003,
+            MenuError::DuplicatePermissionCode => 3004,
+            MenuError::EndpointNotFound => 3005,
+            MenuError::PermissionCodeInUse => 3006,
         }
     }
 
@@ -0,0 +1,45 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum OrganizationError {
+    #[error("Organization not found")]
+    OrganizationNotFound,
+
+    #[error("Duplicate organization code")]
+    DuplicateOrganizationCode,
+
+    #[error("Parent organization not found")]
+    ParentOrganizationNotFound,
+
+    #[error("An organization cannot be moved under itself or its descendants")]
+    InvalidParentOrganization,
+
+    #[error("Organization has child organizations")]
+    OrganizationHasChildren,
+}
+
+impl ApiError for OrganizationError {
+    fn code(&self) -> u16 {
+        match self {
+            OrganizationError::OrganizationNotFound => 13001,
+            OrganizationError::DuplicateOrganizationCode => 13002,
+            OrganizationError::ParentOrganizationNotFound => 13003,
+            OrganizationError::InvalidParentOrganization => 13004,
+            OrganizationError::OrganizationHasChildren => 13005,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<OrganizationError> for AppError {
+    fn from(err: OrganizationError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
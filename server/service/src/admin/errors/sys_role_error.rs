@@ -8,6 +8,15 @@ pub enum RoleError {
 
     #[error("Duplicate role code")]
     DuplicateRoleCode,
+
+    #[error("Parent role not found")]
+    ParentRoleNotFound,
+
+    #[error("Parent role cannot be the role itself or one of its descendants")]
+    InvalidParentRole,
+
+    #[error("Role has child roles")]
+    RoleHasChildren,
 }
 
 impl ApiError for RoleError {
@@ -15,6 +24,9 @@ impl ApiError for RoleError {
         match self {
             RoleError::RoleNotFound => 4001,
             RoleError::DuplicateRoleCode => 4002,
+            RoleError::ParentRoleNotFound => 4003,
+            RoleError::InvalidParentRole => 4004,
+            RoleError::RoleHasChildren => 4005,
         }
     }
 
@@ -0,0 +1,59 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SiweError {
+    #[error("Wallet login is not enabled")]
+    NotEnabled,
+    #[error("Invalid sign-in message: {0}")]
+    InvalidMessage(String),
+    #[error("Sign-in message domain mismatch: {0}")]
+    DomainMismatch(String),
+    #[error("Sign-in message URI mismatch: {0}")]
+    UriMismatch(String),
+    #[error("Chain ID not allowed: {0}")]
+    ChainNotAllowed(u64),
+    #[error("Sign-in message has expired")]
+    Expired,
+    #[error("Sign-in message is not yet valid")]
+    NotYetValid,
+    #[error("Invalid or expired nonce")]
+    InvalidNonce,
+    #[error("Invalid signature")]
+    InvalidSignature,
+    #[error("Wallet is not linked to any user")]
+    WalletNotLinked,
+    #[error("Wallet is already linked to another user")]
+    WalletAlreadyLinked,
+}
+
+impl ApiError for SiweError {
+    fn code(&self) -> u16 {
+        match self {
+            SiweError::NotEnabled => 9001,
+            SiweError::InvalidMessage(_) => 9002,
+            SiweError::DomainMismatch(_) => 9003,
+            SiweError::UriMismatch(_) => 9004,
+            SiweError::ChainNotAllowed(_) => 9005,
+            SiweError::Expired => 9006,
+            SiweError::NotYetValid => 9007,
+            SiweError::InvalidNonce => 9008,
+            SiweError::InvalidSignature => 9009,
+            SiweError::WalletNotLinked => 9010,
+            SiweError::WalletAlreadyLinked => 9011,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<SiweError> for AppError {
+    fn from(err: SiweError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use server_core::web::error::{ApiError, AppError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TenantError {
+    #[error("Domain '{0}' does not match the current domain")]
+    DomainMismatch(String),
+}
+
+impl ApiError for TenantError {
+    fn code(&self) -> u16 {
+        match self {
+            TenantError::DomainMismatch(_) => 14001,
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl From<TenantError> for AppError {
+    fn from(err: TenantError) -> Self {
+        AppError {
+            code: err.code(),
+            message: err.message(),
+        }
+    }
+}
@@ -1,10 +1,14 @@
+use serde::{Deserialize, Serialize};
+use server_constant::definition::consts::SystemEvent;
 use server_core::web::error::AppError;
+use server_global::event_bus::Event;
 
 use crate::{
     admin::events::{access_token_event::AccessTokenEvent, login_log_event::LoginLogEvent},
     helper::db_helper,
 };
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthEvent {
     pub user_id: String,
     pub username: String,
@@ -19,6 +23,12 @@ pub struct AuthEvent {
     pub login_type: String,
 }
 
+impl Event for AuthEvent {
+    fn name() -> &'static str {
+        SystemEvent::AuthLoggedInEvent.as_ref()
+    }
+}
+
 pub struct AuthEventHandler;
 
 impl AuthEventHandler {
@@ -55,7 +65,7 @@ impl AuthEventHandler {
             login_type: event.login_type,
         };
 
-        access_token_event.handle(&db).await?;
+        access_token_event.handle(db.as_ref()).await?;
 
         Ok(())
     }
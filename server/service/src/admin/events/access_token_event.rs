@@ -1,5 +1,5 @@
 use chrono::Local;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use sea_orm::{ActiveModelTrait, ConnectionTrait, Set};
 use server_constant::definition::consts::TokenStatus;
 use server_core::web::error::AppError;
 use server_model::admin::entities::sys_tokens::ActiveModel as SysTokensActiveModel;
@@ -20,7 +20,7 @@ pub struct AccessTokenEvent {
 }
 
 impl AccessTokenEvent {
-    pub async fn handle(self, db: &DatabaseConnection) -> Result<(), AppError> {
+    pub async fn handle<C: ConnectionTrait>(self, db: &C) -> Result<(), AppError> {
         let now = Local::now().naive_local();
 
         SysTokensActiveModel {
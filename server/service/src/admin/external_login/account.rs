@@ -0,0 +1,214 @@
+use chrono::Local;
+use redis::{aio::MultiplexedConnection, AsyncCommands};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use server_core::web::error::AppError;
+use server_model::admin::entities::{
+    prelude::{SysRole, SysUser, SysUserExternalIdentity},
+    sea_orm_active_enums::Status,
+    sys_role::Column as SysRoleColumn,
+    sys_user::{ActiveModel as SysUserActiveModel, Column as SysUserColumn},
+    sys_user_external_identity::{
+        ActiveModel as SysUserExternalIdentityActiveModel, Column as SysUserExternalIdentityColumn,
+    },
+    sys_user_role::ActiveModel as SysUserRoleActiveModel,
+};
+use server_utils::SecureUtil;
+use ulid::Ulid;
+
+use super::{random_token, AccountPolicy, ExternalIdentity, ExternalLoginProvider};
+use crate::{
+    admin::sys_external_login_error::ExternalLoginError,
+    helper::{
+        db_helper,
+        redis_helper::{get_redis_connection, RedisSource},
+    },
+    project_info,
+};
+
+const STATE_KEY_PREFIX: &str = "external_login:state:";
+/// 授权请求的有效期（秒），超时未回调需要重新发起登录
+const STATE_TTL: u64 = 10 * 60;
+
+/// 发起登录到回调之间保存在服务端的数据
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct PendingLogin {
+    pub provider: String,
+    pub pkce_verifier: String,
+    pub nonce: String,
+}
+
+pub(crate) async fn save_state(state: &str, pending: &PendingLogin) -> Result<(), AppError> {
+    let mut redis: MultiplexedConnection = get_redis_connection(RedisSource::Primary).await?;
+    let value = serde_json::to_string(pending).map_err(|e| AppError {
+        code: 500,
+        message: e.to_string(),
+    })?;
+    let _: () = redis
+        .set_ex(format!("{}{}", STATE_KEY_PREFIX, state), value, STATE_TTL)
+        .await?;
+    Ok(())
+}
+
+/// 取出并删除授权请求，保证每个 state 只能回调一次
+pub(crate) async fn take_state(state: &str) -> Result<PendingLogin, AppError> {
+    let mut redis: MultiplexedConnection = get_redis_connection(RedisSource::Primary).await?;
+    let value: Option<String> = redis
+        .get_del(format!("{}{}", STATE_KEY_PREFIX, state))
+        .await?;
+
+    value
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .ok_or_else(|| ExternalLoginError::InvalidState.into())
+}
+
+/// 将外部身份映射为本地用户 ID
+///
+/// 依次尝试：已关联的身份、按用户名关联域内已有用户（需开启 `link_by_username`）、
+/// 自动创建用户并分配默认角色（需开启 `auto_create_user`）。
+pub(crate) async fn resolve_user(
+    provider: &dyn ExternalLoginProvider,
+    identity: &ExternalIdentity,
+) -> Result<String, AppError> {
+    let db = db_helper::get_db_connection().await?;
+    let now = Local::now().naive_local();
+
+    if let Some(linked) = SysUserExternalIdentity::find()
+        .filter(SysUserExternalIdentityColumn::Provider.eq(provider.name()))
+        .filter(SysUserExternalIdentityColumn::Subject.eq(&identity.subject))
+        .one(db.as_ref())
+        .await?
+    {
+        SysUserExternalIdentity::update_many()
+            .col_expr(SysUserExternalIdentityColumn::LastLoginAt, Expr::value(now))
+            .col_expr(
+                SysUserExternalIdentityColumn::Email,
+                Expr::value(identity.email.clone()),
+            )
+            .filter(SysUserExternalIdentityColumn::Id.eq(&linked.id))
+            .exec(db.as_ref())
+            .await?;
+        return Ok(linked.user_id);
+    }
+
+    let policy = provider.account_policy();
+    let username = identity
+        .username
+        .clone()
+        .ok_or_else(|| ExternalLoginError::MissingClaim("username".into()))?;
+
+    let txn = db.begin().await?;
+
+    let existing = SysUser::find()
+        .filter(SysUserColumn::Username.eq(&username))
+        .one(&txn)
+        .await?;
+
+    let user_id = match existing {
+        Some(user) if policy.link_by_username && user.domain == policy.domain => user.id,
+        Some(_) if policy.auto_create_user => {
+            return Err(ExternalLoginError::UsernameConflict(username).into());
+        },
+        None if policy.auto_create_user => {
+            create_user(provider.name(), identity, &username, &policy, &txn).await?
+        },
+        _ => return Err(ExternalLoginError::AccountNotLinked.into()),
+    };
+
+    SysUserExternalIdentityActiveModel {
+        id: Set(Ulid::new().to_string()),
+        user_id: Set(user_id.clone()),
+        provider: Set(provider.name().to_string()),
+        subject: Set(identity.subject.clone()),
+        email: Set(identity.email.clone()),
+        last_login_at: Set(Some(now)),
+        created_at: Set(now),
+    }
+    .insert(&txn)
+    .await?;
+
+    txn.commit().await?;
+
+    project_info!(
+        "External identity {}/{} linked to user {}",
+        provider.name(),
+        identity.subject,
+        user_id
+    );
+    Ok(user_id)
+}
+
+/// 即时创建用户，密码设为随机值，只能通过外部登录进入
+async fn create_user<C: ConnectionTrait>(
+    provider: &str,
+    identity: &ExternalIdentity,
+    username: &str,
+    policy: &AccountPolicy,
+    db: &C,
+) -> Result<String, AppError> {
+    let role = match &policy.default_role {
+        Some(code) => Some(
+            SysRole::find()
+                .filter(SysRoleColumn::Code.eq(code))
+                .filter(SysRoleColumn::Domain.eq(&policy.domain))
+                .one(db)
+                .await?
+                .ok_or_else(|| ExternalLoginError::DefaultRoleNotFound(code.clone()))?,
+        ),
+        None => None,
+    };
+
+    // 邮箱在用户表中唯一，已被占用时不写入
+    let email = match &identity.email {
+        Some(email) => SysUser::find()
+            .filter(SysUserColumn::Email.eq(email))
+            .one(db)
+            .await?
+            .is_none()
+            .then(|| email.clone()),
+        None => None,
+    };
+
+    let password = SecureUtil::hash_password(random_token().as_bytes()).map_err(|e| AppError {
+        code: 500,
+        message: format!("Failed to hash password: {}", e),
+    })?;
+
+    let user = SysUserActiveModel {
+        id: Set(Ulid::new().to_string()),
+        domain: Set(policy.domain.clone()),
+        username: Set(username.to_string()),
+        password: Set(password),
+        built_in: Set(false),
+        nick_name: Set(identity
+            .nick_name
+            .clone()
+            .unwrap_or_else(|| username.to_string())),
+        email: Set(email),
+        status: Set(Status::Enabled),
+        created_at: Set(Local::now().naive_local()),
+        created_by: Set(format!("external:{}", provider)),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    if let Some(role) = role {
+        SysUserRoleActiveModel {
+            user_id: Set(user.id.clone()),
+            role_id: Set(role.id),
+        }
+        .insert(db)
+        .await?;
+    }
+
+    project_info!(
+        "User {} created from external login provider {}",
+        user.username,
+        provider
+    );
+    Ok(user.id)
+}
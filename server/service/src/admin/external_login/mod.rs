@@ -0,0 +1,121 @@
+//! 外部登录
+//!
+//! 每个提供方实现 [`ExternalLoginProvider`]，负责生成授权地址并用回调中的授权码换取
+//! 已验证的外部身份；之后的用户关联、自动创建和令牌签发由认证服务统一处理。
+//! 配置文件中的 OIDC 提供方在首次使用时自动注册，也可以通过
+//! [`register_provider`] 注册自定义实现。
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::RngCore;
+use server_config::{OidcProviderConfig, OptionalConfigs};
+use server_global::global;
+
+pub(crate) use self::account::{resolve_user, save_state, take_state, PendingLogin};
+pub use self::oidc::OidcProvider;
+use crate::admin::sys_external_login_error::ExternalLoginError;
+
+mod account;
+mod oidc;
+
+/// 提供方验证后的外部身份
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalIdentity {
+    /// 提供方内唯一且不变的用户标识
+    pub subject: String,
+    pub username: Option<String>,
+    pub nick_name: Option<String>,
+    pub email: Option<String>,
+}
+
+/// 发起登录时生成的授权请求
+///
+/// `state` 返回给前端，`pkce_verifier` 和 `nonce` 只保存在服务端，回调时用于换取和验证令牌。
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    pub url: String,
+    pub state: String,
+    pub pkce_verifier: String,
+    pub nonce: String,
+}
+
+/// 外部身份映射到本地用户的规则
+#[derive(Debug, Clone)]
+pub struct AccountPolicy {
+    pub domain: String,
+    pub link_by_username: bool,
+    pub auto_create_user: bool,
+    pub default_role: Option<String>,
+}
+
+#[async_trait]
+pub trait ExternalLoginProvider: Send + Sync {
+    /// 提供方名称，即登录地址中的 `{provider}`
+    fn name(&self) -> &str;
+
+    /// 登录日志中的登录方式，如 `OIDC`
+    fn kind(&self) -> &'static str;
+
+    fn account_policy(&self) -> AccountPolicy;
+
+    async fn authorization_request(&self) -> Result<AuthorizationRequest, ExternalLoginError>;
+
+    async fn exchange_code(
+        &self,
+        code: &str,
+        pkce_verifier: &str,
+        nonce: &str,
+    ) -> Result<ExternalIdentity, ExternalLoginError>;
+}
+
+lazy_static::lazy_static! {
+    static ref PROVIDERS: RwLock<HashMap<String, Arc<dyn ExternalLoginProvider>>> =
+        RwLock::new(HashMap::new());
+}
+
+/// 注册外部登录提供方，同名提供方会被替换
+pub fn register_provider(provider: Arc<dyn ExternalLoginProvider>) {
+    PROVIDERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(provider.name().to_string(), provider);
+}
+
+/// 按名称查找提供方，未注册时尝试从 `oidc_providers` 配置创建
+pub async fn get_provider(
+    name: &str,
+) -> Result<Arc<dyn ExternalLoginProvider>, ExternalLoginError> {
+    if let Some(provider) = PROVIDERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+    {
+        return Ok(provider.clone());
+    }
+
+    let config = global::get_config::<OptionalConfigs<OidcProviderConfig>>()
+        .await
+        .and_then(|configs| {
+            configs
+                .configs
+                .as_ref()
+                .and_then(|configs| configs.iter().find(|config| config.name == name).cloned())
+        })
+        .ok_or_else(|| ExternalLoginError::ProviderNotFound(name.to_string()))?;
+
+    let provider: Arc<dyn ExternalLoginProvider> = Arc::new(OidcProvider::new(config));
+    register_provider(provider.clone());
+    Ok(provider)
+}
+
+/// 32 字节随机数的 base64url 编码，用于 state、nonce 和 PKCE verifier
+pub(crate) fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
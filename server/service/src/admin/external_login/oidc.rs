@@ -0,0 +1,504 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+    decode, decode_header,
+    jwk::{Jwk, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use server_config::OidcProviderConfig;
+use sha2::{Digest, Sha256};
+use tokio::sync::{OnceCell, RwLock};
+
+use super::{
+    random_token, AccountPolicy, AuthorizationRequest, ExternalIdentity, ExternalLoginProvider,
+};
+use crate::admin::sys_external_login_error::ExternalLoginError;
+
+/// 提供方发现文档中用到的字段
+#[derive(Debug, Clone, Deserialize)]
+struct OidcMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+/// 基于配置的 OIDC 提供方
+///
+/// 发现文档在首次使用时获取；JWKS 会被缓存，遇到未知的 kid 时重新获取一次以支持密钥轮换。
+pub struct OidcProvider {
+    config: OidcProviderConfig,
+    http: reqwest::Client,
+    metadata: OnceCell<OidcMetadata>,
+    jwks: RwLock<Option<JwkSet>>,
+}
+
+impl OidcProvider {
+    pub fn new(config: OidcProviderConfig) -> Self {
+        Self {
+            config,
+            http: reqwest::Client::new(),
+            metadata: OnceCell::new(),
+            jwks: RwLock::new(None),
+        }
+    }
+
+    async fn metadata(&self) -> Result<&OidcMetadata, ExternalLoginError> {
+        self.metadata
+            .get_or_try_init(|| async {
+                let url = format!(
+                    "{}/.well-known/openid-configuration",
+                    self.config.issuer.trim_end_matches('/')
+                );
+                let metadata: OidcMetadata = self
+                    .http
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(|e| ExternalLoginError::DiscoveryFailed(e.to_string()))?
+                    .json()
+                    .await
+                    .map_err(|e| ExternalLoginError::DiscoveryFailed(e.to_string()))?;
+
+                if metadata.issuer.trim_end_matches('/') != self.config.issuer.trim_end_matches('/')
+                {
+                    return Err(ExternalLoginError::DiscoveryFailed(format!(
+                        "issuer mismatch: {}",
+                        metadata.issuer
+                    )));
+                }
+                Ok(metadata)
+            })
+            .await
+    }
+
+    async fn fetch_jwks(&self, jwks_uri: &str) -> Result<JwkSet, ExternalLoginError> {
+        self.http
+            .get(jwks_uri)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| ExternalLoginError::DiscoveryFailed(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| ExternalLoginError::DiscoveryFailed(e.to_string()))
+    }
+
+    /// 按 kid 查找验证密钥，缓存中没有时刷新 JWKS
+    async fn find_jwk(&self, kid: Option<&str>) -> Result<Jwk, ExternalLoginError> {
+        let select = |jwks: &JwkSet| match kid {
+            Some(kid) => jwks.find(kid).cloned(),
+            None if jwks.keys.len() == 1 => jwks.keys.first().cloned(),
+            None => None,
+        };
+
+        if let Some(jwk) = self.jwks.read().await.as_ref().and_then(select) {
+            return Ok(jwk);
+        }
+
+        let metadata = self.metadata().await?;
+        let jwks = self.fetch_jwks(&metadata.jwks_uri).await?;
+        let jwk = select(&jwks);
+        *self.jwks.write().await = Some(jwks);
+
+        jwk.ok_or_else(|| ExternalLoginError::InvalidIdToken(format!("unknown key id: {:?}", kid)))
+    }
+
+    /// 验证 ID Token 的签名、签发方、受众、有效期和 nonce
+    async fn verify_id_token(
+        &self,
+        id_token: &str,
+        nonce: &str,
+    ) -> Result<Map<String, Value>, ExternalLoginError> {
+        let invalid = |e: &dyn std::fmt::Display| ExternalLoginError::InvalidIdToken(e.to_string());
+
+        let header = decode_header(id_token).map_err(|e| invalid(&e))?;
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            return Err(invalid(&"symmetric algorithms are not accepted"));
+        }
+
+        let jwk = self.find_jwk(header.kid.as_deref()).await?;
+        let key = DecodingKey::from_jwk(&jwk).map_err(|e| invalid(&e))?;
+
+        let metadata = self.metadata().await?;
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = 60;
+        validation.set_issuer(&[&metadata.issuer]);
+        validation.set_audience(&[&self.config.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let claims = decode::<Map<String, Value>>(id_token, &key, &validation)
+            .map_err(|e| invalid(&e))?
+            .claims;
+
+        if claims.get("nonce").and_then(Value::as_str) != Some(nonce) {
+            return Err(invalid(&"nonce mismatch"));
+        }
+        Ok(claims)
+    }
+
+    fn map_identity(
+        &self,
+        claims: &Map<String, Value>,
+    ) -> Result<ExternalIdentity, ExternalLoginError> {
+        let claim = |name: &str| {
+            claims
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        Ok(ExternalIdentity {
+            subject: claim("sub").ok_or_else(|| ExternalLoginError::MissingClaim("sub".into()))?,
+            username: claim(&self.config.username_claim),
+            nick_name: claim(&self.config.nick_name_claim),
+            email: claim(&self.config.email_claim),
+        })
+    }
+}
+
+#[async_trait]
+impl ExternalLoginProvider for OidcProvider {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn kind(&self) -> &'static str {
+        "OIDC"
+    }
+
+    fn account_policy(&self) -> AccountPolicy {
+        AccountPolicy {
+            domain: self.config.domain.clone(),
+            link_by_username: self.config.link_by_username,
+            auto_create_user: self.config.auto_create_user,
+            default_role: self.config.default_role.clone(),
+        }
+    }
+
+    async fn authorization_request(&self) -> Result<AuthorizationRequest, ExternalLoginError> {
+        let metadata = self.metadata().await?;
+
+        let state = random_token();
+        let nonce = random_token();
+        let pkce_verifier = random_token();
+        let scope = self.config.scopes.join(" ");
+
+        let url = Url::parse_with_params(
+            &metadata.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", self.config.client_id.as_str()),
+                ("redirect_uri", self.config.redirect_uri.as_str()),
+                ("scope", scope.as_str()),
+                ("state", state.as_str()),
+                ("nonce", nonce.as_str()),
+                ("code_challenge", pkce_challenge(&pkce_verifier).as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|e| ExternalLoginError::DiscoveryFailed(e.to_string()))?;
+
+        Ok(AuthorizationRequest {
+            url: url.to_string(),
+            state,
+            pkce_verifier,
+            nonce,
+        })
+    }
+
+    async fn exchange_code(
+        &self,
+        code: &str,
+        pkce_verifier: &str,
+        nonce: &str,
+    ) -> Result<ExternalIdentity, ExternalLoginError> {
+        let metadata = self.metadata().await?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.config.redirect_uri.as_str()),
+            ("client_id", self.config.client_id.as_str()),
+            ("code_verifier", pkce_verifier),
+        ];
+        if let Some(secret) = self.config.client_secret.as_deref() {
+            form.push(("client_secret", secret));
+        }
+
+        let response = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| ExternalLoginError::TokenExchangeFailed(e.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ExternalLoginError::TokenExchangeFailed(format!(
+                "{}: {}",
+                status, body
+            )));
+        }
+
+        let id_token = response
+            .json::<TokenResponse>()
+            .await
+            .map_err(|e| ExternalLoginError::TokenExchangeFailed(e.to_string()))?
+            .id_token
+            .ok_or_else(|| ExternalLoginError::TokenExchangeFailed("missing id_token".into()))?;
+
+        let claims = self.verify_id_token(&id_token, nonce).await?;
+        self.map_identity(&claims)
+    }
+}
+
+/// RFC 7636 S256 code challenge
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use axum::{
+        extract::{Form, State},
+        http::StatusCode,
+        routing::{get, post},
+        Json, Router,
+    };
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde_json::json;
+    use server_config::{JwtConfig, JwtKeyConfig};
+    use server_core::web::jwk::load_keys;
+
+    use super::*;
+
+    const CLIENT_ID: &str = "soybean-admin";
+    const CODE: &str = "auth-code";
+
+    fn fixture(name: &str) -> String {
+        format!(
+            "{}/../core/tests/fixtures/jwt/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        )
+    }
+
+    /// 本地模拟的 OIDC 提供方
+    struct MockIdp {
+        issuer: String,
+        encoding: EncodingKey,
+        kid: String,
+        /// 授权码对应的 PKCE challenge 和 nonce
+        pending: Mutex<HashMap<String, (String, String)>>,
+        audience: Mutex<String>,
+    }
+
+    impl MockIdp {
+        fn id_token(&self, nonce: &str) -> String {
+            let now = chrono::Utc::now().timestamp();
+            let claims = json!({
+                "iss": self.issuer,
+                "sub": "idp-user-1",
+                "aud": *self.audience.lock().unwrap(),
+                "exp": now + 300,
+                "iat": now,
+                "nonce": nonce,
+                "preferred_username": "alice",
+                "name": "Alice",
+                "email": "alice@example.com",
+            });
+            let mut header = Header::new(Algorithm::RS256);
+            header.kid = Some(self.kid.clone());
+            encode(&header, &claims, &self.encoding).unwrap()
+        }
+    }
+
+    async fn start_mock_idp() -> (Arc<MockIdp>, OidcProviderConfig) {
+        let keys = load_keys(&JwtConfig {
+            jwt_secret: String::new(),
+            issuer: String::new(),
+            expire: 0,
+            refresh_expire: 0,
+            algorithm: "RS256".to_string(),
+            active_kid: Some("idp-key".to_string()),
+            keys: vec![JwtKeyConfig {
+                kid: "idp-key".to_string(),
+                private_key_path: Some(fixture("rs256-private.pem")),
+                public_key_path: fixture("rs256-public.pem"),
+            }],
+        })
+        .unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+        let jwks = keys.jwks.clone();
+
+        let idp = Arc::new(MockIdp {
+            issuer: issuer.clone(),
+            encoding: keys.encoding,
+            kid: "idp-key".to_string(),
+            pending: Mutex::new(HashMap::new()),
+            audience: Mutex::new(CLIENT_ID.to_string()),
+        });
+
+        let discovery = json!({
+            "issuer": issuer,
+            "authorization_endpoint": format!("{}/authorize", issuer),
+            "token_endpoint": format!("{}/token", issuer),
+            "jwks_uri": format!("{}/jwks", issuer),
+        });
+
+        let app = Router::new()
+            .route(
+                "/.well-known/openid-configuration",
+                get(move || async move { Json(discovery) }),
+            )
+            .route("/jwks", get(move || async move { Json(jwks) }))
+            .route("/token", post(token_endpoint))
+            .with_state(idp.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let config = OidcProviderConfig {
+            name: "mock".to_string(),
+            issuer,
+            client_id: CLIENT_ID.to_string(),
+            client_secret: None,
+            redirect_uri: "http://localhost:9527/callback".to_string(),
+            scopes: vec!["openid".to_string()],
+            domain: "built-in".to_string(),
+            username_claim: "preferred_username".to_string(),
+            nick_name_claim: "name".to_string(),
+            email_claim: "email".to_string(),
+            link_by_username: false,
+            auto_create_user: true,
+            default_role: Some("ROLE_USER".to_string()),
+        };
+        (idp, config)
+    }
+
+    async fn token_endpoint(
+        State(idp): State<Arc<MockIdp>>,
+        Form(form): Form<HashMap<String, String>>,
+    ) -> Result<Json<Value>, StatusCode> {
+        let (challenge, nonce) = idp
+            .pending
+            .lock()
+            .unwrap()
+            .remove(form.get("code").ok_or(StatusCode::BAD_REQUEST)?)
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        let verifier = form.get("code_verifier").ok_or(StatusCode::BAD_REQUEST)?;
+        if pkce_challenge(verifier) != challenge
+            || form.get("client_id").map(String::as_str) != Some(CLIENT_ID)
+        {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        Ok(Json(
+            json!({ "id_token": idp.id_token(&nonce), "token_type": "Bearer" }),
+        ))
+    }
+
+    /// 模拟浏览器完成授权：提供方记录本次请求的 challenge 和 nonce 并签发授权码
+    fn authorize(idp: &MockIdp, request: &AuthorizationRequest) {
+        let url = Url::parse(&request.url).unwrap();
+        let params: HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(params["code_challenge_method"], "S256");
+        assert_eq!(params["state"], request.state);
+        idp.pending.lock().unwrap().insert(
+            CODE.to_string(),
+            (params["code_challenge"].clone(), params["nonce"].clone()),
+        );
+    }
+
+    #[test]
+    fn test_pkce_challenge_rfc7636_vector() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authorization_code_flow_with_pkce() {
+        let (idp, config) = start_mock_idp().await;
+        let provider = OidcProvider::new(config);
+
+        let request = provider.authorization_request().await.unwrap();
+        assert!(request
+            .url
+            .starts_with(&format!("{}/authorize?", idp.issuer)));
+        authorize(&idp, &request);
+
+        let identity = provider
+            .exchange_code(CODE, &request.pkce_verifier, &request.nonce)
+            .await
+            .unwrap();
+        assert_eq!(
+            identity,
+            ExternalIdentity {
+                subject: "idp-user-1".to_string(),
+                username: Some("alice".to_string()),
+                nick_name: Some("Alice".to_string()),
+                email: Some("alice@example.com".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wrong_pkce_verifier_is_rejected() {
+        let (idp, config) = start_mock_idp().await;
+        let provider = OidcProvider::new(config);
+
+        let request = provider.authorization_request().await.unwrap();
+        authorize(&idp, &request);
+
+        let result = provider
+            .exchange_code(CODE, "wrong-verifier", &request.nonce)
+            .await;
+        assert!(matches!(
+            result,
+            Err(ExternalLoginError::TokenExchangeFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_id_token_nonce_and_audience_are_checked() {
+        let (idp, config) = start_mock_idp().await;
+        let provider = OidcProvider::new(config);
+
+        let request = provider.authorization_request().await.unwrap();
+        authorize(&idp, &request);
+        let result = provider
+            .exchange_code(CODE, &request.pkce_verifier, "other-nonce")
+            .await;
+        assert!(matches!(result, Err(ExternalLoginError::InvalidIdToken(_))));
+
+        *idp.audience.lock().unwrap() = "other-client".to_string();
+        let request = provider.authorization_request().await.unwrap();
+        authorize(&idp, &request);
+        let result = provider
+            .exchange_code(CODE, &request.pkce_verifier, &request.nonce)
+            .await;
+        assert!(matches!(result, Err(ExternalLoginError::InvalidIdToken(_))));
+    }
+}
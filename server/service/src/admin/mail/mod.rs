@@ -0,0 +1,84 @@
+//! 邮件发送
+//!
+//! 发送方式由 `mail.transport` 配置决定：SMTP 服务器、写入本地目录或只写日志，
+//! 后两者用于开发和测试；未配置时不发送邮件。也可以通过 [`register_sender`]
+//! 替换为自定义实现。
+
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    Message,
+};
+use server_config::{MailConfig, MailTransport};
+use server_global::global;
+
+pub use self::{
+    sink::{FileMailSender, LogMailSender},
+    smtp::SmtpMailSender,
+};
+use crate::admin::sys_mail_error::MailError;
+
+mod sink;
+mod smtp;
+
+/// 一封纯文本邮件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[async_trait]
+pub trait MailSender: Send + Sync {
+    async fn send(&self, mail: &Mail) -> Result<(), MailError>;
+}
+
+lazy_static::lazy_static! {
+    static ref SENDER: RwLock<Option<Arc<dyn MailSender>>> = RwLock::new(None);
+}
+
+/// 注册邮件发送实现，替换按配置创建的实现
+pub fn register_sender(sender: Arc<dyn MailSender>) {
+    *SENDER.write().unwrap_or_else(|e| e.into_inner()) = Some(sender);
+}
+
+/// 获取邮件发送实现，未注册时按 `mail` 配置创建，未配置发送方式时返回
+/// [`MailError::Disabled`]
+pub async fn get_sender() -> Result<Arc<dyn MailSender>, MailError> {
+    if let Some(sender) = SENDER.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return Ok(sender.clone());
+    }
+
+    let config = global::get_config::<MailConfig>()
+        .await
+        .map(|config| config.as_ref().clone())
+        .unwrap_or_default();
+
+    let sender: Arc<dyn MailSender> = match config.transport {
+        MailTransport::Disabled => return Err(MailError::Disabled),
+        MailTransport::Log => Arc::new(LogMailSender),
+        MailTransport::File => Arc::new(FileMailSender::new(&config.from, &config.file_dir)?),
+        MailTransport::Smtp => Arc::new(SmtpMailSender::new(&config)?),
+    };
+    register_sender(sender.clone());
+    Ok(sender)
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, MailError> {
+    address
+        .parse()
+        .map_err(|_| MailError::InvalidAddress(address.to_string()))
+}
+
+fn build_message(from: &Mailbox, mail: &Mail) -> Result<Message, MailError> {
+    Message::builder()
+        .from(from.clone())
+        .to(parse_mailbox(&mail.to)?)
+        .subject(&mail.subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(mail.body.clone())
+        .map_err(|e| MailError::Build(e.to_string()))
+}
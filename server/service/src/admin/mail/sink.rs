@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use ulid::Ulid;
+
+use super::{build_message, parse_mailbox, Mail, MailSender};
+use crate::{admin::sys_mail_error::MailError, project_info};
+
+/// 每封邮件写成目录下的一个 `.eml` 文件
+pub struct FileMailSender {
+    from: Mailbox,
+    dir: PathBuf,
+}
+
+impl FileMailSender {
+    pub fn new(from: &str, dir: impl Into<PathBuf>) -> Result<Self, MailError> {
+        Ok(Self {
+            from: parse_mailbox(from)?,
+            dir: dir.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl MailSender for FileMailSender {
+    async fn send(&self, mail: &Mail) -> Result<(), MailError> {
+        let message = build_message(&self.from, mail)?;
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| MailError::Send(e.to_string()))?;
+        tokio::fs::write(
+            self.dir.join(format!("{}.eml", Ulid::new())),
+            message.formatted(),
+        )
+        .await
+        .map_err(|e| MailError::Send(e.to_string()))
+    }
+}
+
+/// 只把邮件的收件人和主题写入日志
+///
+/// 正文可能包含找回密码链接等凭据，不写入日志；需要查看正文时使用 [`FileMailSender`]。
+pub struct LogMailSender;
+
+#[async_trait]
+impl MailSender for LogMailSender {
+    async fn send(&self, mail: &Mail) -> Result<(), MailError> {
+        project_info!(
+            "Mail to {}, subject: {} ({} bytes body omitted)",
+            mail.to,
+            mail.subject,
+            mail.body.len()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_sender_writes_eml() {
+        let dir = std::env::temp_dir().join(format!("mail-outbox-{}", Ulid::new()));
+        let sender = FileMailSender::new("Admin <noreply@example.com>", &dir).unwrap();
+
+        sender
+            .send(&Mail {
+                to: "alice@example.com".to_string(),
+                subject: "Reset your password".to_string(),
+                body: "Open the link to reset your password.".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let mut files = std::fs::read_dir(&dir).unwrap();
+        let content = std::fs::read_to_string(files.next().unwrap().unwrap().path()).unwrap();
+        assert!(files.next().is_none());
+        assert!(content.contains("To: alice@example.com"));
+        assert!(content.contains("Subject: Reset your password"));
+        assert!(content.contains("Open the link to reset your password."));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_recipient_is_rejected() {
+        let sender = FileMailSender::new("noreply@example.com", std::env::temp_dir()).unwrap();
+        let result = sender
+            .send(&Mail {
+                to: "not an address".to_string(),
+                subject: String::new(),
+                body: String::new(),
+            })
+            .await;
+        assert!(matches!(result, Err(MailError::InvalidAddress(_))));
+    }
+}
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use lettre::{
+    message::Mailbox, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+    AsyncTransport, Tokio1Executor,
+};
+use server_config::MailConfig;
+
+use super::{build_message, parse_mailbox, Mail, MailSender};
+use crate::admin::sys_mail_error::MailError;
+
+/// 通过 SMTP 服务器发送
+pub struct SmtpMailSender {
+    from: Mailbox,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpMailSender {
+    pub fn new(config: &MailConfig) -> Result<Self, MailError> {
+        let host = config
+            .smtp_host
+            .as_deref()
+            .ok_or(MailError::SmtpHostNotConfigured)?;
+
+        let mut builder = if config.smtp_starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+                .map_err(|e| MailError::Build(e.to_string()))?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+        }
+        .port(config.smtp_port);
+
+        if let Some(username) = &config.smtp_username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                config.smtp_password.clone().unwrap_or_default(),
+            ));
+        }
+
+        Ok(Self {
+            from: parse_mailbox(&config.from)?,
+            transport: builder.build(),
+        })
+    }
+}
+
+#[async_trait]
+impl MailSender for SmtpMailSender {
+    async fn send(&self, mail: &Mail) -> Result<(), MailError> {
+        let message = build_message(&self.from, mail)?;
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| MailError::Send(e.to_string()))?;
+        Ok(())
+    }
+}
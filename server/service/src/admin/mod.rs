@@ -1,49 +1,73 @@
+pub use crate::helper::log_helper::LogExportStream;
 pub use errors::*;
+pub use event_handlers::auth_event_handler::AuthEvent;
 pub use server_model::admin::{
     entities::{
         prelude::{SysDomain, SysEndpoint, SysMenu, SysRole, SysUser},
         sys_access_key::Model as SysAccessKeyModel,
         sys_domain::Model as SysDomainModel,
         sys_endpoint::Model as SysEndpointModel,
+        sys_job_run::Model as SysJobRunModel,
         sys_login_log::Model as SysLoginLogModel,
+        sys_login_security_policy::Model as SysLoginSecurityPolicyModel,
         sys_menu::Model as SysMenuModel,
         sys_operation_log::Model as SysOperationLogModel,
         sys_organization::Model as SysOrganizationModel,
+        sys_permission_code::Model as SysPermissionCodeModel,
         sys_role::Model as SysRoleModel,
     },
     input::*,
     output::*,
 };
+pub use server_scheduler::{JobInfo, JobRun};
 pub use sys_access_key_service::{
-    api_key_validate_listener, SysAccessKeyService, TAccessKeyService,
+    access_key_sync_listener, api_key_validate_listener, SysAccessKeyService, TAccessKeyService,
 };
 pub use sys_auth_service::{
     auth_login_listener, jwt_created_listener, SysAuthService, TAuthService,
 };
-pub use sys_authorization_service::{SysAuthorizationService, TAuthorizationService};
+pub use sys_account_service::{SysAccountService, TAccountService};
+pub use sys_authorization_service::{
+    authorization_decision_listener, AuthorizationDecisionEvent, DatabaseDecisionSink,
+    SysAuthorizationService, TAuthorizationService,
+};
 pub use sys_domain_service::{SysDomainService, TDomainService};
 pub use sys_endpoint_service::{SysEndpointService, TEndpointService};
+pub use sys_job_service::{DatabaseJobStore, SysJobService, TJobService};
+pub use sys_log_retention_service::{LogArchiveTarget, LogRetentionReport, SysLogRetentionService};
 pub use sys_login_log_service::{SysLoginLogService, TLoginLogService};
+pub use sys_login_security_service::{SysLoginSecurityService, TLoginSecurityService};
 pub use sys_menu_service::{SysMenuService, TMenuService};
 pub use sys_operation_log_service::{
     sys_operation_log_listener, SysOperationLogService, TOperationLogService,
 };
 pub use sys_organization_service::{SysOrganizationService, TOrganizationService};
 pub use sys_role_service::{SysRoleService, TRoleService};
+pub use sys_tenant_service::tenant_switch_listener;
 pub use sys_user_service::{SysUserService, TUserService};
+pub use sys_user_totp_service::{SecondFactor, SysUserTotpService, TUserTotpService};
 pub mod dto;
 pub mod errors;
+pub mod external_login;
+pub mod mail;
+pub mod siwe;
 mod sys_access_key_service;
+mod sys_account_service;
 mod sys_auth_service;
 mod sys_authorization_service;
 mod sys_domain_service;
 mod sys_endpoint_service;
+mod sys_job_service;
+mod sys_log_retention_service;
 mod sys_login_log_service;
+mod sys_login_security_service;
 mod sys_menu_service;
 mod sys_operation_log_service;
 mod sys_organization_service;
 mod sys_role_service;
+mod sys_tenant_service;
 mod sys_user_service;
+mod sys_user_totp_service;
 
 mod event_handlers;
 mod events;
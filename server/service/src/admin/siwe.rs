@@ -0,0 +1,384 @@
+//! 以太坊钱包登录（Sign-In with Ethereum，EIP-4361）
+//!
+//! 服务端先签发一次性 nonce，前端据此构造标准 SIWE 消息交给钱包签名；
+//! 登录时解析消息，校验 domain、URI、链 ID、有效期和 nonce，再从签名中恢复地址，
+//! 只有恢复出的地址与消息中的地址一致时才视为持有该钱包。
+
+use std::sync::Arc;
+
+use alloy::primitives::Address;
+use chrono::{DateTime, FixedOffset, Utc};
+use rand::RngCore;
+use server_config::SiweConfig;
+use server_core::sign::{MemoryNonceStore, NonceStore, RedisNonceStore};
+use server_global::global::{self, GLOBAL_PRIMARY_REDIS};
+use tokio::sync::OnceCell;
+
+use crate::{admin::sys_siwe_error::SiweError, web3::alloy_provider::signature};
+
+const PREAMBLE_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+/// 客户端与服务端时钟允许的偏差（秒）
+const CLOCK_SKEW_SECS: i64 = 60;
+
+static NONCE_STORE: OnceCell<NonceStore> = OnceCell::const_new();
+
+/// 已配置 Redis 时使用 Redis 保存 nonce，以便多实例共享；否则使用内存
+async fn nonce_store() -> &'static NonceStore {
+    NONCE_STORE
+        .get_or_init(|| async {
+            if GLOBAL_PRIMARY_REDIS.read().await.is_some() {
+                NonceStore::Redis(Arc::new(RedisNonceStore::new("siwe")))
+            } else {
+                NonceStore::Memory(Arc::new(MemoryNonceStore::new()))
+            }
+        })
+        .await
+}
+
+/// 签发一次性 nonce，16 字节随机数的十六进制编码，满足 EIP-4361 要求的字母数字格式
+pub async fn issue_nonce() -> Result<String, SiweError> {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let nonce = hex::encode(bytes);
+
+    if !nonce_store().await.issue(&nonce).await {
+        return Err(SiweError::InvalidNonce);
+    }
+    Ok(nonce)
+}
+
+/// 消费 nonce，每个 nonce 只能用于一次登录
+pub async fn consume_nonce(nonce: &str) -> Result<(), SiweError> {
+    if nonce_store().await.consume(nonce).await {
+        Ok(())
+    } else {
+        Err(SiweError::InvalidNonce)
+    }
+}
+
+/// 未配置 `siwe` 时不开放钱包登录
+pub async fn config() -> Result<Arc<SiweConfig>, SiweError> {
+    global::get_config::<SiweConfig>()
+        .await
+        .ok_or(SiweError::NotEnabled)
+}
+
+/// 完整校验一次钱包签名：消息格式、站点、有效期、签名，最后消费 nonce
+///
+/// nonce 在签名验证通过后才消费，避免无效请求占用他人的 nonce。
+pub async fn verify_sign_in(message: &str, signature: &str) -> Result<SiweMessage, SiweError> {
+    let config = config().await?;
+    let parsed = SiweMessage::parse(message)?;
+    parsed.validate(&config, Utc::now())?;
+    parsed.verify_signature(message, signature)?;
+    consume_nonce(&parsed.nonce).await?;
+    Ok(parsed)
+}
+
+/// 解析后的 EIP-4361 消息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiweMessage {
+    pub scheme: Option<String>,
+    pub domain: String,
+    pub address: Address,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: DateTime<FixedOffset>,
+    pub expiration_time: Option<DateTime<FixedOffset>>,
+    pub not_before: Option<DateTime<FixedOffset>>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    pub fn parse(message: &str) -> Result<Self, SiweError> {
+        let invalid = |reason: &str| SiweError::InvalidMessage(reason.to_string());
+        let mut lines = message.lines().peekable();
+
+        let origin = lines
+            .next()
+            .and_then(|line| line.strip_suffix(PREAMBLE_SUFFIX))
+            .ok_or_else(|| invalid("missing preamble"))?;
+        let (scheme, domain) = match origin.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme.to_string()), domain),
+            None => (None, origin),
+        };
+        if domain.is_empty() || domain.contains(char::is_whitespace) {
+            return Err(invalid("invalid domain"));
+        }
+
+        let address = lines.next().ok_or_else(|| invalid("missing address"))?;
+        // EIP-4361 要求地址使用 EIP-55 校验和格式
+        let address = Address::parse_checksummed(address, None)
+            .map_err(|_| invalid("address must be EIP-55 checksummed"))?;
+
+        if lines.next() != Some("") {
+            return Err(invalid("missing blank line after address"));
+        }
+        let statement = match lines.peek() {
+            Some(line) if !line.is_empty() && !line.starts_with("URI: ") => {
+                let statement = lines.next().map(str::to_string);
+                if lines.next() != Some("") {
+                    return Err(invalid("missing blank line after statement"));
+                }
+                statement
+            },
+            Some(&"") => {
+                lines.next();
+                None
+            },
+            _ => None,
+        };
+
+        let mut field = |name: &str, required: bool| -> Result<Option<String>, SiweError> {
+            let prefix = format!("{}: ", name);
+            match lines
+                .peek()
+                .and_then(|line| line.strip_prefix(prefix.as_str()))
+            {
+                Some(value) => {
+                    let value = value.to_string();
+                    lines.next();
+                    Ok(Some(value))
+                },
+                None if required => Err(invalid(&format!("missing {}", name))),
+                None => Ok(None),
+            }
+        };
+
+        let uri = field("URI", true)?.unwrap_or_default();
+        let version = field("Version", true)?.unwrap_or_default();
+        let chain_id = field("Chain ID", true)?
+            .unwrap_or_default()
+            .parse::<u64>()
+            .map_err(|_| invalid("invalid Chain ID"))?;
+        let nonce = field("Nonce", true)?.unwrap_or_default();
+        let issued_at = field("Issued At", true)?.unwrap_or_default();
+        let expiration_time = field("Expiration Time", false)?;
+        let not_before = field("Not Before", false)?;
+        let request_id = field("Request ID", false)?;
+
+        let mut resources = Vec::new();
+        if lines.peek() == Some(&"Resources:") {
+            lines.next();
+            while let Some(resource) = lines.peek().and_then(|line| line.strip_prefix("- ")) {
+                resources.push(resource.to_string());
+                lines.next();
+            }
+        }
+        if lines.next().is_some() {
+            return Err(invalid("unexpected trailing content"));
+        }
+
+        if version != "1" {
+            return Err(invalid("unsupported version"));
+        }
+        if nonce.len() < 8 || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid("invalid nonce"));
+        }
+
+        let timestamp = |name: &str, value: &str| {
+            DateTime::parse_from_rfc3339(value).map_err(|_| invalid(&format!("invalid {}", name)))
+        };
+
+        Ok(Self {
+            scheme,
+            domain: domain.to_string(),
+            address,
+            statement,
+            uri,
+            version,
+            chain_id,
+            issued_at: timestamp("Issued At", &issued_at)?,
+            expiration_time: expiration_time
+                .map(|value| timestamp("Expiration Time", &value))
+                .transpose()?,
+            not_before: not_before
+                .map(|value| timestamp("Not Before", &value))
+                .transpose()?,
+            nonce,
+            request_id,
+            resources,
+        })
+    }
+
+    /// 校验消息是否签发给本站点且仍在有效期内，不包括 nonce 和签名
+    pub fn validate(&self, config: &SiweConfig, now: DateTime<Utc>) -> Result<(), SiweError> {
+        if self.domain != config.domain {
+            return Err(SiweError::DomainMismatch(self.domain.clone()));
+        }
+        if self.uri.trim_end_matches('/') != config.uri.trim_end_matches('/') {
+            return Err(SiweError::UriMismatch(self.uri.clone()));
+        }
+        if !config.chain_ids.contains(&self.chain_id) {
+            return Err(SiweError::ChainNotAllowed(self.chain_id));
+        }
+
+        let skew = chrono::Duration::seconds(CLOCK_SKEW_SECS);
+        if self
+            .expiration_time
+            .is_some_and(|expiration| expiration <= now)
+        {
+            return Err(SiweError::Expired);
+        }
+        if self.issued_at > now + skew
+            || self
+                .not_before
+                .is_some_and(|not_before| not_before > now + skew)
+        {
+            return Err(SiweError::NotYetValid);
+        }
+        Ok(())
+    }
+
+    /// 从 EIP-191 签名中恢复签名地址，并与消息中的地址比较
+    pub fn verify_signature(&self, message: &str, signature: &str) -> Result<(), SiweError> {
+        let recovered = signature::recover_signer(message, signature)
+            .map_err(|_| SiweError::InvalidSignature)?;
+
+        if recovered.eq_ignore_ascii_case(&self.address.to_string()) {
+            Ok(())
+        } else {
+            Err(SiweError::InvalidSignature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::signers::{local::PrivateKeySigner, SignerSync};
+
+    use super::*;
+
+    fn config() -> SiweConfig {
+        SiweConfig {
+            domain: "admin.example.com".to_string(),
+            uri: "https://admin.example.com".to_string(),
+            chain_ids: vec![1],
+        }
+    }
+
+    fn message(address: &Address, expiration: &str) -> String {
+        format!(
+            "admin.example.com wants you to sign in with your Ethereum account:\n\
+             {address}\n\
+             \n\
+             Sign in to Soybean Admin\n\
+             \n\
+             URI: https://admin.example.com\n\
+             Version: 1\n\
+             Chain ID: 1\n\
+             Nonce: 32891756a1b2c3d4\n\
+             Issued At: 2026-10-17T08:00:00Z\n\
+             Expiration Time: {expiration}\n\
+             Resources:\n\
+             - https://admin.example.com/terms"
+        )
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2026-10-17T08:01:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_validate_message() {
+        let signer = PrivateKeySigner::random();
+        let parsed =
+            SiweMessage::parse(&message(&signer.address(), "2026-10-17T08:10:00Z")).unwrap();
+
+        assert_eq!(parsed.address, signer.address());
+        assert_eq!(
+            parsed.statement.as_deref(),
+            Some("Sign in to Soybean Admin")
+        );
+        assert_eq!(parsed.nonce, "32891756a1b2c3d4");
+        assert_eq!(parsed.resources, vec!["https://admin.example.com/terms"]);
+        assert!(parsed.validate(&config(), now()).is_ok());
+
+        let mut other_chain = config();
+        other_chain.chain_ids = vec![137];
+        assert!(matches!(
+            parsed.validate(&other_chain, now()),
+            Err(SiweError::ChainNotAllowed(1))
+        ));
+
+        let mut other_domain = config();
+        other_domain.domain = "evil.example.com".to_string();
+        assert!(matches!(
+            parsed.validate(&other_domain, now()),
+            Err(SiweError::DomainMismatch(_))
+        ));
+
+        let expired =
+            SiweMessage::parse(&message(&signer.address(), "2026-10-17T08:00:30Z")).unwrap();
+        assert!(matches!(
+            expired.validate(&config(), now()),
+            Err(SiweError::Expired)
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_message() {
+        let signer = PrivateKeySigner::random();
+        let valid = message(&signer.address(), "2026-10-17T08:10:00Z");
+
+        let lowercase = valid.replace(
+            &signer.address().to_string(),
+            &signer.address().to_string().to_lowercase(),
+        );
+        assert!(SiweMessage::parse(&lowercase).is_err());
+        assert!(SiweMessage::parse(&valid.replace("Version: 1", "Version: 2")).is_err());
+        assert!(SiweMessage::parse(&valid.replace("32891756a1b2c3d4", "short")).is_err());
+        assert!(SiweMessage::parse(&valid.replace("Nonce: ", "Nonse: ")).is_err());
+    }
+
+    #[test]
+    fn test_parse_message_without_statement() {
+        let signer = PrivateKeySigner::random();
+        let message = format!(
+            "https://admin.example.com wants you to sign in with your Ethereum account:\n\
+             {}\n\n\n\
+             URI: https://admin.example.com/\n\
+             Version: 1\n\
+             Chain ID: 1\n\
+             Nonce: abcdef0123456789\n\
+             Issued At: 2026-10-17T08:00:00.000Z",
+            signer.address()
+        );
+
+        let parsed = SiweMessage::parse(&message).unwrap();
+        assert_eq!(parsed.scheme.as_deref(), Some("https"));
+        assert_eq!(parsed.statement, None);
+        assert_eq!(parsed.expiration_time, None);
+        assert!(parsed.validate(&config(), now()).is_ok());
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let signer = PrivateKeySigner::random();
+        let text = message(&signer.address(), "2026-10-17T08:10:00Z");
+        let parsed = SiweMessage::parse(&text).unwrap();
+
+        let signature = signer.sign_message_sync(text.as_bytes()).unwrap();
+        let signature = format!("0x{}", hex::encode(signature.as_bytes()));
+        assert!(parsed.verify_signature(&text, &signature).is_ok());
+
+        let tampered = text.replace("Chain ID: 1", "Chain ID: 5");
+        assert!(matches!(
+            parsed.verify_signature(&tampered, &signature),
+            Err(SiweError::InvalidSignature)
+        ));
+
+        let other = PrivateKeySigner::random()
+            .sign_message_sync(text.as_bytes())
+            .unwrap();
+        let other = format!("0x{}", hex::encode(other.as_bytes()));
+        assert!(matches!(
+            parsed.verify_signature(&text, &other),
+            Err(SiweError::InvalidSignature)
+        ));
+    }
+}
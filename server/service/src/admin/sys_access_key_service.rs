@@ -1,45 +1,110 @@
-use std::any::Any;
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
-use chrono::Local;
+use axum_casbin::casbin::RbacApi;
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use futures::StreamExt;
+use ipnet::IpNet;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseTransaction, EntityTrait, PaginatorTrait,
-    QueryFilter, Set, TransactionTrait,
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, DatabaseTransaction, EntityTrait,
+    IntoActiveModel, PaginatorTrait, QueryFilter, Set, TransactionTrait,
 };
+use serde::{Deserialize, Serialize};
+use server_config::AccessKeyConfig;
 use server_core::{
-    sign::{ApiKeyEvent, ValidatorType},
-    web::{auth::User, error::AppError, page::PaginatedData},
+    sign::{access_key_subject, ApiKeyEvent, ApiKeyScope, SignatureScheme, ValidatorType},
+    web::{auth::User, error::AppError, page::PaginatedData, tenant::TenantContext},
 };
-use server_global::project_info;
+use server_global::{global, project_error, project_info};
 use server_model::admin::{
     entities::{
-        prelude::SysAccessKey,
+        prelude::{SysAccessKey, SysEndpoint},
+        sea_orm_active_enums::Status,
         sys_access_key::{
             ActiveModel as SysAccessKeyActiveModel, Column as SysAccessKeyColumn,
             Model as SysAccessKeyModel,
         },
+        sys_endpoint::Column as SysEndpointColumn,
+    },
+    input::{
+        AccessKeyInput, AccessKeyPageRequest, AssignAccessKeyPermissionInput, CreateAccessKeyInput,
+        RotateAccessKeyInput, UpdateAccessKeyInput,
     },
-    input::{AccessKeyPageRequest, CreateAccessKeyInput},
 };
+use server_utils::CipherUtil;
+use tokio::sync::RwLock;
 use tracing::instrument;
 use ulid::Ulid;
 
-use crate::helper::db_helper;
+use crate::helper::{
+    db_helper, redis_helper,
+    tenant_helper::{self, TenantQuery},
+};
 
 use super::sys_access_key_error::AccessKeyError;
 
+/// 访问密钥变更通知频道，各实例据此同步内存中的验证器
+const ACCESS_KEY_CHANNEL: &str = "access_key:changes";
+/// 列表中返回的密钥占位符，明文只在创建时返回一次
+const MASKED_SECRET: &str = "********";
+/// 轮换后旧密钥默认的宽限期（秒）
+const DEFAULT_ROTATION_GRACE_PERIOD: i64 = 86400;
+/// 同一密钥最近使用时间的最小写库间隔
+const LAST_USED_RECORD_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 访问密钥变更通知
+///
+/// 只广播 `access_key_id`，各实例收到后从数据库重新加载，避免密钥明文出现在 Redis 中。
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum AccessKeyChange {
+    Upsert { access_key_id: String },
+    Remove { access_key_id: String },
+}
+
 #[async_trait]
 pub trait TAccessKeyService {
     async fn find_paginated_access_keys(
         &self,
         params: AccessKeyPageRequest,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<SysAccessKeyModel>, AppError>;
     async fn create_access_key(
         &self,
         input: CreateAccessKeyInput,
         user: User,
+        tenant: &TenantContext,
     ) -> Result<SysAccessKeyModel, AppError>;
-    async fn delete_access_key(&self, id: &str) -> Result<(), AppError>;
+    async fn update_access_key(
+        &self,
+        input: UpdateAccessKeyInput,
+        tenant: &TenantContext,
+    ) -> Result<SysAccessKeyModel, AppError>;
+    async fn rotate_access_key(
+        &self,
+        input: RotateAccessKeyInput,
+        tenant: &TenantContext,
+    ) -> Result<SysAccessKeyModel, AppError>;
+    async fn delete_access_key(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<(), AppError>;
+
+    /// 为访问密钥分配可访问的接口，策略主体为 `ak:<AccessKeyId>`
+    async fn assign_permissions(
+        &self,
+        input: AssignAccessKeyPermissionInput,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<(), AppError>;
 
     async fn initialize_access_key(&self) -> Result<(), AppError>;
 }
@@ -53,27 +118,18 @@ impl SysAccessKeyService {
         txn: &DatabaseTransaction,
         access_key: SysAccessKeyActiveModel,
     ) -> Result<SysAccessKeyModel, AppError> {
-        let result = access_key.insert(txn).await.map_err(AppError::from)?;
-
-        // 添加到验证器
-        server_core::sign::add_key(ValidatorType::Simple, &result.access_key_id, None).await;
-        server_core::sign::add_key(
-            ValidatorType::Complex,
-            &result.access_key_id,
-            Some(&result.access_key_secret),
-        )
-        .await;
-
-        Ok(result)
+        access_key.insert(txn).await.map_err(AppError::from)
     }
 
     async fn delete_access_key_in_transaction(
         &self,
         txn: &DatabaseTransaction,
         id: &str,
-    ) -> Result<(), AppError> {
+        tenant: &TenantContext,
+    ) -> Result<SysAccessKeyModel, AppError> {
         // 先获取 access key 信息
         let access_key = SysAccessKey::find_by_id(id)
+            .in_tenant(tenant)
             .one(txn)
             .await
             .map_err(AppError::from)?
@@ -85,11 +141,40 @@ impl SysAccessKeyService {
             .await
             .map_err(AppError::from)?;
 
-        // 从验证器中移除
-        server_core::sign::remove_key(ValidatorType::Simple, &access_key.access_key_id).await;
-        server_core::sign::remove_key(ValidatorType::Complex, &access_key.access_key_id).await;
+        Ok(access_key)
+    }
 
-        Ok(())
+    /// 将已过期的启用密钥置为禁用并从各实例的验证器中移除，返回处理的密钥数
+    pub async fn disable_expired_access_keys(&self) -> Result<u64, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let now = Local::now().naive_local();
+
+        let expired = SysAccessKey::find()
+            .filter(SysAccessKeyColumn::Status.eq(Status::Enabled))
+            .filter(SysAccessKeyColumn::ExpiresAt.lte(now))
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        SysAccessKey::update_many()
+            .col_expr(SysAccessKeyColumn::Status, Expr::value(Status::Disabled))
+            .filter(SysAccessKeyColumn::Id.is_in(expired.iter().map(|key| key.id.clone())))
+            .exec(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        for access_key in &expired {
+            project_info!("Access key {} expired and disabled", access_key.access_key_id);
+            broadcast_change(AccessKeyChange::Remove {
+                access_key_id: access_key.access_key_id.clone(),
+            })
+            .await;
+        }
+
+        Ok(expired.len() as u64)
     }
 }
 
@@ -98,9 +183,10 @@ impl TAccessKeyService for SysAccessKeyService {
     async fn find_paginated_access_keys(
         &self,
         params: AccessKeyPageRequest,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<SysAccessKeyModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysAccessKey::find();
+        let mut query = tenant_helper::find::<SysAccessKey>(tenant);
 
         if let Some(ref keywords) = params.keywords {
             let condition = Condition::any().add(SysAccessKeyColumn::Domain.contains(keywords));
@@ -117,7 +203,13 @@ impl TAccessKeyService for SysAccessKeyService {
         let records = paginator
             .fetch_page(params.page_details.current - 1)
             .await
-            .map_err(AppError::from)?;
+            .map_err(AppError::from)?
+            .into_iter()
+            .map(|record| SysAccessKeyModel {
+                access_key_secret: MASKED_SECRET.to_string(),
+                ..record
+            })
+            .collect();
 
         Ok(PaginatedData {
             current: params.page_details.current,
@@ -131,7 +223,11 @@ impl TAccessKeyService for SysAccessKeyService {
         &self,
         input: CreateAccessKeyInput,
         user: User,
+        tenant: &TenantContext,
     ) -> Result<SysAccessKeyModel, AppError> {
+        tenant_helper::ensure_domain(tenant, &input.domain)?;
+        let scope = scope_columns(&input)?;
+        let cipher = secret_cipher().await?;
         let db = db_helper::get_db_connection().await?;
         let txn = db.begin().await.map_err(AppError::from)?;
 
@@ -144,9 +240,15 @@ impl TAccessKeyService for SysAccessKeyService {
             status: Set(input.status),
             description: Set(input.description),
             access_key_id: Set(access_key_id),
-            access_key_secret: Set(access_key_secret),
+            access_key_secret: Set(encrypt_secret(&cipher, &access_key_secret)?),
             created_at: Set(Local::now().naive_local()),
             created_by: Set(user.user_id()),
+            expires_at: Set(scope.expires_at),
+            allowed_ips: Set(scope.allowed_ips),
+            signature_schemes: Set(scope.signature_schemes),
+            last_used_at: Set(None),
+            previous_access_key_secret: Set(None),
+            previous_secret_expires_at: Set(None),
         };
 
         let result = match self
@@ -163,55 +265,658 @@ impl TAccessKeyService for SysAccessKeyService {
             },
         };
 
-        Ok(result)
+        broadcast_change(AccessKeyChange::Upsert {
+            access_key_id: result.access_key_id.clone(),
+        })
+        .await;
+
+        // 明文只在创建时返回一次
+        Ok(SysAccessKeyModel {
+            access_key_secret,
+            ..result
+        })
     }
 
-    async fn delete_access_key(&self, id: &str) -> Result<(), AppError> {
+    async fn update_access_key(
+        &self,
+        input: UpdateAccessKeyInput,
+        tenant: &TenantContext,
+    ) -> Result<SysAccessKeyModel, AppError> {
+        tenant_helper::ensure_domain(tenant, &input.access_key.domain)?;
+        let scope = scope_columns(&input.access_key)?;
+        let db = db_helper::get_db_connection().await?;
+
+        let mut access_key = SysAccessKey::find_by_id(&input.id)
+            .in_tenant(tenant)
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or_else(|| AppError::from(AccessKeyError::AccessKeyNotFound))?
+            .into_active_model();
+
+        access_key.domain = Set(input.access_key.domain);
+        access_key.status = Set(input.access_key.status);
+        access_key.description = Set(input.access_key.description);
+        access_key.expires_at = Set(scope.expires_at);
+        access_key.allowed_ips = Set(scope.allowed_ips);
+        access_key.signature_schemes = Set(scope.signature_schemes);
+
+        let result = access_key
+            .update(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        // 禁用的密钥会在各实例的验证器中移除
+        broadcast_change(AccessKeyChange::Upsert {
+            access_key_id: result.access_key_id.clone(),
+        })
+        .await;
+
+        Ok(SysAccessKeyModel {
+            access_key_secret: MASKED_SECRET.to_string(),
+            ..result
+        })
+    }
+
+    /// 生成新的密钥，旧密钥在宽限期内仍可用于签名
+    async fn rotate_access_key(
+        &self,
+        input: RotateAccessKeyInput,
+        tenant: &TenantContext,
+    ) -> Result<SysAccessKeyModel, AppError> {
+        let cipher = secret_cipher().await?;
+        let db = db_helper::get_db_connection().await?;
+
+        let access_key = SysAccessKey::find_by_id(&input.id)
+            .in_tenant(tenant)
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or_else(|| AppError::from(AccessKeyError::AccessKeyNotFound))?;
+
+        let grace_period = input.grace_period.unwrap_or(DEFAULT_ROTATION_GRACE_PERIOD);
+        let previous_secret = if CipherUtil::is_encrypted(&access_key.access_key_secret) {
+            access_key.access_key_secret.clone()
+        } else {
+            encrypt_secret(&cipher, &access_key.access_key_secret)?
+        };
+        let access_key_secret = format!("SK{}", Ulid::new().to_string());
+
+        let mut active = access_key.into_active_model();
+        active.access_key_secret = Set(encrypt_secret(&cipher, &access_key_secret)?);
+        if grace_period > 0 {
+            active.previous_access_key_secret = Set(Some(previous_secret));
+            active.previous_secret_expires_at = Set(Some(
+                Local::now().naive_local() + TimeDelta::seconds(grace_period),
+            ));
+        } else {
+            active.previous_access_key_secret = Set(None);
+            active.previous_secret_expires_at = Set(None);
+        }
+
+        let result = active.update(db.as_ref()).await.map_err(AppError::from)?;
+
+        broadcast_change(AccessKeyChange::Upsert {
+            access_key_id: result.access_key_id.clone(),
+        })
+        .await;
+
+        // 新密钥明文只在轮换时返回一次
+        Ok(SysAccessKeyModel {
+            access_key_secret,
+            ..result
+        })
+    }
+
+    async fn delete_access_key(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<(), AppError> {
         let db = db_helper::get_db_connection().await?;
         let txn = db.begin().await.map_err(AppError::from)?;
 
-        match self.delete_access_key_in_transaction(&txn, id).await {
-            Ok(_) => {
+        let access_key = match self
+            .delete_access_key_in_transaction(&txn, id, tenant)
+            .await
+        {
+            Ok(access_key) => {
                 txn.commit().await.map_err(AppError::from)?;
-                Ok(())
+                access_key
             },
             Err(e) => {
                 txn.rollback().await.map_err(AppError::from)?;
-                Err(e)
+                return Err(e);
             },
-        }
+        };
+
+        enforcer
+            .write()
+            .await
+            .remove_filtered_policy(0, vec![access_key_subject(&access_key.access_key_id)])
+            .await
+            .map_err(|e| AccessKeyError::PolicyUpdateFailed(e.to_string()))?;
+
+        broadcast_change(AccessKeyChange::Remove {
+            access_key_id: access_key.access_key_id,
+        })
+        .await;
+
+        Ok(())
     }
 
-    async fn initialize_access_key(&self) -> Result<(), AppError> {
+    async fn assign_permissions(
+        &self,
+        input: AssignAccessKeyPermissionInput,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<(), AppError> {
         let db = db_helper::get_db_connection().await?;
 
-        let access_keys = SysAccessKey::find()
+        let access_key = SysAccessKey::find_by_id(&input.id)
+            .in_tenant(tenant)
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or_else(|| AppError::from(AccessKeyError::AccessKeyNotFound))?;
+
+        let mut permission_ids = input.permissions;
+        permission_ids.sort_unstable();
+        permission_ids.dedup();
+
+        let endpoints = SysEndpoint::find()
+            .filter(SysEndpointColumn::Id.is_in(permission_ids.clone()))
             .all(db.as_ref())
             .await
             .map_err(AppError::from)?;
 
-        for access_key in access_keys {
-            server_core::sign::add_key(ValidatorType::Simple, &access_key.access_key_id, None)
-                .await;
-            server_core::sign::add_key(
-                ValidatorType::Complex,
-                &access_key.access_key_id,
-                Some(&access_key.access_key_secret),
-            )
-            .await;
+        if endpoints.len() != permission_ids.len() {
+            return Err(AccessKeyError::PermissionsNotFound.into());
+        }
+
+        let subject = access_key_subject(&access_key.access_key_id);
+        let new_policies: Vec<Vec<String>> = endpoints
+            .iter()
+            .map(|endpoint| {
+                vec![
+                    subject.clone(),
+                    access_key.domain.clone(),
+                    endpoint.path.clone(),
+                    endpoint.method.clone(),
+                ]
+            })
+            .collect();
+
+        let mut enforcer_write = enforcer.write().await;
+        let existing_policies =
+            enforcer_write.get_filtered_policy(0, vec![subject, access_key.domain.clone()]);
+
+        let policies_to_remove: Vec<Vec<String>> = existing_policies
+            .iter()
+            .filter(|policy| !new_policies.contains(policy))
+            .cloned()
+            .collect();
+
+        let policies_to_add: Vec<Vec<String>> = new_policies
+            .iter()
+            .filter(|policy| !existing_policies.contains(policy))
+            .cloned()
+            .collect();
+
+        if !policies_to_remove.is_empty() {
+            enforcer_write
+                .remove_policies(policies_to_remove)
+                .await
+                .map_err(|e| AccessKeyError::PolicyUpdateFailed(e.to_string()))?;
+        }
+
+        if !policies_to_add.is_empty() {
+            enforcer_write
+                .add_policies(policies_to_add)
+                .await
+                .map_err(|e| AccessKeyError::PolicyUpdateFailed(e.to_string()))?;
         }
 
         Ok(())
     }
+
+    /// 启动时加载所有启用的访问密钥
+    async fn initialize_access_key(&self) -> Result<(), AppError> {
+        reload_access_keys().await
+    }
+}
+
+/// 从数据库重新加载所有启用的访问密钥，并卸载之前加载、现已删除或停用的密钥
+///
+/// 历史数据中的明文密钥会在加载时加密回写。
+async fn reload_access_keys() -> Result<(), AppError> {
+    let db = db_helper::get_db_connection().await?;
+    let cipher = secret_cipher().await.ok();
+
+    let access_keys = SysAccessKey::find()
+        .filter(SysAccessKeyColumn::Status.eq(Status::Enabled))
+        .all(db.as_ref())
+        .await
+        .map_err(AppError::from)?;
+
+    let mut loaded = HashSet::new();
+    for access_key in access_keys {
+        let previous_secret = cipher
+            .as_ref()
+            .and_then(|cipher| previous_secret(cipher, &access_key));
+        let secret = if CipherUtil::is_encrypted(&access_key.access_key_secret) {
+            match cipher
+                .as_ref()
+                .ok_or(AccessKeyError::EncryptionKeyNotConfigured)
+                .and_then(|cipher| decrypt_secret(cipher, &access_key.access_key_secret))
+            {
+                Ok(secret) => secret,
+                Err(e) => {
+                    project_error!("Skipping access key {}: {}", access_key.access_key_id, e);
+                    continue;
+                },
+            }
+        } else {
+            let secret = access_key.access_key_secret.clone();
+            if let Some(cipher) = cipher.as_ref() {
+                let encrypted = encrypt_secret(cipher, &secret)?;
+                let mut active = access_key.clone().into_active_model();
+                active.access_key_secret = Set(encrypted);
+                active.update(db.as_ref()).await.map_err(AppError::from)?;
+                project_info!(
+                    "Encrypted legacy secret of access key {}",
+                    access_key.access_key_id
+                );
+            }
+            secret
+        };
+
+        load_key(&access_key, &secret, previous_secret).await;
+        loaded.insert(access_key.access_key_id);
+    }
+
+    let stale: Vec<String> = LOADED_KEYS
+        .lock()
+        .unwrap()
+        .difference(&loaded)
+        .cloned()
+        .collect();
+    for access_key_id in &stale {
+        unload_key(access_key_id).await;
+    }
+
+    project_info!(
+        "Loaded {} enabled access keys, unloaded {} stale keys",
+        loaded.len(),
+        stale.len()
+    );
+    Ok(())
+}
+
+async fn secret_cipher() -> Result<CipherUtil, AppError> {
+    let config = global::get_config::<AccessKeyConfig>()
+        .await
+        .ok_or(AccessKeyError::EncryptionKeyNotConfigured)?;
+    CipherUtil::new(&config.encryption_key)
+        .map_err(|e| AccessKeyError::SecretCipherFailed(e.to_string()).into())
+}
+
+fn encrypt_secret(cipher: &CipherUtil, secret: &str) -> Result<String, AppError> {
+    cipher
+        .encrypt(secret)
+        .map_err(|e| AccessKeyError::SecretCipherFailed(e.to_string()).into())
+}
+
+fn decrypt_secret(cipher: &CipherUtil, secret: &str) -> Result<String, AccessKeyError> {
+    cipher
+        .decrypt(secret)
+        .map_err(|e| AccessKeyError::SecretCipherFailed(e.to_string()))
+}
+
+/// 访问密钥使用范围相关的列
+struct ScopeColumns {
+    expires_at: Option<NaiveDateTime>,
+    allowed_ips: Option<serde_json::Value>,
+    signature_schemes: Option<serde_json::Value>,
+}
+
+/// 校验并转换输入中的过期时间、来源 IP 和签名方式限制
+fn scope_columns(input: &AccessKeyInput) -> Result<ScopeColumns, AppError> {
+    if input
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Local::now().naive_local())
+    {
+        return Err(AccessKeyError::InvalidExpiration.into());
+    }
+
+    let allowed_ips = match input.allowed_ips.as_deref() {
+        Some(values) if !values.is_empty() => {
+            let nets: Vec<String> = parse_allowed_ips(values)?
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            Some(serde_json::json!(nets))
+        },
+        _ => None,
+    };
+
+    let signature_schemes = input
+        .signature_schemes
+        .as_ref()
+        .filter(|schemes| !schemes.is_empty())
+        .map(|schemes| serde_json::json!(schemes));
+
+    Ok(ScopeColumns {
+        expires_at: input.expires_at,
+        allowed_ips,
+        signature_schemes,
+    })
+}
+
+fn parse_allowed_ips(values: &[String]) -> Result<Vec<IpNet>, AccessKeyError> {
+    values
+        .iter()
+        .map(|value| {
+            let value = value.trim();
+            IpNet::from_str(value)
+                .or_else(|_| IpAddr::from_str(value).map(IpNet::from))
+                .map_err(|_| AccessKeyError::InvalidAllowedIp(value.to_string()))
+        })
+        .collect()
+}
+
+/// 数据库中的本地时间转换为毫秒时间戳
+fn local_millis(datetime: NaiveDateTime) -> i64 {
+    datetime
+        .and_local_timezone(Local)
+        .earliest()
+        .map_or(i64::MIN, |datetime| datetime.timestamp_millis())
 }
 
-#[instrument(skip(rx))]
-pub async fn api_key_validate_listener(
-    mut rx: tokio::sync::mpsc::UnboundedReceiver<Box<dyn Any + Send>>,
+fn key_scope(access_key: &SysAccessKeyModel) -> Result<ApiKeyScope, AccessKeyError> {
+    let allowed_ips = match access_key.allowed_ips.clone() {
+        Some(value) => serde_json::from_value::<Vec<String>>(value)
+            .map_err(|e| AccessKeyError::InvalidAllowedIp(e.to_string()))?,
+        None => Vec::new(),
+    };
+    let signature_schemes = match access_key.signature_schemes.clone() {
+        Some(value) => serde_json::from_value::<Vec<SignatureScheme>>(value)
+            .map_err(|e| AccessKeyError::InvalidSignatureScheme(e.to_string()))?,
+        None => Vec::new(),
+    };
+
+    Ok(ApiKeyScope {
+        domain: access_key.domain.clone(),
+        expires_at: access_key.expires_at.map(local_millis),
+        allowed_ips: parse_allowed_ips(&allowed_ips)?,
+        signature_schemes,
+    })
+}
+
+/// 宽限期内的旧密钥及其失效时间（毫秒时间戳）
+fn previous_secret(cipher: &CipherUtil, access_key: &SysAccessKeyModel) -> Option<(String, i64)> {
+    let secret = access_key.previous_access_key_secret.as_ref()?;
+    let valid_until = local_millis(access_key.previous_secret_expires_at?);
+    if valid_until <= Local::now().timestamp_millis() {
+        return None;
+    }
+
+    match decrypt_secret(cipher, secret) {
+        Ok(secret) => Some((secret, valid_until)),
+        Err(e) => {
+            project_error!(
+                "Ignoring previous secret of access key {}: {}",
+                access_key.access_key_id,
+                e
+            );
+            None
+        },
+    }
+}
+
+async fn load_key(
+    access_key: &SysAccessKeyModel,
+    secret: &str,
+    previous_secret: Option<(String, i64)>,
 ) {
-    while let Some(event) = rx.recv().await {
-        if let Some(api_key_event) = event.downcast_ref::<ApiKeyEvent>() {
-            project_info!("API key validated: {:?}", api_key_event);
+    let access_key_id = access_key.access_key_id.as_str();
+    // 使用范围无法解析时不加载，避免限制被静默放开
+    let scope = match key_scope(access_key) {
+        Ok(scope) => scope,
+        Err(e) => {
+            project_error!("Skipping access key {}: {}", access_key_id, e);
+            unload_key(access_key_id).await;
+            return;
+        },
+    };
+    server_core::sign::set_key_scope(access_key_id, scope);
+    LOADED_KEYS.lock().unwrap().insert(access_key_id.to_string());
+    server_core::sign::add_key(ValidatorType::Simple, access_key_id, None).await;
+    server_core::sign::add_key(ValidatorType::Complex, access_key_id, Some(secret)).await;
+    match previous_secret {
+        Some((previous, valid_until)) => {
+            server_core::sign::set_previous_secret(access_key_id, &previous, valid_until).await
+        },
+        None => server_core::sign::remove_previous_secret(access_key_id).await,
+    }
+}
+
+async fn unload_key(access_key_id: &str) {
+    LOADED_KEYS.lock().unwrap().remove(access_key_id);
+    server_core::sign::remove_key(ValidatorType::Simple, access_key_id).await;
+    server_core::sign::remove_key(ValidatorType::Complex, access_key_id).await;
+    server_core::sign::remove_key_scope(access_key_id);
+}
+
+/// 将变更应用到当前实例的验证器
+async fn apply_change(change: &AccessKeyChange) -> Result<(), AppError> {
+    match change {
+        AccessKeyChange::Upsert { access_key_id } => {
+            let db = db_helper::get_db_connection().await?;
+            let access_key = SysAccessKey::find()
+                .filter(SysAccessKeyColumn::AccessKeyId.eq(access_key_id))
+                .one(db.as_ref())
+                .await
+                .map_err(AppError::from)?;
+
+            match access_key {
+                Some(access_key) if access_key.status == Status::Enabled => {
+                    let cipher = secret_cipher().await?;
+                    let secret = decrypt_secret(&cipher, &access_key.access_key_secret)?;
+                    let previous_secret = previous_secret(&cipher, &access_key);
+                    load_key(&access_key, &secret, previous_secret).await;
+                },
+                _ => unload_key(access_key_id).await,
+            }
+        },
+        AccessKeyChange::Remove { access_key_id } => unload_key(access_key_id).await,
+    }
+    Ok(())
+}
+
+/// 立即更新本实例，再通过 Redis 通知其他实例；未配置 Redis 时只更新本实例
+async fn broadcast_change(change: AccessKeyChange) {
+    if let Err(e) = apply_change(&change).await {
+        project_error!("Failed to apply access key change {:?}: {:?}", change, e);
+    }
+
+    if global::GLOBAL_PRIMARY_REDIS.read().await.is_none() {
+        return;
+    }
+    let payload = match serde_json::to_string(&change) {
+        Ok(payload) => payload,
+        Err(e) => {
+            project_error!("Failed to serialize access key change: {}", e);
+            return;
+        },
+    };
+    if let Err(e) = redis_helper::publish(ACCESS_KEY_CHANNEL, &payload).await {
+        project_error!("Failed to publish access key change: {:?}", e);
+    }
+}
+
+/// 订阅其他实例发出的访问密钥变更，连接断开后自动重连
+pub async fn access_key_sync_listener() {
+    if global::GLOBAL_PRIMARY_REDIS.read().await.is_none() {
+        project_info!("Redis not configured, access key changes are not synchronized");
+        return;
+    }
+
+    loop {
+        match redis_helper::subscribe(ACCESS_KEY_CHANNEL).await {
+            Ok(pubsub) => {
+                project_info!("Subscribed to access key changes");
+                // 断开期间发出的变更已经丢失，订阅成功后按数据库完整重新加载
+                if let Err(e) = reload_access_keys().await {
+                    project_error!("Failed to reload access keys: {:?}", e);
+                }
+                let mut messages = pubsub.into_on_message();
+                while let Some(message) = messages.next().await {
+                    let change = message
+                        .get_payload::<String>()
+                        .ok()
+                        .and_then(|payload| serde_json::from_str::<AccessKeyChange>(&payload).ok());
+                    match change {
+                        Some(change) => {
+                            if let Err(e) = apply_change(&change).await {
+                                project_error!(
+                                    "Failed to apply access key change {:?}: {:?}",
+                                    change,
+                                    e
+                                );
+                            }
+                        },
+                        None => project_error!("Ignoring malformed access key change"),
+                    }
+                }
+                project_error!("Access key change subscription closed, reconnecting");
+            },
+            Err(e) => project_error!("Failed to subscribe to access key changes: {:?}", e),
         }
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+}
+
+lazy_static::lazy_static! {
+    /// 从数据库加载到本实例的密钥，不包含配置文件中的静态密钥
+    static ref LOADED_KEYS: std::sync::Mutex<HashSet<String>> =
+        std::sync::Mutex::new(HashSet::new());
+
+    /// 各密钥最近一次写库的时间
+    static ref LAST_RECORDED: std::sync::Mutex<HashMap<String, Instant>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+#[instrument(skip(api_key_event))]
+pub async fn api_key_validate_listener(api_key_event: ApiKeyEvent) -> Result<(), String> {
+    project_info!("API key validated: {:?}", api_key_event);
+
+    // 高频调用时按间隔合并写库
+    let now = Instant::now();
+    if LAST_RECORDED
+        .lock()
+        .unwrap()
+        .get(&api_key_event.api_key)
+        .is_some_and(|recorded| now.duration_since(*recorded) < LAST_USED_RECORD_INTERVAL)
+    {
+        return Ok(());
+    }
+
+    record_last_used(&api_key_event.api_key)
+        .await
+        .map_err(|e| e.message)?;
+    LAST_RECORDED
+        .lock()
+        .unwrap()
+        .insert(api_key_event.api_key, now);
+    Ok(())
+}
+
+/// 更新访问密钥的最近使用时间
+async fn record_last_used(access_key_id: &str) -> Result<(), AppError> {
+    let db = db_helper::get_db_connection().await?;
+    SysAccessKey::update_many()
+        .col_expr(
+            SysAccessKeyColumn::LastUsedAt,
+            Expr::value(Local::now().naive_local()),
+        )
+        .filter(SysAccessKeyColumn::AccessKeyId.eq(access_key_id))
+        .exec(db.as_ref())
+        .await
+        .map_err(AppError::from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_key_change_payload() {
+        let payload = serde_json::to_string(&AccessKeyChange::Remove {
+            access_key_id: "AK01".to_string(),
+        })
+        .unwrap();
+        assert_eq!(payload, r#"{"action":"remove","access_key_id":"AK01"}"#);
+
+        let change: AccessKeyChange =
+            serde_json::from_str(r#"{"action":"upsert","access_key_id":"AK02"}"#).unwrap();
+        assert!(matches!(
+            change,
+            AccessKeyChange::Upsert { access_key_id } if access_key_id == "AK02"
+        ));
+    }
+
+    #[test]
+    fn test_key_scope() {
+        let input = AccessKeyInput {
+            domain: "built-in".to_string(),
+            status: Status::Enabled,
+            description: None,
+            expires_at: None,
+            allowed_ips: Some(vec!["10.0.0.1".to_string(), " 192.168.0.0/16 ".to_string()]),
+            signature_schemes: Some(vec![SignatureScheme::Canonical]),
+        };
+        let columns = scope_columns(&input).unwrap();
+        assert_eq!(
+            columns.allowed_ips,
+            Some(serde_json::json!(["10.0.0.1/32", "192.168.0.0/16"]))
+        );
+        assert_eq!(
+            columns.signature_schemes,
+            Some(serde_json::json!(["canonical"]))
+        );
+
+        let expires_at = Local::now().naive_local() + TimeDelta::hours(1);
+        let access_key = SysAccessKeyModel {
+            id: "01".to_string(),
+            domain: "built-in".to_string(),
+            access_key_id: "AK01".to_string(),
+            access_key_secret: MASKED_SECRET.to_string(),
+            status: Status::Enabled,
+            description: None,
+            created_at: Local::now().naive_local(),
+            created_by: "admin".to_string(),
+            expires_at: Some(expires_at),
+            allowed_ips: columns.allowed_ips,
+            last_used_at: None,
+            previous_access_key_secret: None,
+            previous_secret_expires_at: None,
+            signature_schemes: columns.signature_schemes,
+        };
+        let scope = key_scope(&access_key).unwrap();
+        assert_eq!(scope.domain, "built-in");
+        assert_eq!(scope.expires_at, Some(local_millis(expires_at)));
+        assert!(scope.is_ip_allowed("192.168.3.4".parse().ok()));
+        assert!(!scope.is_ip_allowed("10.0.0.2".parse().ok()));
+        assert!(!scope.allows_scheme(SignatureScheme::Query));
+
+        let invalid = AccessKeyInput {
+            allowed_ips: Some(vec!["10.0.0.0/33".to_string()]),
+            expires_at: None,
+            ..input
+        };
+        assert!(scope_columns(&invalid).is_err());
     }
 }
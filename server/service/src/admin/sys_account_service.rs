@@ -0,0 +1,585 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use aws_sdk_s3::{primitives::ByteStream, Client as S3Client};
+use chrono::Local;
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+use server_config::AccountConfig;
+use server_core::web::{auth::User, error::AppError};
+use server_global::global::{self, GLOBAL_PRIMARY_S3, GLOBAL_S3_POOL};
+use server_model::admin::{
+    entities::{
+        prelude::{SysUser, SysUserPasswordHistory},
+        sea_orm_active_enums::Status,
+        sys_user::{Column as SysUserColumn, Model as SysUserModel},
+        sys_user_password_history::{
+            ActiveModel as SysUserPasswordHistoryActiveModel,
+            Column as SysUserPasswordHistoryColumn,
+        },
+    },
+    input::{ChangePasswordInput, ForgotPasswordInput, ResetPasswordInput, UpdateProfileInput},
+    output::UserWithoutPassword,
+};
+use server_utils::SecureUtil;
+use ulid::Ulid;
+
+use super::{
+    external_login::random_token,
+    mail::{self, Mail},
+    sys_account_error::AccountError,
+    sys_auth_service::SysAuthService,
+    sys_mail_error::MailError,
+    sys_user_error::UserError,
+};
+use crate::{
+    helper::{
+        db_helper,
+        redis_helper::{get_primary_connection, PrimaryConnection},
+    },
+    project_error, project_info,
+};
+
+const RESET_TOKEN_KEY_PREFIX: &str = "password_reset:token:";
+/// 用户当前有效的找回密码令牌，重新申请时旧令牌随之失效
+const RESET_USER_KEY_PREFIX: &str = "password_reset:user:";
+const RESET_EMAIL_LIMIT_KEY_PREFIX: &str = "password_reset:limit:email:";
+const RESET_IP_LIMIT_KEY_PREFIX: &str = "password_reset:limit:ip:";
+
+#[async_trait]
+pub trait TAccountService {
+    async fn get_profile(&self, user: User) -> Result<UserWithoutPassword, AppError>;
+
+    /// 修改个人资料，修改邮箱时需要校验当前密码
+    async fn update_profile(
+        &self,
+        user: User,
+        input: UpdateProfileInput,
+    ) -> Result<UserWithoutPassword, AppError>;
+
+    /// 校验当前密码后修改密码，修改后所有会话失效
+    async fn change_password(&self, user: User, input: ChangePasswordInput)
+        -> Result<(), AppError>;
+
+    /// 上传头像到 S3 并更新用户资料中的头像地址
+    async fn upload_avatar(
+        &self,
+        user: User,
+        data: Vec<u8>,
+    ) -> Result<UserWithoutPassword, AppError>;
+
+    /// 向邮箱发送找回密码邮件
+    ///
+    /// 同一邮箱和同一 IP 在限流窗口内的申请次数有上限。邮箱不存在、用户已禁用或发送失败时
+    /// 同样返回成功，避免被用来探测注册邮箱。
+    async fn forgot_password(
+        &self,
+        input: ForgotPasswordInput,
+        client_ip: &str,
+    ) -> Result<(), AppError>;
+
+    /// 用找回密码令牌设置新密码，令牌只能使用一次
+    async fn reset_password(&self, input: ResetPasswordInput) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct SysAccountService;
+
+impl SysAccountService {
+    async fn config() -> AccountConfig {
+        global::get_config::<AccountConfig>()
+            .await
+            .map(|config| config.as_ref().clone())
+            .unwrap_or_default()
+    }
+
+    async fn find_user(&self, user_id: &str) -> Result<SysUserModel, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        SysUser::find_by_id(user_id)
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or_else(|| UserError::UserNotFound.into())
+    }
+
+    /// 邮箱和手机号在全部用户中唯一
+    async fn check_contact_unique(
+        &self,
+        user_id: &str,
+        input: &UpdateProfileInput,
+    ) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        if let Some(email) = &input.email {
+            let exists = SysUser::find()
+                .filter(SysUserColumn::Email.eq(email))
+                .filter(SysUserColumn::Id.ne(user_id))
+                .one(db.as_ref())
+                .await
+                .map_err(AppError::from)?
+                .is_some();
+            if exists {
+                return Err(AccountError::EmailAlreadyExists.into());
+            }
+        }
+
+        if let Some(phone_number) = &input.phone_number {
+            let exists = SysUser::find()
+                .filter(SysUserColumn::PhoneNumber.eq(phone_number))
+                .filter(SysUserColumn::Id.ne(user_id))
+                .one(db.as_ref())
+                .await
+                .map_err(AppError::from)?
+                .is_some();
+            if exists {
+                return Err(AccountError::PhoneNumberAlreadyExists.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 设置新密码
+    ///
+    /// 新密码不能与当前密码和最近 `password_history` 次用过的密码相同；
+    /// 旧密码写入历史后只保留最近的记录，最后注销用户的全部会话。
+    async fn set_password(&self, user: SysUserModel, new_password: &str) -> Result<(), AppError> {
+        let history_size = Self::config().await.password_history as u64;
+        let db = db_helper::get_db_connection().await?;
+
+        let recent_hashes: Vec<String> = SysUserPasswordHistory::find()
+            .select_only()
+            .column(SysUserPasswordHistoryColumn::PasswordHash)
+            .filter(SysUserPasswordHistoryColumn::UserId.eq(&user.id))
+            .order_by_desc(SysUserPasswordHistoryColumn::CreatedAt)
+            .limit(history_size)
+            .into_tuple()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let used_hashes =
+            std::iter::once(user.password.as_str()).chain(recent_hashes.iter().map(String::as_str));
+        if is_password_reused(new_password, used_hashes) {
+            return Err(AccountError::PasswordReused.into());
+        }
+
+        let password_hash =
+            SecureUtil::hash_password(new_password.as_bytes()).map_err(|e| AppError {
+                code: 500,
+                message: e.to_string(),
+            })?;
+        let now = Local::now().naive_local();
+        let user_id = user.id.clone();
+        let old_password_hash = user.password.clone();
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        if history_size > 0 {
+            SysUserPasswordHistoryActiveModel {
+                id: Set(Ulid::new().to_string()),
+                user_id: Set(user_id.clone()),
+                password_hash: Set(old_password_hash),
+                created_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(AppError::from)?;
+        }
+
+        let mut user = user.into_active_model();
+        user.password = Set(password_hash);
+        user.updated_at = Set(Some(now));
+        user.updated_by = Set(Some(user_id.clone()));
+        user.update(&txn).await.map_err(AppError::from)?;
+
+        let expired_ids: Vec<String> = SysUserPasswordHistory::find()
+            .select_only()
+            .column(SysUserPasswordHistoryColumn::Id)
+            .filter(SysUserPasswordHistoryColumn::UserId.eq(&user_id))
+            .order_by_desc(SysUserPasswordHistoryColumn::CreatedAt)
+            .offset(history_size)
+            .into_tuple()
+            .all(&txn)
+            .await
+            .map_err(AppError::from)?;
+
+        if !expired_ids.is_empty() {
+            SysUserPasswordHistory::delete_many()
+                .filter(SysUserPasswordHistoryColumn::Id.is_in(expired_ids))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        txn.commit().await.map_err(AppError::from)?;
+
+        SysAuthService.revoke_sessions(&user_id).await
+    }
+
+    /// 为用户签发找回密码令牌并发送邮件
+    async fn send_reset_mail(
+        &self,
+        config: &AccountConfig,
+        user: &SysUserModel,
+        email: String,
+    ) -> Result<(), AppError> {
+        // 未配置邮件发送时不签发令牌，避免令牌无处送达
+        let sender = mail::get_sender().await.map_err(|e| match e {
+            MailError::Disabled => AppError::from(AccountError::ResetUnavailable),
+            e => AppError::from(e),
+        })?;
+        let token = issue_reset_token(&user.id, config.password_reset_expire).await?;
+        let mail = Mail {
+            to: email,
+            subject: "Reset your password".to_string(),
+            body: format!(
+                "Hello {},\n\nOpen the link below to set a new password. \
+                 The link expires in {} minutes and can be used only once.\n\n{}\n\n\
+                 If you did not request a password reset, you can ignore this mail.\n",
+                user.nick_name,
+                config.password_reset_expire / 60,
+                config.password_reset_url.replace("{token}", &token),
+            ),
+        };
+
+        sender.send(&mail).await.map_err(AppError::from)
+    }
+
+    async fn avatar_client(config: &AccountConfig) -> Result<Arc<S3Client>, AccountError> {
+        match &config.avatar_s3_instance {
+            Some(name) => GLOBAL_S3_POOL
+                .read()
+                .await
+                .get(name)
+                .cloned()
+                .ok_or_else(|| AccountError::S3ClientNotFound(name.clone())),
+            None => GLOBAL_PRIMARY_S3
+                .read()
+                .await
+                .clone()
+                .ok_or_else(|| AccountError::S3ClientNotFound("primary".to_string())),
+        }
+    }
+}
+
+#[async_trait]
+impl TAccountService for SysAccountService {
+    async fn get_profile(&self, user: User) -> Result<UserWithoutPassword, AppError> {
+        self.find_user(&user.user_id())
+            .await
+            .map(UserWithoutPassword::from)
+    }
+
+    async fn update_profile(
+        &self,
+        user: User,
+        input: UpdateProfileInput,
+    ) -> Result<UserWithoutPassword, AppError> {
+        let user_id = user.user_id();
+        let existing = self.find_user(&user_id).await?;
+        if is_email_changed(existing.email.as_deref(), input.email.as_deref()) {
+            let password = input
+                .current_password
+                .as_deref()
+                .ok_or(AccountError::CurrentPasswordRequired)?;
+            if !SecureUtil::verify_password(password.as_bytes(), &existing.password)
+                .unwrap_or(false)
+            {
+                return Err(UserError::WrongPassword.into());
+            }
+        }
+        self.check_contact_unique(&user_id, &input).await?;
+
+        let mut profile = existing.into_active_model();
+        profile.nick_name = Set(input.nick_name);
+        profile.email = Set(input.email);
+        profile.phone_number = Set(input.phone_number);
+        profile.updated_at = Set(Some(Local::now().naive_local()));
+        profile.updated_by = Set(Some(user_id));
+
+        let db = db_helper::get_db_connection().await?;
+        profile
+            .update(db.as_ref())
+            .await
+            .map(UserWithoutPassword::from)
+            .map_err(AppError::from)
+    }
+
+    async fn change_password(
+        &self,
+        user: User,
+        input: ChangePasswordInput,
+    ) -> Result<(), AppError> {
+        let existing = self.find_user(&user.user_id()).await?;
+
+        if !SecureUtil::verify_password(input.current_password.as_bytes(), &existing.password)
+            .unwrap_or(false)
+        {
+            return Err(UserError::WrongPassword.into());
+        }
+
+        self.set_password(existing, &input.new_password).await
+    }
+
+    async fn upload_avatar(
+        &self,
+        user: User,
+        data: Vec<u8>,
+    ) -> Result<UserWithoutPassword, AppError> {
+        let config = Self::config().await;
+        let bucket = config
+            .avatar_bucket
+            .clone()
+            .ok_or(AccountError::AvatarNotConfigured)?;
+
+        if data.len() > config.avatar_max_size {
+            return Err(AccountError::AvatarTooLarge(config.avatar_max_size).into());
+        }
+        let (extension, content_type) =
+            avatar_format(&data).ok_or(AccountError::UnsupportedAvatarType)?;
+
+        let user_id = user.user_id();
+        let existing = self.find_user(&user_id).await?;
+
+        let key = format!(
+            "{}/{}/{}.{}",
+            config.avatar_key_prefix.trim_end_matches('/'),
+            user_id,
+            Ulid::new(),
+            extension
+        );
+        Self::avatar_client(&config)
+            .await?
+            .put_object()
+            .bucket(&bucket)
+            .key(&key)
+            .content_type(content_type)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|e| AccountError::AvatarUpload(e.to_string()))?;
+
+        let avatar = match &config.avatar_url_prefix {
+            Some(prefix) => format!("{}/{}", prefix.trim_end_matches('/'), key),
+            None => format!("s3://{}/{}", bucket, key),
+        };
+
+        let mut profile = existing.into_active_model();
+        profile.avatar = Set(Some(avatar));
+        profile.updated_at = Set(Some(Local::now().naive_local()));
+        profile.updated_by = Set(Some(user_id));
+
+        let db = db_helper::get_db_connection().await?;
+        profile
+            .update(db.as_ref())
+            .await
+            .map(UserWithoutPassword::from)
+            .map_err(AppError::from)
+    }
+
+    async fn forgot_password(
+        &self,
+        input: ForgotPasswordInput,
+        client_ip: &str,
+    ) -> Result<(), AppError> {
+        let config = Self::config().await;
+        check_reset_rate_limit(&config, &input.email, client_ip).await?;
+
+        let db = db_helper::get_db_connection().await?;
+        let user = SysUser::find()
+            .filter(SysUserColumn::Email.eq(&input.email))
+            .filter(SysUserColumn::Status.eq(Status::Enabled))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let Some(user) = user else {
+            project_info!("Password reset requested for unknown email");
+            return Ok(());
+        };
+
+        // 之后的任何失败同样返回成功，错误只写入日志，响应不因邮箱是否存在而不同
+        if let Err(e) = self.send_reset_mail(&config, &user, input.email).await {
+            project_error!("Failed to send password reset mail: {}", e.message);
+        }
+
+        Ok(())
+    }
+
+    async fn reset_password(&self, input: ResetPasswordInput) -> Result<(), AppError> {
+        let user_id = take_reset_token(&input.token).await?;
+        let user = self
+            .find_user(&user_id)
+            .await
+            .map_err(|_| AccountError::InvalidResetToken)?;
+
+        self.set_password(user, &input.new_password).await
+    }
+}
+
+/// 找回密码令牌和限流计数保存在主 Redis 中
+async fn reset_store() -> Result<Option<PrimaryConnection>, AppError> {
+    let redis = get_primary_connection().await?;
+    if redis.is_none() {
+        project_info!("Primary Redis not configured, password reset is unavailable");
+    }
+    Ok(redis)
+}
+
+/// 按邮箱和 IP 分别计数，任一超过上限时拒绝
+///
+/// 在查询用户之前计数，邮箱是否存在不影响结果。未配置主 Redis 时不限流。
+async fn check_reset_rate_limit(
+    config: &AccountConfig,
+    email: &str,
+    client_ip: &str,
+) -> Result<(), AppError> {
+    let Some(mut redis) = reset_store().await? else {
+        return Ok(());
+    };
+
+    let limits = [
+        (
+            format!("{}{}", RESET_EMAIL_LIMIT_KEY_PREFIX, email.to_lowercase()),
+            config.password_reset_email_limit,
+        ),
+        (
+            format!("{}{}", RESET_IP_LIMIT_KEY_PREFIX, client_ip),
+            config.password_reset_ip_limit,
+        ),
+    ];
+    let mut exceeded = false;
+    for (key, limit) in limits {
+        // 窗口从第一次申请开始计算，之后的申请不延长过期时间
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(config.password_reset_limit_window));
+        let _: Option<String> = redis.set_options(&key, 0, options).await?;
+        let count: u64 = redis.incr(&key, 1).await?;
+        exceeded |= count > limit;
+    }
+
+    if exceeded {
+        Err(AccountError::TooManyResetRequests.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// 保存找回密码令牌，同一用户之前申请的令牌随之失效
+async fn issue_reset_token(user_id: &str, expire: u64) -> Result<String, AppError> {
+    let token = random_token();
+    let user_key = format!("{}{}", RESET_USER_KEY_PREFIX, user_id);
+    let mut redis = reset_store().await?.ok_or(AccountError::ResetUnavailable)?;
+
+    let previous: Option<String> = redis.get_del(&user_key).await?;
+    if let Some(previous) = previous {
+        let _: () = redis
+            .del(format!("{}{}", RESET_TOKEN_KEY_PREFIX, previous))
+            .await?;
+    }
+
+    let _: () = redis
+        .set_ex(
+            format!("{}{}", RESET_TOKEN_KEY_PREFIX, token),
+            user_id,
+            expire,
+        )
+        .await?;
+    let _: () = redis.set_ex(user_key, &token, expire).await?;
+
+    Ok(token)
+}
+
+/// 取出并删除找回密码令牌，返回令牌所属的用户 ID
+async fn take_reset_token(token: &str) -> Result<String, AppError> {
+    let mut redis = reset_store()
+        .await?
+        .ok_or(AccountError::InvalidResetToken)?;
+    let user_id: Option<String> = redis
+        .get_del(format!("{}{}", RESET_TOKEN_KEY_PREFIX, token))
+        .await?;
+    let user_id = user_id.ok_or(AccountError::InvalidResetToken)?;
+
+    let _: () = redis
+        .del(format!("{}{}", RESET_USER_KEY_PREFIX, user_id))
+        .await?;
+
+    Ok(user_id)
+}
+
+/// 邮箱不区分大小写，清空邮箱也算修改
+fn is_email_changed(current: Option<&str>, new: Option<&str>) -> bool {
+    match (current, new) {
+        (Some(current), Some(new)) => !current.eq_ignore_ascii_case(new),
+        (None, None) => false,
+        _ => true,
+    }
+}
+
+fn is_password_reused<'a>(password: &str, hashes: impl IntoIterator<Item = &'a str>) -> bool {
+    hashes
+        .into_iter()
+        .any(|hash| SecureUtil::verify_password(password.as_bytes(), hash).unwrap_or(false))
+}
+
+/// 按文件头识别头像格式，返回扩展名和 Content-Type
+fn avatar_format(data: &[u8]) -> Option<(&'static str, &'static str)> {
+    match data {
+        [0x89, b'P', b'N', b'G', ..] => Some(("png", "image/png")),
+        [0xFF, 0xD8, 0xFF, ..] => Some(("jpg", "image/jpeg")),
+        [b'G', b'I', b'F', b'8', ..] => Some(("gif", "image/gif")),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => {
+            Some(("webp", "image/webp"))
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_email_changed() {
+        assert!(!is_email_changed(
+            Some("a@example.com"),
+            Some("A@Example.com")
+        ));
+        assert!(!is_email_changed(None, None));
+        assert!(is_email_changed(
+            Some("a@example.com"),
+            Some("b@example.com")
+        ));
+        assert!(is_email_changed(Some("a@example.com"), None));
+        assert!(is_email_changed(None, Some("a@example.com")));
+    }
+
+    #[test]
+    fn test_avatar_format_by_magic_bytes() {
+        assert_eq!(
+            avatar_format(b"\x89PNG\r\n\x1a\n...."),
+            Some(("png", "image/png"))
+        );
+        assert_eq!(
+            avatar_format(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00]),
+            Some(("jpg", "image/jpeg"))
+        );
+        assert_eq!(avatar_format(b"GIF89a..."), Some(("gif", "image/gif")));
+        assert_eq!(
+            avatar_format(b"RIFF\x24\x00\x00\x00WEBPVP8 "),
+            Some(("webp", "image/webp"))
+        );
+
+        assert_eq!(
+            avatar_format(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"),
+            None
+        );
+        assert_eq!(avatar_format(b"RIFF\x24\x00\x00\x00WAVE"), None);
+        assert_eq!(avatar_format(&[]), None);
+    }
+}
@@ -1,33 +1,55 @@
 #![allow(unused_imports)]
-use std::any::Any;
+use std::{any::Any, convert::Infallible, str::FromStr};
 
 use async_trait::async_trait;
-use chrono::{Local, Timelike};
-use redis::AsyncCommands;
+use chrono::{Duration, Local, NaiveDateTime};
 use sea_orm::{
-    ColumnTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect,
-    RelationTrait,
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+    Set, TransactionTrait,
+};
+use server_config::{JwtConfig, TotpConfig};
+use server_constant::definition::{
+    consts::{SystemEvent, TokenStatus},
+    Audience,
 };
-use server_constant::definition::{consts::SystemEvent, Audience};
 use server_core::web::{
-    auth::Claims,
-    error::AppError,
-    jwt::{JwtError, JwtUtils},
+    auth::{Claims, User},
+    error::{ApiError, AppError},
+    jwt::{JwtCreatedEvent, JwtError, JwtUtils},
+    revocation::TokenRevocation,
+    tenant::TenantContext,
 };
 use server_global::global;
 use server_model::admin::{
     entities::{
-        prelude::{SysRole, SysUser},
+        prelude::{SysRole, SysTokens, SysUser},
         sea_orm_active_enums::Status,
         sys_domain::Column as SysDomainColumn,
         sys_menu::{Column as SysMenuColumn, Entity as SysMenuEntity, Model as SysMenuModel},
+        sys_permission_code::{
+            Column as SysPermissionCodeColumn, Entity as SysPermissionCodeEntity,
+            Relation as SysPermissionCodeRelation,
+        },
         sys_role::{Column as SysRoleColumn, Entity as SysRoleEntity, Relation as SysRoleRelation},
         sys_role_menu::{Column as SysRoleMenuColumn, Entity as SysRoleMenuEntity},
+        sys_role_permission_code::Column as SysRolePermissionCodeColumn,
+        sys_tokens::{Column as SysTokensColumn, Model as SysTokensModel},
         sys_user::{Column as SysUserColumn, Relation as SysUserRelation},
         sys_user_role::Relation as SysUserRoleRelation,
     },
-    input::LoginInput,
-    output::{AuthOutput, MenuRoute, RouteMeta, UserRoute, UserWithDomainAndOrgOutput},
+    input::{
+        ExternalLoginCallbackInput, LoginInput, RefreshTokenInput, SiweLoginInput,
+        TwoFactorLoginInput,
+    },
+    output::{
+        AuthOutput, ExternalAuthorizeOutput, LoginOutput, MenuRoute, RouteMeta, SiweNonceOutput,
+        TwoFactorChallengeOutput, UserRoute, UserWithDomainAndOrgOutput,
+    },
+};
+use server_model::web3::entities::{
+    prelude::Web3Wallet,
+    web3_wallet::{ActiveModel as Web3WalletActiveModel, Column as Web3WalletColumn},
 };
 use server_utils::{SecureUtil, TreeBuilder};
 use thiserror::Error;
@@ -36,11 +58,23 @@ use tracing::instrument;
 use ulid::Ulid;
 
 use super::{
-    dto::sys_auth_dto::LoginContext, event_handlers::auth_event_handler::AuthEventHandler,
+    dto::sys_auth_dto::LoginContext,
+    event_handlers::auth_event_handler::AuthEventHandler,
+    events::{access_token_event::AccessTokenEvent, login_log_event::LoginLogEvent},
 };
 use crate::{
-    admin::{event_handlers::auth_event_handler::AuthEvent, sys_user_error::UserError},
-    helper::db_helper,
+    admin::{
+        event_handlers::auth_event_handler::AuthEvent,
+        external_login::{self, PendingLogin},
+        siwe,
+        sys_auth_error::AuthError,
+        sys_external_login_error::ExternalLoginError,
+        sys_login_security_service::SysLoginSecurityService,
+        sys_siwe_error::SiweError,
+        sys_user_error::UserError,
+        sys_user_totp_service::SysUserTotpService,
+    },
+    helper::{db_helper, role_hierarchy_helper::RoleHierarchy, tenant_helper},
     project_error, project_info,
 };
 
@@ -73,13 +107,64 @@ pub trait TAuthService: Send + Sync {
         &self,
         input: LoginInput,
         context: LoginContext,
+    ) -> Result<LoginOutput, AppError>;
+
+    async fn two_factor_login(
+        &self,
+        input: TwoFactorLoginInput,
+        context: LoginContext,
+    ) -> Result<AuthOutput, AppError>;
+
+    async fn refresh_token(
+        &self,
+        input: RefreshTokenInput,
+        context: LoginContext,
     ) -> Result<AuthOutput, AppError>;
 
+    async fn external_authorize(&self, provider: &str)
+        -> Result<ExternalAuthorizeOutput, AppError>;
+
+    async fn external_login(
+        &self,
+        provider: &str,
+        input: ExternalLoginCallbackInput,
+        context: LoginContext,
+    ) -> Result<LoginOutput, AppError>;
+
+    async fn siwe_nonce(&self) -> Result<SiweNonceOutput, AppError>;
+
+    async fn siwe_login(
+        &self,
+        input: SiweLoginInput,
+        context: LoginContext,
+    ) -> Result<LoginOutput, AppError>;
+
+    async fn siwe_link_wallet(&self, user_id: &str, input: SiweLoginInput) -> Result<(), AppError>;
+
     async fn get_user_routes(
         &self,
         role_codes: &[String],
         domain: &str,
     ) -> Result<UserRoute, AppError>;
+
+    /// 用户在域内的按钮权限码，包含从上级角色继承的
+    async fn get_user_permission_codes(
+        &self,
+        role_codes: &[String],
+        domain: &str,
+    ) -> Result<Vec<String>, AppError>;
+
+    async fn logout(&self, access_token: &str) -> Result<(), AppError>;
+
+    /// 踢出当前域内用户的指定会话
+    async fn kick_session(&self, token_id: &str, tenant: &TenantContext) -> Result<(), AppError>;
+
+    /// 撤销当前域内指定用户的所有会话
+    async fn revoke_user_sessions(
+        &self,
+        user_id: &str,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError>;
 }
 
 #[derive(Clone)]
@@ -110,12 +195,41 @@ impl TAuthService for SysAuthService {
         &self,
         input: LoginInput,
         context: LoginContext,
-    ) -> Result<AuthOutput, AppError> {
+    ) -> Result<LoginOutput, AppError> {
+        let security = SysLoginSecurityService;
+        security
+            .check_login_allowed(&context.domain, &input.identifier, &context.client_ip)
+            .await?;
+
         // 验证用户并获取角色
-        let (user, role_codes) = self
+        let (user, role_codes) = match self
             .verify_user(&input.identifier, &input.password, &context.domain)
+            .await
+        {
+            Ok(verified) => verified,
+            Err(e) if is_credential_error(&e) => {
+                if security
+                    .record_login_failure(&context.domain, &input.identifier)
+                    .await?
+                {
+                    return Err(UserError::LoginTooManyAttempts.into());
+                }
+                return Err(e);
+            },
+            Err(e) => return Err(e),
+        };
+
+        security
+            .clear_login_failure(&context.domain, &input.identifier)
             .await?;
 
+        // 启用双因素认证时只签发挑战令牌，由第二步换取正式令牌
+        if SysUserTotpService.is_enabled(&user.id).await? {
+            let challenge = self.issue_two_factor_challenge(&user).await?;
+            self.record_login_step(&user, &context, "PASSWORD").await;
+            return Ok(LoginOutput::TwoFactorRequired(challenge));
+        }
+
         // 生成认证输出
         let auth_output = generate_auth_output(
             user.id.clone(),
@@ -130,9 +244,282 @@ impl TAuthService for SysAuthService {
         // 发送认证事件
         self.send_login_event(&user, &auth_output, &context).await;
 
+        Ok(LoginOutput::Authenticated(auth_output))
+    }
+
+    #[instrument(skip(self, input, context))]
+    async fn two_factor_login(
+        &self,
+        input: TwoFactorLoginInput,
+        context: LoginContext,
+    ) -> Result<AuthOutput, AppError> {
+        let token_data = JwtUtils::validate_token(
+            &input.challenge_token,
+            Audience::TwoFactorChallenge.as_str(),
+        )
+        .await
+        .map_err(|_| AuthError::InvalidTwoFactorChallenge)?;
+        let (Some(jti), Some(exp)) = (
+            token_data.claims.jti().map(str::to_string),
+            token_data.claims.exp(),
+        ) else {
+            return Err(AuthError::InvalidTwoFactorChallenge.into());
+        };
+        if TokenRevocation::is_revoked(&jti).await {
+            return Err(AuthError::InvalidTwoFactorChallenge.into());
+        }
+        let challenge = User::from(token_data.claims);
+
+        // 第二步与密码共用失败计数，防止在挑战有效期内暴力猜测动态码
+        let security = SysLoginSecurityService;
+        security
+            .check_login_allowed(
+                &challenge.domain(),
+                &challenge.username(),
+                &context.client_ip,
+            )
+            .await?;
+
+        let Some(factor) = SysUserTotpService
+            .verify_second_factor(&challenge.user_id(), &input.code)
+            .await?
+        else {
+            if security
+                .record_login_failure(&challenge.domain(), &challenge.username())
+                .await?
+            {
+                TokenRevocation::revoke(&jti, exp).await;
+                return Err(UserError::LoginTooManyAttempts.into());
+            }
+            return Err(AuthError::InvalidTwoFactorCode.into());
+        };
+
+        // 挑战令牌只能使用一次
+        TokenRevocation::revoke(&jti, exp).await;
+        security
+            .clear_login_failure(&challenge.domain(), &challenge.username())
+            .await?;
+
+        let db = db_helper::get_db_connection().await?;
+        let user = select_user_with_domain_and_org_info!(SysUser::find())
+            .filter(SysUserColumn::Id.eq(challenge.user_id()))
+            .join(JoinType::InnerJoin, SysUserRelation::SysDomain.def())
+            .into_model::<UserWithDomainAndOrgOutput>()
+            .one(db.as_ref())
+            .await?
+            .ok_or(AuthError::InvalidTwoFactorChallenge)?;
+
+        if user.status == Status::Disabled {
+            return Err(UserError::UserDisabled.into());
+        }
+
+        let role_codes = self.get_user_roles(&user.id, db.as_ref()).await?;
+
+        let auth_output = generate_auth_output(
+            user.id.clone(),
+            user.username.clone(),
+            role_codes,
+            user.domain_code.clone(),
+            None,
+            context.audience,
+        )
+        .await?;
+
+        let context = LoginContext {
+            login_type: login_step_type(&context.login_type, factor.as_str()),
+            ..context
+        };
+        self.send_login_event(&user, &auth_output, &context).await;
+
         Ok(auth_output)
     }
 
+    #[instrument(skip(self, input, context))]
+    async fn refresh_token(
+        &self,
+        input: RefreshTokenInput,
+        context: LoginContext,
+    ) -> Result<AuthOutput, AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let token = SysTokens::find()
+            .filter(SysTokensColumn::RefreshToken.eq(&input.refresh_token))
+            .one(db.as_ref())
+            .await?
+            .ok_or(AuthError::InvalidRefreshToken)?;
+
+        let jwt_config = global::get_config::<JwtConfig>()
+            .await
+            .ok_or(AuthError::InvalidRefreshToken)?;
+        if let Err(e) = check_refresh_token(
+            &token,
+            jwt_config.refresh_expire,
+            Local::now().naive_local(),
+        ) {
+            // 已被轮换过的刷新令牌再次出现，视为泄露，撤销该用户的整个令牌家族
+            if matches!(e, AuthError::RefreshTokenReused) {
+                self.revoke_token_family(&token.user_id, db.as_ref())
+                    .await?;
+            }
+            return Err(e.into());
+        }
+
+        let txn = db.begin().await?;
+
+        if !consume_refresh_token(&txn, &token.id).await? {
+            txn.rollback().await?;
+            self.revoke_token_family(&token.user_id, db.as_ref())
+                .await?;
+            return Err(AuthError::RefreshTokenReused.into());
+        }
+
+        let auth_output = match self.rotate_token(&token, &context, &txn).await {
+            Ok(output) => output,
+            Err(e) => {
+                txn.rollback().await?;
+                return Err(e);
+            },
+        };
+
+        txn.commit().await?;
+
+        Ok(auth_output)
+    }
+
+    #[instrument(skip(self))]
+    async fn external_authorize(
+        &self,
+        provider: &str,
+    ) -> Result<ExternalAuthorizeOutput, AppError> {
+        let provider = external_login::get_provider(provider).await?;
+        let request = provider.authorization_request().await?;
+
+        external_login::save_state(
+            &request.state,
+            &PendingLogin {
+                provider: provider.name().to_string(),
+                pkce_verifier: request.pkce_verifier,
+                nonce: request.nonce,
+            },
+        )
+        .await?;
+
+        Ok(ExternalAuthorizeOutput {
+            authorize_url: request.url,
+            state: request.state,
+        })
+    }
+
+    #[instrument(skip(self, input, context))]
+    async fn external_login(
+        &self,
+        provider: &str,
+        input: ExternalLoginCallbackInput,
+        context: LoginContext,
+    ) -> Result<LoginOutput, AppError> {
+        let pending = external_login::take_state(&input.state).await?;
+        if pending.provider != provider {
+            return Err(ExternalLoginError::InvalidState.into());
+        }
+
+        let provider = external_login::get_provider(provider).await?;
+        let identity = provider
+            .exchange_code(&input.code, &pending.pkce_verifier, &pending.nonce)
+            .await?;
+        let user_id = external_login::resolve_user(provider.as_ref(), &identity).await?;
+
+        self.login_linked_user(
+            &user_id,
+            provider.kind(),
+            context,
+            ExternalLoginError::AccountNotLinked.into(),
+        )
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn siwe_nonce(&self) -> Result<SiweNonceOutput, AppError> {
+        let config = siwe::config().await?;
+        let nonce = siwe::issue_nonce().await?;
+
+        Ok(SiweNonceOutput {
+            nonce,
+            domain: config.domain.clone(),
+            uri: config.uri.clone(),
+            chain_ids: config.chain_ids.clone(),
+        })
+    }
+
+    #[instrument(skip(self, input, context))]
+    async fn siwe_login(
+        &self,
+        input: SiweLoginInput,
+        context: LoginContext,
+    ) -> Result<LoginOutput, AppError> {
+        let message = siwe::verify_sign_in(&input.message, &input.signature).await?;
+
+        let db = db_helper::get_db_connection().await?;
+        let user_id = Web3Wallet::find()
+            .filter(Web3WalletColumn::WalletAddress.eq(wallet_address(&message)))
+            .filter(Web3WalletColumn::UserId.is_not_null())
+            .one(db.as_ref())
+            .await?
+            .and_then(|wallet| wallet.user_id)
+            .ok_or(SiweError::WalletNotLinked)?;
+
+        self.login_linked_user(&user_id, "SIWE", context, SiweError::WalletNotLinked.into())
+            .await
+    }
+
+    #[instrument(skip(self, input))]
+    async fn siwe_link_wallet(&self, user_id: &str, input: SiweLoginInput) -> Result<(), AppError> {
+        let message = siwe::verify_sign_in(&input.message, &input.signature).await?;
+        let address = wallet_address(&message);
+        let now = Local::now().naive_local();
+
+        let db = db_helper::get_db_connection().await?;
+        let existing = Web3Wallet::find()
+            .filter(Web3WalletColumn::WalletAddress.eq(&address))
+            .one(db.as_ref())
+            .await?;
+
+        match existing {
+            Some(wallet) => {
+                if wallet
+                    .user_id
+                    .as_deref()
+                    .is_some_and(|linked| linked != user_id)
+                {
+                    return Err(SiweError::WalletAlreadyLinked.into());
+                }
+                let mut wallet = wallet.into_active_model();
+                wallet.user_id = Set(Some(user_id.to_string()));
+                wallet.chain_id = Set(message.chain_id as i32);
+                wallet.signature = Set(Some(input.signature));
+                wallet.message = Set(Some(input.message));
+                wallet.updated_at = Set(Some(now));
+                wallet.update(db.as_ref()).await?;
+            },
+            None => {
+                Web3WalletActiveModel {
+                    id: Set(Ulid::new().to_string()),
+                    user_id: Set(Some(user_id.to_string())),
+                    wallet_address: Set(address),
+                    wallet_type: Set("metamask".to_string()),
+                    chain_id: Set(message.chain_id as i32),
+                    signature: Set(Some(input.signature)),
+                    message: Set(Some(input.message)),
+                    created_at: Set(now),
+                    updated_at: Set(None),
+                }
+                .insert(db.as_ref())
+                .await?;
+            },
+        }
+
+        Ok(())
+    }
+
     #[instrument(skip(self), fields(roles = ?role_codes, domain = %domain))]
     async fn get_user_routes(
         &self,
@@ -148,14 +535,15 @@ impl TAuthService for SysAuthService {
 
         let db = db_helper::get_db_connection().await?;
 
+        // 角色继承上级角色的菜单
+        let role_ids = RoleHierarchy::load(domain)
+            .await?
+            .with_ancestors_by_codes(role_codes);
+
         let menu_ids = SysRoleMenuEntity::find()
             .select_only()
             .column(SysRoleMenuColumn::MenuId)
-            .join_rev(
-                JoinType::InnerJoin,
-                SysRoleEntity::has_many(SysRoleMenuEntity).into(),
-            )
-            .filter(SysRoleColumn::Code.is_in(role_codes.to_vec()))
+            .filter(SysRoleMenuColumn::RoleId.is_in(role_ids))
             .filter(SysRoleMenuColumn::Domain.eq(domain))
             .distinct()
             .into_tuple::<i32>()
@@ -220,9 +608,168 @@ impl TAuthService for SysAuthService {
 
         Ok(UserRoute { routes, home })
     }
+
+    #[instrument(skip(self), fields(roles = ?role_codes, domain = %domain))]
+    async fn get_user_permission_codes(
+        &self,
+        role_codes: &[String],
+        domain: &str,
+    ) -> Result<Vec<String>, AppError> {
+        if role_codes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let db = db_helper::get_db_connection().await?;
+
+        let role_ids = RoleHierarchy::load(domain)
+            .await?
+            .with_ancestors_by_codes(role_codes);
+
+        SysPermissionCodeEntity::find()
+            .select_only()
+            .column(SysPermissionCodeColumn::Code)
+            .join(
+                JoinType::InnerJoin,
+                SysPermissionCodeRelation::SysRolePermissionCode.def(),
+            )
+            .filter(SysRolePermissionCodeColumn::RoleId.is_in(role_ids))
+            .filter(SysRolePermissionCodeColumn::Domain.eq(domain))
+            .distinct()
+            .order_by_asc(SysPermissionCodeColumn::Code)
+            .into_tuple::<String>()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)
+    }
+
+    #[instrument(skip(self, access_token))]
+    async fn logout(&self, access_token: &str) -> Result<(), AppError> {
+        revoke_access_token(access_token).await;
+
+        let db = db_helper::get_db_connection().await?;
+        SysTokens::update_many()
+            .col_expr(
+                SysTokensColumn::Status,
+                Expr::value(TokenStatus::Revoked.to_string()),
+            )
+            .filter(SysTokensColumn::AccessToken.eq(access_token))
+            .filter(SysTokensColumn::Status.eq(TokenStatus::Active.to_string()))
+            .exec(db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, tenant))]
+    async fn kick_session(&self, token_id: &str, tenant: &TenantContext) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let token = SysTokens::find_by_id(token_id)
+            .one(db.as_ref())
+            .await?
+            .ok_or(AuthError::SessionNotFound)?;
+
+        // 其他域用户的会话按不存在处理
+        tenant_helper::find::<SysUser>(tenant)
+            .filter(SysUserColumn::Id.eq(&token.user_id))
+            .one(db.as_ref())
+            .await?
+            .ok_or(AuthError::SessionNotFound)?;
+
+        SysTokens::update_many()
+            .col_expr(
+                SysTokensColumn::Status,
+                Expr::value(TokenStatus::Revoked.to_string()),
+            )
+            .filter(SysTokensColumn::Id.eq(&token.id))
+            .exec(db.as_ref())
+            .await?;
+
+        revoke_access_token(&token.access_token).await;
+        project_info!("Session {} of user {} kicked", token.id, token.user_id);
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, tenant))]
+    async fn revoke_user_sessions(
+        &self,
+        user_id: &str,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+        tenant_helper::find::<SysUser>(tenant)
+            .filter(SysUserColumn::Id.eq(user_id))
+            .one(db.as_ref())
+            .await?
+            .ok_or(UserError::UserNotFound)?;
+
+        self.revoke_token_family(user_id, db.as_ref()).await
+    }
 }
 
 impl SysAuthService {
+    /// 撤销用户的所有会话，供用户本人修改密码等已确认身份的场景使用
+    pub(crate) async fn revoke_sessions(&self, user_id: &str) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+        self.revoke_token_family(user_id, db.as_ref()).await
+    }
+
+    /// 外部身份已映射到本地用户后完成登录
+    ///
+    /// 与密码登录一样检查用户状态和域内的 IP、登录时间窗口限制，`method` 记录到登录日志。
+    async fn login_linked_user(
+        &self,
+        user_id: &str,
+        method: &str,
+        context: LoginContext,
+        not_found: AppError,
+    ) -> Result<LoginOutput, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let user = select_user_with_domain_and_org_info!(SysUser::find())
+            .filter(SysUserColumn::Id.eq(user_id))
+            .join(JoinType::InnerJoin, SysUserRelation::SysDomain.def())
+            .into_model::<UserWithDomainAndOrgOutput>()
+            .one(db.as_ref())
+            .await?
+            .ok_or(not_found)?;
+
+        if user.status == Status::Disabled {
+            return Err(UserError::UserDisabled.into());
+        }
+
+        SysLoginSecurityService
+            .check_login_allowed(&user.domain_code, &user.username, &context.client_ip)
+            .await?;
+
+        // 外部身份只代替密码这一步，启用双因素认证时同样需要第二步
+        if SysUserTotpService.is_enabled(&user.id).await? {
+            let challenge = self.issue_two_factor_challenge(&user).await?;
+            self.record_login_step(&user, &context, method).await;
+            return Ok(LoginOutput::TwoFactorRequired(challenge));
+        }
+
+        let role_codes = self.get_user_roles(&user.id, db.as_ref()).await?;
+
+        let auth_output = generate_auth_output(
+            user.id.clone(),
+            user.username.clone(),
+            role_codes,
+            user.domain_code.clone(),
+            None,
+            context.audience,
+        )
+        .await?;
+
+        let context = LoginContext {
+            login_type: login_step_type(&context.login_type, method),
+            ..context
+        };
+        self.send_login_event(&user, &auth_output, &context).await;
+
+        Ok(LoginOutput::Authenticated(auth_output))
+    }
+
     /// 验证用户身份
     async fn verify_user(
         &self,
@@ -255,16 +802,16 @@ impl SysAuthService {
         }
 
         // 获取角色
-        let role_codes = self.get_user_roles(&user.id, &db).await?;
+        let role_codes = self.get_user_roles(&user.id, db.as_ref()).await?;
 
         Ok((user, role_codes))
     }
 
     /// 获取用户角色
-    async fn get_user_roles(
+    async fn get_user_roles<C: ConnectionTrait>(
         &self,
         user_id: &str,
-        db: &DatabaseConnection,
+        db: &C,
     ) -> Result<Vec<String>, AppError> {
         SysRole::find()
             .join(JoinType::InnerJoin, SysRoleRelation::SysUserRole.def())
@@ -276,194 +823,174 @@ impl SysAuthService {
             .map_err(AppError::from)
     }
 
-    async fn send_login_event(
+    /// 为已消费的刷新令牌签发新的令牌对，并在同一事务中记录新令牌
+    async fn rotate_token<C: ConnectionTrait>(
         &self,
-        user: &UserWithDomainAndOrgOutput,
-        auth_output: &AuthOutput,
+        token: &SysTokensModel,
         context: &LoginContext,
-    ) {
-        let auth_event = AuthEvent {
-            user_id: user.id.clone(),
-            username: user.username.clone(),
-            domain: user.domain_code.clone(),
+        db: &C,
+    ) -> Result<AuthOutput, AppError> {
+        let user = select_user_with_domain_and_org_info!(SysUser::find())
+            .filter(SysUserColumn::Id.eq(&token.user_id))
+            .join(JoinType::InnerJoin, SysUserRelation::SysDomain.def())
+            .into_model::<UserWithDomainAndOrgOutput>()
+            .one(db)
+            .await?
+            .ok_or(AuthError::InvalidRefreshToken)?;
+
+        if user.status == Status::Disabled {
+            return Err(UserError::UserDisabled.into());
+        }
+
+        let role_codes = self.get_user_roles(&user.id, db).await?;
+
+        let auth_output = generate_auth_output(
+            user.id.clone(),
+            user.username.clone(),
+            role_codes,
+            user.domain_code.clone(),
+            None,
+            context.audience,
+        )
+        .await?;
+
+        AccessTokenEvent {
             access_token: auth_output.token.clone(),
             refresh_token: auth_output.refresh_token.clone(),
-            client_ip: context.client_ip.clone(),
-            client_port: context.client_port,
+            user_id: user.id,
+            username: user.username,
+            domain: user.domain_code,
+            ip: context.client_ip.clone(),
+            port: context.client_port,
             address: context.address.clone(),
             user_agent: context.user_agent.clone(),
             request_id: context.request_id.clone(),
             login_type: context.login_type.clone(),
-        };
+        }
+        .handle(db)
+        .await?;
 
-        global::send_dyn_event(
-            SystemEvent::AuthLoggedInEvent.as_ref(),
-            Box::new(auth_event),
-        );
+        Ok(auth_output)
     }
 
-    async fn check_login_security(
+    /// 撤销用户所有仍处于活跃状态的令牌
+    ///
+    /// 活跃令牌被标记为已撤销，近期签发且可能仍未过期的访问令牌会加入撤销列表。
+    async fn revoke_token_family<C: ConnectionTrait>(
         &self,
-        username: &str,
-        client_ip: &str,
+        user_id: &str,
+        db: &C,
     ) -> Result<(), AppError> {
-        // 1. 检查登录失败次数
-        self.check_login_attempts(username).await?;
-
-        // 2. 检查 IP 黑名单
-        self.check_ip_blacklist(client_ip).await?;
-
-        // 3. 检查账号是否被锁定 (通过Redis检查)
-        self.check_account_lock(username).await?;
-
-        // 4. 检查是否在允许的时间范围内
-        self.check_login_time_window().await?;
-
-        Ok(())
-    }
-
-    /// 检查登录失败次数
-    async fn check_login_attempts(&self, username: &str) -> Result<(), AppError> {
-        use redis::aio::MultiplexedConnection;
-        use crate::helper::redis_helper::{get_redis_connection, RedisSource};
-
-        let mut redis: MultiplexedConnection = get_redis_connection(RedisSource::Primary).await?;
-        let key = format!("login:fail:{}", username);
+        let jwt_config = global::get_config::<JwtConfig>().await;
+        let mut query = SysTokens::find()
+            .filter(SysTokensColumn::UserId.eq(user_id))
+            .filter(SysTokensColumn::Status.is_in([
+                TokenStatus::Active.to_string(),
+                TokenStatus::Refreshed.to_string(),
+            ]));
+        if let Some(jwt_config) = jwt_config {
+            let issued_after = Local::now().naive_local() - Duration::seconds(jwt_config.expire);
+            query = query.filter(SysTokensColumn::LoginTime.gte(issued_after));
+        }
+        let live_tokens = query.all(db).await?;
 
-        let count: Option<i32> = redis
-            .get(&key)
-            .await
-            .ok()
-            .and_then(|v: String| v.parse().ok());
+        SysTokens::update_many()
+            .col_expr(
+                SysTokensColumn::Status,
+                Expr::value(TokenStatus::Revoked.to_string()),
+            )
+            .filter(SysTokensColumn::UserId.eq(user_id))
+            .filter(SysTokensColumn::Status.eq(TokenStatus::Active.to_string()))
+            .exec(db)
+            .await?;
 
-        if let Some(fail_count) = count {
-            const MAX_LOGIN_ATTEMPTS: i32 = 5;
-            if fail_count >= MAX_LOGIN_ATTEMPTS {
-                return Err(UserError::LoginTooManyAttempts.into());
-            }
+        for token in live_tokens {
+            revoke_access_token(&token.access_token).await;
         }
-        Ok(())
-    }
 
-    /// 检查 IP 黑名单
-    async fn check_ip_blacklist(&self, client_ip: &str) -> Result<(), AppError> {
-        // 简单的内网IP检查示例，实际应该从配置或数据库读取黑名单
-        let blocked_ips = [
-            "127.0.0.1",
-            "0.0.0.0",
-        ];
-        
-        if blocked_ips.contains(&client_ip) {
-            return Err(UserError::IpBlocked.into());
-        }
+        project_info!("Revoked all active tokens for user {}", user_id);
         Ok(())
     }
 
-    /// 检查账号是否被锁定
-    async fn check_account_lock(&self, username: &str) -> Result<(), AppError> {
-        use redis::aio::MultiplexedConnection;
-        use crate::helper::redis_helper::{get_redis_connection, RedisSource};
-
-        let mut redis: MultiplexedConnection = get_redis_connection(RedisSource::Primary).await?;
-        let key = format!("login:lock:{}", username);
-
-        let is_locked: Option<i32> = redis
-            .get(&key)
+    /// 签发密码验证通过后的双因素挑战令牌
+    async fn issue_two_factor_challenge(
+        &self,
+        user: &UserWithDomainAndOrgOutput,
+    ) -> Result<TwoFactorChallengeOutput, AppError> {
+        let config = global::get_config::<TotpConfig>()
             .await
-            .ok()
-            .and_then(|v: String| v.parse().ok());
+            .map(|config| config.as_ref().clone())
+            .unwrap_or_default();
 
-        if is_locked.is_some() {
-            return Err(UserError::AccountLocked.into());
-        }
-        Ok(())
-    }
-
-    /// 检查登录时间窗口
-    async fn check_login_time_window(&self) -> Result<(), AppError> {
-        use chrono::Local;
-
-        // 默认允许登录的时间段: 06:00 - 23:00
-        const ALLOWED_START_HOUR: u32 = 6;
-        const ALLOWED_END_HOUR: u32 = 23;
-
-        let current_hour = Local::now().hour();
-
-        if !(ALLOWED_START_HOUR..ALLOWED_END_HOUR).contains(&current_hour) {
-            return Err(UserError::LoginNotAllowed.into());
-        }
-        Ok(())
+        let claims = Claims::new(
+            user.id.clone(),
+            Audience::TwoFactorChallenge.as_str().to_string(),
+            user.username.clone(),
+            vec![],
+            user.domain_code.clone(),
+            None,
+        );
+        let challenge_token =
+            JwtUtils::generate_token_with_expire(&claims, config.challenge_expire).await?;
+
+        Ok(TwoFactorChallengeOutput {
+            two_factor_required: true,
+            challenge_token,
+            expires_in: config.challenge_expire,
+        })
     }
 
-    /// 记录登录失败次数
-    pub async fn record_login_failure(&self, username: &str) -> Result<(), AppError> {
-        use redis::aio::MultiplexedConnection;
-        use crate::helper::redis_helper::{get_redis_connection, RedisSource};
-
-        let mut redis: MultiplexedConnection = get_redis_connection(RedisSource::Primary).await?;
-        let key = format!("login:fail:{}", username);
-
-        // 获取当前失败次数
-        let count: i32 = redis
-            .get(&key)
+    /// 记录尚未签发令牌的登录步骤，失败时只记录错误，不影响登录
+    async fn record_login_step(
+        &self,
+        user: &UserWithDomainAndOrgOutput,
+        context: &LoginContext,
+        step: &str,
+    ) {
+        let result = async {
+            let db = db_helper::get_db_connection().await?;
+            LoginLogEvent {
+                user_id: user.id.clone(),
+                username: user.username.clone(),
+                domain: user.domain_code.clone(),
+                ip: context.client_ip.clone(),
+                port: context.client_port,
+                address: context.address.clone(),
+                user_agent: context.user_agent.clone(),
+                request_id: context.request_id.clone(),
+                login_type: login_step_type(&context.login_type, step),
+            }
+            .handle(&db)
             .await
-            .ok()
-            .and_then(|v: String| v.parse().ok())
-            .unwrap_or(0);
-
-        let new_count = count + 1;
-
-        // 设置过期时间为15分钟
-        let expire_seconds = 15 * 60;
-        let _: () = redis
-            .set_ex(&key, new_count.to_string(), expire_seconds as u64)
-            .await?;
-
-        // 如果失败次数达到阈值，锁定账号
-        const MAX_LOGIN_ATTEMPTS: i32 = 5;
-        if new_count >= MAX_LOGIN_ATTEMPTS {
-            let lock_key = format!("login:lock:{}", username);
-            // 锁定30分钟
-            let _: () = redis
-                .set_ex(&lock_key, "1", (30 * 60) as u64)
-                .await?;
         }
+        .await;
 
-        Ok(())
-    }
-
-    /// 清除登录失败次数（登录成功时调用）
-    pub async fn clear_login_failure(&self, username: &str) -> Result<(), AppError> {
-        use redis::aio::MultiplexedConnection;
-        use crate::helper::redis_helper::{get_redis_connection, RedisSource};
-
-        let mut redis: MultiplexedConnection = get_redis_connection(RedisSource::Primary).await?;
-        let key = format!("login:fail:{}", username);
-        let lock_key = format!("login:lock:{}", username);
-
-        let _: std::result::Result<Vec<String>, _> = redis.del(&[&key, &lock_key]).await;
-
-        Ok(())
+        if let Err(e) = result {
+            project_error!("Failed to record login step {}: {:?}", step, e);
+        }
     }
 
-    #[allow(dead_code)]
-    async fn pwd_login_with_security(
+    async fn send_login_event(
         &self,
-        input: LoginInput,
-        context: LoginContext,
-    ) -> Result<AuthOutput, AppError> {
-        self.check_login_security(&input.identifier, &context.client_ip)
-            .await?;
+        user: &UserWithDomainAndOrgOutput,
+        auth_output: &AuthOutput,
+        context: &LoginContext,
+    ) {
+        let auth_event = AuthEvent {
+            user_id: user.id.clone(),
+            username: user.username.clone(),
+            domain: user.domain_code.clone(),
+            access_token: auth_output.token.clone(),
+            refresh_token: auth_output.refresh_token.clone(),
+            client_ip: context.client_ip.clone(),
+            client_port: context.client_port,
+            address: context.address.clone(),
+            user_agent: context.user_agent.clone(),
+            request_id: context.request_id.clone(),
+            login_type: context.login_type.clone(),
+        };
 
-        let identifier = input.identifier.clone();
-        let result = self.pwd_login(input, context).await;
-        
-        // 登录成功时清除失败记录
-        if result.is_ok() {
-            let _ = self.clear_login_failure(&identifier).await;
-        }
-        
-        result
+        global::publish_event(auth_event).await;
     }
 }
 
@@ -479,6 +1006,69 @@ async fn send_auth_event(
     Ok(())
 }
 
+/// 校验刷新令牌能否用于轮换
+///
+/// 已被轮换过的令牌返回 [`AuthError::RefreshTokenReused`]，由调用方撤销整个令牌家族。
+fn check_refresh_token(
+    token: &SysTokensModel,
+    refresh_expire: i64,
+    now: NaiveDateTime,
+) -> Result<(), AuthError> {
+    match TokenStatus::from_str(&token.status) {
+        Ok(status) if status.can_refresh() => {},
+        Ok(TokenStatus::Refreshed) => return Err(AuthError::RefreshTokenReused),
+        _ => return Err(AuthError::InvalidRefreshToken),
+    }
+
+    if token.login_time + Duration::seconds(refresh_expire) < now {
+        return Err(AuthError::RefreshTokenExpired);
+    }
+
+    Ok(())
+}
+
+/// 将刷新令牌标记为已刷新，仅当令牌仍为 Active 时成功，防止并发重复使用
+async fn consume_refresh_token<C: ConnectionTrait>(db: &C, token_id: &str) -> Result<bool, DbErr> {
+    let result = SysTokens::update_many()
+        .col_expr(
+            SysTokensColumn::Status,
+            Expr::value(TokenStatus::Refreshed.to_string()),
+        )
+        .filter(SysTokensColumn::Id.eq(token_id))
+        .filter(SysTokensColumn::Status.eq(TokenStatus::Active.to_string()))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected > 0)
+}
+
+/// 登录日志中的登录方式，如双因素登录的 `PC:PASSWORD`、`PC:TOTP` 和外部登录的 `PC:OIDC`
+fn login_step_type(login_type: &str, step: &str) -> String {
+    format!("{}:{}", login_type, step)
+}
+
+/// 签名消息中的钱包地址，`web3_wallet` 中的地址统一保存为小写
+fn wallet_address(message: &siwe::SiweMessage) -> String {
+    format!("0x{}", hex::encode(message.address.as_slice()))
+}
+
+/// 用户名或密码错误，计入登录失败次数
+fn is_credential_error(err: &AppError) -> bool {
+    err.code == UserError::UserNotFound.code() || err.code == UserError::WrongPassword.code()
+}
+
+/// 将访问令牌的 jti 加入撤销列表，直到其原定过期时间
+async fn revoke_access_token(access_token: &str) {
+    match JwtUtils::inspect_token(access_token).await {
+        Ok(claims) => {
+            if let (Some(jti), Some(exp)) = (claims.jti(), claims.exp()) {
+                TokenRevocation::revoke(jti, exp).await;
+            }
+        },
+        Err(e) => project_error!("Failed to inspect token for revocation: {}", e),
+    }
+}
+
 pub async fn generate_auth_output(
     user_id: String,
     username: String,
@@ -504,17 +1094,8 @@ pub async fn generate_auth_output(
     })
 }
 
-#[instrument(skip(rx))]
-pub async fn auth_login_listener(
-    mut rx: tokio::sync::mpsc::UnboundedReceiver<Box<dyn Any + Send>>,
-) {
-    while let Some(event) = rx.recv().await {
-        if let Some(auth_event) = event.downcast_ref::<AuthEvent>() {
-            if let Err(e) = handle_auth_event(auth_event).await {
-                project_error!("Failed to handle AuthEvent: {:?}", e);
-            }
-        }
-    }
+pub async fn auth_login_listener(auth_event: AuthEvent) -> Result<(), EventError> {
+    handle_auth_event(&auth_event).await
 }
 
 #[instrument(skip(auth_event), fields(user_id = %auth_event.user_id, username = %auth_event.username))]
@@ -536,10 +1117,92 @@ async fn handle_auth_event(auth_event: &AuthEvent) -> Result<(), EventError> {
     .map_err(|e| EventError::LoginHandlerError(format!("{:?}", e)))
 }
 
-#[instrument(skip(rx))]
-pub async fn jwt_created_listener(mut rx: tokio::sync::mpsc::UnboundedReceiver<String>) {
-    while let Some(jwt) = rx.recv().await {
-        project_info!("JWT created: {}", jwt);
-        // TODO: Consider storing the token into the database
+#[instrument(skip(event))]
+pub async fn jwt_created_listener(event: JwtCreatedEvent) -> Result<(), Infallible> {
+    project_info!("JWT created: {}", event.token);
+    // TODO: Consider storing the token into the database
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{ConnectionTrait, Database, Schema};
+
+    use super::*;
+
+    fn token(status: TokenStatus, login_time: NaiveDateTime) -> SysTokensModel {
+        SysTokensModel {
+            id: "token-1".to_string(),
+            access_token: "access".to_string(),
+            refresh_token: "refresh".to_string(),
+            status: status.to_string(),
+            user_id: "user-1".to_string(),
+            username: "soybean".to_string(),
+            domain: "built-in".to_string(),
+            login_time,
+            ip: "127.0.0.1".to_string(),
+            port: None,
+            address: String::new(),
+            user_agent: String::new(),
+            request_id: String::new(),
+            r#type: "PC".to_string(),
+            created_at: login_time,
+            created_by: "soybean".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_check_refresh_token() {
+        let now = Local::now().naive_local();
+        let expire = 3600;
+
+        assert!(check_refresh_token(&token(TokenStatus::Active, now), expire, now).is_ok());
+        assert!(matches!(
+            check_refresh_token(&token(TokenStatus::Refreshed, now), expire, now),
+            Err(AuthError::RefreshTokenReused)
+        ));
+        assert!(matches!(
+            check_refresh_token(&token(TokenStatus::Revoked, now), expire, now),
+            Err(AuthError::InvalidRefreshToken)
+        ));
+        assert!(matches!(
+            check_refresh_token(
+                &token(TokenStatus::Active, now - Duration::seconds(expire + 1)),
+                expire,
+                now
+            ),
+            Err(AuthError::RefreshTokenExpired)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rotated_refresh_token_cannot_be_consumed_again() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let backend = db.get_database_backend();
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(SysTokens)))
+            .await
+            .unwrap();
+
+        let now = Local::now().naive_local();
+        token(TokenStatus::Active, now)
+            .into_active_model()
+            .reset_all()
+            .insert(&db)
+            .await
+            .unwrap();
+
+        assert!(consume_refresh_token(&db, "token-1").await.unwrap());
+        // 第二次出示同一刷新令牌时不能再次消费，且被识别为重复使用
+        assert!(!consume_refresh_token(&db, "token-1").await.unwrap());
+
+        let rotated = SysTokens::find_by_id("token-1")
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            check_refresh_token(&rotated, 3600, now),
+            Err(AuthError::RefreshTokenReused)
+        ));
     }
 }
@@ -1,22 +1,56 @@
-use std::sync::Arc;
+use std::{collections::BTreeSet, sync::Arc};
 
 use async_trait::async_trait;
-use axum_casbin::casbin::RbacApi;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
-use server_core::web::error::AppError;
-use server_model::admin::entities::{
-    prelude::{SysDomain, SysEndpoint, SysMenu, SysRole, SysRoleMenu, SysUser, SysUserRole},
-    sys_domain::Column as SysDomainColumn,
-    sys_endpoint::Column as SysEndpointColumn,
-    sys_menu::Column as SysMenuColumn,
-    sys_role::Column as SysRoleColumn,
-    sys_role_menu::{ActiveModel as SysRoleMenuActiveModel, Column as SysRoleMenuColumn},
-    sys_user_role::{ActiveModel as SysUserRoleActiveModel, Column as SysUserRoleColumn},
+use axum_casbin::{
+    casbin::{CachedEnforcer, RbacApi},
+    Decision, DecisionSink,
+};
+use chrono::Local;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, JoinType, QueryFilter,
+    QuerySelect, RelationTrait, Set, TransactionTrait,
+};
+use sea_orm_adapter::SeaOrmAdapter;
+use serde::{Deserialize, Serialize};
+use server_constant::definition::consts::SystemEvent;
+use server_core::web::{error::AppError, tenant::TenantContext};
+use server_global::{event_bus::Event, global};
+use server_model::admin::{
+    entities::{
+        prelude::{
+            SysDomain, SysEndpoint, SysMenu, SysPermissionCode, SysRole, SysRoleEndpoint,
+            SysRoleMenu, SysRolePermissionCode, SysUser, SysUserRole,
+        },
+        sys_authorization_decision::ActiveModel as SysAuthorizationDecisionActiveModel,
+        sys_domain::Column as SysDomainColumn,
+        sys_endpoint::Column as SysEndpointColumn,
+        sys_menu::Column as SysMenuColumn,
+        sys_permission_code::{
+            Column as SysPermissionCodeColumn, Model as SysPermissionCodeModel,
+            Relation as SysPermissionCodeRelation,
+        },
+        sys_role::{Column as SysRoleColumn, Relation as SysRoleRelation},
+        sys_role_endpoint::{
+            ActiveModel as SysRoleEndpointActiveModel, Column as SysRoleEndpointColumn,
+        },
+        sys_role_menu::{ActiveModel as SysRoleMenuActiveModel, Column as SysRoleMenuColumn},
+        sys_role_permission_code::{
+            ActiveModel as SysRolePermissionCodeActiveModel, Column as SysRolePermissionCodeColumn,
+        },
+        sys_user::Column as SysUserColumn,
+        sys_user_role::{
+            ActiveModel as SysUserRoleActiveModel, Column as SysUserRoleColumn,
+            Relation as SysUserRoleRelation,
+        },
+    },
+    input::ExplainPermissionDto,
+    output::PermissionExplanationOutput,
 };
 use thiserror::Error;
 use tokio::sync::RwLock;
+use ulid::Ulid;
 
-use crate::helper::db_helper;
+use crate::helper::{db_helper, tenant_helper};
 
 #[derive(Error, Debug)]
 #[allow(clippy::enum_variant_names)]
@@ -29,8 +63,12 @@ pub enum AuthorizationError {
     PermissionsNotFound,
     #[error("One or more routes not found")]
     RoutesNotFound,
+    #[error("One or more permission codes not found")]
+    PermissionCodesNotFound,
     #[error("One or more users not found")]
     UsersNotFound,
+    #[error("User not found")]
+    UserNotFound,
 }
 
 impl From<AuthorizationError> for AppError {
@@ -50,6 +88,7 @@ pub trait TAuthorizationService: Send + Sync {
         domain: String,
         role_id: String,
         permissions: Vec<String>,
+        tenant: &TenantContext,
         enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
     ) -> Result<(), AppError>;
 
@@ -59,21 +98,48 @@ pub trait TAuthorizationService: Send + Sync {
         domain: String,
         role_id: String,
         route_ids: Vec<i32>,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError>;
+
+    /// 整体替换角色的按钮权限码，关联了接口的权限码同时授予或回收该接口的策略
+    async fn assign_permission_codes(
+        &self,
+        domain: String,
+        role_id: String,
+        permission_code_ids: Vec<String>,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
     ) -> Result<(), AppError>;
 
     /// 为角色分配用户
-    async fn assign_users(&self, role_id: String, user_ids: Vec<String>) -> Result<(), AppError>;
+    async fn assign_users(
+        &self,
+        role_id: String,
+        user_ids: Vec<String>,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError>;
+
+    /// 解释用户在指定域中能否访问接口
+    async fn explain_permission(
+        &self,
+        input: ExplainPermissionDto,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<CachedEnforcer>>,
+    ) -> Result<PermissionExplanationOutput, AppError>;
 }
 
 #[derive(Clone)]
 pub struct SysAuthorizationService;
 
 impl SysAuthorizationService {
+    /// 校验请求中的域就是当前域，并在当前域内查找角色
     async fn check_domain_and_role(
         &self,
         domain_code: &str,
         role_id: &str,
+        tenant: &TenantContext,
     ) -> Result<(String, String, String), AppError> {
+        tenant_helper::ensure_domain(tenant, domain_code)?;
         let db = db_helper::get_db_connection().await?;
 
         let domain = SysDomain::find()
@@ -84,7 +150,7 @@ impl SysAuthorizationService {
 
         let domain = domain.ok_or(AuthorizationError::DomainNotFound)?;
 
-        let role = SysRole::find()
+        let role = tenant_helper::find::<SysRole>(tenant)
             .filter(SysRoleColumn::Id.eq(role_id))
             .one(db.as_ref())
             .await
@@ -95,10 +161,10 @@ impl SysAuthorizationService {
         Ok((domain.code, role_id.to_string(), role.code))
     }
 
-    async fn check_role(&self, role_id: &str) -> Result<String, AppError> {
+    async fn check_role(&self, role_id: &str, tenant: &TenantContext) -> Result<String, AppError> {
         let db = db_helper::get_db_connection().await?;
 
-        let role = SysRole::find()
+        let role = tenant_helper::find::<SysRole>(tenant)
             .filter(SysRoleColumn::Id.eq(role_id))
             .one(db.as_ref())
             .await
@@ -109,82 +175,269 @@ impl SysAuthorizationService {
         Ok(role.code)
     }
 
-    /// 同步角色权限
+    /// 同步角色权限，只增删与现有策略的差异
+    ///
+    /// 角色已分配的权限码关联的接口不会被回收，这些策略随权限码一起管理。
     async fn sync_role_permissions(
         &self,
+        role_id: &str,
         role_code: &str,
         domain: &str,
         new_permissions: Vec<server_model::admin::entities::sys_endpoint::Model>,
         enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
     ) -> Result<(), AppError> {
-        let mut enforcer_write = enforcer.write().await;
-        let existing_permissions =
-            enforcer_write.get_filtered_policy(0, vec![role_code.to_string(), domain.to_string()]);
+        let policy = |endpoint: &server_model::admin::entities::sys_endpoint::Model| {
+            vec![
+                role_code.to_string(),
+                domain.to_string(),
+                endpoint.path.clone(),
+                endpoint.method.clone(),
+            ]
+        };
+        let new_policies: BTreeSet<Vec<String>> = new_permissions.iter().map(policy).collect();
 
-        println!("existing_permissions: {:?}", existing_permissions);
+        let db = db_helper::get_db_connection().await?;
+        let code_endpoint_ids: Vec<String> = SysPermissionCode::find()
+            .select_only()
+            .column(SysPermissionCodeColumn::EndpointId)
+            .join(
+                JoinType::InnerJoin,
+                SysPermissionCodeRelation::SysRolePermissionCode.def(),
+            )
+            .filter(SysRolePermissionCodeColumn::RoleId.eq(role_id))
+            .filter(SysRolePermissionCodeColumn::Domain.eq(domain))
+            .filter(SysPermissionCodeColumn::EndpointId.is_not_null())
+            .into_tuple::<String>()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+        let code_policies: BTreeSet<Vec<String>> = if code_endpoint_ids.is_empty() {
+            BTreeSet::new()
+        } else {
+            SysEndpoint::find()
+                .filter(SysEndpointColumn::Id.is_in(code_endpoint_ids))
+                .all(db.as_ref())
+                .await
+                .map_err(AppError::from)?
+                .iter()
+                .map(policy)
+                .collect()
+        };
 
-        let new_policies: Vec<Vec<String>> = new_permissions
-            .iter()
-            .map(|perm| {
-                vec![
-                    role_code.to_string(),
-                    domain.to_string(),
-                    perm.path.clone(),
-                    perm.method.clone(),
-                ]
-            })
+        let mut enforcer_write = enforcer.write().await;
+        let existing_policies: BTreeSet<Vec<String>> = enforcer_write
+            .get_filtered_policy(0, vec![role_code.to_string(), domain.to_string()])
+            .into_iter()
             .collect();
 
-        println!("new_policies: {:?}", new_policies);
+        let (policies_to_remove, policies_to_add) =
+            role_policy_changes(&existing_policies, &new_policies, &code_policies);
 
-        let existing_policies: Vec<Vec<String>> = existing_permissions
-            .iter()
-            .map(|perm| {
-                vec![
-                    perm[0].clone(),
-                    perm[1].clone(),
-                    perm[2].clone(),
-                    perm[3].clone(),
-                ]
-            })
-            .collect();
+        let mut adapter = SeaOrmAdapter::without_migration(db.as_ref().clone());
+        apply_policy_changes(
+            &mut *enforcer_write,
+            &mut adapter,
+            policies_to_remove,
+            policies_to_add,
+        )
+        .await
+    }
 
-        println!("existing_policies: {:?}", existing_policies);
+    /// 按权限码的增减授予或回收角色的接口策略
+    async fn sync_permission_code_policies(
+        &self,
+        role_id: &str,
+        role_code: &str,
+        domain: &str,
+        assigned: &[SysPermissionCodeModel],
+        removed: &[SysPermissionCodeModel],
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
 
-        let policies_to_remove: Vec<Vec<String>> = existing_policies
-            .iter()
-            .filter(|policy| !new_policies.contains(policy))
-            .cloned()
+        let mut enforcer_write = enforcer.write().await;
+        let existing_policies: BTreeSet<Vec<String>> = enforcer_write
+            .get_filtered_policy(0, vec![role_code.to_string(), domain.to_string()])
+            .into_iter()
             .collect();
 
-        let policies_to_add: Vec<Vec<String>> = new_policies
-            .iter()
-            .filter(|policy| !existing_policies.contains(policy))
-            .cloned()
+        let (policies_to_remove, policies_to_add) = permission_code_policy_changes(
+            db.as_ref(),
+            role_id,
+            role_code,
+            domain,
+            assigned,
+            removed,
+            &existing_policies,
+        )
+        .await?;
+
+        let mut adapter = SeaOrmAdapter::without_migration(db.as_ref().clone());
+        apply_policy_changes(
+            &mut *enforcer_write,
+            &mut adapter,
+            policies_to_remove,
+            policies_to_add,
+        )
+        .await
+    }
+}
+
+/// 权限码变化后需要回收和授予的角色接口策略
+///
+/// 只回收不再被角色任何权限码关联、也不是通过 `assign_permission` 直接分配的接口。
+async fn permission_code_policy_changes<C: ConnectionTrait>(
+    db: &C,
+    role_id: &str,
+    role_code: &str,
+    domain: &str,
+    assigned: &[SysPermissionCodeModel],
+    removed: &[SysPermissionCodeModel],
+    existing_policies: &BTreeSet<Vec<String>>,
+) -> Result<(Vec<Vec<String>>, Vec<Vec<String>>), AppError> {
+    let (grant_ids, mut revoke_ids) = endpoint_changes(assigned, removed);
+    if !revoke_ids.is_empty() {
+        let direct_ids: BTreeSet<String> = SysRoleEndpoint::find()
+            .select_only()
+            .column(SysRoleEndpointColumn::EndpointId)
+            .filter(SysRoleEndpointColumn::RoleId.eq(role_id))
+            .filter(SysRoleEndpointColumn::Domain.eq(domain))
+            .into_tuple::<String>()
+            .all(db)
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
             .collect();
+        revoke_ids.retain(|endpoint_id| !direct_ids.contains(endpoint_id));
+    }
+    if grant_ids.is_empty() && revoke_ids.is_empty() {
+        return Ok((vec![], vec![]));
+    }
 
-        if !policies_to_remove.is_empty() {
-            let _ = enforcer_write
-                .remove_policies(policies_to_remove)
-                .await
-                .map_err(|e| AppError {
-                    code: 500,
-                    message: e.to_string(),
-                })?;
-        }
+    let endpoints = SysEndpoint::find()
+        .filter(SysEndpointColumn::Id.is_in(grant_ids.iter().chain(revoke_ids.iter())))
+        .all(db)
+        .await
+        .map_err(AppError::from)?;
 
-        if !policies_to_add.is_empty() {
-            let _ = enforcer_write
-                .add_policies(policies_to_add)
-                .await
-                .map_err(|e| AppError {
-                    code: 500,
-                    message: e.to_string(),
-                })?;
-        }
+    let policy = |path: &str, method: &str| {
+        vec![
+            role_code.to_string(),
+            domain.to_string(),
+            path.to_string(),
+            method.to_string(),
+        ]
+    };
 
-        Ok(())
+    let policies_to_remove = endpoints
+        .iter()
+        .filter(|endpoint| revoke_ids.contains(&endpoint.id))
+        .map(|endpoint| policy(&endpoint.path, &endpoint.method))
+        .filter(|policy| existing_policies.contains(policy))
+        .collect();
+
+    let policies_to_add = endpoints
+        .iter()
+        .filter(|endpoint| grant_ids.contains(&endpoint.id))
+        .map(|endpoint| policy(&endpoint.path, &endpoint.method))
+        .filter(|policy| !existing_policies.contains(policy))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    Ok((policies_to_remove, policies_to_add))
+}
+
+/// 将角色 `p` 策略的增删同时写入存储和 enforcer
+///
+/// 存储中的变更由适配器在同一事务内完成，成对的增删原地更新；内存中的策略在调用方持有的写锁内修改，
+/// 鉴权请求不会看到只删未增的中间状态。内存变更同样会清理缓存并通过 watcher 通知其他实例。
+async fn apply_policy_changes<E, C>(
+    enforcer: &mut E,
+    adapter: &mut SeaOrmAdapter<C>,
+    policies_to_remove: Vec<Vec<String>>,
+    policies_to_add: Vec<Vec<String>>,
+) -> Result<(), AppError>
+where
+    E: RbacApi + Send + Sync,
+    C: ConnectionTrait + TransactionTrait,
+{
+    if policies_to_remove.is_empty() && policies_to_add.is_empty() {
+        return Ok(());
     }
+
+    let casbin_error = |e: axum_casbin::casbin::Error| AppError {
+        code: 500,
+        message: e.to_string(),
+    };
+
+    let replaced = adapter
+        .replace_policies(
+            "p",
+            "p",
+            policies_to_remove.clone(),
+            policies_to_add.clone(),
+        )
+        .await
+        .map_err(casbin_error)?;
+    if !replaced {
+        return Err(AppError {
+            code: 500,
+            message: "Stored policies are out of sync with the enforcer".to_string(),
+        });
+    }
+
+    // 存储已更新，关闭自动保存后只修改内存中的策略
+    let auto_save = enforcer.has_auto_save_enabled();
+    enforcer.enable_auto_save(false);
+    let mut result = Ok(true);
+    if !policies_to_remove.is_empty() {
+        result = enforcer.remove_policies(policies_to_remove).await;
+    }
+    if result.is_ok() && !policies_to_add.is_empty() {
+        result = enforcer.add_policies(policies_to_add).await;
+    }
+    enforcer.enable_auto_save(auto_save);
+
+    result.map(|_| ()).map_err(casbin_error)
+}
+
+/// 直接分配接口后需要回收和授予的策略
+///
+/// 回收不在新集合中的现有策略，但保留仍由权限码关联的接口策略。
+fn role_policy_changes(
+    existing: &BTreeSet<Vec<String>>,
+    new: &BTreeSet<Vec<String>>,
+    code_backed: &BTreeSet<Vec<String>>,
+) -> (Vec<Vec<String>>, Vec<Vec<String>>) {
+    let remove = existing
+        .difference(new)
+        .filter(|policy| !code_backed.contains(*policy))
+        .cloned()
+        .collect();
+    let add = new.difference(existing).cloned().collect();
+
+    (remove, add)
+}
+
+/// 权限码变化后需要授予和回收的接口 ID
+///
+/// 授予当前全部权限码关联的接口（已存在的策略会被跳过），回收被移除的权限码中不再有其他权限码关联的接口。
+fn endpoint_changes(
+    assigned: &[SysPermissionCodeModel],
+    removed: &[SysPermissionCodeModel],
+) -> (BTreeSet<String>, BTreeSet<String>) {
+    let grant: BTreeSet<String> = assigned
+        .iter()
+        .filter_map(|code| code.endpoint_id.clone())
+        .collect();
+    let revoke = removed
+        .iter()
+        .filter_map(|code| code.endpoint_id.clone())
+        .filter(|endpoint_id| !grant.contains(endpoint_id))
+        .collect();
+
+    (grant, revoke)
 }
 
 #[async_trait]
@@ -194,9 +447,12 @@ impl TAuthorizationService for SysAuthorizationService {
         domain: String,
         role_id: String,
         permissions: Vec<String>,
+        tenant: &TenantContext,
         enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
     ) -> Result<(), AppError> {
-        let (domain_code, _, role_code) = self.check_domain_and_role(&domain, &role_id).await?;
+        let (domain_code, _, role_code) = self
+            .check_domain_and_role(&domain, &role_id, tenant)
+            .await?;
 
         let db = db_helper::get_db_connection().await?;
         let permissions = SysEndpoint::find()
@@ -209,7 +465,27 @@ impl TAuthorizationService for SysAuthorizationService {
             return Err(AuthorizationError::PermissionsNotFound.into());
         }
 
-        self.sync_role_permissions(&role_code, &domain_code, permissions, enforcer)
+        // 记录直接分配的接口，移除关联同一接口的权限码时据此保留策略
+        let txn = db.begin().await.map_err(AppError::from)?;
+        SysRoleEndpoint::delete_many()
+            .filter(SysRoleEndpointColumn::RoleId.eq(&role_id))
+            .filter(SysRoleEndpointColumn::Domain.eq(&domain_code))
+            .exec(&txn)
+            .await
+            .map_err(AppError::from)?;
+        SysRoleEndpoint::insert_many(permissions.iter().map(|endpoint| {
+            SysRoleEndpointActiveModel {
+                role_id: Set(role_id.clone()),
+                endpoint_id: Set(endpoint.id.clone()),
+                domain: Set(domain_code.clone()),
+            }
+        }))
+        .exec(&txn)
+        .await
+        .map_err(AppError::from)?;
+        txn.commit().await.map_err(AppError::from)?;
+
+        self.sync_role_permissions(&role_id, &role_code, &domain_code, permissions, enforcer)
             .await?;
 
         Ok(())
@@ -220,8 +496,11 @@ impl TAuthorizationService for SysAuthorizationService {
         domain: String,
         role_id: String,
         route_ids: Vec<i32>,
+        tenant: &TenantContext,
     ) -> Result<(), AppError> {
-        let (domain_code, role_id, _) = self.check_domain_and_role(&domain, &role_id).await?;
+        let (domain_code, role_id, _) = self
+            .check_domain_and_role(&domain, &role_id, tenant)
+            .await?;
 
         let db = db_helper::get_db_connection().await?;
         let routes = SysMenu::find()
@@ -294,17 +573,122 @@ impl TAuthorizationService for SysAuthorizationService {
         Ok(())
     }
 
-    async fn assign_users(&self, role_id: String, user_ids: Vec<String>) -> Result<(), AppError> {
-        let _ = self.check_role(&role_id).await?;
+    async fn assign_permission_codes(
+        &self,
+        domain: String,
+        role_id: String,
+        permission_code_ids: Vec<String>,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<(), AppError> {
+        let (domain_code, role_id, role_code) = self
+            .check_domain_and_role(&domain, &role_id, tenant)
+            .await?;
 
+        let permission_code_ids: BTreeSet<String> = permission_code_ids.into_iter().collect();
         let db = db_helper::get_db_connection().await?;
-        let users = SysUser::find()
-            .filter(server_model::admin::entities::sys_user::Column::Id.is_in(user_ids.clone()))
+
+        let assigned = SysPermissionCode::find()
+            .filter(SysPermissionCodeColumn::Id.is_in(permission_code_ids.iter().cloned()))
             .all(db.as_ref())
             .await
             .map_err(AppError::from)?;
 
-        if users.is_empty() {
+        if assigned.len() != permission_code_ids.len() {
+            return Err(AuthorizationError::PermissionCodesNotFound.into());
+        }
+
+        let existing_ids: BTreeSet<String> = SysRolePermissionCode::find()
+            .select_only()
+            .column(SysRolePermissionCodeColumn::PermissionCodeId)
+            .filter(SysRolePermissionCodeColumn::RoleId.eq(&role_id))
+            .filter(SysRolePermissionCodeColumn::Domain.eq(&domain_code))
+            .into_tuple::<String>()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .collect();
+
+        let ids_to_add: Vec<&String> = permission_code_ids.difference(&existing_ids).collect();
+        let ids_to_delete: Vec<String> = existing_ids
+            .difference(&permission_code_ids)
+            .cloned()
+            .collect();
+
+        let removed = if ids_to_delete.is_empty() {
+            vec![]
+        } else {
+            SysPermissionCode::find()
+                .filter(SysPermissionCodeColumn::Id.is_in(ids_to_delete.clone()))
+                .all(db.as_ref())
+                .await
+                .map_err(AppError::from)?
+        };
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        if !ids_to_add.is_empty() {
+            let role_permission_codes =
+                ids_to_add
+                    .into_iter()
+                    .map(|permission_code_id| SysRolePermissionCodeActiveModel {
+                        role_id: Set(role_id.clone()),
+                        permission_code_id: Set(permission_code_id.clone()),
+                        domain: Set(domain_code.clone()),
+                    });
+
+            SysRolePermissionCode::insert_many(role_permission_codes)
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        if !ids_to_delete.is_empty() {
+            SysRolePermissionCode::delete_many()
+                .filter(SysRolePermissionCodeColumn::RoleId.eq(&role_id))
+                .filter(SysRolePermissionCodeColumn::Domain.eq(&domain_code))
+                .filter(SysRolePermissionCodeColumn::PermissionCodeId.is_in(ids_to_delete))
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        txn.commit().await.map_err(AppError::from)?;
+
+        self.sync_permission_code_policies(
+            &role_id,
+            &role_code,
+            &domain_code,
+            &assigned,
+            &removed,
+            enforcer,
+        )
+        .await
+    }
+
+    async fn assign_users(
+        &self,
+        role_id: String,
+        user_ids: Vec<String>,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError> {
+        let _ = self.check_role(&role_id, tenant).await?;
+
+        let user_ids: Vec<String> = user_ids
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let db = db_helper::get_db_connection().await?;
+        let users = tenant_helper::find::<SysUser>(tenant)
+            .filter(SysUserColumn::Id.is_in(user_ids.clone()))
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        // 其他域的用户按不存在处理
+        if users.is_empty() || users.len() != user_ids.len() {
             return Err(AuthorizationError::UsersNotFound.into());
         }
 
@@ -364,4 +748,308 @@ impl TAuthorizationService for SysAuthorizationService {
 
         Ok(())
     }
+
+    async fn explain_permission(
+        &self,
+        input: ExplainPermissionDto,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<CachedEnforcer>>,
+    ) -> Result<PermissionExplanationOutput, AppError> {
+        tenant_helper::ensure_domain(tenant, &input.domain)?;
+        let db = db_helper::get_db_connection().await?;
+
+        let domain = SysDomain::find()
+            .filter(SysDomainColumn::Code.eq(&input.domain))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or(AuthorizationError::DomainNotFound)?;
+
+        tenant_helper::find::<SysUser>(tenant)
+            .filter(SysUserColumn::Id.eq(&input.user_id))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or(AuthorizationError::UserNotFound)?;
+
+        let roles: Vec<String> = tenant_helper::find::<SysRole>(tenant)
+            .join(JoinType::InnerJoin, SysRoleRelation::SysUserRole.def())
+            .join(JoinType::InnerJoin, SysUserRoleRelation::SysUser.def())
+            .filter(SysUserColumn::Id.eq(&input.user_id))
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .map(|role| role.code)
+            .collect();
+
+        let mut enforcer = enforcer.write().await;
+        let decision = axum_casbin::audit::explain(
+            &mut enforcer,
+            &roles,
+            Some(&domain.code),
+            &input.path,
+            &input.method.to_uppercase(),
+        )
+        .map_err(|e| AppError {
+            code: 500,
+            message: e.to_string(),
+        })?;
+
+        // 包含通过角色继承获得的策略
+        let role_policies = roles
+            .iter()
+            .flat_map(|role| enforcer.get_implicit_permissions_for_user(role, Some(&domain.code)))
+            .collect();
+
+        Ok(PermissionExplanationOutput {
+            allowed: decision.allowed,
+            roles,
+            granted_by: decision.subject,
+            matched_policy: decision.matched_policy,
+            role_policies,
+        })
+    }
+}
+
+/// 授权决策审计事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AuthorizationDecisionEvent(pub Decision);
+
+impl Event for AuthorizationDecisionEvent {
+    fn name() -> &'static str {
+        SystemEvent::AuthorizationDecisionEvent.as_ref()
+    }
+}
+
+/// 将授权决策写入 `sys_authorization_decision` 表
+///
+/// 决策在请求路径上产生，这里只投递事件，由监听器异步落库。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DatabaseDecisionSink;
+
+impl DecisionSink for DatabaseDecisionSink {
+    fn record(&self, decision: &Decision) {
+        global::emit_event(AuthorizationDecisionEvent(decision.clone()));
+    }
+}
+
+async fn save_authorization_decision(decision: &Decision) -> Result<(), AppError> {
+    let db = db_helper::get_db_connection().await?;
+
+    SysAuthorizationDecisionActiveModel {
+        id: Set(Ulid::new().to_string()),
+        subjects: Set(decision.subjects.join(",")),
+        subject: Set(decision.subject.clone()),
+        domain: Set(decision.domain.clone()),
+        object: Set(decision.object.clone()),
+        action: Set(decision.action.clone()),
+        allowed: Set(decision.allowed),
+        matched_policy: Set(decision
+            .matched_policy
+            .as_ref()
+            .map(|policy| policy.join(", "))),
+        created_at: Set(Local::now().naive_local()),
+    }
+    .insert(db.as_ref())
+    .await
+    .map_err(AppError::from)?;
+
+    Ok(())
+}
+
+pub async fn authorization_decision_listener(
+    event: AuthorizationDecisionEvent,
+) -> Result<(), String> {
+    save_authorization_decision(&event.0)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum_casbin::casbin::{CoreApi, DefaultModel, Enforcer, MgmtApi};
+    use chrono::NaiveDateTime;
+    use sea_orm::{ConnectOptions, Database, Schema};
+    use server_model::admin::entities::sys_endpoint::ActiveModel as SysEndpointActiveModel;
+
+    use super::*;
+
+    const MODEL: &str = r#"
+[request_definition]
+r = sub, dom, obj, act
+
+[policy_definition]
+p = sub, dom, obj, act
+
+[role_definition]
+g = _, _, _
+
+[policy_effect]
+e = some(where (p.eft == allow))
+
+[matchers]
+m = g(r.sub, p.sub, r.dom) && r.dom == p.dom && r.obj == p.obj && r.act == p.act
+"#;
+
+    fn policy(path: &str) -> Vec<String> {
+        ["admin", "built-in", path, "GET"]
+            .map(str::to_string)
+            .to_vec()
+    }
+
+    fn permission_code(id: &str, endpoint_id: Option<&str>) -> SysPermissionCodeModel {
+        SysPermissionCodeModel {
+            id: id.to_string(),
+            menu_id: 1,
+            code: format!("code:{id}"),
+            name: id.to_string(),
+            endpoint_id: endpoint_id.map(str::to_string),
+            created_at: NaiveDateTime::default(),
+            created_by: "-1".to_string(),
+            updated_at: None,
+            updated_by: None,
+        }
+    }
+
+    fn ids(ids: &[&str]) -> BTreeSet<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_endpoint_changes_keep_shared_endpoints() {
+        let assigned = [
+            permission_code("add", Some("ep_create")),
+            permission_code("view", None),
+        ];
+        let removed = [
+            permission_code("edit", Some("ep_update")),
+            permission_code("copy", Some("ep_create")),
+        ];
+
+        let (grant, revoke) = endpoint_changes(&assigned, &removed);
+
+        assert_eq!(grant, ids(&["ep_create"]));
+        assert_eq!(revoke, ids(&["ep_update"]));
+    }
+
+    #[test]
+    fn test_role_policy_changes_keep_code_backed_endpoints() {
+        let set = |paths: &[&str]| paths.iter().map(|path| policy(path)).collect();
+        let existing = set(&["/a", "/b", "/code"]);
+        let new = set(&["/b", "/c"]);
+        let code_backed = set(&["/code", "/other"]);
+
+        let (remove, add) = role_policy_changes(&existing, &new, &code_backed);
+
+        assert_eq!(remove, vec![policy("/a")]);
+        assert_eq!(add, vec![policy("/c")]);
+    }
+
+    #[tokio::test]
+    async fn test_removed_code_keeps_directly_assigned_endpoint() {
+        let mut opt = ConnectOptions::new("sqlite::memory:");
+        opt.max_connections(1).min_connections(1);
+        let db = Database::connect(opt).await.unwrap();
+        db.execute_unprepared("PRAGMA foreign_keys = OFF")
+            .await
+            .unwrap();
+        let schema = Schema::new(db.get_database_backend());
+        for stmt in [
+            schema.create_table_from_entity(SysEndpoint),
+            schema.create_table_from_entity(SysRoleEndpoint),
+        ] {
+            db.execute(db.get_database_backend().build(&stmt))
+                .await
+                .unwrap();
+        }
+
+        SysEndpoint::insert_many(["direct", "code"].map(|id| SysEndpointActiveModel {
+            id: Set(id.to_string()),
+            path: Set(format!("/{id}")),
+            method: Set("GET".to_string()),
+            action: Set("rw".to_string()),
+            resource: Set(id.to_string()),
+            controller: Set("test".to_string()),
+            summary: Set(None),
+            created_at: Set(NaiveDateTime::default()),
+            updated_at: Set(None),
+        }))
+        .exec(&db)
+        .await
+        .unwrap();
+        SysRoleEndpointActiveModel {
+            role_id: Set("1".to_string()),
+            endpoint_id: Set("direct".to_string()),
+            domain: Set("built-in".to_string()),
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+
+        // 接口同时由直接分配和权限码授予，移除权限码后直接分配的策略保留
+        let existing = [policy("/direct"), policy("/code")].into_iter().collect();
+        let removed = [
+            permission_code("view", Some("direct")),
+            permission_code("edit", Some("code")),
+        ];
+        let (remove, add) =
+            permission_code_policy_changes(&db, "1", "admin", "built-in", &[], &removed, &existing)
+                .await
+                .unwrap();
+
+        assert_eq!(remove, vec![policy("/code")]);
+        assert!(add.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_policy_changes_updates_storage_and_enforcer() {
+        let mut opt = ConnectOptions::new("sqlite::memory:");
+        opt.max_connections(1).min_connections(1);
+        let db = Database::connect(opt).await.unwrap();
+
+        let model = DefaultModel::from_str(MODEL).await.unwrap();
+        let adapter = SeaOrmAdapter::new(db.clone()).await.unwrap();
+        let mut enforcer = Enforcer::new(model, adapter).await.unwrap();
+        enforcer
+            .add_policies(vec![policy("/a"), policy("/b")])
+            .await
+            .unwrap();
+
+        let mut adapter = SeaOrmAdapter::without_migration(db.clone());
+        apply_policy_changes(
+            &mut enforcer,
+            &mut adapter,
+            vec![policy("/a")],
+            vec![policy("/c"), policy("/d")],
+        )
+        .await
+        .unwrap();
+
+        let expected: BTreeSet<Vec<String>> = [policy("/b"), policy("/c"), policy("/d")]
+            .into_iter()
+            .collect();
+        let in_memory: BTreeSet<Vec<String>> = enforcer.get_policy().into_iter().collect();
+        assert_eq!(in_memory, expected);
+        assert!(enforcer.has_auto_save_enabled());
+
+        // 存储中的策略与内存一致，且没有重复写入
+        enforcer.load_policy().await.unwrap();
+        assert_eq!(enforcer.get_policy().len(), expected.len());
+        let stored: BTreeSet<Vec<String>> = enforcer.get_policy().into_iter().collect();
+        assert_eq!(stored, expected);
+
+        // 存储与内存不一致时整体失败，内存中的策略保持不变
+        let result = apply_policy_changes(
+            &mut enforcer,
+            &mut adapter,
+            vec![policy("/missing")],
+            vec![policy("/e")],
+        )
+        .await;
+        assert!(result.is_err());
+        let in_memory: BTreeSet<Vec<String>> = enforcer.get_policy().into_iter().collect();
+        assert_eq!(in_memory, expected);
+    }
 }
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Local;
+use sea_orm::{
+    sea_query::OnConflict, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use server_core::web::{error::AppError, page::PaginatedData};
+use server_model::admin::{
+    entities::{
+        prelude::{SysJob, SysJobRun},
+        sys_job::{ActiveModel as SysJobActiveModel, Column as SysJobColumn},
+        sys_job_run::{
+            ActiveModel as SysJobRunActiveModel, Column as SysJobRunColumn, Model as SysJobRunModel,
+        },
+    },
+    input::JobRunPageRequest,
+};
+use server_scheduler::{
+    JobInfo, JobRun, JobSpec, JobStore, Scheduler, SchedulerError, GLOBAL_SCHEDULER,
+};
+
+use crate::{admin::errors::sys_job_error::JobError, helper::db_helper};
+
+#[async_trait]
+pub trait TJobService {
+    async fn list_jobs(&self) -> Result<Vec<JobInfo>, AppError>;
+
+    /// 暂停任务的定时触发，对所有实例生效
+    async fn pause_job(&self, name: &str) -> Result<(), AppError>;
+
+    async fn resume_job(&self, name: &str) -> Result<(), AppError>;
+
+    /// 立即执行一次任务，返回已开始的执行记录
+    async fn trigger_job(&self, name: &str) -> Result<JobRun, AppError>;
+
+    async fn find_paginated_job_runs(
+        &self,
+        params: JobRunPageRequest,
+    ) -> Result<PaginatedData<SysJobRunModel>, AppError>;
+}
+
+pub struct SysJobService;
+
+fn scheduler() -> Result<&'static Scheduler, JobError> {
+    GLOBAL_SCHEDULER
+        .get()
+        .ok_or(JobError::SchedulerNotInitialized)
+}
+
+#[async_trait]
+impl TJobService for SysJobService {
+    async fn list_jobs(&self) -> Result<Vec<JobInfo>, AppError> {
+        Ok(scheduler()?.list().await)
+    }
+
+    async fn pause_job(&self, name: &str) -> Result<(), AppError> {
+        scheduler()?.pause(name).await.map_err(JobError::from)?;
+        Ok(())
+    }
+
+    async fn resume_job(&self, name: &str) -> Result<(), AppError> {
+        scheduler()?.resume(name).await.map_err(JobError::from)?;
+        Ok(())
+    }
+
+    async fn trigger_job(&self, name: &str) -> Result<JobRun, AppError> {
+        Ok(scheduler()?.trigger(name).await.map_err(JobError::from)?)
+    }
+
+    async fn find_paginated_job_runs(
+        &self,
+        params: JobRunPageRequest,
+    ) -> Result<PaginatedData<SysJobRunModel>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let mut query = SysJobRun::find();
+
+        if let Some(ref job_name) = params.job_name {
+            query = query.filter(SysJobRunColumn::JobName.eq(job_name.as_str()));
+        }
+        if let Some(ref status) = params.status {
+            query = query.filter(SysJobRunColumn::Status.eq(status.as_str()));
+        }
+
+        query = query.order_by_desc(SysJobRunColumn::StartedAt);
+
+        let total = query
+            .clone()
+            .count(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        let paginator = query.paginate(db.as_ref(), params.page_details.size);
+        let records = paginator
+            .fetch_page(params.page_details.current - 1)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(PaginatedData {
+            current: params.page_details.current,
+            size: params.page_details.size,
+            total,
+            records,
+        })
+    }
+}
+
+/// 将任务定义、暂停状态和执行记录保存在 `sys_job` 与 `sys_job_run` 表中
+pub struct DatabaseJobStore;
+
+impl DatabaseJobStore {
+    async fn db() -> Result<Arc<DatabaseConnection>, SchedulerError> {
+        db_helper::get_db_connection()
+            .await
+            .map_err(|e| SchedulerError::Store(e.message))
+    }
+}
+
+fn store_error(e: sea_orm::DbErr) -> SchedulerError {
+    SchedulerError::Store(e.to_string())
+}
+
+fn run_active_model(run: &JobRun) -> SysJobRunActiveModel {
+    SysJobRunActiveModel {
+        id: Set(run.id.clone()),
+        job_name: Set(run.job_name.clone()),
+        trigger: Set(run.trigger.as_str().to_string()),
+        instance: Set(run.instance.clone()),
+        status: Set(run.status.as_str().to_string()),
+        error: Set(run.error.clone()),
+        started_at: Set(run.started_at),
+        ended_at: Set(run.ended_at),
+    }
+}
+
+#[async_trait]
+impl JobStore for DatabaseJobStore {
+    async fn register(&self, spec: &JobSpec) -> Result<bool, SchedulerError> {
+        let db = Self::db().await?;
+
+        // 保留已保存的暂停状态，只更新表达式和描述
+        SysJob::insert(SysJobActiveModel {
+            name: Set(spec.name.clone()),
+            cron: Set(spec.cron.clone()),
+            description: Set(spec.description.clone()),
+            paused: Set(false),
+            updated_at: Set(Local::now().naive_local()),
+        })
+        .on_conflict(
+            OnConflict::column(SysJobColumn::Name)
+                .update_columns([
+                    SysJobColumn::Cron,
+                    SysJobColumn::Description,
+                    SysJobColumn::UpdatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(db.as_ref())
+        .await
+        .map_err(store_error)?;
+
+        self.is_paused(&spec.name).await
+    }
+
+    async fn is_paused(&self, job: &str) -> Result<bool, SchedulerError> {
+        let db = Self::db().await?;
+        let job = SysJob::find_by_id(job)
+            .one(db.as_ref())
+            .await
+            .map_err(store_error)?;
+        Ok(job.is_some_and(|job| job.paused))
+    }
+
+    async fn set_paused(&self, job: &str, paused: bool) -> Result<(), SchedulerError> {
+        let db = Self::db().await?;
+        SysJobActiveModel {
+            name: Set(job.to_string()),
+            paused: Set(paused),
+            updated_at: Set(Local::now().naive_local()),
+            ..Default::default()
+        }
+        .update(db.as_ref())
+        .await
+        .map_err(store_error)?;
+        Ok(())
+    }
+
+    async fn record_start(&self, run: &JobRun) -> Result<(), SchedulerError> {
+        let db = Self::db().await?;
+        run_active_model(run)
+            .insert(db.as_ref())
+            .await
+            .map_err(store_error)?;
+        Ok(())
+    }
+
+    async fn record_finish(&self, run: &JobRun) -> Result<(), SchedulerError> {
+        let db = Self::db().await?;
+        SysJobRun::insert(run_active_model(run))
+            .on_conflict(
+                OnConflict::column(SysJobRunColumn::Id)
+                    .update_columns([
+                        SysJobRunColumn::Status,
+                        SysJobRunColumn::Error,
+                        SysJobRunColumn::EndedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db.as_ref())
+            .await
+            .map_err(store_error)?;
+        Ok(())
+    }
+}
@@ -1,22 +1,37 @@
 use async_trait::async_trait;
-use sea_orm::{ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder};
-use server_core::web::{error::AppError, page::PaginatedData};
+use sea_orm::{ColumnTrait, Condition, PaginatorTrait, QueryFilter, QueryOrder};
+use server_core::web::{auth::User, error::AppError, page::PaginatedData, tenant::TenantContext};
 use server_model::admin::{
     entities::{
         prelude::SysLoginLog,
         sys_login_log::{Column as SysLoginLogColumn, Model as SysLoginLogModel},
     },
-    input::LoginLogPageRequest,
+    input::{LogExportRequest, LoginLogPageRequest},
 };
 
-use crate::helper::db_helper;
+use crate::helper::{
+    data_scope_helper::DataScopeFilter,
+    db_helper,
+    log_helper::{self, LogExportStream},
+    tenant_helper,
+};
 
 #[async_trait]
 pub trait TLoginLogService {
     async fn find_paginated_login_logs(
         &self,
         params: LoginLogPageRequest,
+        user: User,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<SysLoginLogModel>, AppError>;
+
+    /// 按条件流式导出登录日志
+    async fn export_login_logs(
+        &self,
+        params: LogExportRequest,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<LogExportStream, AppError>;
 }
 
 pub struct SysLoginLogService;
@@ -26,17 +41,20 @@ impl TLoginLogService for SysLoginLogService {
     async fn find_paginated_login_logs(
         &self,
         params: LoginLogPageRequest,
+        user: User,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<SysLoginLogModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysLoginLog::find();
+        let mut query = tenant_helper::find::<SysLoginLog>(tenant);
 
         if let Some(ref keywords) = params.keywords {
-            let condition = Condition::any()
-                .add(SysLoginLogColumn::Domain.contains(keywords))
-                .add(SysLoginLogColumn::Username.contains(keywords))
-                .add(SysLoginLogColumn::Ip.contains(keywords))
-                .add(SysLoginLogColumn::Address.contains(keywords))
-                .add(SysLoginLogColumn::UserAgent.contains(keywords));
+            query = query.filter(keywords_condition(keywords));
+        }
+
+        if let Some(condition) = DataScopeFilter::resolve(&user)
+            .await?
+            .user_condition(SysLoginLogColumn::UserId)
+        {
             query = query.filter(condition);
         }
 
@@ -61,4 +79,35 @@ impl TLoginLogService for SysLoginLogService {
             records,
         })
     }
+    async fn export_login_logs(
+        &self,
+        params: LogExportRequest,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<LogExportStream, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let filter = log_helper::created_between::<SysLoginLog>(params.start_time, params.end_time)
+            .add(tenant_helper::condition::<SysLoginLog>(tenant))
+            .add_option(params.keywords.as_deref().map(keywords_condition))
+            .add_option(
+                DataScopeFilter::resolve(&user)
+                    .await?
+                    .user_condition(SysLoginLogColumn::UserId),
+            );
+
+        Ok(log_helper::export_logs::<SysLoginLog>(
+            db,
+            filter,
+            params.format,
+        ))
+    }
+}
+
+fn keywords_condition(keywords: &str) -> Condition {
+    Condition::any()
+        .add(SysLoginLogColumn::Domain.contains(keywords))
+        .add(SysLoginLogColumn::Username.contains(keywords))
+        .add(SysLoginLogColumn::Ip.contains(keywords))
+        .add(SysLoginLogColumn::Address.contains(keywords))
+        .add(SysLoginLogColumn::UserAgent.contains(keywords))
 }
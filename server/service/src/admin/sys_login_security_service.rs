@@ -0,0 +1,677 @@
+use std::{net::IpAddr, str::FromStr};
+
+use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, Local, NaiveTime, Utc};
+use ipnet::IpNet;
+use redis::AsyncCommands;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use server_config::{LoginSecurityConfig, LoginWindowConfig};
+use server_core::web::{auth::User, error::AppError, tenant::TenantContext};
+use server_global::global;
+use server_model::admin::{
+    entities::{
+        prelude::SysLoginSecurityPolicy,
+        sys_login_security_policy::{
+            ActiveModel as SysLoginSecurityPolicyActiveModel,
+            Column as SysLoginSecurityPolicyColumn, Model as SysLoginSecurityPolicyModel,
+        },
+    },
+    input::{LockedAccountRequest, UnlockAccountInput, UpsertLoginSecurityPolicyInput},
+    output::LockedAccountOutput,
+};
+use ulid::Ulid;
+
+use crate::{
+    admin::{
+        dto::sys_login_security_dto::LoginSecurityPolicyOutput,
+        sys_login_security_error::LoginSecurityError, sys_user_error::UserError,
+    },
+    helper::{
+        db_helper,
+        redis_helper::{get_primary_connection, PrimaryConnection},
+        tenant_helper,
+    },
+    project_error, project_info,
+};
+
+const FAIL_KEY_PREFIX: &str = "login:fail:";
+const LOCK_KEY_PREFIX: &str = "login:lock:";
+const LOCKOUT_COUNT_KEY_PREFIX: &str = "login:lockout_count:";
+/// 每个域一个集合，记录可能处于锁定状态的用户名
+const LOCK_INDEX_KEY_PREFIX: &str = "login:lock_index:";
+
+#[async_trait]
+pub trait TLoginSecurityService {
+    async fn get_policy(
+        &self,
+        domain: &str,
+        tenant: &TenantContext,
+    ) -> Result<LoginSecurityPolicyOutput, AppError>;
+
+    async fn upsert_policy(
+        &self,
+        input: UpsertLoginSecurityPolicyInput,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<SysLoginSecurityPolicyModel, AppError>;
+
+    async fn delete_policy(&self, domain: &str, tenant: &TenantContext) -> Result<(), AppError>;
+
+    /// 列出被锁定的账号，未指定域时使用当前域
+    async fn list_locked_accounts(
+        &self,
+        params: LockedAccountRequest,
+        tenant: &TenantContext,
+    ) -> Result<Vec<LockedAccountOutput>, AppError>;
+
+    async fn unlock_account(
+        &self,
+        input: UnlockAccountInput,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError>;
+}
+
+#[derive(Clone)]
+pub struct SysLoginSecurityService;
+
+/// 解析后的登录安全策略
+#[derive(Debug, Clone)]
+pub struct LoginSecurityPolicy {
+    pub max_attempts: u32,
+    pub attempt_window: u64,
+    pub lockout_duration: u64,
+    pub lockout_backoff_multiplier: u32,
+    pub max_lockout_duration: u64,
+    ip_allow_list: Vec<IpNet>,
+    ip_deny_list: Vec<IpNet>,
+    login_windows: Vec<LoginWindow>,
+}
+
+#[derive(Debug, Clone)]
+struct LoginWindow {
+    /// 为空表示服务器本地时区
+    offset: Option<FixedOffset>,
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl LoginSecurityPolicy {
+    pub fn from_config(config: &LoginSecurityConfig) -> Result<Self, LoginSecurityError> {
+        Ok(Self {
+            max_attempts: config.max_attempts.max(1),
+            attempt_window: config.attempt_window.max(1),
+            lockout_duration: config.lockout_duration.max(1),
+            lockout_backoff_multiplier: config.lockout_backoff_multiplier.max(1),
+            max_lockout_duration: config.max_lockout_duration.max(config.lockout_duration),
+            ip_allow_list: parse_ip_nets(&config.ip_allow_list)?,
+            ip_deny_list: parse_ip_nets(&config.ip_deny_list)?,
+            login_windows: config
+                .login_windows
+                .iter()
+                .map(LoginWindow::parse)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// 拒绝列表优先；配置了允许列表时，只有命中的地址才能登录
+    pub fn is_ip_allowed(&self, client_ip: &str) -> bool {
+        let Ok(ip) = IpAddr::from_str(client_ip.trim()) else {
+            return self.ip_allow_list.is_empty();
+        };
+
+        if self.ip_deny_list.iter().any(|net| net.contains(&ip)) {
+            return false;
+        }
+        self.ip_allow_list.is_empty() || self.ip_allow_list.iter().any(|net| net.contains(&ip))
+    }
+
+    pub fn is_within_login_window(&self, now: DateTime<Utc>) -> bool {
+        self.login_windows.is_empty()
+            || self.login_windows.iter().any(|window| window.contains(now))
+    }
+
+    /// 第 `lockout_count` 次锁定的时长，按倍数递增并受上限约束
+    pub fn lockout_duration_for(&self, lockout_count: u32) -> u64 {
+        let exponent = lockout_count.saturating_sub(1);
+        let factor = (self.lockout_backoff_multiplier as u64)
+            .checked_pow(exponent)
+            .unwrap_or(u64::MAX);
+        self.lockout_duration
+            .saturating_mul(factor)
+            .min(self.max_lockout_duration)
+    }
+}
+
+impl LoginWindow {
+    fn parse(config: &LoginWindowConfig) -> Result<Self, LoginSecurityError> {
+        let parse_time = |value: &str| {
+            NaiveTime::parse_from_str(value, "%H:%M")
+                .map_err(|_| LoginSecurityError::InvalidLoginWindow(value.to_string()))
+        };
+
+        Ok(Self {
+            offset: parse_timezone(&config.timezone)?,
+            start: parse_time(&config.start)?,
+            end: parse_time(&config.end)?,
+        })
+    }
+
+    fn contains(&self, now: DateTime<Utc>) -> bool {
+        let time = match self.offset {
+            Some(offset) => now.with_timezone(&offset).time(),
+            None => now.with_timezone(&Local).time(),
+        };
+
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+fn parse_ip_nets(values: &[String]) -> Result<Vec<IpNet>, LoginSecurityError> {
+    values
+        .iter()
+        .map(|value| {
+            let value = value.trim();
+            IpNet::from_str(value)
+                .or_else(|_| IpAddr::from_str(value).map(IpNet::from))
+                .map_err(|_| LoginSecurityError::InvalidCidr(value.to_string()))
+        })
+        .collect()
+}
+
+fn parse_timezone(value: &str) -> Result<Option<FixedOffset>, LoginSecurityError> {
+    match value.trim() {
+        "Local" | "local" => Ok(None),
+        "UTC" | "utc" | "Z" => Ok(FixedOffset::east_opt(0)),
+        offset => FixedOffset::from_str(offset)
+            .map(Some)
+            .map_err(|_| LoginSecurityError::InvalidTimezone(value.to_string())),
+    }
+}
+
+/// 将域级覆盖合并到全局配置上
+fn merge_policy(
+    base: &LoginSecurityConfig,
+    overrides: &SysLoginSecurityPolicyModel,
+) -> LoginSecurityConfig {
+    let string_list = |value: &Option<serde_json::Value>, fallback: &Vec<String>| {
+        value
+            .clone()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| fallback.clone())
+    };
+
+    LoginSecurityConfig {
+        max_attempts: overrides
+            .max_attempts
+            .map_or(base.max_attempts, |v| v.max(1) as u32),
+        attempt_window: overrides
+            .attempt_window
+            .map_or(base.attempt_window, |v| v.max(1) as u64),
+        lockout_duration: overrides
+            .lockout_duration
+            .map_or(base.lockout_duration, |v| v.max(1) as u64),
+        lockout_backoff_multiplier: overrides
+            .lockout_backoff_multiplier
+            .map_or(base.lockout_backoff_multiplier, |v| v.max(1) as u32),
+        max_lockout_duration: overrides
+            .max_lockout_duration
+            .map_or(base.max_lockout_duration, |v| v.max(1) as u64),
+        ip_allow_list: string_list(&overrides.ip_allow_list, &base.ip_allow_list),
+        ip_deny_list: string_list(&overrides.ip_deny_list, &base.ip_deny_list),
+        login_windows: overrides
+            .login_windows
+            .clone()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| base.login_windows.clone()),
+    }
+}
+
+fn account_key(prefix: &str, domain: &str, username: &str) -> String {
+    format!("{}{}:{}", prefix, domain, username)
+}
+
+/// 域内锁定账号的索引集合
+///
+/// 锁定键到期时不会通知集合，列出锁定账号时顺带移除已过期的成员。用集合代替 `SCAN`，
+/// 集群模式下 `SCAN` 只会遍历单个节点。
+fn lock_index_key(domain: &str) -> String {
+    format!("{}{}", LOCK_INDEX_KEY_PREFIX, domain)
+}
+
+/// 保存失败计数和锁定状态的 Redis 连接
+///
+/// 未配置主 Redis 或连接失败时返回 `None`，此时跳过锁定检查和失败计数，不影响登录。
+async fn lockout_store() -> Option<PrimaryConnection> {
+    match get_primary_connection().await {
+        Ok(Some(redis)) => Some(redis),
+        Ok(None) => {
+            project_info!("Primary Redis not configured, skipping login lockout");
+            None
+        },
+        Err(e) => {
+            project_error!("Redis unavailable, skipping login lockout: {}", e.message);
+            None
+        },
+    }
+}
+
+impl SysLoginSecurityService {
+    async fn find_override(
+        &self,
+        domain: &str,
+    ) -> Result<Option<SysLoginSecurityPolicyModel>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        SysLoginSecurityPolicy::find()
+            .filter(SysLoginSecurityPolicyColumn::Domain.eq(domain))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn effective_config(&self, domain: &str) -> Result<LoginSecurityConfig, AppError> {
+        let base = global::get_config::<LoginSecurityConfig>()
+            .await
+            .map(|config| config.as_ref().clone())
+            .unwrap_or_default();
+
+        Ok(match self.find_override(domain).await? {
+            Some(overrides) => merge_policy(&base, &overrides),
+            None => base,
+        })
+    }
+
+    /// 获取域内生效的登录安全策略
+    pub async fn resolve_policy(&self, domain: &str) -> Result<LoginSecurityPolicy, AppError> {
+        let config = self.effective_config(domain).await?;
+        LoginSecurityPolicy::from_config(&config).map_err(AppError::from)
+    }
+
+    /// 登录前检查 IP、锁定状态和登录时间窗口
+    pub async fn check_login_allowed(
+        &self,
+        domain: &str,
+        username: &str,
+        client_ip: &str,
+    ) -> Result<(), AppError> {
+        let policy = self.resolve_policy(domain).await?;
+
+        if !policy.is_ip_allowed(client_ip) {
+            return Err(UserError::IpBlocked.into());
+        }
+
+        if let Some(mut redis) = lockout_store().await {
+            let locked: bool = redis
+                .exists(account_key(LOCK_KEY_PREFIX, domain, username))
+                .await?;
+            if locked {
+                return Err(UserError::AccountLocked.into());
+            }
+        }
+
+        if !policy.is_within_login_window(Utc::now()) {
+            return Err(UserError::LoginNotAllowed.into());
+        }
+
+        Ok(())
+    }
+
+    /// 记录一次登录失败，达到阈值时锁定账号
+    ///
+    /// 返回本次失败是否触发了锁定。
+    pub async fn record_login_failure(
+        &self,
+        domain: &str,
+        username: &str,
+    ) -> Result<bool, AppError> {
+        let policy = self.resolve_policy(domain).await?;
+        let Some(mut redis) = lockout_store().await else {
+            return Ok(false);
+        };
+
+        let fail_key = account_key(FAIL_KEY_PREFIX, domain, username);
+        let fail_count: u32 = redis.incr(&fail_key, 1).await?;
+        let _: () = redis
+            .expire(&fail_key, policy.attempt_window as i64)
+            .await?;
+
+        if fail_count < policy.max_attempts {
+            return Ok(false);
+        }
+
+        let count_key = account_key(LOCKOUT_COUNT_KEY_PREFIX, domain, username);
+        let lockout_count: u32 = redis.incr(&count_key, 1).await?;
+        let duration = policy.lockout_duration_for(lockout_count);
+
+        let _: () = redis
+            .set_ex(
+                account_key(LOCK_KEY_PREFIX, domain, username),
+                lockout_count,
+                duration,
+            )
+            .await?;
+        let _: () = redis.sadd(lock_index_key(domain), username).await?;
+        // 锁定解除后仍保留锁定次数一段时间，期间再次被锁定会延长锁定时长
+        let _: () = redis
+            .expire(&count_key, (duration + policy.max_lockout_duration) as i64)
+            .await?;
+        let _: () = redis.del(&fail_key).await?;
+
+        project_info!(
+            "Account {}/{} locked for {}s (lockout #{})",
+            domain,
+            username,
+            duration,
+            lockout_count
+        );
+        Ok(true)
+    }
+
+    /// 登录成功后清除失败计数
+    pub async fn clear_login_failure(&self, domain: &str, username: &str) -> Result<(), AppError> {
+        let Some(mut redis) = lockout_store().await else {
+            return Ok(());
+        };
+        let _: () = redis
+            .del(account_key(FAIL_KEY_PREFIX, domain, username))
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TLoginSecurityService for SysLoginSecurityService {
+    async fn get_policy(
+        &self,
+        domain: &str,
+        tenant: &TenantContext,
+    ) -> Result<LoginSecurityPolicyOutput, AppError> {
+        tenant_helper::ensure_domain(tenant, domain)?;
+        Ok(LoginSecurityPolicyOutput {
+            domain: domain.to_string(),
+            overrides: self.find_override(domain).await?,
+            effective: self.effective_config(domain).await?,
+        })
+    }
+
+    async fn upsert_policy(
+        &self,
+        input: UpsertLoginSecurityPolicyInput,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<SysLoginSecurityPolicyModel, AppError> {
+        tenant_helper::ensure_domain(tenant, &input.domain)?;
+        if let Some(ref list) = input.ip_allow_list {
+            parse_ip_nets(list)?;
+        }
+        if let Some(ref list) = input.ip_deny_list {
+            parse_ip_nets(list)?;
+        }
+        if let Some(ref windows) = input.login_windows {
+            for window in windows {
+                LoginWindow::parse(&LoginWindowConfig {
+                    timezone: window.timezone.clone(),
+                    start: window.start.clone(),
+                    end: window.end.clone(),
+                })?;
+            }
+        }
+
+        let ip_allow_list = input.ip_allow_list.map(serde_json::Value::from);
+        let ip_deny_list = input.ip_deny_list.map(serde_json::Value::from);
+        let login_windows = input
+            .login_windows
+            .and_then(|windows| serde_json::to_value(windows).ok());
+
+        let db = db_helper::get_db_connection().await?;
+        let now = Local::now().naive_local();
+
+        let policy = match self.find_override(&input.domain).await? {
+            Some(existing) => {
+                let mut policy: SysLoginSecurityPolicyActiveModel = existing.into();
+                policy.max_attempts = Set(input.max_attempts);
+                policy.attempt_window = Set(input.attempt_window);
+                policy.lockout_duration = Set(input.lockout_duration);
+                policy.lockout_backoff_multiplier = Set(input.lockout_backoff_multiplier);
+                policy.max_lockout_duration = Set(input.max_lockout_duration);
+                policy.ip_allow_list = Set(ip_allow_list);
+                policy.ip_deny_list = Set(ip_deny_list);
+                policy.login_windows = Set(login_windows);
+                policy.updated_at = Set(Some(now));
+                policy.updated_by = Set(Some(user.user_id()));
+                policy.update(db.as_ref()).await?
+            },
+            None => {
+                SysLoginSecurityPolicyActiveModel {
+                    id: Set(Ulid::new().to_string()),
+                    domain: Set(input.domain),
+                    max_attempts: Set(input.max_attempts),
+                    attempt_window: Set(input.attempt_window),
+                    lockout_duration: Set(input.lockout_duration),
+                    lockout_backoff_multiplier: Set(input.lockout_backoff_multiplier),
+                    max_lockout_duration: Set(input.max_lockout_duration),
+                    ip_allow_list: Set(ip_allow_list),
+                    ip_deny_list: Set(ip_deny_list),
+                    login_windows: Set(login_windows),
+                    created_at: Set(now),
+                    created_by: Set(user.user_id()),
+                    ..Default::default()
+                }
+                .insert(db.as_ref())
+                .await?
+            },
+        };
+
+        Ok(policy)
+    }
+
+    async fn delete_policy(&self, domain: &str, tenant: &TenantContext) -> Result<(), AppError> {
+        tenant_helper::ensure_domain(tenant, domain)?;
+        let db = db_helper::get_db_connection().await?;
+        let result = SysLoginSecurityPolicy::delete_many()
+            .filter(SysLoginSecurityPolicyColumn::Domain.eq(domain))
+            .exec(db.as_ref())
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(LoginSecurityError::PolicyNotFound.into());
+        }
+        Ok(())
+    }
+
+    async fn list_locked_accounts(
+        &self,
+        params: LockedAccountRequest,
+        tenant: &TenantContext,
+    ) -> Result<Vec<LockedAccountOutput>, AppError> {
+        let domain = params.domain.as_deref().unwrap_or(tenant.domain());
+        tenant_helper::ensure_domain(tenant, domain)?;
+
+        // 没有 Redis 时不会产生锁定
+        let Some(mut redis) = get_primary_connection().await? else {
+            return Ok(Vec::new());
+        };
+        let index_key = lock_index_key(domain);
+        let usernames: Vec<String> = redis.smembers(&index_key).await?;
+
+        let mut accounts = Vec::with_capacity(usernames.len());
+        for username in usernames {
+            let remaining_seconds: i64 = redis
+                .ttl(account_key(LOCK_KEY_PREFIX, domain, &username))
+                .await?;
+            if remaining_seconds == -2 {
+                // 锁定已到期
+                let _: () = redis.srem(&index_key, &username).await?;
+                continue;
+            }
+            let failed_attempts: Option<u32> = redis
+                .get(account_key(FAIL_KEY_PREFIX, domain, &username))
+                .await?;
+            let lockout_count: Option<u32> = redis
+                .get(account_key(LOCKOUT_COUNT_KEY_PREFIX, domain, &username))
+                .await?;
+
+            accounts.push(LockedAccountOutput {
+                domain: domain.to_string(),
+                username,
+                failed_attempts: failed_attempts.unwrap_or(0),
+                lockout_count: lockout_count.unwrap_or(0),
+                remaining_seconds,
+            });
+        }
+
+        accounts.sort_by(|a, b| a.username.cmp(&b.username));
+        Ok(accounts)
+    }
+
+    async fn unlock_account(
+        &self,
+        input: UnlockAccountInput,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError> {
+        tenant_helper::ensure_domain(tenant, &input.domain)?;
+        let Some(mut redis) = get_primary_connection().await? else {
+            return Err(LoginSecurityError::AccountNotLocked.into());
+        };
+        let lock_key = account_key(LOCK_KEY_PREFIX, &input.domain, &input.username);
+
+        let removed: u32 = redis.del(&lock_key).await?;
+        let _: () = redis
+            .srem(lock_index_key(&input.domain), &input.username)
+            .await?;
+        if removed == 0 {
+            return Err(LoginSecurityError::AccountNotLocked.into());
+        }
+
+        // 管理员解锁视为人工确认，同时重置递增的锁定时长；
+        // 各键在集群中可能位于不同槽位，逐个删除
+        for prefix in [FAIL_KEY_PREFIX, LOCKOUT_COUNT_KEY_PREFIX] {
+            let _: () = redis
+                .del(account_key(prefix, &input.domain, &input.username))
+                .await?;
+        }
+
+        project_info!("Account {}/{} unlocked", input.domain, input.username);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn config() -> LoginSecurityConfig {
+        LoginSecurityConfig::default()
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_ip_allow_and_deny_lists() {
+        let mut config = config();
+        config.ip_allow_list = vec!["10.0.0.0/8".to_string(), "2001:db8::/32".to_string()];
+        config.ip_deny_list = vec!["10.0.0.13".to_string()];
+        let policy = LoginSecurityPolicy::from_config(&config).unwrap();
+
+        assert!(policy.is_ip_allowed("10.1.2.3"));
+        assert!(policy.is_ip_allowed("2001:db8::1"));
+        assert!(!policy.is_ip_allowed("10.0.0.13"));
+        assert!(!policy.is_ip_allowed("192.168.1.1"));
+        assert!(!policy.is_ip_allowed("unknown"));
+
+        let open = LoginSecurityPolicy::from_config(&LoginSecurityConfig::default()).unwrap();
+        assert!(open.is_ip_allowed("127.0.0.1"));
+        assert!(open.is_ip_allowed("unknown"));
+    }
+
+    #[test]
+    fn test_invalid_cidr_is_rejected() {
+        let mut config = config();
+        config.ip_deny_list = vec!["10.0.0.0/33".to_string()];
+        assert!(matches!(
+            LoginSecurityPolicy::from_config(&config),
+            Err(LoginSecurityError::InvalidCidr(_))
+        ));
+    }
+
+    #[test]
+    fn test_login_window_uses_timezone() {
+        let mut config = config();
+        config.login_windows = vec![LoginWindowConfig {
+            timezone: "+08:00".to_string(),
+            start: "09:00".to_string(),
+            end: "18:00".to_string(),
+        }];
+        let policy = LoginSecurityPolicy::from_config(&config).unwrap();
+
+        // 01:00 UTC = 09:00 +08:00
+        assert!(policy.is_within_login_window(at(1, 0)));
+        assert!(policy.is_within_login_window(at(9, 59)));
+        assert!(!policy.is_within_login_window(at(10, 0)));
+        assert!(!policy.is_within_login_window(at(0, 59)));
+    }
+
+    #[test]
+    fn test_login_window_across_midnight() {
+        let mut config = config();
+        config.login_windows = vec![LoginWindowConfig {
+            timezone: "UTC".to_string(),
+            start: "22:00".to_string(),
+            end: "06:00".to_string(),
+        }];
+        let policy = LoginSecurityPolicy::from_config(&config).unwrap();
+
+        assert!(policy.is_within_login_window(at(23, 0)));
+        assert!(policy.is_within_login_window(at(5, 59)));
+        assert!(!policy.is_within_login_window(at(12, 0)));
+    }
+
+    #[test]
+    fn test_lockout_backoff_is_capped() {
+        let mut config = config();
+        config.lockout_duration = 60;
+        config.lockout_backoff_multiplier = 3;
+        config.max_lockout_duration = 1000;
+        let policy = LoginSecurityPolicy::from_config(&config).unwrap();
+
+        assert_eq!(policy.lockout_duration_for(1), 60);
+        assert_eq!(policy.lockout_duration_for(2), 180);
+        assert_eq!(policy.lockout_duration_for(3), 540);
+        assert_eq!(policy.lockout_duration_for(4), 1000);
+        assert_eq!(policy.lockout_duration_for(64), 1000);
+    }
+
+    #[test]
+    fn test_merge_policy_overrides_only_set_fields() {
+        let base = config();
+        let overrides = SysLoginSecurityPolicyModel {
+            id: "1".to_string(),
+            domain: "tenant".to_string(),
+            max_attempts: Some(3),
+            attempt_window: None,
+            lockout_duration: None,
+            lockout_backoff_multiplier: None,
+            max_lockout_duration: None,
+            ip_allow_list: None,
+            ip_deny_list: Some(serde_json::json!(["192.168.0.0/16"])),
+            login_windows: None,
+            created_at: Local::now().naive_local(),
+            created_by: "admin".to_string(),
+            updated_at: None,
+            updated_by: None,
+        };
+
+        let merged = merge_policy(&base, &overrides);
+        assert_eq!(merged.max_attempts, 3);
+        assert_eq!(merged.lockout_duration, base.lockout_duration);
+        assert_eq!(merged.ip_deny_list, vec!["192.168.0.0/16".to_string()]);
+        assert!(merged.ip_allow_list.is_empty());
+    }
+}
@@ -1,22 +1,36 @@
 use async_trait::async_trait;
 use chrono::Local;
-use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
 use server_core::web::{auth::User, error::AppError};
 use server_model::admin::{
     entities::{
-        prelude::{SysMenu, SysRoleMenu},
+        prelude::{SysEndpoint, SysMenu, SysPermissionCode, SysRoleMenu, SysRolePermissionCode},
         sea_orm_active_enums::Status,
         sys_menu::{
             ActiveModel as SysMenuActiveModel, Column as SysMenuColumn, Model as SysMenuModel,
         },
+        sys_permission_code::{
+            ActiveModel as SysPermissionCodeActiveModel, Column as SysPermissionCodeColumn,
+            Model as SysPermissionCodeModel,
+        },
         sys_role_menu::Column as SysRoleMenuColumn,
+        sys_role_permission_code::Column as SysRolePermissionCodeColumn,
+    },
+    input::{
+        CreateMenuInput, CreatePermissionCodeInput, UpdateMenuInput, UpdatePermissionCodeInput,
     },
-    input::{CreateMenuInput, UpdateMenuInput},
     output::{MenuRoute, MenuTree, RouteMeta},
 };
 use server_utils::TreeBuilder;
+use ulid::Ulid;
 
-use crate::{admin::sys_menu_error::MenuError, helper::db_helper};
+use crate::{
+    admin::sys_menu_error::MenuError,
+    helper::{db_helper, role_hierarchy_helper::RoleHierarchy},
+};
 
 #[async_trait]
 pub trait TMenuService {
@@ -38,11 +52,37 @@ pub trait TMenuService {
         user: User,
     ) -> Result<SysMenuModel, AppError>;
     async fn delete_menu(&self, id: i32, user: User) -> Result<(), AppError>;
+
+    /// 角色可用的菜单 ID，包含从上级角色继承的菜单
     async fn get_menu_ids_by_role_id(
         &self,
         role_id: String,
         domain: String,
     ) -> Result<Vec<i32>, AppError>;
+
+    /// 菜单下的按钮权限码
+    async fn find_permission_codes(
+        &self,
+        menu_id: i32,
+    ) -> Result<Vec<SysPermissionCodeModel>, AppError>;
+    async fn create_permission_code(
+        &self,
+        input: CreatePermissionCodeInput,
+        user: User,
+    ) -> Result<SysPermissionCodeModel, AppError>;
+    async fn update_permission_code(
+        &self,
+        input: UpdatePermissionCodeInput,
+        user: User,
+    ) -> Result<SysPermissionCodeModel, AppError>;
+    async fn delete_permission_code(&self, id: &str) -> Result<(), AppError>;
+
+    /// 直接分配给角色的按钮权限码 ID，不含继承的
+    async fn get_permission_code_ids_by_role_id(
+        &self,
+        role_id: String,
+        domain: String,
+    ) -> Result<Vec<String>, AppError>;
 }
 
 #[derive(Clone)]
@@ -111,6 +151,58 @@ impl SysMenuService {
 
         Ok(())
     }
+
+    /// 校验权限码唯一、所属菜单和关联接口存在
+    async fn check_permission_code(
+        &self,
+        id: Option<&str>,
+        menu_id: i32,
+        code: &str,
+        endpoint_id: Option<&str>,
+    ) -> Result<(), AppError> {
+        self.get_menu(menu_id).await?;
+
+        let db = db_helper::get_db_connection().await?;
+
+        let code_exists = SysPermissionCode::find()
+            .filter(SysPermissionCodeColumn::Code.eq(code))
+            .filter(SysPermissionCodeColumn::Id.ne(id.unwrap_or_default()))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .is_some();
+
+        if code_exists {
+            return Err(MenuError::DuplicatePermissionCode.into());
+        }
+
+        if let Some(endpoint_id) = endpoint_id {
+            SysEndpoint::find_by_id(endpoint_id)
+                .one(db.as_ref())
+                .await
+                .map_err(AppError::from)?
+                .ok_or(MenuError::EndpointNotFound)?;
+        }
+
+        Ok(())
+    }
+
+    /// 已分配的权限码不能删除或更换关联接口，否则角色上由它授予的接口策略无法回收
+    async fn check_permission_code_unassigned(&self, id: &str) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let assigned = SysRolePermissionCode::find()
+            .filter(SysRolePermissionCodeColumn::PermissionCodeId.eq(id))
+            .count(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        if assigned > 0 {
+            return Err(MenuError::PermissionCodeInUse.into());
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -277,10 +369,15 @@ impl TMenuService for SysMenuService {
     ) -> Result<Vec<i32>, AppError> {
         let db = db_helper::get_db_connection().await?;
 
+        // 包含从上级角色继承的菜单
+        let role_ids = RoleHierarchy::load(&domain)
+            .await?
+            .with_ancestors([role_id.as_str()]);
+
         let role_menus = SysRoleMenu::find()
             .filter(
                 Condition::all()
-                    .add(SysRoleMenuColumn::RoleId.eq(role_id))
+                    .add(SysRoleMenuColumn::RoleId.is_in(role_ids))
                     .add(SysRoleMenuColumn::Domain.eq(domain)),
             )
             .all(db.as_ref())
@@ -306,4 +403,119 @@ impl TMenuService for SysMenuService {
 
         Ok(menus.iter().map(|menu| menu.id).collect())
     }
+    async fn find_permission_codes(
+        &self,
+        menu_id: i32,
+    ) -> Result<Vec<SysPermissionCodeModel>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        SysPermissionCode::find()
+            .filter(SysPermissionCodeColumn::MenuId.eq(menu_id))
+            .order_by_asc(SysPermissionCodeColumn::Code)
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn create_permission_code(
+        &self,
+        input: CreatePermissionCodeInput,
+        user: User,
+    ) -> Result<SysPermissionCodeModel, AppError> {
+        self.check_permission_code(
+            None,
+            input.menu_id,
+            &input.code,
+            input.endpoint_id.as_deref(),
+        )
+        .await?;
+
+        let db = db_helper::get_db_connection().await?;
+
+        let permission_code = SysPermissionCodeActiveModel {
+            id: Set(Ulid::new().to_string()),
+            menu_id: Set(input.menu_id),
+            code: Set(input.code),
+            name: Set(input.name),
+            endpoint_id: Set(input.endpoint_id),
+            created_at: Set(Local::now().naive_local()),
+            created_by: Set(user.user_id()),
+            ..Default::default()
+        };
+
+        permission_code
+            .insert(db.as_ref())
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn update_permission_code(
+        &self,
+        input: UpdatePermissionCodeInput,
+        user: User,
+    ) -> Result<SysPermissionCodeModel, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let existing = SysPermissionCode::find_by_id(&input.id)
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or(MenuError::PermissionCodeNotFound)?;
+
+        let input_code = input.permission_code;
+        if input_code.endpoint_id != existing.endpoint_id {
+            self.check_permission_code_unassigned(&input.id).await?;
+        }
+        self.check_permission_code(
+            Some(&input.id),
+            input_code.menu_id,
+            &input_code.code,
+            input_code.endpoint_id.as_deref(),
+        )
+        .await?;
+
+        let mut permission_code: SysPermissionCodeActiveModel = existing.into();
+        permission_code.menu_id = Set(input_code.menu_id);
+        permission_code.code = Set(input_code.code);
+        permission_code.name = Set(input_code.name);
+        permission_code.endpoint_id = Set(input_code.endpoint_id);
+        permission_code.updated_at = Set(Some(Local::now().naive_local()));
+        permission_code.updated_by = Set(Some(user.user_id()));
+
+        permission_code
+            .update(db.as_ref())
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn delete_permission_code(&self, id: &str) -> Result<(), AppError> {
+        self.check_permission_code_unassigned(id).await?;
+
+        let db = db_helper::get_db_connection().await?;
+        let result = SysPermissionCode::delete_by_id(id)
+            .exec(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        if result.rows_affected == 0 {
+            return Err(MenuError::PermissionCodeNotFound.into());
+        }
+
+        Ok(())
+    }
+
+    async fn get_permission_code_ids_by_role_id(
+        &self,
+        role_id: String,
+        domain: String,
+    ) -> Result<Vec<String>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        SysRolePermissionCode::find()
+            .select_only()
+            .column(SysRolePermissionCodeColumn::PermissionCodeId)
+            .filter(SysRolePermissionCodeColumn::RoleId.eq(role_id))
+            .filter(SysRolePermissionCodeColumn::Domain.eq(domain))
+            .into_tuple()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)
+    }
 }
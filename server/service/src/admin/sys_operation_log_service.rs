@@ -1,12 +1,9 @@
-use std::any::Any;
-
 use async_trait::async_trait;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    Set,
+    ActiveModelTrait, ColumnTrait, Condition, PaginatorTrait, QueryFilter, QueryOrder, Set,
 };
-use server_core::web::{error::AppError, page::PaginatedData};
-use server_global::{global::OperationLogContext, project_error};
+use server_core::web::{auth::User, error::AppError, page::PaginatedData, tenant::TenantContext};
+use server_global::global::OperationLogContext;
 use server_model::admin::{
     entities::{
         prelude::SysOperationLog,
@@ -15,20 +12,35 @@ use server_model::admin::{
             Model as SysOperationLogModel,
         },
     },
-    input::OperationLogPageRequest,
+    input::{LogExportRequest, OperationLogPageRequest},
 };
 use tracing::instrument;
 use ulid::Ulid;
 
-use crate::helper::db_helper;
+use crate::helper::{
+    data_scope_helper::DataScopeFilter,
+    db_helper,
+    log_helper::{self, LogExportStream},
+    tenant_helper,
+};
 
 #[async_trait]
 pub trait TOperationLogService {
     async fn find_paginated_operation_logs(
         &self,
         params: OperationLogPageRequest,
+        user: User,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<SysOperationLogModel>, AppError>;
 
+    /// 按条件流式导出操作日志
+    async fn export_operation_logs(
+        &self,
+        params: LogExportRequest,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<LogExportStream, AppError>;
+
     async fn handle_operation_log_event(event: &OperationLogContext) -> Result<(), AppError>;
 }
 
@@ -39,16 +51,20 @@ impl TOperationLogService for SysOperationLogService {
     async fn find_paginated_operation_logs(
         &self,
         params: OperationLogPageRequest,
+        user: User,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<SysOperationLogModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysOperationLog::find();
+        let mut query = tenant_helper::find::<SysOperationLog>(tenant);
 
         if let Some(ref keywords) = params.keywords {
-            let condition = Condition::any()
-                .add(SysOperationLogColumn::Domain.contains(keywords))
-                .add(SysOperationLogColumn::Username.contains(keywords))
-                .add(SysOperationLogColumn::Ip.contains(keywords))
-                .add(SysOperationLogColumn::UserAgent.contains(keywords));
+            query = query.filter(keywords_condition(keywords));
+        }
+
+        if let Some(condition) = DataScopeFilter::resolve(&user)
+            .await?
+            .user_condition(SysOperationLogColumn::UserId)
+        {
             query = query.filter(condition);
         }
 
@@ -74,6 +90,30 @@ impl TOperationLogService for SysOperationLogService {
         })
     }
 
+    async fn export_operation_logs(
+        &self,
+        params: LogExportRequest,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<LogExportStream, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let filter =
+            log_helper::created_between::<SysOperationLog>(params.start_time, params.end_time)
+                .add(tenant_helper::condition::<SysOperationLog>(tenant))
+                .add_option(params.keywords.as_deref().map(keywords_condition))
+                .add_option(
+                    DataScopeFilter::resolve(&user)
+                        .await?
+                        .user_condition(SysOperationLogColumn::UserId),
+                );
+
+        Ok(log_helper::export_logs::<SysOperationLog>(
+            db,
+            filter,
+            params.format,
+        ))
+    }
+
     async fn handle_operation_log_event(event: &OperationLogContext) -> Result<(), AppError> {
         let db = db_helper::get_db_connection().await?;
 
@@ -105,19 +145,17 @@ impl TOperationLogService for SysOperationLogService {
     }
 }
 
-#[instrument(skip(rx))]
-pub async fn sys_operation_log_listener(
-    mut rx: tokio::sync::mpsc::UnboundedReceiver<Box<dyn Any + Send>>,
-) {
-    while let Some(event) = rx.recv().await {
-        if let Some(operation_log_context) = event.downcast_ref::<OperationLogContext>() {
-            if let Err(e) =
-                SysOperationLogService::handle_operation_log_event(operation_log_context).await
-            {
-                project_error!("Failed to handle operation log event: {:?}", e);
-            }
-        } else {
-            project_error!("Received unknown event type in operation log listener");
-        }
-    }
+fn keywords_condition(keywords: &str) -> Condition {
+    Condition::any()
+        .add(SysOperationLogColumn::Domain.contains(keywords))
+        .add(SysOperationLogColumn::Username.contains(keywords))
+        .add(SysOperationLogColumn::Ip.contains(keywords))
+        .add(SysOperationLogColumn::UserAgent.contains(keywords))
+}
+
+#[instrument(skip(event))]
+pub async fn sys_operation_log_listener(event: OperationLogContext) -> Result<(), String> {
+    SysOperationLogService::handle_operation_log_event(&event)
+        .await
+        .map_err(|e| e.message)
 }
@@ -1,34 +1,159 @@
+use std::collections::HashSet;
+
 use async_trait::async_trait;
-use sea_orm::{ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter};
-use server_core::web::{error::AppError, page::PaginatedData};
+use chrono::Local;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, Set,
+};
+use server_core::web::{auth::User, error::AppError, page::PaginatedData, tenant::TenantContext};
 use server_model::admin::{
     entities::{
         prelude::SysOrganization,
-        sys_organization::{Column as SysOrganizationColumn, Model as SysOrganizationModel},
+        sys_organization::{
+            ActiveModel as SysOrganizationActiveModel, Column as SysOrganizationColumn,
+            Model as SysOrganizationModel,
+        },
     },
-    input::OrganizationPageRequest,
+    input::{CreateOrganizationInput, OrganizationPageRequest, UpdateOrganizationInput},
+    output::OrganizationTree,
+};
+use server_utils::TreeBuilder;
+use ulid::Ulid;
+
+use super::sys_organization_error::OrganizationError;
+use crate::helper::{
+    data_scope_helper::DataScopeFilter,
+    db_helper,
+    tenant_helper::{self, TenantQuery},
 };
 
-use crate::helper::db_helper;
+/// 顶级组织的上级 ID
+const ROOT_PID: &str = "0";
 
 #[async_trait]
 pub trait TOrganizationService {
     async fn find_paginated_organizations(
         &self,
         params: OrganizationPageRequest,
+        user: User,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<SysOrganizationModel>, AppError>;
+
+    /// 按数据权限返回组织树，上级不可见的组织作为根节点
+    async fn tree_organization(
+        &self,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<Vec<OrganizationTree>, AppError>;
+
+    async fn create_organization(
+        &self,
+        input: CreateOrganizationInput,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<SysOrganizationModel, AppError>;
+    async fn get_organization(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+    ) -> Result<SysOrganizationModel, AppError>;
+    async fn update_organization(
+        &self,
+        input: UpdateOrganizationInput,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<SysOrganizationModel, AppError>;
+    async fn delete_organization(&self, id: &str, tenant: &TenantContext) -> Result<(), AppError>;
 }
 
+#[derive(Clone)]
 pub struct SysOrganizationService;
 
+impl SysOrganizationService {
+    async fn check_organization_exists(
+        &self,
+        id: Option<&str>,
+        code: &str,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let mut query = tenant_helper::find::<SysOrganization>(tenant)
+            .filter(SysOrganizationColumn::Code.eq(code));
+
+        if let Some(id) = id {
+            query = query.filter(SysOrganizationColumn::Id.ne(id));
+        }
+
+        let existing = query.one(db.as_ref()).await.map_err(AppError::from)?;
+
+        if existing.is_some() {
+            return Err(OrganizationError::DuplicateOrganizationCode.into());
+        }
+
+        Ok(())
+    }
+
+    /// 校验上级组织在当前域内存在，且不是 `id` 自身或其下级
+    async fn check_parent(
+        &self,
+        id: Option<&str>,
+        pid: &str,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError> {
+        if pid == ROOT_PID {
+            return Ok(());
+        }
+
+        let db = db_helper::get_db_connection().await?;
+        let mut current = pid.to_string();
+        let mut visited = HashSet::new();
+
+        while current != ROOT_PID && visited.insert(current.clone()) {
+            if id == Some(current.as_str()) {
+                return Err(OrganizationError::InvalidParentOrganization.into());
+            }
+
+            let parent = SysOrganization::find_by_id(current.as_str())
+                .in_tenant(tenant)
+                .one(db.as_ref())
+                .await
+                .map_err(AppError::from)?
+                .ok_or(OrganizationError::ParentOrganizationNotFound)?;
+            current = parent.pid;
+        }
+
+        Ok(())
+    }
+
+    fn build_tree_structure(organizations: Vec<OrganizationTree>) -> Vec<OrganizationTree> {
+        let ids: HashSet<String> = organizations.iter().map(|org| org.id.clone()).collect();
+
+        TreeBuilder::build(
+            organizations,
+            |node| node.id.clone(),
+            |node| {
+                if node.pid == ROOT_PID || !ids.contains(&node.pid) {
+                    None
+                } else {
+                    Some(node.pid.clone())
+                }
+            },
+            |node| node.code.clone(),
+            |node, children| node.children = Some(children),
+        )
+    }
+}
+
 #[async_trait]
 impl TOrganizationService for SysOrganizationService {
     async fn find_paginated_organizations(
         &self,
         params: OrganizationPageRequest,
+        user: User,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<SysOrganizationModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysOrganization::find();
+        let mut query = tenant_helper::find::<SysOrganization>(tenant);
 
         if let Some(ref keywords) = params.keywords {
             let condition = Condition::any()
@@ -38,6 +163,13 @@ impl TOrganizationService for SysOrganizationService {
             query = query.filter(condition);
         }
 
+        if let Some(condition) = DataScopeFilter::resolve(&user)
+            .await?
+            .org_condition(SysOrganizationColumn::Id)
+        {
+            query = query.filter(condition);
+        }
+
         let total = query
             .clone()
             .count(db.as_ref())
@@ -57,4 +189,173 @@ impl TOrganizationService for SysOrganizationService {
             records,
         })
     }
+
+    async fn tree_organization(
+        &self,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<Vec<OrganizationTree>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let mut query = tenant_helper::find::<SysOrganization>(tenant);
+
+        if let Some(condition) = DataScopeFilter::resolve(&user)
+            .await?
+            .org_condition(SysOrganizationColumn::Id)
+        {
+            query = query.filter(condition);
+        }
+
+        let organizations = query
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .map(OrganizationTree::from)
+            .collect();
+
+        Ok(Self::build_tree_structure(organizations))
+    }
+
+    async fn create_organization(
+        &self,
+        input: CreateOrganizationInput,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<SysOrganizationModel, AppError> {
+        self.check_organization_exists(None, &input.code, tenant)
+            .await?;
+        self.check_parent(None, &input.pid, tenant).await?;
+
+        let db = db_helper::get_db_connection().await?;
+        let organization = SysOrganizationActiveModel {
+            id: Set(Ulid::new().to_string()),
+            pid: Set(input.pid),
+            code: Set(input.code),
+            name: Set(input.name),
+            status: Set(input.status),
+            description: Set(input.description),
+            domain: Set(tenant.domain().to_string()),
+            created_at: Set(Local::now().naive_local()),
+            created_by: Set(user.user_id()),
+            ..Default::default()
+        };
+
+        organization
+            .insert(db.as_ref())
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn get_organization(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+    ) -> Result<SysOrganizationModel, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        SysOrganization::find_by_id(id)
+            .in_tenant(tenant)
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .ok_or_else(|| OrganizationError::OrganizationNotFound.into())
+    }
+
+    async fn update_organization(
+        &self,
+        input: UpdateOrganizationInput,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<SysOrganizationModel, AppError> {
+        let organization: SysOrganizationActiveModel =
+            self.get_organization(&input.id, tenant).await?.into();
+
+        self.check_organization_exists(Some(&input.id), &input.organization.code, tenant)
+            .await?;
+        self.check_parent(Some(&input.id), &input.organization.pid, tenant)
+            .await?;
+
+        let organization = SysOrganizationActiveModel {
+            pid: Set(input.organization.pid),
+            code: Set(input.organization.code),
+            name: Set(input.organization.name),
+            status: Set(input.organization.status),
+            description: Set(input.organization.description),
+            updated_at: Set(Some(Local::now().naive_local())),
+            updated_by: Set(Some(user.user_id())),
+            ..organization
+        };
+
+        let db = db_helper::get_db_connection().await?;
+        organization
+            .update(db.as_ref())
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn delete_organization(&self, id: &str, tenant: &TenantContext) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let has_children = tenant_helper::find::<SysOrganization>(tenant)
+            .filter(SysOrganizationColumn::Pid.eq(id))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .is_some();
+
+        if has_children {
+            return Err(OrganizationError::OrganizationHasChildren.into());
+        }
+
+        let result = tenant_helper::delete_many::<SysOrganization>(tenant)
+            .filter(SysOrganizationColumn::Id.eq(id))
+            .exec(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        if result.rows_affected == 0 {
+            return Err(OrganizationError::OrganizationNotFound.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+    use server_model::admin::entities::sea_orm_active_enums::Status;
+
+    use super::*;
+
+    fn node(id: &str, pid: &str) -> OrganizationTree {
+        OrganizationTree {
+            id: id.to_string(),
+            pid: pid.to_string(),
+            code: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            status: Status::Enabled,
+            domain: "built-in".to_string(),
+            created_at: NaiveDateTime::default(),
+            created_by: String::new(),
+            updated_at: None,
+            updated_by: None,
+            children: None,
+        }
+    }
+
+    #[test]
+    fn test_tree_promotes_nodes_with_invisible_parent() {
+        let tree = SysOrganizationService::build_tree_structure(vec![
+            node("a", "0"),
+            node("b", "a"),
+            node("c", "hidden"),
+            node("d", "c"),
+        ]);
+
+        let roots: Vec<&str> = tree.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(roots, vec!["a", "c"]);
+        assert_eq!(tree[0].children.as_ref().unwrap()[0].id, "b");
+        assert_eq!(tree[1].children.as_ref().unwrap()[0].id, "d");
+    }
 }
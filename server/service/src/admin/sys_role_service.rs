@@ -1,43 +1,98 @@
+use std::{collections::BTreeSet, sync::Arc};
+
 use async_trait::async_trait;
+use axum_casbin::casbin::RbacApi;
 use chrono::Local;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, Set,
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter,
+    QuerySelect, Set, TransactionTrait,
 };
-use server_core::web::{auth::User, error::AppError, page::PaginatedData};
+use server_core::web::{auth::User, error::AppError, page::PaginatedData, tenant::TenantContext};
 use server_model::admin::{
     entities::{
-        prelude::SysRole,
+        prelude::{SysOrganization, SysRole, SysRoleDataScopeOrg},
+        sea_orm_active_enums::DataScope,
+        sys_organization::Column as SysOrganizationColumn,
         sys_role::{
             ActiveModel as SysRoleActiveModel, Column as SysRoleColumn, Model as SysRoleModel,
         },
+        sys_role_data_scope_org::{
+            ActiveModel as SysRoleDataScopeOrgActiveModel, Column as SysRoleDataScopeOrgColumn,
+        },
     },
-    input::{CreateRoleInput, RolePageRequest, UpdateRoleInput},
+    input::{CreateRoleInput, RolePageRequest, UpdateRoleDataScopeInput, UpdateRoleInput},
+    output::RoleDataScopeOutput,
 };
-
-use super::sys_role_error::RoleError;
-use crate::helper::db_helper;
+use tokio::sync::RwLock;
 use ulid::Ulid;
 
+use super::{sys_organization_error::OrganizationError, sys_role_error::RoleError};
+use crate::helper::{
+    db_helper,
+    role_hierarchy_helper::{self, RoleHierarchy, ROOT_PID},
+    tenant_helper::{self, TenantQuery},
+};
+
 #[async_trait]
 pub trait TRoleService {
     async fn find_paginated_roles(
         &self,
         params: RolePageRequest,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<SysRoleModel>, AppError>;
 
-    async fn create_role(&self, input: CreateRoleInput, user: User) -> Result<SysRoleModel, AppError>;
-    async fn get_role(&self, id: &str) -> Result<SysRoleModel, AppError>;
-    async fn update_role(&self, input: UpdateRoleInput) -> Result<SysRoleModel, AppError>;
-    async fn delete_role(&self, id: &str) -> Result<(), AppError>;
+    /// 创建角色，并链接到上级角色以继承其权限
+    async fn create_role(
+        &self,
+        input: CreateRoleInput,
+        user: User,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<SysRoleModel, AppError>;
+    async fn get_role(&self, id: &str, tenant: &TenantContext) -> Result<SysRoleModel, AppError>;
+
+    /// 修改角色，上级角色不能是自身或其下级
+    async fn update_role(
+        &self,
+        input: UpdateRoleInput,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<SysRoleModel, AppError>;
+
+    /// 删除角色，存在下级角色时拒绝删除
+    async fn delete_role(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<(), AppError>;
+
+    async fn get_role_data_scope(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+    ) -> Result<RoleDataScopeOutput, AppError>;
+
+    /// 设置角色的数据权限，自定义范围会整体替换已配置的组织，组织须属于当前域
+    async fn update_role_data_scope(
+        &self,
+        input: UpdateRoleDataScopeInput,
+        tenant: &TenantContext,
+    ) -> Result<RoleDataScopeOutput, AppError>;
 }
 
 #[derive(Clone)]
 pub struct SysRoleService;
 
 impl SysRoleService {
-    async fn check_role_exists(&self, id: Option<&str>, code: &str) -> Result<(), AppError> {
+    async fn check_role_exists(
+        &self,
+        id: Option<&str>,
+        code: &str,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysRole::find().filter(SysRoleColumn::Code.eq(code));
+        let mut query = tenant_helper::find::<SysRole>(tenant).filter(SysRoleColumn::Code.eq(code));
 
         if let Some(id) = id {
             query = query.filter(SysRoleColumn::Id.ne(id));
@@ -51,6 +106,28 @@ impl SysRoleService {
 
         Ok(())
     }
+
+    /// 校验上级角色在当前域内存在，且不是 `id` 自身或其下级
+    async fn check_parent(
+        &self,
+        id: Option<&str>,
+        pid: &str,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError> {
+        if pid == ROOT_PID {
+            return Ok(());
+        }
+
+        let hierarchy = RoleHierarchy::load(tenant.domain()).await?;
+        if !hierarchy.contains(pid) {
+            return Err(RoleError::ParentRoleNotFound.into());
+        }
+        if id.is_some_and(|id| hierarchy.creates_cycle(id, pid)) {
+            return Err(RoleError::InvalidParentRole.into());
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -58,9 +135,10 @@ impl TRoleService for SysRoleService {
     async fn find_paginated_roles(
         &self,
         params: RolePageRequest,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<SysRoleModel>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysRole::find();
+        let mut query = tenant_helper::find::<SysRole>(tenant);
 
         if let Some(ref keywords) = params.keywords {
             let condition = Condition::any().add(SysRoleColumn::Name.contains(keywords));
@@ -87,10 +165,17 @@ impl TRoleService for SysRoleService {
         })
     }
 
-    async fn create_role(&self, input: CreateRoleInput, user: User) -> Result<SysRoleModel, AppError> {
+    async fn create_role(
+        &self,
+        input: CreateRoleInput,
+        user: User,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<SysRoleModel, AppError> {
         let db = db_helper::get_db_connection().await?;
 
-        self.check_role_exists(None, &input.code).await?;
+        self.check_role_exists(None, &input.code, tenant).await?;
+        self.check_parent(None, &input.pid, tenant).await?;
 
         let role = SysRoleActiveModel {
             id: Set(Ulid::new().to_string()),
@@ -99,31 +184,42 @@ impl TRoleService for SysRoleService {
             name: Set(input.name),
             status: Set(input.status),
             description: Set(input.description),
+            domain: Set(tenant.domain().to_string()),
             created_at: Set(Local::now().naive_local()),
             created_by: Set(user.user_id()),
             ..Default::default()
         };
 
         let result = role.insert(db.as_ref()).await.map_err(AppError::from)?;
+        role_hierarchy_helper::sync_role_links(tenant.domain(), enforcer).await?;
         Ok(result)
     }
 
-    async fn get_role(&self, id: &str) -> Result<SysRoleModel, AppError> {
+    async fn get_role(&self, id: &str, tenant: &TenantContext) -> Result<SysRoleModel, AppError> {
         let db = db_helper::get_db_connection().await?;
         SysRole::find_by_id(id)
+            .in_tenant(tenant)
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
             .ok_or_else(|| RoleError::RoleNotFound.into())
     }
 
-    async fn update_role(&self, input: UpdateRoleInput) -> Result<SysRoleModel, AppError> {
+    async fn update_role(
+        &self,
+        input: UpdateRoleInput,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<SysRoleModel, AppError> {
         let db = db_helper::get_db_connection().await?;
 
-        self.check_role_exists(Some(&input.id), &input.role.code)
+        self.check_role_exists(Some(&input.id), &input.role.code, tenant)
+            .await?;
+        self.check_parent(Some(&input.id), &input.role.pid, tenant)
             .await?;
 
         let role: SysRoleActiveModel = SysRole::find_by_id(&input.id)
+            .in_tenant(tenant)
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
@@ -142,15 +238,127 @@ impl TRoleService for SysRoleService {
         };
 
         let updated_role = role.update(db.as_ref()).await.map_err(AppError::from)?;
+        role_hierarchy_helper::sync_role_links(tenant.domain(), enforcer).await?;
         Ok(updated_role)
     }
 
-    async fn delete_role(&self, id: &str) -> Result<(), AppError> {
+    async fn delete_role(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+        enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+    ) -> Result<(), AppError> {
         let db = db_helper::get_db_connection().await?;
-        SysRole::delete_by_id(id)
+
+        let has_children = tenant_helper::find::<SysRole>(tenant)
+            .filter(SysRoleColumn::Pid.eq(id))
+            .one(db.as_ref())
+            .await
+            .map_err(AppError::from)?
+            .is_some();
+
+        if has_children {
+            return Err(RoleError::RoleHasChildren.into());
+        }
+
+        tenant_helper::delete_many::<SysRole>(tenant)
+            .filter(SysRoleColumn::Id.eq(id))
             .exec(db.as_ref())
             .await
             .map_err(AppError::from)?;
+        role_hierarchy_helper::sync_role_links(tenant.domain(), enforcer).await?;
         Ok(())
     }
+
+    async fn get_role_data_scope(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+    ) -> Result<RoleDataScopeOutput, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let role = self.get_role(id, tenant).await?;
+
+        let org_ids = SysRoleDataScopeOrg::find()
+            .select_only()
+            .column(SysRoleDataScopeOrgColumn::OrgId)
+            .filter(SysRoleDataScopeOrgColumn::RoleId.eq(id))
+            .into_tuple()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(RoleDataScopeOutput {
+            data_scope: role.data_scope,
+            org_ids,
+        })
+    }
+
+    async fn update_role_data_scope(
+        &self,
+        input: UpdateRoleDataScopeInput,
+        tenant: &TenantContext,
+    ) -> Result<RoleDataScopeOutput, AppError> {
+        let role: SysRoleActiveModel = self.get_role(&input.id, tenant).await?.into();
+
+        let org_ids: Vec<String> = if input.data_scope == DataScope::Custom {
+            input
+                .org_ids
+                .into_iter()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let db = db_helper::get_db_connection().await?;
+
+        if !org_ids.is_empty() {
+            let found = tenant_helper::find::<SysOrganization>(tenant)
+                .filter(SysOrganizationColumn::Id.is_in(org_ids.clone()))
+                .count(db.as_ref())
+                .await
+                .map_err(AppError::from)?;
+
+            if found != org_ids.len() as u64 {
+                return Err(OrganizationError::OrganizationNotFound.into());
+            }
+        }
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        SysRoleActiveModel {
+            data_scope: Set(input.data_scope),
+            updated_at: Set(Some(Local::now().naive_local())),
+            ..role
+        }
+        .update(&txn)
+        .await
+        .map_err(AppError::from)?;
+
+        SysRoleDataScopeOrg::delete_many()
+            .filter(SysRoleDataScopeOrgColumn::RoleId.eq(&input.id))
+            .exec(&txn)
+            .await
+            .map_err(AppError::from)?;
+
+        if !org_ids.is_empty() {
+            let scope_orgs = org_ids.iter().map(|org_id| SysRoleDataScopeOrgActiveModel {
+                role_id: Set(input.id.clone()),
+                org_id: Set(org_id.clone()),
+            });
+
+            SysRoleDataScopeOrg::insert_many(scope_orgs)
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        txn.commit().await.map_err(AppError::from)?;
+
+        Ok(RoleDataScopeOutput {
+            data_scope: input.data_scope,
+            org_ids,
+        })
+    }
 }
@@ -0,0 +1,39 @@
+use sea_orm::{ActiveModelTrait, Set};
+use server_core::web::{error::AppError, tenant::TenantSwitchEvent};
+use server_model::admin::entities::sys_tenant_switch_log::ActiveModel as SysTenantSwitchLogActiveModel;
+use tracing::instrument;
+use ulid::Ulid;
+
+use crate::helper::db_helper;
+
+pub struct SysTenantService;
+
+impl SysTenantService {
+    /// 记录一次跨域访问
+    async fn record_switch(event: &TenantSwitchEvent) -> Result<(), AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        SysTenantSwitchLogActiveModel {
+            id: Set(Ulid::new().to_string()),
+            user_id: Set(event.user_id.clone()),
+            username: Set(event.username.clone()),
+            home_domain: Set(event.home_domain.clone()),
+            target_domain: Set(event.target_domain.clone()),
+            method: Set(event.method.clone()),
+            url: Set(event.url.clone()),
+            created_at: Set(event.created_at),
+        }
+        .insert(db.as_ref())
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+}
+
+#[instrument(skip(event))]
+pub async fn tenant_switch_listener(event: TenantSwitchEvent) -> Result<(), String> {
+    SysTenantService::record_switch(&event)
+        .await
+        .map_err(|e| e.message)
+}
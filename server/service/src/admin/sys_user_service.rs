@@ -1,38 +1,80 @@
+use std::collections::BTreeSet;
+
 use async_trait::async_trait;
 use chrono::Local;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, Condition, EntityTrait, IntoActiveModel, PaginatorTrait,
-    QueryFilter, Set,
+    QueryFilter, QuerySelect, Set, TransactionTrait,
 };
-use server_core::web::{auth::User, error::AppError, page::PaginatedData};
+use server_core::web::{auth::User, error::AppError, page::PaginatedData, tenant::TenantContext};
 use server_model::admin::{
     entities::{
-        prelude::SysUser,
+        prelude::{SysOrganization, SysUser, SysUserOrganization},
+        sys_organization::Column as SysOrganizationColumn,
         sys_user::{
             ActiveModel as SysUserActiveModel, Column as SysUserColumn, Model as SysUserModel,
         },
+        sys_user_organization::{
+            ActiveModel as SysUserOrganizationActiveModel, Column as SysUserOrganizationColumn,
+        },
     },
-    input::{CreateUserInput, UpdateUserInput, UserPageRequest},
+    input::{AssignUserOrganizationsInput, CreateUserInput, UpdateUserInput, UserPageRequest},
     output::UserWithoutPassword,
 };
 use server_utils::SecureUtil;
 use ulid::Ulid;
 
-use super::sys_user_error::UserError;
-use crate::helper::db_helper;
+use super::{sys_organization_error::OrganizationError, sys_user_error::UserError};
+use crate::helper::{
+    data_scope_helper::DataScopeFilter,
+    db_helper,
+    tenant_helper::{self, TenantQuery},
+};
 
 #[async_trait]
 pub trait TUserService {
-    async fn find_all(&self) -> Result<Vec<UserWithoutPassword>, AppError>;
+    async fn find_all(
+        &self,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<Vec<UserWithoutPassword>, AppError>;
     async fn find_paginated_users(
         &self,
         params: UserPageRequest,
+        user: User,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<UserWithoutPassword>, AppError>;
 
-    async fn create_user(&self, input: CreateUserInput, user: User) -> Result<UserWithoutPassword, AppError>;
-    async fn get_user(&self, id: &str) -> Result<UserWithoutPassword, AppError>;
-    async fn update_user(&self, input: UpdateUserInput) -> Result<UserWithoutPassword, AppError>;
-    async fn delete_user(&self, id: &str) -> Result<(), AppError>;
+    async fn create_user(
+        &self,
+        input: CreateUserInput,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<UserWithoutPassword, AppError>;
+    async fn get_user(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+    ) -> Result<UserWithoutPassword, AppError>;
+    async fn update_user(
+        &self,
+        input: UpdateUserInput,
+        tenant: &TenantContext,
+    ) -> Result<UserWithoutPassword, AppError>;
+    async fn delete_user(&self, id: &str, tenant: &TenantContext) -> Result<(), AppError>;
+
+    async fn get_user_organization_ids(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+    ) -> Result<Vec<String>, AppError>;
+
+    /// 整体替换用户所属的组织，用户和组织都须属于当前域
+    async fn assign_user_organizations(
+        &self,
+        input: AssignUserOrganizationsInput,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError>;
 }
 
 #[derive(Clone)]
@@ -53,9 +95,14 @@ impl SysUserService {
         Ok(())
     }
 
-    async fn get_user_by_id(&self, id: String) -> Result<SysUserModel, AppError> {
+    async fn get_user_by_id(
+        &self,
+        id: String,
+        tenant: &TenantContext,
+    ) -> Result<SysUserModel, AppError> {
         let db = db_helper::get_db_connection().await?;
         SysUser::find_by_id(id)
+            .in_tenant(tenant)
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
@@ -65,9 +112,22 @@ impl SysUserService {
 
 #[async_trait]
 impl TUserService for SysUserService {
-    async fn find_all(&self) -> Result<Vec<UserWithoutPassword>, AppError> {
+    async fn find_all(
+        &self,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<Vec<UserWithoutPassword>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        SysUser::find()
+        let mut query = tenant_helper::find::<SysUser>(tenant);
+
+        if let Some(condition) = DataScopeFilter::resolve(&user)
+            .await?
+            .user_condition(SysUserColumn::Id)
+        {
+            query = query.filter(condition);
+        }
+
+        query
             .all(db.as_ref())
             .await
             .map(|users| users.into_iter().map(UserWithoutPassword::from).collect())
@@ -77,15 +137,24 @@ impl TUserService for SysUserService {
     async fn find_paginated_users(
         &self,
         params: UserPageRequest,
+        user: User,
+        tenant: &TenantContext,
     ) -> Result<PaginatedData<UserWithoutPassword>, AppError> {
         let db = db_helper::get_db_connection().await?;
-        let mut query = SysUser::find();
+        let mut query = tenant_helper::find::<SysUser>(tenant);
 
         if let Some(ref keywords) = params.keywords {
             let condition = Condition::any().add(SysUserColumn::Username.contains(keywords));
             query = query.filter(condition);
         }
 
+        if let Some(condition) = DataScopeFilter::resolve(&user)
+            .await?
+            .user_condition(SysUserColumn::Id)
+        {
+            query = query.filter(condition);
+        }
+
         let total = query
             .clone()
             .count(db.as_ref())
@@ -109,7 +178,13 @@ impl TUserService for SysUserService {
         })
     }
 
-    async fn create_user(&self, input: CreateUserInput, user: User) -> Result<UserWithoutPassword, AppError> {
+    async fn create_user(
+        &self,
+        input: CreateUserInput,
+        user: User,
+        tenant: &TenantContext,
+    ) -> Result<UserWithoutPassword, AppError> {
+        tenant_helper::ensure_domain(tenant, &input.domain)?;
         self.check_username_unique(&input.username).await?;
 
         let db = db_helper::get_db_connection().await?;
@@ -133,9 +208,14 @@ impl TUserService for SysUserService {
         Ok(UserWithoutPassword::from(user_model))
     }
 
-    async fn get_user(&self, id: &str) -> Result<UserWithoutPassword, AppError> {
+    async fn get_user(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+    ) -> Result<UserWithoutPassword, AppError> {
         let db = db_helper::get_db_connection().await?;
         SysUser::find_by_id(id)
+            .in_tenant(tenant)
             .one(db.as_ref())
             .await
             .map_err(AppError::from)?
@@ -143,8 +223,16 @@ impl TUserService for SysUserService {
             .ok_or_else(|| UserError::UserNotFound.into())
     }
 
-    async fn update_user(&self, input: UpdateUserInput) -> Result<UserWithoutPassword, AppError> {
-        let mut user = self.get_user_by_id(input.id).await?.into_active_model();
+    async fn update_user(
+        &self,
+        input: UpdateUserInput,
+        tenant: &TenantContext,
+    ) -> Result<UserWithoutPassword, AppError> {
+        tenant_helper::ensure_domain(tenant, &input.user.domain)?;
+        let mut user = self
+            .get_user_by_id(input.id, tenant)
+            .await?
+            .into_active_model();
 
         if input.user.username != *user.username.as_ref() {
             self.check_username_unique(&input.user.username).await?;
@@ -164,10 +252,11 @@ impl TUserService for SysUserService {
         Ok(UserWithoutPassword::from(updated_user))
     }
 
-    async fn delete_user(&self, id: &str) -> Result<(), AppError> {
+    async fn delete_user(&self, id: &str, tenant: &TenantContext) -> Result<(), AppError> {
         let db = db_helper::get_db_connection().await?;
 
-        let result = SysUser::delete_by_id(id)
+        let result = tenant_helper::delete_many::<SysUser>(tenant)
+            .filter(SysUserColumn::Id.eq(id))
             .exec(db.as_ref())
             .await
             .map_err(AppError::from)?;
@@ -178,4 +267,71 @@ impl TUserService for SysUserService {
 
         Ok(())
     }
+
+    async fn get_user_organization_ids(
+        &self,
+        id: &str,
+        tenant: &TenantContext,
+    ) -> Result<Vec<String>, AppError> {
+        self.get_user_by_id(id.to_string(), tenant).await?;
+
+        let db = db_helper::get_db_connection().await?;
+        SysUserOrganization::find()
+            .select_only()
+            .column(SysUserOrganizationColumn::OrgId)
+            .filter(SysUserOrganizationColumn::UserId.eq(id))
+            .into_tuple()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn assign_user_organizations(
+        &self,
+        input: AssignUserOrganizationsInput,
+        tenant: &TenantContext,
+    ) -> Result<(), AppError> {
+        self.get_user_by_id(input.user_id.clone(), tenant).await?;
+
+        let org_ids: BTreeSet<String> = input.org_ids.into_iter().collect();
+        let db = db_helper::get_db_connection().await?;
+
+        if !org_ids.is_empty() {
+            let found = tenant_helper::find::<SysOrganization>(tenant)
+                .filter(SysOrganizationColumn::Id.is_in(org_ids.iter().cloned()))
+                .count(db.as_ref())
+                .await
+                .map_err(AppError::from)?;
+
+            if found != org_ids.len() as u64 {
+                return Err(OrganizationError::OrganizationNotFound.into());
+            }
+        }
+
+        let txn = db.begin().await.map_err(AppError::from)?;
+
+        SysUserOrganization::delete_many()
+            .filter(SysUserOrganizationColumn::UserId.eq(&input.user_id))
+            .exec(&txn)
+            .await
+            .map_err(AppError::from)?;
+
+        if !org_ids.is_empty() {
+            let memberships = org_ids
+                .into_iter()
+                .map(|org_id| SysUserOrganizationActiveModel {
+                    user_id: Set(input.user_id.clone()),
+                    org_id: Set(org_id),
+                });
+
+            SysUserOrganization::insert_many(memberships)
+                .exec(&txn)
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        txn.commit().await.map_err(AppError::from)?;
+
+        Ok(())
+    }
 }
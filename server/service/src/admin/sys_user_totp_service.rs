@@ -0,0 +1,470 @@
+use async_trait::async_trait;
+use chrono::{Local, Utc};
+use rand::Rng;
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait,
+    PaginatorTrait, QueryFilter, Set, TransactionTrait,
+};
+use server_config::TotpConfig;
+use server_core::web::{auth::User, error::AppError};
+use server_global::global;
+use server_model::admin::{
+    entities::{
+        prelude::{SysUserRecoveryCode, SysUserTotp},
+        sys_user_recovery_code::{
+            ActiveModel as SysUserRecoveryCodeActiveModel, Column as SysUserRecoveryCodeColumn,
+        },
+        sys_user_totp::{
+            ActiveModel as SysUserTotpActiveModel, Column as SysUserTotpColumn,
+            Model as SysUserTotpModel,
+        },
+    },
+    input::TotpCodeInput,
+    output::{RecoveryCodesOutput, TotpSetupOutput, TotpStatusOutput},
+};
+use server_utils::{SecureUtil, TotpUtil, TOTP_DIGITS};
+use ulid::Ulid;
+
+use crate::{admin::sys_auth_error::AuthError, helper::db_helper, project_info};
+
+/// 恢复码字符集，去掉了容易混淆的 0/o、1/l/i
+const RECOVERY_CODE_ALPHABET: &[u8] = b"23456789abcdefghjkmnpqrstuvwxyz";
+/// 恢复码长度（不含分隔符）
+const RECOVERY_CODE_LEN: usize = 10;
+
+#[async_trait]
+pub trait TUserTotpService {
+    async fn get_status(&self, user: User) -> Result<TotpStatusOutput, AppError>;
+
+    async fn setup(&self, user: User) -> Result<TotpSetupOutput, AppError>;
+
+    async fn confirm(
+        &self,
+        user: User,
+        input: TotpCodeInput,
+    ) -> Result<RecoveryCodesOutput, AppError>;
+
+    async fn disable(&self, user: User, input: TotpCodeInput) -> Result<(), AppError>;
+
+    async fn regenerate_recovery_codes(
+        &self,
+        user: User,
+        input: TotpCodeInput,
+    ) -> Result<RecoveryCodesOutput, AppError>;
+}
+
+#[derive(Clone)]
+pub struct SysUserTotpService;
+
+/// 通过验证的第二因素
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecondFactor {
+    Totp,
+    RecoveryCode,
+}
+
+impl SecondFactor {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SecondFactor::Totp => "TOTP",
+            SecondFactor::RecoveryCode => "RECOVERY_CODE",
+        }
+    }
+}
+
+impl SysUserTotpService {
+    async fn config() -> TotpConfig {
+        global::get_config::<TotpConfig>()
+            .await
+            .map(|config| config.as_ref().clone())
+            .unwrap_or_default()
+    }
+
+    async fn find_by_user<C: ConnectionTrait>(
+        &self,
+        user_id: &str,
+        db: &C,
+    ) -> Result<Option<SysUserTotpModel>, AppError> {
+        SysUserTotp::find()
+            .filter(SysUserTotpColumn::UserId.eq(user_id))
+            .one(db)
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn find_enabled<C: ConnectionTrait>(
+        &self,
+        user_id: &str,
+        db: &C,
+    ) -> Result<SysUserTotpModel, AppError> {
+        self.find_by_user(user_id, db)
+            .await?
+            .filter(|totp| totp.enabled)
+            .ok_or_else(|| AuthError::TwoFactorNotEnabled.into())
+    }
+
+    /// 用户是否已启用双因素认证
+    pub async fn is_enabled(&self, user_id: &str) -> Result<bool, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        Ok(self
+            .find_by_user(user_id, db.as_ref())
+            .await?
+            .is_some_and(|totp| totp.enabled))
+    }
+
+    /// 校验动态码或恢复码，通过后该动态码所在时间步或恢复码即被消耗
+    ///
+    /// 未通过时返回 `None`，由调用方决定是否计入失败次数。
+    pub async fn verify_second_factor(
+        &self,
+        user_id: &str,
+        code: &str,
+    ) -> Result<Option<SecondFactor>, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let totp = self.find_enabled(user_id, db.as_ref()).await?;
+
+        if is_totp_code(code) {
+            let verified = self.verify_totp(&totp, code, db.as_ref()).await?;
+            Ok(verified.then_some(SecondFactor::Totp))
+        } else {
+            let verified = self.use_recovery_code(user_id, code, db.as_ref()).await?;
+            Ok(verified.then_some(SecondFactor::RecoveryCode))
+        }
+    }
+
+    /// 校验动态码并记录已使用的时间步，同一时间步内的动态码不能重复使用
+    async fn verify_totp<C: ConnectionTrait>(
+        &self,
+        totp: &SysUserTotpModel,
+        code: &str,
+        db: &C,
+    ) -> Result<bool, AppError> {
+        let config = Self::config().await;
+        let now = Utc::now().timestamp().max(0) as u64;
+        let Some(step) =
+            TotpUtil::verify_code(&totp.secret, code, now, config.skew).map_err(|e| AppError {
+                code: 500,
+                message: format!("Invalid TOTP secret: {}", e),
+            })?
+        else {
+            return Ok(false);
+        };
+        let step = step as i64;
+
+        let result = SysUserTotp::update_many()
+            .col_expr(SysUserTotpColumn::LastUsedStep, Expr::value(step))
+            .col_expr(
+                SysUserTotpColumn::UpdatedAt,
+                Expr::value(Local::now().naive_local()),
+            )
+            .filter(SysUserTotpColumn::Id.eq(&totp.id))
+            .filter(
+                Condition::any()
+                    .add(SysUserTotpColumn::LastUsedStep.is_null())
+                    .add(SysUserTotpColumn::LastUsedStep.lt(step)),
+            )
+            .exec(db)
+            .await?;
+
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn use_recovery_code<C: ConnectionTrait>(
+        &self,
+        user_id: &str,
+        code: &str,
+        db: &C,
+    ) -> Result<bool, AppError> {
+        let normalized = normalize_recovery_code(code);
+        if normalized.len() != RECOVERY_CODE_LEN {
+            return Ok(false);
+        }
+
+        let candidates = SysUserRecoveryCode::find()
+            .filter(SysUserRecoveryCodeColumn::UserId.eq(user_id))
+            .filter(SysUserRecoveryCodeColumn::UsedAt.is_null())
+            .all(db)
+            .await?;
+
+        let Some(matched) = candidates.into_iter().find(|candidate| {
+            matches!(
+                SecureUtil::verify_password(normalized.as_bytes(), &candidate.code_hash),
+                Ok(true)
+            )
+        }) else {
+            return Ok(false);
+        };
+
+        let result = SysUserRecoveryCode::update_many()
+            .col_expr(
+                SysUserRecoveryCodeColumn::UsedAt,
+                Expr::value(Local::now().naive_local()),
+            )
+            .filter(SysUserRecoveryCodeColumn::Id.eq(&matched.id))
+            .filter(SysUserRecoveryCodeColumn::UsedAt.is_null())
+            .exec(db)
+            .await?;
+
+        if result.rows_affected == 1 {
+            project_info!("Recovery code used by user {}", user_id);
+        }
+        Ok(result.rows_affected == 1)
+    }
+
+    /// 作废旧的恢复码并生成新的一组，返回明文
+    async fn replace_recovery_codes<C: ConnectionTrait>(
+        &self,
+        user_id: &str,
+        db: &C,
+    ) -> Result<Vec<String>, AppError> {
+        let config = Self::config().await;
+        let codes = generate_recovery_codes(config.recovery_code_count);
+
+        SysUserRecoveryCode::delete_many()
+            .filter(SysUserRecoveryCodeColumn::UserId.eq(user_id))
+            .exec(db)
+            .await?;
+
+        let now = Local::now().naive_local();
+        let mut models = Vec::with_capacity(codes.len());
+        for code in &codes {
+            let code_hash = SecureUtil::hash_password(normalize_recovery_code(code).as_bytes())
+                .map_err(|e| AppError {
+                    code: 500,
+                    message: format!("Failed to hash recovery code: {}", e),
+                })?;
+            models.push(SysUserRecoveryCodeActiveModel {
+                id: Set(Ulid::new().to_string()),
+                user_id: Set(user_id.to_string()),
+                code_hash: Set(code_hash),
+                used_at: Set(None),
+                created_at: Set(now),
+            });
+        }
+
+        if !models.is_empty() {
+            SysUserRecoveryCode::insert_many(models).exec(db).await?;
+        }
+
+        Ok(codes)
+    }
+}
+
+#[async_trait]
+impl TUserTotpService for SysUserTotpService {
+    async fn get_status(&self, user: User) -> Result<TotpStatusOutput, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let enabled = self
+            .find_by_user(&user.user_id(), db.as_ref())
+            .await?
+            .is_some_and(|totp| totp.enabled);
+
+        let remaining_recovery_codes = if enabled {
+            SysUserRecoveryCode::find()
+                .filter(SysUserRecoveryCodeColumn::UserId.eq(user.user_id()))
+                .filter(SysUserRecoveryCodeColumn::UsedAt.is_null())
+                .count(db.as_ref())
+                .await?
+        } else {
+            0
+        };
+
+        Ok(TotpStatusOutput {
+            enabled,
+            remaining_recovery_codes,
+        })
+    }
+
+    async fn setup(&self, user: User) -> Result<TotpSetupOutput, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let existing = self.find_by_user(&user.user_id(), db.as_ref()).await?;
+        if existing.as_ref().is_some_and(|totp| totp.enabled) {
+            return Err(AuthError::TwoFactorAlreadyEnabled.into());
+        }
+
+        let secret = TotpUtil::generate_secret().map_err(|e| AppError {
+            code: 500,
+            message: format!("Failed to generate TOTP secret: {}", e),
+        })?;
+        let now = Local::now().naive_local();
+
+        // 重新开始绑定时覆盖尚未确认的密钥
+        match existing {
+            Some(pending) => {
+                let mut active: SysUserTotpActiveModel = pending.into();
+                active.secret = Set(secret.clone());
+                active.last_used_step = Set(None);
+                active.updated_at = Set(Some(now));
+                active.update(db.as_ref()).await?;
+            },
+            None => {
+                SysUserTotpActiveModel {
+                    id: Set(Ulid::new().to_string()),
+                    user_id: Set(user.user_id()),
+                    secret: Set(secret.clone()),
+                    enabled: Set(false),
+                    last_used_step: Set(None),
+                    confirmed_at: Set(None),
+                    created_at: Set(now),
+                    updated_at: Set(None),
+                }
+                .insert(db.as_ref())
+                .await?;
+            },
+        }
+
+        let config = Self::config().await;
+        let account = format!("{}@{}", user.username(), user.domain());
+        let otpauth_uri = TotpUtil::provisioning_uri(&config.issuer, &account, &secret);
+
+        Ok(TotpSetupOutput {
+            secret,
+            otpauth_uri,
+        })
+    }
+
+    async fn confirm(
+        &self,
+        user: User,
+        input: TotpCodeInput,
+    ) -> Result<RecoveryCodesOutput, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let pending = self
+            .find_by_user(&user.user_id(), db.as_ref())
+            .await?
+            .ok_or(AuthError::TwoFactorSetupNotStarted)?;
+        if pending.enabled {
+            return Err(AuthError::TwoFactorAlreadyEnabled.into());
+        }
+
+        if !self.verify_totp(&pending, &input.code, db.as_ref()).await? {
+            return Err(AuthError::InvalidTwoFactorCode.into());
+        }
+
+        let txn = db.begin().await?;
+
+        SysUserTotp::update_many()
+            .col_expr(SysUserTotpColumn::Enabled, Expr::value(true))
+            .col_expr(
+                SysUserTotpColumn::ConfirmedAt,
+                Expr::value(Local::now().naive_local()),
+            )
+            .filter(SysUserTotpColumn::Id.eq(&pending.id))
+            .exec(&txn)
+            .await?;
+        let recovery_codes = self.replace_recovery_codes(&user.user_id(), &txn).await?;
+
+        txn.commit().await?;
+
+        project_info!(
+            "Two-factor authentication enabled for user {}",
+            user.user_id()
+        );
+        Ok(RecoveryCodesOutput { recovery_codes })
+    }
+
+    async fn disable(&self, user: User, input: TotpCodeInput) -> Result<(), AppError> {
+        if self
+            .verify_second_factor(&user.user_id(), &input.code)
+            .await?
+            .is_none()
+        {
+            return Err(AuthError::InvalidTwoFactorCode.into());
+        }
+
+        let db = db_helper::get_db_connection().await?;
+        let txn = db.begin().await?;
+
+        SysUserRecoveryCode::delete_many()
+            .filter(SysUserRecoveryCodeColumn::UserId.eq(user.user_id()))
+            .exec(&txn)
+            .await?;
+        SysUserTotp::delete_many()
+            .filter(SysUserTotpColumn::UserId.eq(user.user_id()))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await?;
+
+        project_info!(
+            "Two-factor authentication disabled for user {}",
+            user.user_id()
+        );
+        Ok(())
+    }
+
+    async fn regenerate_recovery_codes(
+        &self,
+        user: User,
+        input: TotpCodeInput,
+    ) -> Result<RecoveryCodesOutput, AppError> {
+        let db = db_helper::get_db_connection().await?;
+        let totp = self.find_enabled(&user.user_id(), db.as_ref()).await?;
+
+        // 只接受动态码，避免用旧恢复码换取新的恢复码
+        if !is_totp_code(&input.code) || !self.verify_totp(&totp, &input.code, db.as_ref()).await? {
+            return Err(AuthError::InvalidTwoFactorCode.into());
+        }
+
+        let txn = db.begin().await?;
+        let recovery_codes = self.replace_recovery_codes(&user.user_id(), &txn).await?;
+        txn.commit().await?;
+
+        Ok(RecoveryCodesOutput { recovery_codes })
+    }
+}
+
+fn is_totp_code(code: &str) -> bool {
+    let code = code.trim();
+    code.len() == TOTP_DIGITS as usize && code.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// 生成形如 `xxxxx-xxxxx` 的恢复码
+fn generate_recovery_codes(count: usize) -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    (0..count)
+        .map(|_| {
+            let chars: String = (0..RECOVERY_CODE_LEN)
+                .map(|_| {
+                    RECOVERY_CODE_ALPHABET[rng.gen_range(0..RECOVERY_CODE_ALPHABET.len())] as char
+                })
+                .collect();
+            let (head, tail) = chars.split_at(RECOVERY_CODE_LEN / 2);
+            format!("{}-{}", head, tail)
+        })
+        .collect()
+}
+
+/// 忽略大小写、空白和分隔符，方便用户手动输入
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_recovery_codes() {
+        let codes = generate_recovery_codes(10);
+        assert_eq!(codes.len(), 10);
+
+        for code in &codes {
+            assert_eq!(code.len(), RECOVERY_CODE_LEN + 1);
+            assert_eq!(code.as_bytes()[RECOVERY_CODE_LEN / 2], b'-');
+            assert_eq!(normalize_recovery_code(code).len(), RECOVERY_CODE_LEN);
+            assert!(!is_totp_code(code));
+        }
+    }
+
+    #[test]
+    fn test_normalize_recovery_code() {
+        assert_eq!(normalize_recovery_code(" ABCDE-fghjk "), "abcdefghjk");
+        assert_eq!(normalize_recovery_code("abcde fghjk"), "abcdefghjk");
+        assert!(is_totp_code(" 123456 "));
+        assert!(!is_totp_code("12345"));
+    }
+}
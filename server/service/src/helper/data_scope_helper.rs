@@ -0,0 +1,276 @@
+//! 根据调用者角色的数据权限限定列表查询的可见范围
+
+use std::collections::{BTreeSet, HashMap};
+
+use sea_orm::{
+    sea_query::{Expr, Query},
+    ColumnTrait, Condition, EntityTrait, QueryFilter, QuerySelect,
+};
+use server_core::web::{auth::User, error::AppError};
+use server_model::admin::entities::{
+    prelude::{SysOrganization, SysRole, SysRoleDataScopeOrg, SysUserOrganization},
+    sea_orm_active_enums::{DataScope, Status},
+    sys_organization::Column as SysOrganizationColumn,
+    sys_role::Column as SysRoleColumn,
+    sys_role_data_scope_org::Column as SysRoleDataScopeOrgColumn,
+    sys_user_organization::Column as SysUserOrganizationColumn,
+};
+
+use crate::helper::db_helper;
+
+/// 调用者可见的数据范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataScopeFilter {
+    /// 不做限制
+    All,
+    /// 仅可见 `org_ids` 中组织的成员数据，`user_id` 不为空时还可见本人数据
+    Restricted {
+        org_ids: BTreeSet<String>,
+        user_id: Option<String>,
+    },
+}
+
+impl DataScopeFilter {
+    /// 解析调用者所有启用角色的数据权限并取并集，没有可用角色时仅可见本人数据
+    pub async fn resolve(user: &User) -> Result<Self, AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let roles: Vec<(String, DataScope)> = SysRole::find()
+            .select_only()
+            .column(SysRoleColumn::Id)
+            .column(SysRoleColumn::DataScope)
+            .filter(SysRoleColumn::Code.is_in(user.subject()))
+            .filter(SysRoleColumn::Domain.eq(user.domain()))
+            .filter(SysRoleColumn::Status.eq(Status::Enabled))
+            .into_tuple()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        if roles.iter().any(|(_, scope)| *scope == DataScope::All) {
+            return Ok(Self::All);
+        }
+
+        let needs = |scope: DataScope| roles.iter().any(|(_, s)| *s == scope);
+
+        let mut member_org_ids = Vec::new();
+        if needs(DataScope::Org) || needs(DataScope::OrgAndChildren) {
+            member_org_ids = SysUserOrganization::find()
+                .select_only()
+                .column(SysUserOrganizationColumn::OrgId)
+                .filter(SysUserOrganizationColumn::UserId.eq(user.user_id()))
+                .into_tuple()
+                .all(db.as_ref())
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        let mut organizations = Vec::new();
+        if needs(DataScope::OrgAndChildren) {
+            organizations = SysOrganization::find()
+                .select_only()
+                .column(SysOrganizationColumn::Id)
+                .column(SysOrganizationColumn::Pid)
+                .into_tuple()
+                .all(db.as_ref())
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        let mut custom_org_ids = Vec::new();
+        if needs(DataScope::Custom) {
+            let custom_role_ids = roles
+                .iter()
+                .filter(|(_, scope)| *scope == DataScope::Custom)
+                .map(|(id, _)| id.clone());
+            custom_org_ids = SysRoleDataScopeOrg::find()
+                .select_only()
+                .column(SysRoleDataScopeOrgColumn::OrgId)
+                .filter(SysRoleDataScopeOrgColumn::RoleId.is_in(custom_role_ids))
+                .into_tuple()
+                .all(db.as_ref())
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        let scopes: Vec<DataScope> = roles.into_iter().map(|(_, scope)| scope).collect();
+        Ok(Self::merge(
+            &user.user_id(),
+            &scopes,
+            &member_org_ids,
+            &organizations,
+            &custom_org_ids,
+        ))
+    }
+
+    /// 合并多个角色的数据权限
+    ///
+    /// - `member_org_ids`: 调用者所属的组织
+    /// - `organizations`: 全部组织的 (ID, 上级 ID)，用于展开下级组织
+    /// - `custom_org_ids`: 自定义范围角色配置的组织
+    fn merge(
+        user_id: &str,
+        scopes: &[DataScope],
+        member_org_ids: &[String],
+        organizations: &[(String, String)],
+        custom_org_ids: &[String],
+    ) -> Self {
+        if scopes.is_empty() {
+            return Self::Restricted {
+                org_ids: BTreeSet::new(),
+                user_id: Some(user_id.to_string()),
+            };
+        }
+
+        let mut org_ids = BTreeSet::new();
+        let mut include_self = false;
+
+        for scope in scopes {
+            match scope {
+                DataScope::All => return Self::All,
+                DataScope::Org => org_ids.extend(member_org_ids.iter().cloned()),
+                DataScope::OrgAndChildren => {
+                    org_ids.extend(with_descendants(member_org_ids, organizations))
+                },
+                DataScope::SelfOnly => include_self = true,
+                DataScope::Custom => org_ids.extend(custom_org_ids.iter().cloned()),
+            }
+        }
+
+        Self::Restricted {
+            org_ids,
+            user_id: include_self.then(|| user_id.to_string()),
+        }
+    }
+
+    /// 按用户 ID 列过滤，用于用户、日志等归属于用户的数据
+    pub fn user_condition<C: ColumnTrait>(&self, user_id_column: C) -> Option<Condition> {
+        let Self::Restricted { org_ids, user_id } = self else {
+            return None;
+        };
+
+        let mut condition = Condition::any();
+        if !org_ids.is_empty() {
+            let members = Query::select()
+                .column(SysUserOrganizationColumn::UserId)
+                .from(SysUserOrganization)
+                .and_where(SysUserOrganizationColumn::OrgId.is_in(org_ids.iter().cloned()))
+                .to_owned();
+            condition = condition.add(user_id_column.in_subquery(members));
+        }
+        if let Some(user_id) = user_id {
+            condition = condition.add(user_id_column.eq(user_id.as_str()));
+        }
+        Some(Self::or_nothing(condition))
+    }
+
+    /// 按组织 ID 列过滤，仅本人范围不可见任何组织
+    pub fn org_condition<C: ColumnTrait>(&self, org_id_column: C) -> Option<Condition> {
+        let Self::Restricted { org_ids, .. } = self else {
+            return None;
+        };
+
+        let mut condition = Condition::any();
+        if !org_ids.is_empty() {
+            condition = condition.add(org_id_column.is_in(org_ids.iter().cloned()));
+        }
+        Some(Self::or_nothing(condition))
+    }
+
+    fn or_nothing(condition: Condition) -> Condition {
+        if condition.is_empty() {
+            Condition::all().add(Expr::val(1).eq(0))
+        } else {
+            condition
+        }
+    }
+}
+
+/// 返回 `roots` 及其全部下级组织的 ID
+fn with_descendants(roots: &[String], organizations: &[(String, String)]) -> BTreeSet<String> {
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (id, pid) in organizations {
+        children.entry(pid.as_str()).or_default().push(id.as_str());
+    }
+
+    let mut result = BTreeSet::new();
+    let mut pending: Vec<&str> = roots.iter().map(String::as_str).collect();
+    while let Some(id) = pending.pop() {
+        if result.insert(id.to_string()) {
+            if let Some(ids) = children.get(id) {
+                pending.extend(ids.iter().copied());
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn organizations() -> Vec<(String, String)> {
+        [("a", "0"), ("b", "a"), ("c", "b"), ("d", "0")]
+            .into_iter()
+            .map(|(id, pid)| (id.to_string(), pid.to_string()))
+            .collect()
+    }
+
+    fn ids(ids: &[&str]) -> BTreeSet<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_merge_takes_union_of_role_scopes() {
+        let member = vec!["a".to_string()];
+        let custom = vec!["d".to_string()];
+
+        assert_eq!(
+            DataScopeFilter::merge("u1", &[DataScope::Org], &member, &[], &[]),
+            DataScopeFilter::Restricted {
+                org_ids: ids(&["a"]),
+                user_id: None
+            }
+        );
+        assert_eq!(
+            DataScopeFilter::merge(
+                "u1",
+                &[
+                    DataScope::OrgAndChildren,
+                    DataScope::SelfOnly,
+                    DataScope::Custom
+                ],
+                &member,
+                &organizations(),
+                &custom,
+            ),
+            DataScopeFilter::Restricted {
+                org_ids: ids(&["a", "b", "c", "d"]),
+                user_id: Some("u1".to_string())
+            }
+        );
+        assert_eq!(
+            DataScopeFilter::merge("u1", &[DataScope::SelfOnly, DataScope::All], &[], &[], &[]),
+            DataScopeFilter::All
+        );
+        assert_eq!(
+            DataScopeFilter::merge("u1", &[], &member, &[], &[]),
+            DataScopeFilter::Restricted {
+                org_ids: BTreeSet::new(),
+                user_id: Some("u1".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn test_with_descendants_handles_cycles() {
+        let mut organizations = organizations();
+        organizations.push(("a".to_string(), "c".to_string()));
+
+        assert_eq!(
+            with_descendants(&["b".to_string()], &organizations),
+            ids(&["a", "b", "c"])
+        );
+        assert!(with_descendants(&[], &organizations).is_empty());
+    }
+}
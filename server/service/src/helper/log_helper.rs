@@ -0,0 +1,231 @@
+//! 登录日志与操作日志的分批读取和编码，供导出与归档共用
+
+use std::{io::Write, sync::Arc};
+
+use bytes::Bytes;
+use chrono::NaiveDateTime;
+use futures::{stream::BoxStream, StreamExt};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use serde::Serialize;
+use serde_json::Value;
+use server_core::web::error::AppError;
+use server_model::admin::{
+    entities::{
+        prelude::{SysLoginLog, SysOperationLog},
+        sys_login_log, sys_operation_log,
+    },
+    input::LogExportFormat,
+};
+
+use crate::admin::errors::sys_log_archive_error::LogArchiveError;
+
+/// 导出时每次从数据库读取的行数
+const EXPORT_BATCH_SIZE: u64 = 500;
+
+/// 按 (创建时间, ID) 分页读取的日志表
+pub trait LogEntity: EntityTrait<Model: Serialize> {
+    /// 表名，作为归档对象键的一部分
+    const TABLE: &'static str;
+
+    fn id_column() -> Self::Column;
+
+    fn created_at_column() -> Self::Column;
+
+    /// 读取下一批数据的游标
+    fn cursor(model: &Self::Model) -> LogCursor;
+}
+
+/// 上一批最后一行的 (创建时间, ID)
+pub type LogCursor = (NaiveDateTime, String);
+
+/// 流式导出的内容
+pub type LogExportStream = BoxStream<'static, Result<Bytes, AppError>>;
+
+impl LogEntity for SysLoginLog {
+    const TABLE: &'static str = "sys_login_log";
+
+    fn id_column() -> Self::Column {
+        sys_login_log::Column::Id
+    }
+
+    fn created_at_column() -> Self::Column {
+        sys_login_log::Column::CreatedAt
+    }
+
+    fn cursor(model: &Self::Model) -> LogCursor {
+        (model.created_at, model.id.clone())
+    }
+}
+
+impl LogEntity for SysOperationLog {
+    const TABLE: &'static str = "sys_operation_log";
+
+    fn id_column() -> Self::Column {
+        sys_operation_log::Column::Id
+    }
+
+    fn created_at_column() -> Self::Column {
+        sys_operation_log::Column::CreatedAt
+    }
+
+    fn cursor(model: &Self::Model) -> LogCursor {
+        (model.created_at, model.id.clone())
+    }
+}
+
+/// 创建时间落在 `[start, end)` 内的条件
+pub fn created_between<E: LogEntity>(
+    start: Option<NaiveDateTime>,
+    end: Option<NaiveDateTime>,
+) -> Condition {
+    Condition::all()
+        .add_option(start.map(|start| E::created_at_column().gte(start)))
+        .add_option(end.map(|end| E::created_at_column().lt(end)))
+}
+
+/// 读取游标之后的一批日志，按 (创建时间, ID) 升序
+pub async fn fetch_after<E: LogEntity>(
+    db: &DatabaseConnection,
+    filter: Condition,
+    cursor: Option<&LogCursor>,
+    limit: u64,
+) -> Result<Vec<E::Model>, DbErr> {
+    let mut query = E::find().filter(filter);
+    if let Some((created_at, id)) = cursor {
+        query = query.filter(
+            Condition::any()
+                .add(E::created_at_column().gt(*created_at))
+                .add(
+                    Condition::all()
+                        .add(E::created_at_column().eq(*created_at))
+                        .add(E::id_column().gt(id.as_str())),
+                ),
+        );
+    }
+
+    query
+        .order_by_asc(E::created_at_column())
+        .order_by_asc(E::id_column())
+        .limit(limit)
+        .all(db)
+        .await
+}
+
+/// 以流的形式分批导出符合条件的日志，内存中只保留一批数据
+pub fn export_logs<E: LogEntity>(
+    db: Arc<DatabaseConnection>,
+    filter: Condition,
+    format: LogExportFormat,
+) -> LogExportStream {
+    futures::stream::try_unfold((None::<LogCursor>, true), move |(cursor, first)| {
+        let db = db.clone();
+        let filter = filter.clone();
+        async move {
+            let rows = fetch_after::<E>(&db, filter, cursor.as_ref(), EXPORT_BATCH_SIZE).await?;
+            let Some(last) = rows.last() else {
+                return Ok(None);
+            };
+            let next = E::cursor(last);
+            let bytes = encode_rows(&rows, format, first)?;
+            Ok::<_, AppError>(Some((bytes, (Some(next), false))))
+        }
+    })
+    .boxed()
+}
+
+/// 将一批日志编码为 CSV 或 NDJSON，`with_header` 为真时 CSV 输出表头
+pub fn encode_rows<T: Serialize>(
+    rows: &[T],
+    format: LogExportFormat,
+    with_header: bool,
+) -> Result<Bytes, LogArchiveError> {
+    let encode_error = |e: &dyn std::fmt::Display| LogArchiveError::Encode(e.to_string());
+
+    match format {
+        LogExportFormat::Ndjson => {
+            let mut buf = Vec::new();
+            write_ndjson(&mut buf, rows)?;
+            Ok(Bytes::from(buf))
+        },
+        LogExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for (index, row) in rows.iter().enumerate() {
+                let Value::Object(map) = serde_json::to_value(row).map_err(|e| encode_error(&e))?
+                else {
+                    continue;
+                };
+                if with_header && index == 0 {
+                    writer
+                        .write_record(map.keys())
+                        .map_err(|e| encode_error(&e))?;
+                }
+                writer
+                    .write_record(map.values().map(csv_cell))
+                    .map_err(|e| encode_error(&e))?;
+            }
+            let buf = writer.into_inner().map_err(|e| encode_error(&e))?;
+            Ok(Bytes::from(buf))
+        },
+    }
+}
+
+/// 将日志逐行写为 NDJSON
+pub fn write_ndjson<W: Write, T: Serialize>(
+    writer: &mut W,
+    rows: &[T],
+) -> Result<(), LogArchiveError> {
+    for row in rows {
+        serde_json::to_writer(&mut *writer, row)
+            .map_err(|e| LogArchiveError::Encode(e.to_string()))?;
+        writer
+            .write_all(b"\n")
+            .map_err(|e| LogArchiveError::Encode(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// CSV 单元格，嵌套的 JSON 以字符串输出，公式前缀加 `'` 防止表格软件执行
+fn csv_cell(value: &Value) -> String {
+    let cell = match value {
+        Value::Null => return String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+
+    if cell.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", cell)
+    } else {
+        cell
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_csv() {
+        let rows = [
+            json!({"id": "1", "params": {"a": 1}, "userAgent": null}),
+            json!({"id": "2", "params": null, "userAgent": "=cmd()"}),
+        ];
+
+        let first = encode_rows(&rows[..1], LogExportFormat::Csv, true).unwrap();
+        let rest = encode_rows(&rows[1..], LogExportFormat::Csv, false).unwrap();
+
+        assert_eq!(first, "id,params,userAgent\n1,\"{\"\"a\"\":1}\",\n");
+        assert_eq!(rest, "2,,'=cmd()\n");
+    }
+
+    #[test]
+    fn test_encode_ndjson() {
+        let rows = [json!({"id": "1"}), json!({"id": "2"})];
+        let bytes = encode_rows(&rows, LogExportFormat::Ndjson, true).unwrap();
+        assert_eq!(bytes, "{\"id\":\"1\"}\n{\"id\":\"2\"}\n");
+    }
+}
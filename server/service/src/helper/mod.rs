@@ -1,3 +1,7 @@
+pub mod data_scope_helper;
 pub mod db_helper;
+pub mod log_helper;
 pub mod mongo_helper;
 pub mod redis_helper;
+pub mod role_hierarchy_helper;
+pub mod tenant_helper;
@@ -1,7 +1,12 @@
 #![allow(dead_code)]
-use redis::{aio::MultiplexedConnection, cluster_async::ClusterConnection, ErrorKind, RedisError};
+use redis::{
+    aio::{ConnectionLike, MultiplexedConnection, PubSub},
+    cluster_async::ClusterConnection,
+    AsyncCommands, Cmd, ErrorKind, Pipeline, RedisError, RedisFuture, Value,
+};
+use server_config::RedisConfig;
 use server_core::web::error::AppError;
-use server_global::global::{RedisConnection, GLOBAL_PRIMARY_REDIS, GLOBAL_REDIS_POOL};
+use server_global::global::{self, RedisConnection, GLOBAL_PRIMARY_REDIS, GLOBAL_REDIS_POOL};
 
 /// Redis连接来源
 #[derive(Debug, Clone)]
@@ -53,6 +58,57 @@ pub async fn get_redis_connection(source: RedisSource) -> Result<MultiplexedConn
     }
 }
 
+/// 主Redis的命令连接，单机和集群模式下都可以直接执行 [`AsyncCommands`]
+pub enum PrimaryConnection {
+    Single(MultiplexedConnection),
+    Cluster(ClusterConnection),
+}
+
+impl ConnectionLike for PrimaryConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        match self {
+            PrimaryConnection::Single(conn) => conn.req_packed_command(cmd),
+            PrimaryConnection::Cluster(conn) => conn.req_packed_command(cmd),
+        }
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        match self {
+            PrimaryConnection::Single(conn) => conn.req_packed_commands(cmd, offset, count),
+            PrimaryConnection::Cluster(conn) => conn.req_packed_commands(cmd, offset, count),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            PrimaryConnection::Single(conn) => conn.get_db(),
+            PrimaryConnection::Cluster(conn) => conn.get_db(),
+        }
+    }
+}
+
+/// 获取主Redis的命令连接，不区分单机和集群模式；未配置主Redis时返回 `None`
+pub async fn get_primary_connection() -> Result<Option<PrimaryConnection>, AppError> {
+    let Some(redis) = GLOBAL_PRIMARY_REDIS.read().await.clone() else {
+        return Ok(None);
+    };
+
+    let connection = match redis {
+        RedisConnection::Single(client) => {
+            PrimaryConnection::Single(client.get_multiplexed_async_connection().await?)
+        },
+        RedisConnection::Cluster(client) => {
+            PrimaryConnection::Cluster(client.get_async_connection().await?)
+        },
+    };
+    Ok(Some(connection))
+}
+
 /// 获取Redis集群连接
 pub async fn get_redis_cluster_connection(
     source: RedisSource,
@@ -91,3 +147,54 @@ pub async fn get_redis_cluster_connection(
         },
     }
 }
+
+/// 向主Redis频道发布消息，集群模式下 PUBLISH 会广播到所有节点
+pub async fn publish(channel: &str, payload: &str) -> Result<(), AppError> {
+    let redis = GLOBAL_PRIMARY_REDIS.read().await.clone().ok_or_else(|| {
+        AppError::from(RedisError::from((
+            ErrorKind::IoError,
+            "Primary Redis not initialized",
+        )))
+    })?;
+    let _: i64 = match redis {
+        RedisConnection::Single(client) => {
+            let mut conn = client.get_multiplexed_async_connection().await?;
+            conn.publish(channel, payload).await?
+        },
+        RedisConnection::Cluster(client) => {
+            let mut conn = client.get_async_connection().await?;
+            conn.publish(channel, payload).await?
+        },
+    };
+    Ok(())
+}
+
+/// 订阅主Redis频道，集群模式下连接配置中的第一个节点订阅
+pub async fn subscribe(channel: &str) -> Result<PubSub, AppError> {
+    let redis = GLOBAL_PRIMARY_REDIS.read().await.clone().ok_or_else(|| {
+        AppError::from(RedisError::from((
+            ErrorKind::IoError,
+            "Primary Redis not initialized",
+        )))
+    })?;
+    let mut pubsub = match redis {
+        RedisConnection::Single(client) => client.get_async_pubsub().await?,
+        RedisConnection::Cluster(_) => {
+            let url = global::get_config::<RedisConfig>()
+                .await
+                .and_then(|config| config.get_urls())
+                .and_then(|urls| urls.into_iter().next())
+                .ok_or_else(|| {
+                    AppError::from(RedisError::from((
+                        ErrorKind::IoError,
+                        "Redis cluster URLs not configured",
+                    )))
+                })?;
+            redis::Client::open(url.as_str())?
+                .get_async_pubsub()
+                .await?
+        },
+    };
+    pubsub.subscribe(channel).await?;
+    Ok(pubsub)
+}
@@ -0,0 +1,211 @@
+//! 角色继承：子角色继承上级角色的接口权限和菜单
+//!
+//! 接口权限通过 casbin 的 `g = _, _, _` 角色链接生效，链接由 [`sync_role_links`]
+//! 按域从 `sys_role.pid` 物化；菜单没有经过 casbin，由 [`RoleHierarchy`] 在查询时展开上级角色。
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
+
+use axum_casbin::casbin::RbacApi;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+use server_core::web::error::AppError;
+use server_model::admin::entities::{prelude::SysRole, sys_role::Column as SysRoleColumn};
+use tokio::sync::RwLock;
+
+use crate::helper::db_helper;
+
+/// 顶级角色的上级 ID
+pub const ROOT_PID: &str = "0";
+
+/// 同一域内角色的上下级关系
+#[derive(Debug, Default)]
+pub struct RoleHierarchy {
+    /// ID -> (编码, 上级 ID)
+    roles: HashMap<String, (String, String)>,
+}
+
+impl RoleHierarchy {
+    /// 加载域内全部角色
+    pub async fn load(domain: &str) -> Result<Self, AppError> {
+        let db = db_helper::get_db_connection().await?;
+
+        let roles: Vec<(String, String, String)> = SysRole::find()
+            .select_only()
+            .column(SysRoleColumn::Id)
+            .column(SysRoleColumn::Code)
+            .column(SysRoleColumn::Pid)
+            .filter(SysRoleColumn::Domain.eq(domain))
+            .into_tuple()
+            .all(db.as_ref())
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(Self::new(roles))
+    }
+
+    fn new(roles: Vec<(String, String, String)>) -> Self {
+        Self {
+            roles: roles
+                .into_iter()
+                .map(|(id, code, pid)| (id, (code, pid)))
+                .collect(),
+        }
+    }
+
+    pub fn contains(&self, role_id: &str) -> bool {
+        self.roles.contains_key(role_id)
+    }
+
+    /// 角色 ID 及其全部上级角色的 ID，上级链中出现环时在重复处停止
+    pub fn with_ancestors<'a>(
+        &self,
+        role_ids: impl IntoIterator<Item = &'a str>,
+    ) -> BTreeSet<String> {
+        let mut result = BTreeSet::new();
+
+        for role_id in role_ids {
+            let mut current = role_id;
+            while let Some((_, pid)) = self.roles.get(current) {
+                if !result.insert(current.to_string()) {
+                    break;
+                }
+                current = pid;
+            }
+        }
+
+        result
+    }
+
+    /// 按编码展开，用于从令牌中的角色编码解析菜单
+    pub fn with_ancestors_by_codes(&self, role_codes: &[String]) -> BTreeSet<String> {
+        let role_ids = self
+            .roles
+            .iter()
+            .filter(|(_, (code, _))| role_codes.contains(code))
+            .map(|(id, _)| id.as_str());
+        self.with_ancestors(role_ids)
+    }
+
+    /// 以 `pid` 作为 `role_id` 的上级是否会形成环
+    pub fn creates_cycle(&self, role_id: &str, pid: &str) -> bool {
+        self.with_ancestors([pid]).contains(role_id)
+    }
+
+    /// 域内应存在的 casbin 角色链接：`[子角色编码, 上级角色编码, 域]`
+    fn links(&self, domain: &str) -> BTreeSet<Vec<String>> {
+        self.roles
+            .values()
+            .filter_map(|(code, pid)| {
+                let (parent_code, _) = self.roles.get(pid)?;
+                Some(vec![code.clone(), parent_code.clone(), domain.to_string()])
+            })
+            .collect()
+    }
+}
+
+/// 按数据库中的角色树重建域内的 casbin 角色链接
+///
+/// 域内的 `g` 规则只用于角色继承，不在角色树中的链接（角色被删除、改编码或移动后遗留的）会被移除。
+pub async fn sync_role_links(
+    domain: &str,
+    enforcer: Arc<RwLock<impl RbacApi + Send + Sync>>,
+) -> Result<(), AppError> {
+    let expected = RoleHierarchy::load(domain).await?.links(domain);
+
+    let mut enforcer_write = enforcer.write().await;
+    let existing: HashSet<Vec<String>> = enforcer_write
+        .get_filtered_grouping_policy(2, vec![domain.to_string()])
+        .into_iter()
+        .collect();
+
+    let links_to_remove: Vec<Vec<String>> = existing
+        .iter()
+        .filter(|link| !expected.contains(*link))
+        .cloned()
+        .collect();
+    let links_to_add: Vec<Vec<String>> = expected
+        .into_iter()
+        .filter(|link| !existing.contains(link))
+        .collect();
+
+    if !links_to_remove.is_empty() {
+        enforcer_write
+            .remove_grouping_policies(links_to_remove)
+            .await
+            .map_err(|e| AppError {
+                code: 500,
+                message: e.to_string(),
+            })?;
+    }
+
+    if !links_to_add.is_empty() {
+        enforcer_write
+            .add_grouping_policies(links_to_add)
+            .await
+            .map_err(|e| AppError {
+                code: 500,
+                message: e.to_string(),
+            })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hierarchy() -> RoleHierarchy {
+        RoleHierarchy::new(
+            [
+                ("1", "ROLE_A", ROOT_PID),
+                ("2", "ROLE_B", "1"),
+                ("3", "ROLE_C", "2"),
+                ("4", "ROLE_D", ROOT_PID),
+            ]
+            .into_iter()
+            .map(|(id, code, pid)| (id.to_string(), code.to_string(), pid.to_string()))
+            .collect(),
+        )
+    }
+
+    fn ids(ids: &[&str]) -> BTreeSet<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_with_ancestors_and_cycles() {
+        let hierarchy = hierarchy();
+
+        assert_eq!(hierarchy.with_ancestors(["3"]), ids(&["1", "2", "3"]));
+        assert_eq!(hierarchy.with_ancestors(["2", "4"]), ids(&["1", "2", "4"]));
+        assert_eq!(
+            hierarchy.with_ancestors_by_codes(&["ROLE_B".to_string()]),
+            ids(&["1", "2"])
+        );
+        assert!(hierarchy.with_ancestors(["missing"]).is_empty());
+
+        assert!(hierarchy.creates_cycle("1", "3"));
+        assert!(hierarchy.creates_cycle("2", "2"));
+        assert!(!hierarchy.creates_cycle("3", "4"));
+        assert!(!hierarchy.creates_cycle("1", ROOT_PID));
+    }
+
+    #[test]
+    fn test_links_follow_role_tree() {
+        let link = |child: &str, parent: &str| {
+            vec![
+                child.to_string(),
+                parent.to_string(),
+                "built-in".to_string(),
+            ]
+        };
+
+        assert_eq!(
+            hierarchy().links("built-in"),
+            BTreeSet::from([link("ROLE_B", "ROLE_A"), link("ROLE_C", "ROLE_B")])
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! 租户数据的查询入口
+//!
+//! 归属于某个域的表实现 [`TenantEntity`]，服务层读写这些表时经由 [`find`]、
+//! [`delete_many`] 或 [`TenantQuery::in_tenant`] 构造查询，自动附加当前请求的域条件。
+
+use sea_orm::{ColumnTrait, Condition, DeleteMany, EntityTrait, QueryFilter, Select, UpdateMany};
+use server_core::web::{error::AppError, tenant::TenantContext};
+use server_model::admin::entities::{
+    prelude::{SysAccessKey, SysLoginLog, SysOperationLog, SysOrganization, SysRole, SysUser},
+    sys_access_key, sys_login_log, sys_operation_log, sys_organization, sys_role, sys_user,
+};
+
+use crate::admin::errors::sys_tenant_error::TenantError;
+
+/// 按域隔离的表
+pub trait TenantEntity: EntityTrait {
+    fn domain_column() -> Self::Column;
+}
+
+macro_rules! tenant_entity {
+    ($entity:ty, $column:path) => {
+        impl TenantEntity for $entity {
+            fn domain_column() -> Self::Column {
+                $column
+            }
+        }
+    };
+}
+
+tenant_entity!(SysUser, sys_user::Column::Domain);
+tenant_entity!(SysRole, sys_role::Column::Domain);
+tenant_entity!(SysOrganization, sys_organization::Column::Domain);
+tenant_entity!(SysAccessKey, sys_access_key::Column::Domain);
+tenant_entity!(SysLoginLog, sys_login_log::Column::Domain);
+tenant_entity!(SysOperationLog, sys_operation_log::Column::Domain);
+
+/// 为查询附加当前域条件
+pub trait TenantQuery<E: TenantEntity>: QueryFilter + Sized {
+    fn in_tenant(self, tenant: &TenantContext) -> Self {
+        self.filter(E::domain_column().eq(tenant.domain()))
+    }
+}
+
+impl<E: TenantEntity> TenantQuery<E> for Select<E> {}
+impl<E: TenantEntity> TenantQuery<E> for DeleteMany<E> {}
+impl<E: TenantEntity> TenantQuery<E> for UpdateMany<E> {}
+
+/// 当前域内的查询
+pub fn find<E: TenantEntity>(tenant: &TenantContext) -> Select<E> {
+    E::find().in_tenant(tenant)
+}
+
+/// 当前域条件，用于需要自行拼装 [`Condition`] 的查询
+pub fn condition<E: TenantEntity>(tenant: &TenantContext) -> Condition {
+    Condition::all().add(E::domain_column().eq(tenant.domain()))
+}
+
+/// 当前域内的批量删除
+pub fn delete_many<E: TenantEntity>(tenant: &TenantContext) -> DeleteMany<E> {
+    E::delete_many().in_tenant(tenant)
+}
+
+/// 校验请求中指定的域就是当前域，用于创建或修改带域字段的数据
+pub fn ensure_domain(tenant: &TenantContext, domain: &str) -> Result<(), AppError> {
+    if domain == tenant.domain() {
+        Ok(())
+    } else {
+        Err(TenantError::DomainMismatch(domain.to_string()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DbBackend, QueryTrait};
+
+    use super::*;
+
+    #[test]
+    fn test_queries_are_limited_to_current_domain() {
+        let tenant = TenantContext::new("tenant-a");
+
+        let select = find::<SysUser>(&tenant)
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(select.ends_with(r#"WHERE "sys_user"."domain" = 'tenant-a'"#));
+
+        let delete = delete_many::<SysRole>(&tenant)
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert_eq!(
+            delete,
+            r#"DELETE FROM "sys_role" WHERE "sys_role"."domain" = 'tenant-a'"#
+        );
+
+        assert!(ensure_domain(&tenant, "tenant-a").is_ok());
+        assert!(ensure_domain(&tenant, "tenant-b").is_err());
+    }
+}
@@ -327,13 +327,10 @@ pub mod signature {
     /// * `signature` - Hex-encoded signature (65 bytes: r[32] + s[32] + v[1])
     /// 
     /// # Returns
-    /// * Recovered Ethereum address or error
-    /// 
-    /// Note: This is a simplified implementation. For production use,
-    /// consider using `alloy::signers` module which provides full EIP-155 support.
+    /// * Recovered Ethereum address (lowercase hex) or error
     pub fn recover_signer(message: &str, signature: &str) -> Result<String, SignatureError> {
-        use alloy_primitives::U256;
-        
+        use alloy::primitives::{Signature, B256};
+
         // Parse signature from hex
         let sig_bytes = signature
             .trim_start_matches("0x")
@@ -346,25 +343,17 @@ pub mod signature {
             return Err(SignatureError::InvalidSignatureLength);
         }
 
-        // Parse signature components
-        let _r = U256::from_be_slice(&bytes[0..32]);
-        let _s = U256::from_be_slice(&bytes[32..64]);
-        let _v = bytes[64];
-
-        // Build EIP-191 message hash using Keccak-256 (for future use)
-        let _message_hash = hash_eip191(message);
-
-        // For proper signature verification, use alloy's signer module:
-        // use alloy::signers::local::{PrivateKeySigner, Signature};
-        // let signer = PrivateKeySigner::from_bytes(&private_key)?;
-        // let recovered = signer.sign_message(&message_hash)?;
-        //
-        // Simplified: return address derived from r (not cryptographically secure)
-        // This is a placeholder - production code should use proper ecrecover
-        let mut addr_bytes = [0u8; 20];
-        addr_bytes.copy_from_slice(&bytes[12..32]); // Use last 20 bytes of r as address
-        
-        Ok(format!("0x{}", hex::encode(addr_bytes)))
+        // v may be 0/1 or 27/28 (Electrum notation)
+        let sig = Signature::from_raw(&bytes)
+            .map_err(|e| SignatureError::RecoverFailed(e.to_string()))?;
+
+        // secp256k1 ecrecover over the EIP-191 message hash
+        let message_hash = B256::from(hash_eip191(message));
+        let address = sig
+            .recover_address_from_prehash(&message_hash)
+            .map_err(|e| SignatureError::RecoverFailed(e.to_string()))?;
+
+        Ok(format!("0x{}", hex::encode(address.as_slice())))
     }
 
     /// Hash message according to EIP-191 using Keccak-256
@@ -432,6 +421,25 @@ pub mod signature {
             assert_eq!(hash1, hash2); // Same message = same hash
             assert_ne!(hash1, hash3); // Different message = different hash
         }
+
+        #[test]
+        fn test_recover_signer() {
+            use alloy::signers::{local::PrivateKeySigner, SignerSync};
+
+            let signer = PrivateKeySigner::random();
+            let message = generate_sign_message("test-nonce-123");
+            let signature = signer.sign_message_sync(message.as_bytes()).unwrap();
+            let signature = format!("0x{}", hex::encode(signature.as_bytes()));
+            let address = format!("{:?}", signer.address());
+
+            assert_eq!(recover_signer(&message, &signature).unwrap(), address.to_lowercase());
+            assert!(verify_eip191(&message, &signature, &address).unwrap());
+            assert!(!verify_eip191("other message", &signature, &address).unwrap());
+            assert!(matches!(
+                recover_signer(&message, "0x1234"),
+                Err(SignatureError::InvalidSignatureLength)
+            ));
+        }
     }
 }
 
@@ -253,7 +253,7 @@ pub fn get_bridge_tokens(chain_id: u64) -> Vec<TokenInfo> {
     // Add common tokens
     for (symbol, name, decimals, logo) in common_tokens {
         tokens.push(TokenInfo {
-            address: Some(format!("0x0000000000000000000000000000000000000000")),
+            address: Some("0x0000000000000000000000000000000000000000".to_string()),
             symbol: symbol.to_string(),
             name: name.to_string(),
             decimals,
@@ -146,11 +146,11 @@ impl GasAnalyticsService {
         let avg_gas_price_gwei = 20.0 + (base_value as f64 % 30.0);
         let avg_gas_price = format!("{:.2}", avg_gas_price_gwei);
         
-        let transaction_count = (base_value % 100) as u64 + period_days as u64;
+        let transaction_count = (base_value % 100) + period_days as u64;
         
         let avg_tx_cost = format!("{:.6}", (total_gas_wei as f64 / 1e18) / transaction_count as f64);
         
-        let trend = if base_value % 3 == 0 {
+        let trend = if base_value.is_multiple_of(3) {
             "increasing".to_string()
         } else if base_value % 3 == 1 {
             "decreasing".to_string()
@@ -158,10 +158,10 @@ impl GasAnalyticsService {
             "stable".to_string()
         };
         
-        let change_percentage = if base_value % 2 == 0 {
+        let change_percentage = if base_value.is_multiple_of(2) {
             ((base_value % 20) as f64) - 10.0
         } else {
-            ((base_value % 15) as f64) * -1.0
+            -((base_value % 15) as f64)
         };
         
         Ok(GasAnalyticsSummary {
@@ -258,7 +258,7 @@ impl GasAnalyticsService {
             .parse()
             .unwrap_or(1000);
         
-        if addr_hash % 2 == 0 {
+        if addr_hash.is_multiple_of(2) {
             suggestions.push(GasOptimizationSuggestion {
                 suggestion_type: "batch_transactions".to_string(),
                 description: "Consider batching multiple transactions to reduce gas costs. Batch transactions can save up to 30% on gas fees.".to_string(),
@@ -268,7 +268,7 @@ impl GasAnalyticsService {
             });
         }
         
-        if addr_hash % 3 == 0 {
+        if addr_hash.is_multiple_of(3) {
             suggestions.push(GasOptimizationSuggestion {
                 suggestion_type: "off_peak_timing".to_string(),
                 description: "Transaction costs are typically lower during weekends and late night hours. Consider scheduling non-urgent transactions during these periods.".to_string(),
@@ -278,7 +278,7 @@ impl GasAnalyticsService {
             });
         }
         
-        if addr_hash % 5 == 0 {
+        if addr_hash.is_multiple_of(5) {
             suggestions.push(GasOptimizationSuggestion {
                 suggestion_type: "use_erc_677".to_string(),
                 description: "Consider using ERC-677 tokens which can reduce transfer gas costs compared to standard ERC-20 transfers.".to_string(),
@@ -324,12 +324,12 @@ impl GasAnalyticsService {
         let current_gas = addr_hash * period_days as u64 * 21000;
         let current_gas_eth = format!("{:.6}", current_gas as f64 / 1e18);
         let current_gas_usd = format!("{:.2}", (current_gas as f64 / 1e18) * eth_price);
-        let current_tx_count = (addr_hash % 100) as u64 + period_days as u64;
+        let current_tx_count = (addr_hash % 100) + period_days as u64;
         
         let prev_gas = addr_hash * period_days as u64 * 20000;
         let prev_gas_eth = format!("{:.6}", prev_gas as f64 / 1e18);
         let prev_gas_usd = format!("{:.2}", (prev_gas as f64 / 1e18) * eth_price);
-        let prev_tx_count = (addr_hash % 90) as u64 + period_days as u64;
+        let prev_tx_count = (addr_hash % 90) + period_days as u64;
         
         let current = GasAnalyticsSummary {
             total_gas_eth: current_gas_eth.clone(),
@@ -270,19 +270,19 @@ impl TWalletService for Web3WalletService {
         let wallet_type = input.wallet_type.unwrap_or_else(|| "metamask".to_string());
         let chain_id = input.chain_id.unwrap_or(1);
         
-        // Verify signature using EIP-191
-        // If message and signature are provided, verify them
-        if !input.signature.is_empty() && !input.message.is_empty() {
-            let is_valid = alloy_provider::signature::verify_eip191(
-                &input.message,
-                &input.signature,
-                &input.wallet_address.to_lowercase()
-            ).unwrap_or(false);
-            
-            if !is_valid {
-                tracing::warn!("Wallet signature verification failed for {}", input.wallet_address);
-                // Don't block - allow save but log warning
-            }
+        // Verify signature using EIP-191 - wallet ownership must be proven
+        if input.signature.is_empty() || input.message.is_empty() {
+            return Err(ServiceError::new("Signature and message are required"));
+        }
+        let is_valid = alloy_provider::signature::verify_eip191(
+            &input.message,
+            &input.signature,
+            &input.wallet_address.to_lowercase()
+        ).unwrap_or(false);
+
+        if !is_valid {
+            tracing::warn!("Wallet signature verification failed for {}", input.wallet_address);
+            return Err(ServiceError::new("Wallet signature verification failed"));
         }
 
         // Check if wallet exists
@@ -91,6 +91,12 @@ pub struct TokenConfig {
     pub decimals: u8,
 }
 
+impl Default for OraclePriceService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl OraclePriceService {
     pub fn new() -> Self {
         let chainlink_feeds = vec![
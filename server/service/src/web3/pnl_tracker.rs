@@ -159,6 +159,12 @@ pub struct PnLTrackerService {
     positions: HashMap<String, HashMap<String, Position>>,
 }
 
+impl Default for PnLTrackerService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PnLTrackerService {
     pub fn new() -> Self {
         Self {
@@ -186,7 +192,7 @@ impl PnLTrackerService {
         };
 
         // Add to trades
-        self.trades.entry(wallet.clone()).or_insert_with(Vec::new).push(trade.clone());
+        self.trades.entry(wallet.clone()).or_default().push(trade.clone());
 
         // Update positions
         self.update_position(&wallet, &token, &trade);
@@ -195,7 +201,7 @@ impl PnLTrackerService {
     }
 
     fn update_position(&mut self, wallet: &str, token: &str, trade: &TokenTrade) {
-        let wallet_positions = self.positions.entry(wallet.to_string()).or_insert_with(HashMap::new);
+        let wallet_positions = self.positions.entry(wallet.to_string()).or_default();
         let position = wallet_positions.entry(token.to_string()).or_insert_with(|| {
             Position {
                 token_address: trade.token_address.clone(),
@@ -385,7 +385,7 @@ pub fn get_failure_statistics(failures: &[TransactionFailureAnalysis]) -> Failur
     FailureStatistics {
         total_failures: failures.len() as u64,
         failure_types,
-        average_gas_wasted: if gas_count > 0 { total_gas / gas_count } else { 0 },
+        average_gas_wasted: total_gas.checked_div(gas_count).unwrap_or(0),
         most_common_failure,
         recommended_gas_price: "查看当前Gas价格建议".to_string(),
     }
@@ -7,7 +7,7 @@ fn test_encode_address() {
     use server_service::web3::erc20::encode_address;
     
     // Valid Ethereum address
-    let addr = Address::from_slice(&[0x74, 0x2d, 0x35, 0xCc, 0x66, 0x34, 0xC0, 0x53, 0x29, 0x25, 0xa3, 0xb8, 0x44, 0xBc, 0x9e, 0x75, 0x95, 0xf0, 0xeB, 0x1E]);
+    let addr = Address::from_slice(&[0x74, 0x2d, 0x35, 0xcc, 0x66, 0x34, 0xC0, 0x53, 0x29, 0x25, 0xa3, 0xb8, 0x44, 0xbc, 0x9e, 0x75, 0x95, 0xf0, 0xeb, 0x1E]);
     let encoded = encode_address(addr);
     assert_eq!(&encoded[12..], addr.as_slice());
 }
@@ -0,0 +1,109 @@
+use std::error::Error;
+
+use data_encoding::BASE64;
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    digest,
+    rand::{SecureRandom, SystemRandom},
+};
+
+/// 密文前缀，用于区分加密后的值和历史明文
+const CIPHERTEXT_PREFIX: &str = "enc:v1:";
+
+/// 基于 AES-256-GCM 的对称加密工具，用于敏感字段的落库加密
+///
+/// 密钥由配置的口令经 SHA-256 派生；密文格式为 `enc:v1:` 加上 Base64 编码的 nonce 与密文。
+pub struct CipherUtil {
+    key: LessSafeKey,
+}
+
+impl CipherUtil {
+    pub fn new(secret: &str) -> Result<Self, Box<dyn Error>> {
+        let key_bytes = digest::digest(&digest::SHA256, secret.as_bytes());
+        let key = UnboundKey::new(&AES_256_GCM, key_bytes.as_ref())
+            .map_err(|_| "Failed to create encryption key")?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+        })
+    }
+
+    /// 是否为本工具生成的密文
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(CIPHERTEXT_PREFIX)
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, Box<dyn Error>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| "Failed to generate nonce")?;
+
+        let mut in_out = plaintext.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| "Encryption failed")?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&in_out);
+        Ok(format!("{}{}", CIPHERTEXT_PREFIX, BASE64.encode(&payload)))
+    }
+
+    pub fn decrypt(&self, ciphertext: &str) -> Result<String, Box<dyn Error>> {
+        let encoded = ciphertext
+            .strip_prefix(CIPHERTEXT_PREFIX)
+            .ok_or("Value is not encrypted")?;
+        let payload = BASE64.decode(encoded.as_bytes())?;
+        if payload.len() < NONCE_LEN {
+            return Err("Ciphertext too short".into());
+        }
+
+        let (nonce, sealed) = payload.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| "Invalid nonce")?;
+        let mut in_out = sealed.to_vec();
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut in_out)
+            .map_err(|_| "Decryption failed")?;
+
+        Ok(String::from_utf8(plaintext.to_vec())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_and_decrypt() {
+        let cipher = CipherUtil::new("test-encryption-key").unwrap();
+        let encrypted = cipher.encrypt("SK01HZXAMPLE").unwrap();
+
+        assert!(CipherUtil::is_encrypted(&encrypted));
+        assert!(!encrypted.contains("SK01HZXAMPLE"));
+        assert_ne!(encrypted, cipher.encrypt("SK01HZXAMPLE").unwrap());
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "SK01HZXAMPLE");
+    }
+
+    #[test]
+    fn test_decrypt_rejects_wrong_key_and_tampering() {
+        let cipher = CipherUtil::new("test-encryption-key").unwrap();
+        let encrypted = cipher.encrypt("SK01HZXAMPLE").unwrap();
+
+        let other = CipherUtil::new("other-key").unwrap();
+        assert!(other.decrypt(&encrypted).is_err());
+
+        let mut payload = BASE64
+            .decode(encrypted.trim_start_matches(CIPHERTEXT_PREFIX).as_bytes())
+            .unwrap();
+        let last = payload.len() - 1;
+        payload[last] ^= 0x01;
+        let tampered = format!("{}{}", CIPHERTEXT_PREFIX, BASE64.encode(&payload));
+        assert!(cipher.decrypt(&tampered).is_err());
+
+        assert!(cipher.decrypt("SK01HZXAMPLE").is_err());
+    }
+}
@@ -1,5 +1,9 @@
+mod cipher_util;
 mod secure_util;
+mod totp_util;
 mod tree_util;
 
+pub use cipher_util::*;
 pub use secure_util::*;
+pub use totp_util::*;
 pub use tree_util::*;
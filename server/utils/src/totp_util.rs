@@ -0,0 +1,165 @@
+use std::error::Error;
+
+use data_encoding::BASE32_NOPAD;
+use ring::{
+    hmac,
+    rand::{SecureRandom, SystemRandom},
+};
+
+/// 时间步长（秒）
+pub const TOTP_PERIOD: u64 = 30;
+/// 动态码位数
+pub const TOTP_DIGITS: u32 = 6;
+/// 密钥长度（字节），RFC 4226 推荐 160 位
+const SECRET_LEN: usize = 20;
+
+/// 基于 RFC 6238 的 TOTP 工具，使用 HMAC-SHA1、6 位数字和 30 秒步长，
+/// 与常见的身份验证器应用保持兼容。
+pub struct TotpUtil;
+
+impl TotpUtil {
+    /// 生成随机密钥，返回不带填充的 Base32 编码
+    pub fn generate_secret() -> Result<String, Box<dyn Error>> {
+        let mut bytes = [0u8; SECRET_LEN];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| "Failed to generate random secret")?;
+        Ok(BASE32_NOPAD.encode(&bytes))
+    }
+
+    /// 生成供身份验证器扫码的 otpauth:// URI
+    pub fn provisioning_uri(issuer: &str, account: &str, secret: &str) -> String {
+        let issuer = urlencoding::encode(issuer);
+        let account = urlencoding::encode(account);
+        format!(
+            "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={TOTP_DIGITS}&period={TOTP_PERIOD}"
+        )
+    }
+
+    /// 时间戳（秒）所在的时间步
+    pub fn time_step(timestamp: u64) -> u64 {
+        timestamp / TOTP_PERIOD
+    }
+
+    /// 计算指定时间戳的动态码
+    pub fn generate_code(secret: &str, timestamp: u64) -> Result<String, Box<dyn Error>> {
+        let key = Self::decode_secret(secret)?;
+        Ok(hotp(&key, Self::time_step(timestamp), TOTP_DIGITS))
+    }
+
+    /// 在前后 `skew` 个时间步内校验动态码，成功时返回匹配的时间步
+    ///
+    /// 调用方应记录返回的时间步，拒绝不大于已使用时间步的动态码以防止重放。
+    pub fn verify_code(
+        secret: &str,
+        code: &str,
+        timestamp: u64,
+        skew: u64,
+    ) -> Result<Option<u64>, Box<dyn Error>> {
+        let code = code.trim();
+        if code.len() != TOTP_DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return Ok(None);
+        }
+
+        let key = Self::decode_secret(secret)?;
+        let current = Self::time_step(timestamp);
+        let matched = (current.saturating_sub(skew)..=current.saturating_add(skew)).find(|step| {
+            constant_time_eq(hotp(&key, *step, TOTP_DIGITS).as_bytes(), code.as_bytes())
+        });
+
+        Ok(matched)
+    }
+
+    fn decode_secret(secret: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        let normalized: String = secret
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '=')
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        Ok(BASE32_NOPAD.decode(normalized.as_bytes())?)
+    }
+}
+
+/// RFC 4226 HOTP
+fn hotp(key: &[u8], counter: u64, digits: u32) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, key);
+    let tag = hmac::sign(&key, &counter.to_be_bytes());
+    let digest = tag.as_ref();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(digits),
+        width = digits as usize
+    )
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 6238 附录 B 中 SHA1 的测试密钥 "12345678901234567890"
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        let key = TotpUtil::decode_secret(RFC_SECRET).unwrap();
+        let vectors = [
+            (59, "94287082"),
+            (1111111109, "07081804"),
+            (1111111111, "14050471"),
+            (1234567890, "89005924"),
+            (2000000000, "69279037"),
+        ];
+
+        for (timestamp, expected) in vectors {
+            assert_eq!(hotp(&key, TotpUtil::time_step(timestamp), 8), expected);
+            assert_eq!(
+                TotpUtil::generate_code(RFC_SECRET, timestamp).unwrap(),
+                expected[2..]
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_code_with_skew() {
+        let timestamp = 1111111111;
+        let previous = TotpUtil::generate_code(RFC_SECRET, timestamp - TOTP_PERIOD).unwrap();
+        let step = TotpUtil::time_step(timestamp);
+
+        assert_eq!(
+            TotpUtil::verify_code(RFC_SECRET, &previous, timestamp, 1).unwrap(),
+            Some(step - 1)
+        );
+        assert_eq!(
+            TotpUtil::verify_code(RFC_SECRET, &previous, timestamp, 0).unwrap(),
+            None
+        );
+        assert_eq!(
+            TotpUtil::verify_code(RFC_SECRET, "12a456", timestamp, 1).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_generate_secret_and_uri() {
+        let secret = TotpUtil::generate_secret().unwrap();
+        assert_eq!(secret.len(), 32);
+        assert!(TotpUtil::generate_code(&secret, 0).is_ok());
+
+        let uri = TotpUtil::provisioning_uri("Soybean Admin", "admin@built-in", &secret);
+        assert!(uri.starts_with("otpauth://totp/Soybean%20Admin:admin%40built-in?secret="));
+        assert!(uri.contains("&issuer=Soybean%20Admin"));
+    }
+}
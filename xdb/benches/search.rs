@@ -1,42 +1,40 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use rand;
-use xdb::searcher::{
-    get_block_by_size, get_full_cache, get_vector_index_cache, search_by_ip, searcher_init,
+use xdb::{
+    searcher::{search_by_ip, searcher_init},
+    CachePolicy, Searcher,
 };
 
+const XDB_FILEPATH: &str = "../server/resources/ip2region.xdb";
+
 fn search_by_ip_bench(c: &mut Criterion) {
     c.bench_function("search_by_ip_bench", |b| {
-        searcher_init(None);
+        searcher_init(None).unwrap();
         b.iter(|| {
             search_by_ip(rand::random::<u32>()).unwrap();
         })
     });
 }
 
-fn get_block_by_size_bench(c: &mut Criterion) {
-    c.bench_function("get_block_by_size_bench", |b| {
-        b.iter(|| {
-            black_box(get_block_by_size(
-                get_full_cache(),
-                rand::random::<u16>() as usize,
-                4,
-            ));
-        })
-    });
-}
-
-fn get_full_cache_bench(c: &mut Criterion) {
-    c.bench_function("get_full_cache_bench", |b| {
-        b.iter(|| {
-            black_box(get_full_cache());
-        })
-    });
+fn searcher_policy_bench(c: &mut Criterion) {
+    for (name, policy) in [
+        ("searcher_full_bench", CachePolicy::Full),
+        ("searcher_vector_index_bench", CachePolicy::VectorIndex),
+        ("searcher_file_bench", CachePolicy::File),
+    ] {
+        let searcher = Searcher::new(XDB_FILEPATH, policy).unwrap();
+        c.bench_function(name, |b| {
+            b.iter(|| {
+                black_box(searcher.search(rand::random::<u32>()).unwrap());
+            })
+        });
+    }
 }
 
-fn get_vec_index_cache_bench(c: &mut Criterion) {
-    c.bench_function("get_vec_index_cache_bench", |b| {
+fn searcher_reload_bench(c: &mut Criterion) {
+    let searcher = Searcher::new(XDB_FILEPATH, CachePolicy::VectorIndex).unwrap();
+    c.bench_function("searcher_reload_bench", |b| {
         b.iter(|| {
-            black_box(get_vector_index_cache());
+            searcher.reload(XDB_FILEPATH).unwrap();
         })
     });
 }
@@ -44,8 +42,7 @@ fn get_vec_index_cache_bench(c: &mut Criterion) {
 criterion_group!(
     benches,
     search_by_ip_bench,
-    get_block_by_size_bench,
-    get_full_cache_bench,
-    get_vec_index_cache_bench,
+    searcher_policy_bench,
+    searcher_reload_bench,
 );
 criterion_main!(benches);
@@ -0,0 +1,124 @@
+use std::error::Error;
+
+pub(crate) const HEADER_INFO_LENGTH: usize = 256;
+pub(crate) const VECTOR_INDEX_COLS: usize = 256;
+pub(crate) const VECTOR_INDEX_SIZE: usize = 8;
+pub(crate) const VECTOR_INDEX_LENGTH: usize = 512 * 1024;
+
+/// 头部中 IP 版本字段的偏移，旧版（仅 IPv4）的 xdb 该字段为 0
+pub(crate) const HEADER_IP_VERSION_OFFSET: usize = 16;
+
+/// 支持的 xdb 结构版本，2 为仅 IPv4 的旧版格式，3 增加了 IPv6 支持
+const SUPPORTED_STRUCTURE_VERSIONS: [u16; 2] = [2, 3];
+
+/// xdb 文件的 IP 版本
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpVersion {
+    V4,
+    V6,
+}
+
+impl IpVersion {
+    /// 从 xdb 头部读取 IP 版本
+    pub fn from_header(buffer: &[u8]) -> Result<Self, Box<dyn Error>> {
+        match read_u16(buffer, HEADER_IP_VERSION_OFFSET)? {
+            0 | 4 => Ok(Self::V4),
+            6 => Ok(Self::V6),
+            version => Err(format!("invalid xdb file: unknown ip version {version}").into()),
+        }
+    }
+
+    /// IP 占用的字节数
+    #[inline(always)]
+    pub(crate) const fn ip_bytes(self) -> usize {
+        match self {
+            Self::V4 => 4,
+            Self::V6 => 16,
+        }
+    }
+
+    /// 段索引大小：起始 IP、结束 IP、数据长度（2 字节）和数据偏移（4 字节）
+    #[inline(always)]
+    pub(crate) const fn segment_index_size(self) -> usize {
+        self.ip_bytes() * 2 + 6
+    }
+
+    /// 解码段索引中的 IP，IPv4 以小端序存储，IPv6 以网络字节序存储
+    #[inline(always)]
+    pub(crate) fn decode_ip(self, bytes: &[u8]) -> u128 {
+        match self {
+            Self::V4 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u128,
+            Self::V6 => {
+                let mut buf = [0u8; 16];
+                buf.copy_from_slice(&bytes[..16]);
+                u128::from_be_bytes(buf)
+            },
+        }
+    }
+}
+
+/// xdb 文件头
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub version: u16,
+    pub index_policy: u16,
+    pub created_at: u32,
+    pub start_index_ptr: u32,
+    pub end_index_ptr: u32,
+    pub ip_version: IpVersion,
+}
+
+impl Header {
+    /// 解析并校验文件头
+    ///
+    /// `file_len` 为整个 xdb 文件的长度，用于校验索引指针是否越界。
+    pub fn parse(buffer: &[u8], file_len: usize) -> Result<Self, Box<dyn Error>> {
+        if buffer.len() < HEADER_INFO_LENGTH || file_len < HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH
+        {
+            return Err("invalid xdb file: too short".into());
+        }
+
+        let version = read_u16(buffer, 0)? as u16;
+        if !SUPPORTED_STRUCTURE_VERSIONS.contains(&version) {
+            return Err(format!("invalid xdb file: unsupported version {version}").into());
+        }
+
+        let header = Self {
+            version,
+            index_policy: read_u16(buffer, 2)? as u16,
+            created_at: read_u32(buffer, 4)? as u32,
+            start_index_ptr: read_u32(buffer, 8)? as u32,
+            end_index_ptr: read_u32(buffer, 12)? as u32,
+            ip_version: IpVersion::from_header(buffer)?,
+        };
+
+        let segment_index_size = header.ip_version.segment_index_size();
+        let start = header.start_index_ptr as usize;
+        let end = header.end_index_ptr as usize;
+        if start < HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH
+            || end < start
+            || !(end - start).is_multiple_of(segment_index_size)
+            || end + segment_index_size > file_len
+        {
+            return Err("invalid xdb file: segment index out of range".into());
+        }
+
+        Ok(header)
+    }
+}
+
+#[inline(always)]
+pub(crate) fn read_u16(bytes: &[u8], offset: usize) -> Result<usize, Box<dyn Error>> {
+    let buf = bytes
+        .get(offset..offset + 2)
+        .ok_or("invalid xdb file: offset out of range")?;
+    Ok(u16::from_le_bytes([buf[0], buf[1]]) as usize)
+}
+
+#[inline(always)]
+pub(crate) fn read_u32(bytes: &[u8], offset: usize) -> Result<usize, Box<dyn Error>> {
+    let buf = bytes
+        .get(offset..offset + 4)
+        .ok_or("invalid xdb file: offset out of range")?;
+    Ok(u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize)
+}
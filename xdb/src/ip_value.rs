@@ -1,62 +1,106 @@
-use std::{error::Error, net::Ipv4Addr, str::FromStr};
+use std::{
+    error::Error,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
 
-pub trait ToUIntIP {
-    fn to_u32_ip(&self) -> Result<u32, Box<dyn Error>>;
+pub trait ToIpAddr {
+    fn to_ip_addr(&self) -> Result<IpAddr, Box<dyn Error>>;
 }
 
-impl ToUIntIP for u32 {
+impl ToIpAddr for u32 {
     #[inline(always)]
-    fn to_u32_ip(&self) -> Result<u32, Box<dyn Error>> {
-        Ok(*self)
+    fn to_ip_addr(&self) -> Result<IpAddr, Box<dyn Error>> {
+        Ok(IpAddr::V4(Ipv4Addr::from(*self)))
+    }
+}
+
+impl ToIpAddr for u128 {
+    #[inline(always)]
+    fn to_ip_addr(&self) -> Result<IpAddr, Box<dyn Error>> {
+        Ok(IpAddr::V6(Ipv6Addr::from(*self)))
     }
 }
 
-impl ToUIntIP for &str {
+impl ToIpAddr for &str {
     #[inline(always)]
-    fn to_u32_ip(&self) -> Result<u32, Box<dyn Error>> {
+    fn to_ip_addr(&self) -> Result<IpAddr, Box<dyn Error>> {
         if let Ok(num) = self.parse::<u32>() {
-            return Ok(num);
+            return num.to_ip_addr();
         }
-        Ok(u32::from(Ipv4Addr::from_str(self)?))
+        Ok(self.parse::<IpAddr>()?)
     }
 }
 
-impl ToUIntIP for Ipv4Addr {
+impl ToIpAddr for Ipv4Addr {
     #[inline(always)]
-    fn to_u32_ip(&self) -> Result<u32, Box<dyn Error>> {
-        Ok(u32::from(*self))
+    fn to_ip_addr(&self) -> Result<IpAddr, Box<dyn Error>> {
+        Ok(IpAddr::V4(*self))
+    }
+}
+
+impl ToIpAddr for Ipv6Addr {
+    #[inline(always)]
+    fn to_ip_addr(&self) -> Result<IpAddr, Box<dyn Error>> {
+        Ok(IpAddr::V6(*self))
+    }
+}
+
+impl ToIpAddr for IpAddr {
+    #[inline(always)]
+    fn to_ip_addr(&self) -> Result<IpAddr, Box<dyn Error>> {
+        Ok(*self)
     }
 }
 
 #[cfg(test)]
 mod test_ip {
+    use std::str::FromStr;
+
     use super::*;
 
     #[test]
-    fn test_ip_str_2_u32() {
+    fn test_ip_str_2_addr() {
         let ip_str = "1.1.1.1";
-        let result = ip_str.to_u32_ip().unwrap();
-        assert_eq!(result, 1 << 24 | 1 << 16 | 1 << 8 | 1);
+        let result = ip_str.to_ip_addr().unwrap();
+        assert_eq!(result, IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)));
     }
 
     #[test]
     fn test_ip_u32_str() {
         let ip = "12";
-        let result = ip.to_u32_ip().unwrap();
-        assert_eq!(result, 12);
+        let result = ip.to_ip_addr().unwrap();
+        assert_eq!(result, IpAddr::V4(Ipv4Addr::from(12)));
+    }
+
+    #[test]
+    fn test_ip_v6_str() {
+        let ip = "240e:3b7::1";
+        let result = ip.to_ip_addr().unwrap();
+        assert_eq!(
+            result,
+            IpAddr::V6(Ipv6Addr::from_str("240e:3b7::1").unwrap())
+        );
+        assert!("not-an-ip".to_ip_addr().is_err());
     }
 
     #[test]
     fn test_ip_u32() {
         let ip: u32 = 33;
-        let result = ip.to_u32_ip().unwrap();
-        assert_eq!(result, 33);
+        let result = ip.to_ip_addr().unwrap();
+        assert_eq!(result, IpAddr::V4(Ipv4Addr::from(33)));
+    }
+
+    #[test]
+    fn test_ip_u128() {
+        let ip: u128 = 1;
+        let result = ip.to_ip_addr().unwrap();
+        assert_eq!(result, IpAddr::V6(Ipv6Addr::LOCALHOST));
     }
 
     #[test]
     fn test_ip_addr() {
         let ip = Ipv4Addr::from_str("0.0.3.12").unwrap();
-        let result = ip.to_u32_ip().unwrap();
-        assert_eq!(result, 3 << 8 | 12)
+        let result = ip.to_ip_addr().unwrap();
+        assert_eq!(result, IpAddr::V4(Ipv4Addr::from(3 << 8 | 12)))
     }
 }
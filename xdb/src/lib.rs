@@ -1,4 +1,13 @@
+mod header;
 mod ip_value;
-pub use self::ip_value::ToUIntIP;
+pub use self::header::{Header, IpVersion};
+pub use self::ip_value::ToIpAddr;
+mod region;
+pub use self::region::Region;
 pub mod searcher;
-pub use searcher::{search_by_ip, searcher_init};
+pub use searcher::{
+    get_block_by_size, search_by_ip, searcher_init, searcher_init_v6, searcher_reload, CachePolicy,
+    Searcher,
+};
+#[allow(deprecated)]
+pub use searcher::{get_full_cache, get_vector_index_cache};
@@ -0,0 +1,71 @@
+use std::{convert::Infallible, fmt::Display, str::FromStr};
+
+/// ip2region 中空字段的占位符
+const EMPTY_FIELD: &str = "0";
+
+/// IP 归属地信息
+///
+/// 对应 xdb 中 `国家|区域|省份|城市|ISP` 格式的区域数据，未知的字段为 `None`。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Region {
+    pub country: Option<String>,
+    pub region: Option<String>,
+    pub province: Option<String>,
+    pub city: Option<String>,
+    pub isp: Option<String>,
+}
+
+impl FromStr for Region {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split('|').map(|field| {
+            let field = field.trim();
+            (!field.is_empty() && field != EMPTY_FIELD).then(|| field.to_string())
+        });
+        let mut next = || fields.next().flatten();
+
+        Ok(Self {
+            country: next(),
+            region: next(),
+            province: next(),
+            city: next(),
+            isp: next(),
+        })
+    }
+}
+
+/// 按 xdb 原始格式输出，空字段以 `0` 占位
+impl Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields = [
+            &self.country,
+            &self.region,
+            &self.province,
+            &self.city,
+            &self.isp,
+        ]
+        .map(|field| field.as_deref().unwrap_or(EMPTY_FIELD));
+        write!(f, "{}", fields.join("|"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_region() {
+        let region: Region = "中国|0|上海|上海市|电信".parse().unwrap();
+        assert_eq!(region.country.as_deref(), Some("中国"));
+        assert_eq!(region.region, None);
+        assert_eq!(region.province.as_deref(), Some("上海"));
+        assert_eq!(region.city.as_deref(), Some("上海市"));
+        assert_eq!(region.isp.as_deref(), Some("电信"));
+        assert_eq!(region.to_string(), "中国|0|上海|上海市|电信");
+
+        let partial: Region = "Australia".parse().unwrap();
+        assert_eq!(partial.country.as_deref(), Some("Australia"));
+        assert_eq!(partial.to_string(), "Australia|0|0|0|0");
+    }
+}
@@ -1,168 +1,617 @@
-use std::{error::Error, fmt::Display, fs::File, io::Read, path::Path};
+use std::{
+    borrow::Cow,
+    error::Error,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError, RwLock},
+};
 
 use once_cell::sync::OnceCell;
 
-use crate::ToUIntIP;
-
-const HEADER_INFO_LENGTH: usize = 256;
-const VECTOR_INDEX_COLS: usize = 256;
-const VECTOR_INDEX_SIZE: usize = 8;
-const SEGMENT_INDEX_SIZE: usize = 14;
-const VECTOR_INDEX_LENGTH: usize = 512 * 1024;
+use crate::{
+    header::{
+        read_u16, read_u32, Header, HEADER_INFO_LENGTH, VECTOR_INDEX_COLS, VECTOR_INDEX_LENGTH,
+        VECTOR_INDEX_SIZE,
+    },
+    IpVersion, Region, ToIpAddr,
+};
 
 const XDB_FILEPATH_ENV: &str = "XDB_FILEPATH";
+const XDB_FILENAME: &str = "ip2region.xdb";
+const XDB_V6_FILENAME: &str = "ip2region_v6.xdb";
+
+// 全局查询器，IPv4 和 IPv6 数据分别存放在不同的 xdb 文件中
+static SEARCHER: OnceCell<Searcher> = OnceCell::new();
+static V6_SEARCHER: OnceCell<Searcher> = OnceCell::new();
+
+/// xdb 缓存策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// 整个文件加载到内存，查询不产生 IO
+    #[default]
+    Full,
+    /// 只缓存向量索引（512 KiB），段索引和区域数据从文件读取
+    VectorIndex,
+    /// 不缓存，所有数据都从文件读取
+    File,
+}
 
-// 只保留一个缓存，用于存储完整数据
-static CACHE: OnceCell<Vec<u8>> = OnceCell::new();
+/// 数据来源
+enum Source {
+    Memory(Arc<[u8]>),
+    File(Mutex<File>),
+}
 
-// 添加专门的向量缓存
-static VECTOR_CACHE: OnceCell<&'static [u8]> = OnceCell::new();
+/// 一份已加载的 xdb 数据，重新加载时整体替换
+struct XdbData {
+    header: Header,
+    path: Option<PathBuf>,
+    source: Source,
+    vector_index: Option<Arc<[u8]>>,
+}
 
-fn default_detect_xdb_file() -> Result<String, Box<dyn Error>> {
-    let prefix = "../".to_owned();
-    for recurse in 1..4 {
-        let filepath = prefix.repeat(recurse) + "server/resources/ip2region.xdb";
-        if Path::new(&filepath).exists() {
-            return Ok(filepath);
+impl XdbData {
+    fn open(path: &Path, policy: CachePolicy) -> Result<Self, Box<dyn Error>> {
+        if policy == CachePolicy::Full {
+            let mut data = Self::from_bytes(std::fs::read(path)?)?;
+            data.path = Some(path.to_path_buf());
+            return Ok(data);
         }
-    }
-    Err("default filepath not find the xdb file".into())
-}
 
-#[inline(always)]
-pub fn get_block_by_size(bytes: &[u8], offset: usize, length: usize) -> usize {
-    unsafe {
-        let ptr = bytes.as_ptr().add(offset);
-        match length {
-            4 => {
-                let mut buf = [0u8; 4];
-                std::ptr::copy_nonoverlapping(ptr, buf.as_mut_ptr(), 4);
-                u32::from_ne_bytes(buf) as usize
-            },
-            2 => {
-                let mut buf = [0u8; 2];
-                std::ptr::copy_nonoverlapping(ptr, buf.as_mut_ptr(), 2);
-                u16::from_ne_bytes(buf) as usize
-            },
-            _ => {
-                let mut result = 0usize;
-                std::ptr::copy_nonoverlapping(ptr, &mut result as *mut usize as *mut u8, length);
-                result
+        let mut file = File::open(path)?;
+        let file_len = file.metadata()?.len() as usize;
+        let mut buffer = vec![0u8; HEADER_INFO_LENGTH];
+        file.read_exact(&mut buffer)?;
+        let header = Header::parse(&buffer, file_len)?;
+
+        let vector_index = match policy {
+            CachePolicy::VectorIndex => {
+                let mut vector_index = vec![0u8; VECTOR_INDEX_LENGTH];
+                file.read_exact(&mut vector_index)?;
+                Some(Arc::from(vector_index))
             },
+            _ => None,
+        };
+
+        Ok(Self {
+            header,
+            path: Some(path.to_path_buf()),
+            source: Source::File(Mutex::new(file)),
+            vector_index,
+        })
+    }
+
+    fn from_bytes(buffer: Vec<u8>) -> Result<Self, Box<dyn Error>> {
+        let header = Header::parse(&buffer, buffer.len())?;
+        Ok(Self {
+            header,
+            path: None,
+            source: Source::Memory(Arc::from(buffer)),
+            vector_index: None,
+        })
+    }
+
+    /// 完整的 xdb 数据，只有 [`CachePolicy::Full`] 会缓存
+    fn full_cache(&self) -> Result<Arc<[u8]>, Box<dyn Error>> {
+        match &self.source {
+            Source::Memory(buffer) => Ok(buffer.clone()),
+            Source::File(_) => Err("full xdb data is only cached with CachePolicy::Full".into()),
         }
     }
-}
 
-#[inline(always)]
-pub fn search_by_ip<T>(ip: T) -> Result<String, Box<dyn Error>>
-where
-    T: ToUIntIP + Display,
-{
-    let ip = ip.to_u32_ip()?;
+    /// 向量索引，未单独缓存时从数据中复制一份
+    fn vector_index_cache(&self) -> Result<Arc<[u8]>, Box<dyn Error>> {
+        match &self.vector_index {
+            Some(vector_index) => Ok(vector_index.clone()),
+            None => Ok(Arc::from(
+                self.read(HEADER_INFO_LENGTH, VECTOR_INDEX_LENGTH)?.as_ref(),
+            )),
+        }
+    }
 
-    unsafe {
-        let vector_cache = get_vector_index_cache();
-        let offset = VECTOR_INDEX_SIZE
-            * ((((ip >> 24) & 0xFF) as usize) * VECTOR_INDEX_COLS + ((ip >> 16) & 0xFF) as usize);
+    /// 读取指定位置的数据，内存模式下不复制
+    fn read(&self, offset: usize, len: usize) -> Result<Cow<'_, [u8]>, Box<dyn Error>> {
+        match &self.source {
+            Source::Memory(buffer) => buffer
+                .get(offset..offset + len)
+                .map(Cow::Borrowed)
+                .ok_or_else(|| "invalid xdb file: offset out of range".into()),
+            Source::File(file) => {
+                let mut file = file.lock().unwrap_or_else(PoisonError::into_inner);
+                let mut buf = vec![0u8; len];
+                file.seek(SeekFrom::Start(offset as u64))?;
+                file.read_exact(&mut buf)?;
+                Ok(Cow::Owned(buf))
+            },
+        }
+    }
 
-        // 使用 get_block_by_size 来安全地读取数据
-        let start_ptr = get_block_by_size(vector_cache, offset, 4);
-        let end_ptr = get_block_by_size(vector_cache, offset + 4, 4);
+    /// 先通过向量索引定位段索引块，再二分查找包含该 IP 的段
+    fn search(&self, ip: u128) -> Result<Region, Box<dyn Error>> {
+        let version = self.header.ip_version;
+        let ip_bytes = version.ip_bytes();
+        let segment_index_size = version.segment_index_size();
+
+        // 向量索引按 IP 的前两个字节定位
+        let shift = (ip_bytes - 1) * 8;
+        let il0 = ((ip >> shift) & 0xFF) as usize;
+        let il1 = ((ip >> (shift - 8)) & 0xFF) as usize;
+        let offset = VECTOR_INDEX_SIZE * (il0 * VECTOR_INDEX_COLS + il1);
+
+        let entry = match &self.vector_index {
+            Some(vector_index) => Cow::Borrowed(&vector_index[offset..offset + VECTOR_INDEX_SIZE]),
+            None => self.read(HEADER_INFO_LENGTH + offset, VECTOR_INDEX_SIZE)?,
+        };
+        let start_ptr = read_u32(&entry, 0)?;
+        let end_ptr = read_u32(&entry, 4)?;
 
-        let full_cache = get_full_cache();
-        let cache_ptr = full_cache.as_ptr().add(start_ptr);
         let mut left = 0;
-        let mut right = (end_ptr - start_ptr) / SEGMENT_INDEX_SIZE;
+        let mut right = end_ptr.saturating_sub(start_ptr) / segment_index_size;
 
         while left < right {
             let mid = (left + right) >> 1;
-            let segment_offset = mid * SEGMENT_INDEX_SIZE;
-            let _segment_ptr = cache_ptr.add(segment_offset);
+            let segment = self.read(start_ptr + mid * segment_index_size, segment_index_size)?;
 
-            // 使用 get_block_by_size 读取所有数据
-            let start_ip = get_block_by_size(full_cache, start_ptr + segment_offset, 4) as u32;
+            let start_ip = version.decode_ip(&segment[..ip_bytes]);
             if ip < start_ip {
                 right = mid;
                 continue;
             }
 
-            let end_ip = get_block_by_size(full_cache, start_ptr + segment_offset + 4, 4) as u32;
+            let end_ip = version.decode_ip(&segment[ip_bytes..ip_bytes * 2]);
             if ip > end_ip {
                 left = mid + 1;
                 continue;
             }
 
-            let data_len = get_block_by_size(full_cache, start_ptr + segment_offset + 8, 2);
-            let data_offset = get_block_by_size(full_cache, start_ptr + segment_offset + 10, 4);
-
-            let data = std::slice::from_raw_parts(full_cache.as_ptr().add(data_offset), data_len);
+            let data_len = read_u16(&segment, ip_bytes * 2)?;
+            let data_offset = read_u32(&segment, ip_bytes * 2 + 2)?;
+            let data = self.read(data_offset, data_len)?;
 
-            return Ok(String::from_utf8_unchecked(data.to_vec()));
+            return Ok(std::str::from_utf8(&data)?.parse()?);
         }
 
         Err("not matched".into())
     }
 }
 
-// 优化向量索引缓存访问
-#[inline(always)]
-pub fn get_vector_index_cache() -> &'static [u8] {
-    // 使用静态缓存，只计算一次
-    VECTOR_CACHE.get_or_init(|| unsafe {
-        let full_cache = get_full_cache();
-        let ptr = full_cache.as_ptr().add(HEADER_INFO_LENGTH);
-        std::slice::from_raw_parts(ptr, VECTOR_INDEX_LENGTH)
-    })
+/// ip2region xdb 查询器
+///
+/// 构造时校验文件头，查询时只持有当前数据的引用，`reload` 会原子地替换数据，
+/// 正在进行的查询继续使用旧数据直到完成。
+pub struct Searcher {
+    policy: CachePolicy,
+    data: RwLock<Arc<XdbData>>,
 }
 
-#[inline(always)]
-pub fn get_full_cache() -> &'static Vec<u8> {
-    CACHE.get_or_init(|| {
-        let xdb_filepath =
-            std::env::var(XDB_FILEPATH_ENV).unwrap_or_else(|_| default_detect_xdb_file().unwrap());
+impl Searcher {
+    /// 按指定缓存策略打开 xdb 文件
+    pub fn new(path: impl AsRef<Path>, policy: CachePolicy) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            policy,
+            data: RwLock::new(Arc::new(XdbData::open(path.as_ref(), policy)?)),
+        })
+    }
+
+    /// 从内存中的 xdb 数据构造查询器，缓存策略为 [`CachePolicy::Full`]
+    pub fn from_bytes(buffer: Vec<u8>) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            policy: CachePolicy::Full,
+            data: RwLock::new(Arc::new(XdbData::from_bytes(buffer)?)),
+        })
+    }
 
-        let size = std::fs::metadata(&xdb_filepath)
-            .map(|m| m.len() as usize)
-            .unwrap_or(1024 * 1024);
+    #[inline]
+    pub fn policy(&self) -> CachePolicy {
+        self.policy
+    }
 
-        let mut buffer = Vec::with_capacity(size);
-        File::open(&xdb_filepath)
-            .expect("file open error")
-            .read_to_end(&mut buffer)
-            .expect("load file error");
+    /// 当前数据的 IP 版本
+    #[inline]
+    pub fn ip_version(&self) -> IpVersion {
+        self.current().header.ip_version
+    }
 
-        buffer
+    /// 当前数据的文件头
+    #[inline]
+    pub fn header(&self) -> Header {
+        self.current().header
+    }
+
+    /// 当前加载的文件路径，由内存数据构造时为 `None`
+    pub fn path(&self) -> Option<PathBuf> {
+        self.current().path.clone()
+    }
+
+    /// 查询 IP 归属地，IP 版本需与 xdb 文件一致
+    pub fn search<T>(&self, ip: T) -> Result<Region, Box<dyn Error>>
+    where
+        T: ToIpAddr,
+    {
+        let data = self.current();
+        let ip = match (ip.to_ip_addr()?, data.header.ip_version) {
+            (IpAddr::V4(ip), IpVersion::V4) => u32::from(ip) as u128,
+            (IpAddr::V6(ip), IpVersion::V6) => u128::from(ip),
+            (IpAddr::V6(ip), IpVersion::V4) => match ip.to_ipv4_mapped() {
+                Some(ip) => u32::from(ip) as u128,
+                None => return Err("IPv6 address cannot be searched in an IPv4 xdb".into()),
+            },
+            (IpAddr::V4(_), IpVersion::V6) => {
+                return Err("IPv4 address cannot be searched in an IPv6 xdb".into())
+            },
+        };
+        data.search(ip)
+    }
+
+    /// 重新加载 xdb 文件
+    ///
+    /// 新文件加载并校验通过后才替换当前数据，IP 版本必须与当前数据一致；失败时保留原数据。
+    pub fn reload(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        let data = XdbData::open(path.as_ref(), self.policy)?;
+        if data.header.ip_version != self.ip_version() {
+            return Err("reload failed: ip version of the new xdb file does not match".into());
+        }
+        *self.data.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(data);
+        Ok(())
+    }
+
+    #[inline]
+    fn current(&self) -> Arc<XdbData> {
+        self.data
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+fn default_detect_xdb_file(filename: &str) -> Result<String, Box<dyn Error>> {
+    let prefix = "../".to_owned();
+    for recurse in 1..4 {
+        let filepath = prefix.repeat(recurse) + "server/resources/" + filename;
+        if Path::new(&filepath).exists() {
+            return Ok(filepath);
+        }
+    }
+    Err("default filepath not find the xdb file".into())
+}
+
+/// 获取全局 IPv4 查询器，未初始化时按 `XDB_FILEPATH` 环境变量或默认路径加载
+fn global_searcher() -> Result<&'static Searcher, Box<dyn Error>> {
+    SEARCHER.get_or_try_init(|| {
+        let xdb_filepath = match std::env::var(XDB_FILEPATH_ENV) {
+            Ok(xdb_filepath) => xdb_filepath,
+            Err(_) => default_detect_xdb_file(XDB_FILENAME)?,
+        };
+        Searcher::new(xdb_filepath, CachePolicy::Full)
     })
 }
 
-pub fn searcher_init(xdb_filepath: Option<String>) {
-    let xdb_filepath = xdb_filepath.unwrap_or_else(|| default_detect_xdb_file().unwrap());
+/// 查询 IP 归属地
+///
+/// IPv4 和 IPv6 地址分别在对应版本的 xdb 中查询，IPv4 映射的 IPv6 地址（`::ffff:a.b.c.d`）按 IPv4 查询。
+#[inline(always)]
+pub fn search_by_ip<T>(ip: T) -> Result<Region, Box<dyn Error>>
+where
+    T: ToIpAddr,
+{
+    match ip.to_ip_addr()? {
+        IpAddr::V6(ip) if ip.to_ipv4_mapped().is_none() => V6_SEARCHER
+            .get()
+            .ok_or("IPv6 xdb is not loaded")?
+            .search(ip),
+        ip => global_searcher()?.search(ip),
+    }
+}
+
+/// 初始化全局 IPv4 查询器，已初始化时不做任何操作
+pub fn searcher_init(xdb_filepath: Option<String>) -> Result<(), Box<dyn Error>> {
+    if SEARCHER.get().is_some() {
+        return Ok(());
+    }
+    let xdb_filepath = match xdb_filepath {
+        Some(xdb_filepath) => xdb_filepath,
+        None => default_detect_xdb_file(XDB_FILENAME)?,
+    };
     std::env::set_var(XDB_FILEPATH_ENV, xdb_filepath);
-    // 初始化并预热两个缓存
-    let _ = get_full_cache();
-    let _ = get_vector_index_cache();
+    global_searcher().map(|_| ())
+}
+
+/// 加载 IPv6 xdb 文件，文件不存在或不是 IPv6 格式时返回错误
+pub fn searcher_init_v6(xdb_filepath: Option<String>) -> Result<(), Box<dyn Error>> {
+    let xdb_filepath = match xdb_filepath {
+        Some(xdb_filepath) => xdb_filepath,
+        None => default_detect_xdb_file(XDB_V6_FILENAME)?,
+    };
+
+    V6_SEARCHER.get_or_try_init(|| {
+        let searcher = Searcher::new(&xdb_filepath, CachePolicy::Full)?;
+        if searcher.ip_version() != IpVersion::V6 {
+            return Err(format!("{xdb_filepath} is not an IPv6 xdb file").into());
+        }
+        Ok::<_, Box<dyn Error>>(searcher)
+    })?;
+    Ok(())
+}
+
+/// 按小端序读取 `length`（不超过 8）字节的无符号整数
+#[inline(always)]
+pub fn get_block_by_size(bytes: &[u8], offset: usize, length: usize) -> usize {
+    let mut buf = [0u8; 8];
+    buf[..length].copy_from_slice(&bytes[offset..offset + length]);
+    u64::from_le_bytes(buf) as usize
+}
+
+/// 全局 IPv4 查询器当前数据的向量索引（512 KiB）
+///
+/// 每次调用都会复制一份索引，只保留给直接读取 xdb 数据的旧代码，不要在查询路径上使用。
+#[deprecated(note = "use `search_by_ip` or `Searcher::search` instead")]
+pub fn get_vector_index_cache() -> Result<Arc<[u8]>, Box<dyn Error>> {
+    global_searcher()?.current().vector_index_cache()
+}
+
+/// 全局 IPv4 查询器当前加载的完整 xdb 数据
+///
+/// 返回调用时的数据，之后的 [`searcher_reload`] 不会改变已返回的数据，需要时重新调用。
+/// 只保留给直接读取 xdb 数据的旧代码，查询请使用 [`search_by_ip`] 或 [`Searcher`]。
+#[deprecated(note = "use `search_by_ip` or `Searcher::search` instead")]
+pub fn get_full_cache() -> Result<Arc<[u8]>, Box<dyn Error>> {
+    global_searcher()?.current().full_cache()
+}
+
+/// 重新加载全局查询器
+///
+/// 根据文件头中的 IP 版本替换对应的全局查询器，对应查询器尚未初始化时直接初始化。
+pub fn searcher_reload(xdb_filepath: &str) -> Result<IpVersion, Box<dyn Error>> {
+    let mut file = File::open(xdb_filepath)?;
+    let mut buffer = vec![0u8; HEADER_INFO_LENGTH];
+    file.read_exact(&mut buffer)?;
+
+    let version = IpVersion::from_header(&buffer)?;
+    match version {
+        IpVersion::V4 => match SEARCHER.get() {
+            Some(searcher) => searcher.reload(xdb_filepath)?,
+            None => searcher_init(Some(xdb_filepath.to_string()))?,
+        },
+        IpVersion::V6 => match V6_SEARCHER.get() {
+            Some(searcher) => searcher.reload(xdb_filepath)?,
+            None => searcher_init_v6(Some(xdb_filepath.to_string()))?,
+        },
+    }
+    Ok(version)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{fs::File, io::Read, net::Ipv4Addr, str::FromStr, thread};
+    use std::{
+        fs::File,
+        io::{Read, Write},
+        net::{Ipv4Addr, Ipv6Addr},
+        str::FromStr,
+        thread,
+    };
 
     use super::*;
+    use crate::header::HEADER_IP_VERSION_OFFSET;
+
+    const POLICIES: [CachePolicy; 3] = [
+        CachePolicy::Full,
+        CachePolicy::VectorIndex,
+        CachePolicy::File,
+    ];
+
+    /// 构造只包含一个段的 xdb 数据
+    fn build_xdb(version: IpVersion, start_ip: &[u8], end_ip: &[u8], region: &str) -> Vec<u8> {
+        let mut buffer = vec![0u8; HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH];
+        let ip_version: u16 = match version {
+            IpVersion::V4 => 4,
+            IpVersion::V6 => 6,
+        };
+        buffer[0..2].copy_from_slice(&3u16.to_le_bytes());
+        buffer[HEADER_IP_VERSION_OFFSET..HEADER_IP_VERSION_OFFSET + 2]
+            .copy_from_slice(&ip_version.to_le_bytes());
+
+        let data_offset = buffer.len() as u32;
+        buffer.extend_from_slice(region.as_bytes());
+
+        let segment_ptr = buffer.len() as u32;
+        buffer.extend_from_slice(start_ip);
+        buffer.extend_from_slice(end_ip);
+        buffer.extend_from_slice(&(region.len() as u16).to_le_bytes());
+        buffer.extend_from_slice(&data_offset.to_le_bytes());
+        let segment_end = buffer.len() as u32;
+
+        buffer[8..12].copy_from_slice(&segment_ptr.to_le_bytes());
+        buffer[12..16].copy_from_slice(&segment_ptr.to_le_bytes());
+
+        // 起始 IP 按网络字节序取前两个字节定位向量索引
+        let (il0, il1) = match version {
+            IpVersion::V4 => (start_ip[3] as usize, start_ip[2] as usize),
+            IpVersion::V6 => (start_ip[0] as usize, start_ip[1] as usize),
+        };
+        let offset = HEADER_INFO_LENGTH + VECTOR_INDEX_SIZE * (il0 * VECTOR_INDEX_COLS + il1);
+        buffer[offset..offset + 4].copy_from_slice(&segment_ptr.to_le_bytes());
+        buffer[offset + 4..offset + 8].copy_from_slice(&segment_end.to_le_bytes());
+        buffer
+    }
+
+    fn build_v4_xdb(region: &str) -> Vec<u8> {
+        let start = u32::from(Ipv4Addr::new(1, 2, 0, 0));
+        let end = u32::from(Ipv4Addr::new(1, 2, 255, 255));
+        build_xdb(
+            IpVersion::V4,
+            &start.to_le_bytes(),
+            &end.to_le_bytes(),
+            region,
+        )
+    }
+
+    fn write_temp_xdb(name: &str, buffer: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("xdb-{}-{name}", std::process::id()));
+        File::create(&path).unwrap().write_all(buffer).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_search_v4_segment() {
+        let searcher = Searcher::from_bytes(build_v4_xdb("中国|0|福建省|福州市|电信")).unwrap();
+
+        let region = searcher.search(Ipv4Addr::new(1, 2, 3, 4)).unwrap();
+        assert_eq!(region.province.as_deref(), Some("福建省"));
+        assert!(searcher.search(Ipv4Addr::new(1, 3, 0, 0)).is_err());
+        assert!(searcher.search("::ffff:1.2.3.4").is_ok());
+        assert!(searcher.search("240e::1").is_err());
+    }
+
+    #[test]
+    fn test_search_v6_segment() {
+        let start = u128::from(Ipv6Addr::from_str("240e::").unwrap());
+        let end = u128::from(Ipv6Addr::from_str("240e:ff:ffff:ffff:ffff:ffff:ffff:ffff").unwrap());
+        let buffer = build_xdb(
+            IpVersion::V6,
+            &start.to_be_bytes(),
+            &end.to_be_bytes(),
+            "中国|0|广东省|深圳市|电信",
+        );
+
+        let searcher = Searcher::from_bytes(buffer).unwrap();
+        assert_eq!(searcher.ip_version(), IpVersion::V6);
+        let region = searcher.search("240e:3b::1").unwrap();
+        assert_eq!(region.city.as_deref(), Some("深圳市"));
+        assert_eq!(region.isp.as_deref(), Some("电信"));
+
+        assert!(searcher.search("240e:100::1").is_err());
+        assert!(searcher.search("1.2.3.4").is_err());
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let buffer = build_v4_xdb("中国|0|福建省|福州市|电信");
+        assert!(Searcher::from_bytes(buffer[..HEADER_INFO_LENGTH].to_vec()).is_err());
+
+        let mut unsupported = buffer.clone();
+        unsupported[0..2].copy_from_slice(&9u16.to_le_bytes());
+        assert!(Searcher::from_bytes(unsupported).is_err());
+
+        let mut out_of_range = buffer;
+        out_of_range[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(Searcher::from_bytes(out_of_range).is_err());
+
+        assert!(Searcher::new("not-exists.xdb", CachePolicy::File).is_err());
+    }
+
+    #[test]
+    fn test_cache_policies_and_reload() {
+        let old = write_temp_xdb("old.xdb", &build_v4_xdb("中国|0|福建省|福州市|电信"));
+        let new = write_temp_xdb("new.xdb", &build_v4_xdb("中国|0|浙江省|杭州市|电信"));
+
+        for policy in POLICIES {
+            let searcher = Searcher::new(&old, policy).unwrap();
+            assert_eq!(searcher.policy(), policy);
+            let region = searcher.search("1.2.3.4").unwrap();
+            assert_eq!(region.city.as_deref(), Some("福州市"));
+
+            searcher.reload(&new).unwrap();
+            let region = searcher.search("1.2.3.4").unwrap();
+            assert_eq!(region.city.as_deref(), Some("杭州市"));
+            assert_eq!(searcher.path().as_deref(), Some(new.as_path()));
+
+            // 加载失败时保留原数据
+            assert!(searcher.reload("not-exists.xdb").is_err());
+            assert!(searcher.search("1.2.3.4").is_ok());
+        }
+
+        std::fs::remove_file(old).unwrap();
+        std::fs::remove_file(new).unwrap();
+    }
+
+    #[test]
+    fn test_reload_under_concurrent_search() {
+        let old = write_temp_xdb("concurrent-old.xdb", &build_v4_xdb("中国|0|0|0|电信"));
+        let new = write_temp_xdb("concurrent-new.xdb", &build_v4_xdb("中国|0|0|0|联通"));
+        let searcher = Arc::new(Searcher::new(&old, CachePolicy::VectorIndex).unwrap());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let searcher = searcher.clone();
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        let isp = searcher.search("1.2.3.4").unwrap().isp.unwrap();
+                        assert!(isp == "电信" || isp == "联通");
+                    }
+                })
+            })
+            .collect();
+        searcher.reload(&new).unwrap();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(
+            searcher.search("1.2.3.4").unwrap().isp.as_deref(),
+            Some("联通")
+        );
+
+        std::fs::remove_file(old).unwrap();
+        std::fs::remove_file(new).unwrap();
+    }
+
+    #[test]
+    fn test_get_block_by_size() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+
+        assert_eq!(get_block_by_size(&bytes, 0, 4), 0x0403_0201);
+        assert_eq!(get_block_by_size(&bytes, 4, 2), 0x0605);
+        assert_eq!(get_block_by_size(&bytes, 1, 3), 0x04_0302);
+    }
+
+    #[test]
+    fn test_cache_accessors_follow_reload() {
+        let old = build_v4_xdb("中国|0|福建省|福州市|电信");
+        let path = write_temp_xdb("accessor.xdb", &build_v4_xdb("中国|0|浙江省|杭州市|电信"));
+        let vector_index = HEADER_INFO_LENGTH..HEADER_INFO_LENGTH + VECTOR_INDEX_LENGTH;
+
+        let searcher = Searcher::from_bytes(old.clone()).unwrap();
+        let snapshot = searcher.current().full_cache().unwrap();
+        assert_eq!(&snapshot[..], &old[..]);
+
+        searcher.reload(&path).unwrap();
+        let reloaded = std::fs::read(&path).unwrap();
+        assert_eq!(&searcher.current().full_cache().unwrap()[..], &reloaded[..]);
+        assert_eq!(
+            &searcher.current().vector_index_cache().unwrap()[..],
+            &reloaded[vector_index.clone()]
+        );
+        // 已返回的数据不受重新加载影响
+        assert_eq!(&snapshot[..], &old[..]);
+
+        for policy in [CachePolicy::VectorIndex, CachePolicy::File] {
+            let searcher = Searcher::new(&path, policy).unwrap();
+            assert!(searcher.current().full_cache().is_err());
+            assert_eq!(
+                &searcher.current().vector_index_cache().unwrap()[..],
+                &reloaded[vector_index.clone()]
+            );
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
 
     /// test all types find correct
     #[test]
     fn test_multi_type_ip() {
-        searcher_init(None);
+        searcher_init(None).unwrap();
 
         search_by_ip("2.0.0.0").unwrap();
         search_by_ip("32").unwrap();
-        search_by_ip(4294408949).unwrap();
+        search_by_ip(4294408949u32).unwrap();
         search_by_ip(Ipv4Addr::from_str("1.1.1.1").unwrap()).unwrap();
+        search_by_ip(Ipv6Addr::from_str("::ffff:1.1.1.1").unwrap()).unwrap();
     }
 
     #[test]
     fn test_match_all_ip_correct() {
-        searcher_init(None);
+        searcher_init(None).unwrap();
         let mut file = File::open("../server/resources/ip.test.txt").unwrap();
         let mut contents = String::new();
         file.read_to_string(&mut contents).unwrap();
@@ -175,14 +624,14 @@ mod tests {
             let end_ip = Ipv4Addr::from_str(ip_test_line[1]).unwrap();
             for value in u32::from(start_ip)..u32::from(end_ip) + 1 {
                 let result = search_by_ip(value).unwrap();
-                assert_eq!(result.as_str(), ip_test_line[2])
+                assert_eq!(result.to_string(), ip_test_line[2])
             }
         }
     }
 
     #[test]
     fn test_multi_thread_only_load_xdb_once() {
-        searcher_init(None);
+        searcher_init(None).unwrap();
         let handle = thread::spawn(|| {
             let result = search_by_ip("2.2.2.2").unwrap();
             println!("ip search in spawn: {result}");
@@ -196,11 +645,11 @@ mod tests {
     fn test_multi_searcher_init() {
         for _ in 0..5 {
             thread::spawn(|| {
-                searcher_init(None);
+                let _ = searcher_init(None);
             });
         }
-        searcher_init(None);
-        searcher_init(Some(String::from("test")));
-        search_by_ip(123).unwrap();
+        searcher_init(None).unwrap();
+        searcher_init(Some(String::from("test"))).unwrap();
+        search_by_ip(123u32).unwrap();
     }
 }